@@ -189,6 +189,8 @@ fn synthetic_platform() -> Arc<dyn Platform> {
         fs_type: "apfs".to_string(),
         mount_point: mount_path.clone(),
         is_readonly: false,
+        total_inodes: 0,
+        free_inodes: 0,
     };
     let stats_by_mount = HashMap::from([(mount_path, fs_stats)]);
     let memory = MemoryInfo {
@@ -0,0 +1,143 @@
+//! Safe wrapper around the Linux `FS_IOC_FIEMAP` ioctl.
+//!
+//! The main `storage_ballast_helper` crate forbids unsafe code. This crate keeps
+//! the ioctl boundary narrow and exposes summed extent byte counts only.
+
+#![cfg(target_os = "linux")]
+
+use std::fs::File;
+use std::io;
+use std::os::fd::AsRawFd;
+
+/// Extents requested per ioctl call.
+const EXTENT_BATCH: u32 = 128;
+
+/// Last extent of the file.
+const FIEMAP_EXTENT_LAST: u32 = 0x0000_0001;
+/// Extent data is shared with another file (reflink, snapshot, dedupe).
+const FIEMAP_EXTENT_SHARED: u32 = 0x0000_2000;
+
+/// `struct fiemap` from `<linux/fiemap.h>`, without the trailing extent array.
+#[repr(C)]
+#[allow(clippy::struct_field_names)] // kernel field names
+#[derive(Debug, Clone, Copy, Default)]
+struct FiemapHeader {
+    fm_start: u64,
+    fm_length: u64,
+    fm_flags: u32,
+    fm_mapped_extents: u32,
+    fm_extent_count: u32,
+    fm_reserved: u32,
+}
+
+/// `struct fiemap_extent` from `<linux/fiemap.h>`.
+#[repr(C)]
+#[allow(clippy::struct_field_names)] // kernel field names
+#[derive(Debug, Clone, Copy, Default)]
+struct FiemapExtent {
+    fe_logical: u64,
+    fe_physical: u64,
+    fe_length: u64,
+    fe_reserved64: [u64; 2],
+    fe_flags: u32,
+    fe_reserved: [u32; 3],
+}
+
+/// A `struct fiemap` with room for [`EXTENT_BATCH`] extents, laid out the way
+/// the kernel expects the flexible array member to follow the header.
+#[repr(C)]
+struct FiemapRequest {
+    header: FiemapHeader,
+    extents: [FiemapExtent; EXTENT_BATCH as usize],
+}
+
+/// `_IOWR('f', 11, struct fiemap)`.
+const FS_IOC_FIEMAP: libc::Ioctl = libc::_IOWR::<FiemapHeader>(b'f' as u32, 11);
+
+/// Byte totals over every mapped extent of a file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExtentUsage {
+    /// Sum of all mapped extent lengths.
+    pub mapped_bytes: u64,
+    /// Portion of `mapped_bytes` in extents flagged `FIEMAP_EXTENT_SHARED`.
+    pub shared_bytes: u64,
+}
+
+/// Map every extent of `file` and sum how many bytes are shared.
+///
+/// Filesystems without FIEMAP support fail with `EOPNOTSUPP`. No sync flag is
+/// passed, so delayed-allocation data that has not reached disk yet is not
+/// reported as mapped.
+pub fn extent_usage(file: &File) -> io::Result<ExtentUsage> {
+    let fd = file.as_raw_fd();
+    let mut usage = ExtentUsage::default();
+    let mut start = 0u64;
+
+    loop {
+        let mut request = FiemapRequest {
+            header: FiemapHeader {
+                fm_start: start,
+                fm_length: u64::MAX - start,
+                fm_extent_count: EXTENT_BATCH,
+                ..FiemapHeader::default()
+            },
+            extents: [FiemapExtent::default(); EXTENT_BATCH as usize],
+        };
+        // SAFETY: `request` is a live, writable `struct fiemap` whose
+        // `fm_extent_count` matches the extent array that follows the header.
+        let code = unsafe { libc::ioctl(fd, FS_IOC_FIEMAP, &raw mut request) };
+        if code < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mapped = request.header.fm_mapped_extents.min(EXTENT_BATCH) as usize;
+        let mut last = mapped == 0;
+        let mut next = start;
+        for extent in &request.extents[..mapped] {
+            usage.mapped_bytes = usage.mapped_bytes.saturating_add(extent.fe_length);
+            if extent.fe_flags & FIEMAP_EXTENT_SHARED != 0 {
+                usage.shared_bytes = usage.shared_bytes.saturating_add(extent.fe_length);
+            }
+            last |= extent.fe_flags & FIEMAP_EXTENT_LAST != 0;
+            next = next.max(extent.fe_logical.saturating_add(extent.fe_length));
+        }
+        // A kernel that reports no progress would otherwise loop forever.
+        if last || next <= start {
+            return Ok(usage);
+        }
+        start = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FS_IOC_FIEMAP, FiemapExtent, FiemapHeader, extent_usage};
+    use std::io::Write;
+    use std::mem::size_of;
+
+    #[test]
+    fn structs_match_the_kernel_abi() {
+        assert_eq!(size_of::<FiemapHeader>(), 32);
+        assert_eq!(size_of::<FiemapExtent>(), 56);
+        #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+        #[allow(clippy::unnecessary_cast)] // `Ioctl` is `c_int` on musl
+        {
+            assert_eq!(FS_IOC_FIEMAP as u64, 0xC020_660B);
+        }
+    }
+
+    #[test]
+    fn unshared_file_reports_no_shared_bytes() {
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(&vec![0x5a; 256 * 1024]).unwrap();
+        file.sync_all().unwrap();
+
+        match extent_usage(&file) {
+            Ok(usage) => {
+                assert_eq!(usage.shared_bytes, 0);
+                assert!(usage.mapped_bytes <= 512 * 1024);
+            }
+            Err(error) => assert_eq!(error.raw_os_error(), Some(libc::EOPNOTSUPP)),
+        }
+    }
+}
@@ -55,6 +55,8 @@ fn main() {
             candidates.push(CandidateInput {
                 path: entry.path.clone(),
                 size_bytes: entry.metadata.content_size_bytes,
+                entry_count: entry.metadata.content_entry_count,
+                shared_bytes: 0,
                 age,
                 classification,
                 signals: entry.structural_signals,
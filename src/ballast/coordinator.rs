@@ -623,6 +623,8 @@ mod tests {
                     fs_type: "ext4".to_string(),
                     mount_point: dir_data.to_path_buf(),
                     is_readonly: false,
+                    total_inodes: 0,
+                    free_inodes: 0,
                 },
             ),
             (
@@ -634,6 +636,8 @@ mod tests {
                     fs_type: "ext4".to_string(),
                     mount_point: dir_tmp.to_path_buf(),
                     is_readonly: false,
+                    total_inodes: 0,
+                    free_inodes: 0,
                 },
             ),
         ]);
@@ -696,6 +700,8 @@ mod tests {
                     fs_type: "ext4".to_string(),
                     mount_point: dir_data.path().to_path_buf(),
                     is_readonly: false,
+                    total_inodes: 0,
+                    free_inodes: 0,
                 },
             ),
             (
@@ -707,6 +713,8 @@ mod tests {
                     fs_type: "tmpfs".to_string(),
                     mount_point: dir_tmp.path().to_path_buf(),
                     is_readonly: false,
+                    total_inodes: 0,
+                    free_inodes: 0,
                 },
             ),
         ]);
@@ -1085,6 +1093,8 @@ mod tests {
                     fs_type: "ext4".to_string(),
                     mount_point: dir_data.path().to_path_buf(),
                     is_readonly: false,
+                    total_inodes: 0,
+                    free_inodes: 0,
                 },
             )]);
             MockPlatform::new(
@@ -1176,6 +1186,8 @@ mod tests {
                     fs_type: "ext4".to_string(),
                     mount_point: dir_data.path().to_path_buf(),
                     is_readonly: false,
+                    total_inodes: 0,
+                    free_inodes: 0,
                 },
             )]);
             MockPlatform::new(
@@ -1266,6 +1278,8 @@ mod tests {
                 fs_type: "ext4".to_string(),
                 mount_point: dir_data.path().to_path_buf(),
                 is_readonly: true, // <-- read-only
+                total_inodes: 0,
+                free_inodes: 0,
             },
         )]);
         let platform = MockPlatform::new(
@@ -247,6 +247,7 @@ mod tests {
     use super::*;
     use crate::ballast::manager::BallastManager;
     use crate::core::config::BallastConfig;
+    use crate::monitor::pid::PressureDimension;
 
     fn test_config() -> BallastConfig {
         BallastConfig {
@@ -269,6 +270,7 @@ mod tests {
             causing_mount: PathBuf::from("/test"),
             free_pct: 5.0,
             predicted_seconds: None,
+            dimension: PressureDimension::Bytes,
         }
     }
 
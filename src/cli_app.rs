@@ -1,5 +1,6 @@
 //! Top-level CLI definition and dispatch.
 
+use std::borrow::Cow;
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
@@ -18,35 +19,54 @@ use storage_ballast_helper::ballast::manager::{
 use storage_ballast_helper::cli::RELEASE_REPOSITORY;
 use storage_ballast_helper::cli::update::{UpdateReport, UpdateServiceRestart};
 use storage_ballast_helper::core::config::{
-    Config, PathsConfig, ScannerEngineMode, load_sacred_config, sacred_config_path_for,
-    write_sacred_config,
+    Config, InodePressureConfig, PathsConfig, PressureThresholds, ScannerEngineMode,
+    load_sacred_config, sacred_config_path_for, write_sacred_config,
+};
+#[cfg(unix)]
+use storage_ballast_helper::daemon::control::{
+    CONTROL_CLIENT_TIMEOUT, ControlRequest, ControlResponse, send_request,
 };
 use storage_ballast_helper::daemon::loop_main::{
     DaemonArgs as RuntimeDaemonArgs, MonitoringDaemon,
 };
-use storage_ballast_helper::daemon::process_io_history::ProcessIoHistory;
+use storage_ballast_helper::daemon::process_io_history::{ProcessIoHistory, ProcessTreeWrites};
+use storage_ballast_helper::daemon::reservations::{self, read_reservations};
 use storage_ballast_helper::daemon::self_monitor::DAEMON_STATE_STALE_THRESHOLD_SECS;
 use storage_ballast_helper::daemon::service::{
     LAUNCHD_LABEL_ENV, LaunchdConfig, LaunchdServiceManager, LaunchdStatusReport,
     ServiceActionResult, SystemdServiceManager, launchd_labels_for_discovery,
     launchd_system_plist_path_for_label, launchd_user_plist_path_for_label,
 };
-use storage_ballast_helper::logger::sqlite::SqliteLogger;
+use storage_ballast_helper::logger::sqlite::{DecisionQuery, SqliteLogger};
 use storage_ballast_helper::logger::stats::{StatsEngine, window_label};
 use storage_ballast_helper::monitor::fs_stats::FsStatsCollector;
+use storage_ballast_helper::platform::cleanup_catalog::{
+    CleanupConfidence, CleanupRuleSource, ReclaimCommand,
+};
+use storage_ballast_helper::platform::linux::cleanup_catalog as linux_cleanup_catalog;
 use storage_ballast_helper::platform::pal::{
-    BlockDeviceInfo, MemoryInfo, Platform, ServiceManager, detect_platform,
+    BlockDeviceInfo, MemoryInfo, MountPoint, Platform, ServiceManager, detect_platform,
 };
 use storage_ballast_helper::platform::types::{
-    Capacity, FullDiskAccessState, FullDiskAccessStatus, MemoryPressure, MemoryPressureLevel,
-    ProcessInfo, ProcessIo, ServiceKind,
+    Capacity, CgroupIo, FsSnapshotInfo, FullDiskAccessState, FullDiskAccessStatus, MemoryPressure,
+    MemoryPressureLevel, ProcessInfo, ProcessIo, ServiceKind,
+};
+use storage_ballast_helper::scanner::compress;
+use storage_ballast_helper::scanner::cow_extents;
+use storage_ballast_helper::scanner::decision_record::{
+    DecisionRecord, ExplainLevel, format_explain, parse_decision_from_details,
 };
+use storage_ballast_helper::scanner::deleted_open::{HeldDeletedFile, group_deleted_open_files};
 use storage_ballast_helper::scanner::deletion::{DeletionConfig, DeletionExecutor, DeletionPlan};
 use storage_ballast_helper::scanner::engine::{ScannerEngine, SelectedScannerEngine};
 use storage_ballast_helper::scanner::patterns::{
-    ArtifactCategory, ArtifactPatternRegistry, OpaqueTreeDisposition,
+    ArtifactCategory, ArtifactPatternRegistry, OpaqueTreeDisposition, platform_cleanup_rules,
 };
 use storage_ballast_helper::scanner::protection::{self, ProtectionRegistry};
+use storage_ballast_helper::scanner::provenance::{
+    ArtifactOwner, ArtifactProvenance, PRODUCED_DIR_DEPTH, RunRecord, directory_snapshot,
+    produced_directories,
+};
 use storage_ballast_helper::scanner::scoring::{
     ActiveReferenceSummary, CandidacyScore, CandidateInput, ScoringEngine,
 };
@@ -58,6 +78,15 @@ use storage_ballast_helper::scanner::walker::{
 
 const LIVE_REFRESH_MIN_MS: u64 = 100;
 const STATUS_WATCH_REFRESH_MS: u64 = 1_000;
+/// Deleted-but-open files `sbh status` lists without a daemon, largest first.
+const STATUS_DELETED_OPEN_LIMIT: usize = 50;
+/// How often `sbh reserve` re-checks a pending lease.
+#[cfg(unix)]
+const RESERVE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How often `sbh run` samples the wrapped process tree's writes.
+const RUN_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+/// How often `sbh run` checks whether the wrapped command has exited.
+const RUN_EXIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
 const LOCAL_SNAPSHOT_THIN_AMOUNT_BYTES: u64 = 9_999_999_999_999_999;
 const LOCAL_SNAPSHOT_THIN_URGENCY: u8 = 4;
 
@@ -109,6 +138,8 @@ enum Command {
     Service(ServiceArgs),
     /// Show aggregated historical statistics.
     Stats(StatsArgs),
+    /// Explain why sbh kept or deleted a path.
+    Explain(ExplainArgs),
     /// Run a manual scan for reclaim candidates.
     Scan(ScanArgs),
     /// Run a manual cleanup pass.
@@ -129,6 +160,10 @@ enum Command {
     Tune(TuneArgs),
     /// Pre-build disk pressure check.
     Check(CheckArgs),
+    /// Reserve disk space for an upcoming build (requires running daemon).
+    Reserve(ReserveArgs),
+    /// Run a command with a pressure check, write tracking, and artifact attribution.
+    Run(RunArgs),
     /// Attribute disk pressure by process/agent.
     Blame(BlameArgs),
     /// Live TUI-style dashboard.
@@ -146,6 +181,18 @@ enum Command {
     Log(LogArgs),
     /// Truncate active append-only logs in place (e.g. agent codex-tui.log).
     TruncateLogs(TruncateLogsArgs),
+    /// Restore files that sbh compressed in place to `.zst`.
+    Decompress(DecompressArgs),
+}
+
+#[derive(Debug, Clone, Args, Serialize, Default)]
+struct DecompressArgs {
+    /// `.zst` files, or directories to restore every `.zst` file below.
+    #[arg(value_name = "PATH", required = true)]
+    paths: Vec<PathBuf>,
+    /// Print what would be restored without writing.
+    #[arg(long)]
+    dry_run: bool,
 }
 
 #[derive(Debug, Clone, Args, Serialize, Default)]
@@ -334,9 +381,10 @@ struct ServiceLogsArgs {
     tail: usize,
 }
 
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone, Args, Serialize, Default)]
 #[command(
-    after_long_help = "Platform notes:\n  Use --pal for platform diagnostics.\n  Use --system for host tuning checks (kernel writeback / dirty-page limits on Linux).\n  Use --release for macOS release signing/notarization/Homebrew readiness.\n  On macOS --pal includes launchd, APFS, codesign/notarization, and Full Disk Access checks."
+    after_long_help = "Platform notes:\n  Use --pal for platform diagnostics.\n  Use --system for host tuning checks (kernel writeback / dirty-page limits on Linux).\n  Use --release for macOS release signing/notarization/Homebrew readiness.\n  Use --catalog to list active cleanup catalog rules, built-in and configured.\n  On macOS --pal includes launchd, APFS, codesign/notarization, and Full Disk Access checks."
 )]
 struct DoctorArgs {
     /// Probe the Platform Abstraction Layer implementation.
@@ -348,6 +396,9 @@ struct DoctorArgs {
     /// Check host-level tuning (kernel writeback / dirty-page limits).
     #[arg(long)]
     system: bool,
+    /// List the active cleanup catalog rules and where each was defined.
+    #[arg(long)]
+    catalog: bool,
 }
 
 #[derive(Debug, Clone, Args, Serialize, Default)]
@@ -366,6 +417,31 @@ struct StatsArgs {
     pressure_history: bool,
 }
 
+#[derive(Debug, Clone, Args, Serialize)]
+#[command(group(
+    ArgGroup::new("explain_target")
+        .required(true)
+        .multiple(true)
+        .args(["id", "path", "since"])
+))]
+struct ExplainArgs {
+    /// Decision id (`42`) or trace id (`sbh-0000002a`).
+    #[arg(long, value_name = "DECISION-ID")]
+    id: Option<String>,
+    /// Explain decisions for this path or anything beneath it.
+    #[arg(long, value_name = "PATH")]
+    path: Option<PathBuf>,
+    /// Only decisions within this window (for example: `1h`, `24h`, `7d`).
+    #[arg(long, value_name = "WINDOW")]
+    since: Option<String>,
+    /// Detail level 0-3 (default: 1 with --id, 0 otherwise).
+    #[arg(long, value_name = "LEVEL", value_parser = clap::value_parser!(u8).range(0..=3))]
+    level: Option<u8>,
+    /// Maximum number of decisions to show.
+    #[arg(long, default_value_t = 20, value_name = "N")]
+    limit: u32,
+}
+
 #[derive(Debug, Clone, Args, Serialize, Default)]
 struct ScanArgs {
     /// Paths to scan (falls back to configured watched paths when omitted).
@@ -383,6 +459,14 @@ struct ScanArgs {
     /// Include per-candidate confidence and safety-check traces.
     #[arg(long)]
     explain: bool,
+    /// Ask the running daemon to scan (and clean) these paths through its
+    /// pipeline instead of printing a local report. The report options do
+    /// not apply to a daemon scan.
+    #[arg(
+        long,
+        conflicts_with_all = ["top", "min_score", "explain", "show_protected"]
+    )]
+    daemon: bool,
 }
 
 #[derive(Debug, Clone, Args, Serialize)]
@@ -569,7 +653,7 @@ struct CheckArgs {
     /// Path to evaluate (defaults to cwd).
     #[arg(value_name = "PATH")]
     path: Option<PathBuf>,
-    /// Desired minimum free percentage.
+    /// Desired minimum free percentage (defaults to the mount's yellow threshold).
     #[arg(long, value_name = "PERCENT")]
     target_free: Option<f64>,
     /// Minimum required free space. Accepts bytes or K/M/G/T suffixes, e.g. 5G.
@@ -580,6 +664,83 @@ struct CheckArgs {
     predict: Option<u64>,
 }
 
+#[derive(Debug, Clone, Args, Serialize)]
+struct ReserveArgs {
+    /// Space to reserve. Accepts bytes or K/M/G/T suffixes, e.g. 20G.
+    #[arg(
+        long,
+        value_name = "SIZE",
+        value_parser = parse_byte_count,
+        required_unless_present_any = ["list", "release"]
+    )]
+    need: Option<u64>,
+    /// Lease lifetime (for example: `30m`, `2h`); the lease expires on its own.
+    #[arg(long, default_value = "30m", value_name = "DURATION")]
+    ttl: String,
+    /// Path on the volume to reserve (defaults to cwd).
+    #[arg(long, value_name = "PATH")]
+    path: Option<PathBuf>,
+    /// How long to wait for the daemon to free enough space; `0` returns
+    /// immediately with the lease still pending.
+    #[arg(long, default_value = "5m", value_name = "DURATION")]
+    wait: String,
+    /// Free-form label recorded with the lease (e.g. the build name).
+    #[arg(long, value_name = "NAME")]
+    holder: Option<String>,
+    /// List active reservations.
+    #[arg(long, conflicts_with_all = ["need", "release"])]
+    list: bool,
+    /// Release a reservation early.
+    #[arg(long, value_name = "ID", conflicts_with = "need")]
+    release: Option<String>,
+}
+
+#[derive(Debug, Clone, Args, Serialize)]
+struct RunArgs {
+    /// Minimum free space required before starting. Accepts bytes or K/M/G/T
+    /// suffixes, e.g. 20G.
+    #[arg(long, value_name = "SIZE", value_parser = parse_byte_count)]
+    need: Option<u64>,
+    /// Desired minimum free percentage (defaults to the mount's yellow threshold).
+    #[arg(long, value_name = "PERCENT", conflicts_with = "reserve")]
+    target_free: Option<f64>,
+    /// Hold a `--need` reservation for the duration of the command (requires
+    /// running daemon) instead of a one-off check.
+    #[arg(long, requires = "need")]
+    reserve: bool,
+    /// Reservation lifetime with `--reserve`.
+    #[arg(
+        long,
+        default_value = "30m",
+        value_name = "DURATION",
+        requires = "reserve"
+    )]
+    ttl: String,
+    /// How long to wait for a `--reserve` lease to be granted.
+    #[arg(
+        long,
+        default_value = "5m",
+        value_name = "DURATION",
+        requires = "reserve"
+    )]
+    wait: String,
+    /// Start the command without the pre-flight pressure check.
+    #[arg(long, conflicts_with_all = ["need", "target_free", "reserve"])]
+    no_check: bool,
+    /// Name recorded as the owner of produced directories (defaults to the
+    /// command line).
+    #[arg(long, value_name = "NAME")]
+    label: Option<String>,
+    /// Command to run, after `--`.
+    #[arg(
+        value_name = "COMMAND",
+        required = true,
+        trailing_var_arg = true,
+        allow_hyphen_values = true
+    )]
+    command: Vec<String>,
+}
+
 #[derive(Debug, Clone, Args, Serialize)]
 struct BlameArgs {
     /// Maximum rows to return.
@@ -591,6 +752,9 @@ struct BlameArgs {
     /// Render parent-child process tree in human output.
     #[arg(long)]
     tree: bool,
+    /// Aggregate processes by cgroup, systemd unit, user, or executable.
+    #[arg(long, value_enum, value_name = "KEY", conflicts_with = "tree")]
+    group_by: Option<BlameGroupBy>,
 }
 
 impl Default for BlameArgs {
@@ -599,10 +763,22 @@ impl Default for BlameArgs {
             top: 25,
             since: "15m".to_string(),
             tree: false,
+            group_by: None,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+enum BlameGroupBy {
+    /// cgroup v2 path.
+    Cgroup,
+    /// Innermost systemd service or scope.
+    Unit,
+    User,
+    Exe,
+}
+
 #[derive(Debug, Clone, Args, Serialize)]
 struct DashboardArgs {
     /// Refresh interval for live view.
@@ -767,6 +943,9 @@ pub enum CliError {
     /// Output write failed.
     #[error("failed to write output: {0}")]
     Io(#[from] io::Error),
+    /// A command wrapped by `sbh run` failed; its exit code is passed through.
+    #[error("command exited with status {0}")]
+    Wrapped(i32),
 }
 
 impl CliError {
@@ -777,6 +956,7 @@ impl CliError {
             Self::Runtime(_) | Self::Io(_) => 2,
             Self::Internal(_) | Self::Json(_) => 3,
             Self::Partial(_) => 4,
+            Self::Wrapped(code) => *code,
         }
     }
 }
@@ -794,6 +974,7 @@ pub fn run(cli: &Cli) -> Result<(), CliError> {
         Command::Status(args) => run_status(cli, args),
         Command::Service(args) => run_service(cli, args),
         Command::Stats(args) => run_stats(cli, args),
+        Command::Explain(args) => run_explain(cli, args),
         Command::Scan(args) => run_scan(cli, args),
         Command::Clean(args) => run_clean(cli, args),
         Command::Ballast(args) => run_ballast(cli, args),
@@ -804,6 +985,8 @@ pub fn run(cli: &Cli) -> Result<(), CliError> {
         Command::Unprotect(args) => run_unprotect(cli, args),
         Command::Tune(args) => run_tune(cli, args),
         Command::Check(args) => run_check(cli, args),
+        Command::Reserve(args) => run_reserve(cli, args),
+        Command::Run(args) => run_wrapped_command(cli, args),
         Command::Blame(args) => run_blame(cli, args),
         Command::Dashboard(args) => run_dashboard(cli, args),
         Command::Doctor(args) => run_doctor(cli, args),
@@ -817,6 +1000,7 @@ pub fn run(cli: &Cli) -> Result<(), CliError> {
         Command::Setup(args) => run_setup(cli, args),
         Command::Log(args) => run_log(cli, args),
         Command::TruncateLogs(args) => run_truncate_logs(cli, args),
+        Command::Decompress(args) => run_decompress(cli, args),
     }
 }
 
@@ -872,6 +1056,78 @@ fn run_truncate_logs(cli: &Cli, args: &TruncateLogsArgs) -> Result<(), CliError>
     Ok(())
 }
 
+fn run_decompress(cli: &Cli, args: &DecompressArgs) -> Result<(), CliError> {
+    let mut errors = Vec::new();
+    let mut targets = Vec::with_capacity(args.paths.len());
+    for path in &args.paths {
+        if std::fs::symlink_metadata(path).is_err() {
+            return Err(CliError::User(format!(
+                "cannot access path {}",
+                path.display()
+            )));
+        }
+        let report = compress::decompress_in_place(path, args.dry_run);
+        let (files, bytes_after) = if report.dry_run {
+            (report.files_would_restore, report.bytes_would_restore)
+        } else {
+            (report.files_restored, report.bytes_after)
+        };
+        if output_mode(cli) == OutputMode::Human {
+            let verb = if report.dry_run {
+                "would restore"
+            } else {
+                "restored"
+            };
+            println!(
+                "{}: {verb} {files} file(s), {} -> {bytes_after} bytes in {} ms",
+                path.display(),
+                report.bytes_before,
+                report.duration.as_millis(),
+            );
+            if report.files_not_ours > 0 {
+                println!(
+                    "  left {} .zst file(s) sbh did not compress",
+                    report.files_not_ours
+                );
+            }
+            for (file, err) in &report.errors {
+                eprintln!("  error: {} — {err}", file.display());
+            }
+        }
+        targets.push(json!({
+            "path": path.to_string_lossy(),
+            "files": files,
+            "bytes_before": report.bytes_before,
+            "bytes_after": bytes_after,
+            "not_ours": report.files_not_ours,
+            "errors": report
+                .errors
+                .iter()
+                .map(|(file, err)| json!({"path": file.to_string_lossy(), "error": err}))
+                .collect::<Vec<_>>(),
+        }));
+        errors.extend(report.errors);
+    }
+
+    if output_mode(cli) == OutputMode::Json {
+        let payload = json!({
+            "command": "decompress",
+            "dry_run": args.dry_run,
+            "targets": targets,
+        });
+        write_json_line(&payload)?;
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::Partial(format!(
+            "{} file(s) could not be restored",
+            errors.len()
+        )))
+    }
+}
+
 fn to_runtime_daemon_args(args: &DaemonArgs) -> RuntimeDaemonArgs {
     RuntimeDaemonArgs {
         foreground: !args.background,
@@ -2675,6 +2931,10 @@ struct BlameReport {
     io_error_count: usize,
     open_file_error_count: usize,
     open_file_roots: Vec<PathBuf>,
+    /// `sbh run` invocations that finished inside the window.
+    runs: Vec<RunRecord>,
+    /// Deleted files still held open, largest first.
+    deleted_open: Vec<HeldDeletedFile>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -2688,6 +2948,195 @@ struct BlameRow {
     recent_read_bytes: u64,
     recent_written_bytes: u64,
     open_files: Vec<PathBuf>,
+    /// Command of the active `sbh run` this process belongs to.
+    run_command: Option<String>,
+    uid: Option<u32>,
+    cgroup: Option<String>,
+}
+
+/// Blame rows aggregated under one `--group-by` key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BlameGroup {
+    key: String,
+    /// cgroup whose `io.stat` backs `cgroup_io` (cgroup and unit grouping).
+    cgroup: Option<String>,
+    process_count: usize,
+    /// Heaviest writers first.
+    pids: Vec<i32>,
+    recent_read_bytes: u64,
+    recent_written_bytes: u64,
+    open_file_count: usize,
+    /// Cumulative since the cgroup was created, including exited processes.
+    cgroup_io: Option<CgroupIo>,
+}
+
+impl BlameGroup {
+    const fn new(key: String, cgroup: Option<String>) -> Self {
+        Self {
+            key,
+            cgroup,
+            process_count: 0,
+            pids: Vec::new(),
+            recent_read_bytes: 0,
+            recent_written_bytes: 0,
+            open_file_count: 0,
+            cgroup_io: None,
+        }
+    }
+}
+
+/// Parse `sbh explain --id`: a decimal decision id or its `sbh-<hex>` trace id.
+fn parse_decision_id(raw: &str) -> Result<u64, CliError> {
+    let raw = raw.trim();
+    let parsed = raw.strip_prefix("sbh-").map_or_else(
+        || raw.parse::<u64>().ok(),
+        |hex| u64::from_str_radix(hex, 16).ok(),
+    );
+    parsed.ok_or_else(|| {
+        CliError::User(format!(
+            "invalid decision id: {raw} (expected a number or an sbh-<hex> trace id)"
+        ))
+    })
+}
+
+/// Resolve `--path` for decision lookup. The path may already have been
+/// deleted, so fall back to a cwd-relative join when it cannot be canonicalized.
+fn resolve_explain_path(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    std::env::current_dir().map_or_else(|_| path.to_path_buf(), |cwd| cwd.join(path))
+}
+
+fn explain_since_timestamp(window: std::time::Duration) -> String {
+    let now = chrono::Utc::now();
+    chrono::Duration::from_std(window)
+        .ok()
+        .and_then(|delta| now.checked_sub_signed(delta))
+        .unwrap_or(chrono::DateTime::<chrono::Utc>::MIN_UTC)
+        .to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
+#[allow(clippy::too_many_lines)]
+fn run_explain(cli: &Cli, args: &ExplainArgs) -> Result<(), CliError> {
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let decision_id = args.id.as_deref().map(parse_decision_id).transpose()?;
+    let since = args
+        .since
+        .as_deref()
+        .map(parse_window_duration)
+        .transpose()?;
+    let path = args.path.as_deref().map(resolve_explain_path);
+    let level = ExplainLevel::from_int(
+        args.level
+            .unwrap_or_else(|| u8::from(decision_id.is_some())),
+    );
+
+    if !config.paths.sqlite_db.exists() {
+        match output_mode(cli) {
+            OutputMode::Human => {
+                println!(
+                    "No activity database found at {}.",
+                    config.paths.sqlite_db.display()
+                );
+                println!("  Decisions are recorded once the daemon evaluates candidates.");
+            }
+            OutputMode::Json => {
+                let payload = json!({
+                    "command": "explain",
+                    "error": "no_database",
+                    "db_path": config.paths.sqlite_db.to_string_lossy(),
+                });
+                write_json_line(&payload)?;
+            }
+        }
+        return Ok(());
+    }
+
+    let db = SqliteLogger::open(&config.paths.sqlite_db)
+        .map_err(|e| CliError::Runtime(format!("open activity database: {e}")))?;
+    let rows = db
+        .decision_rows(&DecisionQuery {
+            decision_id,
+            path: path.as_ref().map(|p| p.to_string_lossy().into_owned()),
+            since: since.map(explain_since_timestamp),
+            limit: args.limit.max(1),
+        })
+        .map_err(|e| CliError::Runtime(format!("query decisions: {e}")))?;
+    let records: Vec<DecisionRecord> = rows
+        .iter()
+        .filter_map(|row| parse_decision_from_details(&row.details))
+        .collect();
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            if records.is_empty() {
+                println!("No recorded decisions match.");
+                println!("  Decisions are kept for 30 days; try a wider --since window.");
+                return Ok(());
+            }
+            if let Some(id) = decision_id
+                && records.len() > 1
+            {
+                println!(
+                    "{} decisions share id {id} (ids restart with each daemon run); newest first.",
+                    records.len()
+                );
+                println!();
+            }
+            for (i, record) in records.iter().enumerate() {
+                if i > 0 && level >= ExplainLevel::L1 {
+                    println!();
+                }
+                let effective = record
+                    .effective_action
+                    .map_or_else(|| record.action.to_string(), |a| a.to_string());
+                println!(
+                    "[{trace}] {ts}  policy={mode} effective={effective}",
+                    trace = record.trace_id,
+                    ts = record.timestamp,
+                    mode = record.policy_mode,
+                );
+                println!("{}", format_explain(record, level).trim_end());
+            }
+        }
+        OutputMode::Json => {
+            let decisions: Vec<Value> = records
+                .iter()
+                .map(|record| {
+                    let mut value = record.to_json_at_level(level);
+                    if let Some(obj) = value.as_object_mut() {
+                        obj.insert("trace_id".to_string(), json!(record.trace_id));
+                        obj.insert("timestamp".to_string(), json!(record.timestamp));
+                        obj.insert(
+                            "effective_action".to_string(),
+                            json!(record.effective_action),
+                        );
+                    }
+                    value
+                })
+                .collect();
+            let payload = json!({
+                "command": "explain",
+                "level": level as u8,
+                "query": {
+                    "id": decision_id,
+                    "path": path.as_ref().map(|p| p.to_string_lossy()),
+                    "since_secs": since.map(|d| d.as_secs()),
+                    "limit": args.limit.max(1),
+                },
+                "count": decisions.len(),
+                "decisions": decisions,
+            });
+            write_json_line(&payload)?;
+        }
+    }
+
+    Ok(())
 }
 
 fn run_blame(cli: &Cli, args: &BlameArgs) -> Result<(), CliError> {
@@ -2698,15 +3147,20 @@ fn run_blame(cli: &Cli, args: &BlameArgs) -> Result<(), CliError> {
     let history = ProcessIoHistory::load_or_new(ProcessIoHistory::snapshot_path_for_state_file(
         &config.paths.state_file,
     ));
+    let provenance = ArtifactProvenance::load(&config.paths.artifact_provenance_file());
     let start = std::time::Instant::now();
-    let report = collect_blame_report_at(
+    let mut report = collect_blame_report_at(
         &config,
         platform.as_ref(),
         &history,
+        &provenance,
         since,
-        args.top,
         unix_time_ms_for_cli(),
     )?;
+    let groups = args
+        .group_by
+        .map(|group_by| group_blame_rows(platform.as_ref(), &report.rows, group_by, args.top));
+    report.rows.truncate(args.top);
     let elapsed = start.elapsed();
 
     match output_mode(cli) {
@@ -2720,10 +3174,22 @@ fn run_blame(cli: &Cli, args: &BlameArgs) -> Result<(), CliError> {
 
             if report.rows.is_empty() {
                 println!("  No process I/O attribution data found.");
+            } else if let (Some(groups), Some(group_by)) = (&groups, args.group_by) {
+                print_blame_groups_human(groups, group_by);
             } else {
                 print_blame_human(&report, args.tree);
             }
 
+            if !report.runs.is_empty() {
+                println!();
+                print_blame_runs_human(&report.runs);
+            }
+
+            if !report.deleted_open.is_empty() {
+                println!();
+                print_blame_deleted_open_human(&report.deleted_open, &config);
+            }
+
             if report.io_error_count > 0 || report.open_file_error_count > 0 {
                 println!();
                 println!(
@@ -2747,9 +3213,13 @@ fn run_blame(cli: &Cli, args: &BlameArgs) -> Result<(), CliError> {
                         "recent_bytes_written": row.recent_written_bytes,
                         "recent_bytes_read": row.recent_read_bytes,
                         "open_files": row.open_files.iter().map(|path| path.display().to_string()).collect::<Vec<_>>(),
+                        "run_command": row.run_command,
+                        "uid": row.uid,
+                        "cgroup": row.cgroup,
                     })
                 })
                 .collect();
+            let runs_json = blame_runs_json(&report.runs);
 
             let payload = json!({
                 "command": "blame",
@@ -2757,6 +3227,14 @@ fn run_blame(cli: &Cli, args: &BlameArgs) -> Result<(), CliError> {
                 "since_label": window_label(report.since),
                 "tree_mode": args.tree,
                 "rows": rows_json,
+                "group_by": args.group_by,
+                "groups": groups.as_deref().map(blame_groups_json),
+                "runs": runs_json,
+                "deleted_open_files": report
+                    .deleted_open
+                    .iter()
+                    .map(|file| file.status_json(&config.scanner.deleted_open_files))
+                    .collect::<Vec<_>>(),
                 "elapsed_ms": u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
                 "processes_scanned": report.process_count,
                 "io_error_count": report.io_error_count,
@@ -2774,8 +3252,8 @@ fn collect_blame_report_at(
     config: &Config,
     platform: &dyn Platform,
     history: &ProcessIoHistory,
+    provenance: &ArtifactProvenance,
     since: Duration,
-    top: usize,
     collected_at_unix_ms: i64,
 ) -> Result<BlameReport, CliError> {
     let processes = platform
@@ -2784,6 +3262,10 @@ fn collect_blame_report_at(
     let open_file_roots = canonical_blame_roots(config);
     let (open_files_by_pid, open_file_error_count) =
         collect_blame_open_files(platform, &open_file_roots);
+    let parents: HashMap<i32, i32> = processes
+        .iter()
+        .filter_map(|process| Some((process.pid, process.parent_pid?)))
+        .collect();
     let mut io_error_count = 0;
     let mut rows = Vec::with_capacity(processes.len());
 
@@ -2815,6 +3297,10 @@ fn collect_blame_report_at(
             recent_read_bytes: recent.0,
             recent_written_bytes: recent.1,
             open_files,
+            run_command: wrapping_run(provenance, &parents, process.pid)
+                .map(|owner| owner.display_name().to_string()),
+            uid: process.uid,
+            cgroup: process.cgroup.clone(),
         });
     }
 
@@ -2827,7 +3313,17 @@ fn collect_blame_report_at(
             .then_with(|| left.name.cmp(&right.name))
             .then_with(|| left.pid.cmp(&right.pid))
     });
-    rows.truncate(top);
+
+    let window_start = u64::try_from(collected_at_unix_ms / 1_000)
+        .unwrap_or(0)
+        .saturating_sub(since.as_secs());
+    let runs = provenance
+        .runs()
+        .iter()
+        .filter(|run| run.owner.finished_at.is_some_and(|at| at >= window_start))
+        .rev()
+        .cloned()
+        .collect();
 
     Ok(BlameReport {
         rows,
@@ -2836,74 +3332,335 @@ fn collect_blame_report_at(
         io_error_count,
         open_file_error_count,
         open_file_roots,
+        runs,
+        deleted_open: local_deleted_open_files(platform),
     })
 }
 
-fn blame_recent_totals(
-    history: &ProcessIoHistory,
-    process: &ProcessInfo,
-    io: &ProcessIo,
-    since: Duration,
-    collected_at_unix_ms: i64,
-) -> (u64, u64) {
-    if let Some(recent) = history.recent_totals_for_process(
-        io,
-        process.start_time_unix_ms,
-        collected_at_unix_ms,
-        since,
-    ) {
-        return (recent.bytes_read, recent.bytes_written);
+/// Aggregate ranked rows by `group_by`, attach cgroup `io.stat` totals, and
+/// keep the `top` heaviest groups.
+///
+/// cgroup and unit grouping also enumerate the cgroup tree, so a cgroup whose
+/// processes have all exited still shows up with its `io.stat` total. Those
+/// groups rank by that total first, since the window only sees live PIDs.
+fn group_blame_rows(
+    platform: &dyn Platform,
+    rows: &[BlameRow],
+    group_by: BlameGroupBy,
+    top: usize,
+) -> Vec<BlameGroup> {
+    let mut user_names: HashMap<u32, String> = HashMap::new();
+    let mut index_by_key: HashMap<String, usize> = HashMap::new();
+    let mut groups: Vec<BlameGroup> = Vec::new();
+    for row in rows {
+        let (key, cgroup) = match group_by {
+            BlameGroupBy::Cgroup | BlameGroupBy::Unit => row
+                .cgroup
+                .as_deref()
+                .and_then(|cgroup| cgroup_blame_key(cgroup, group_by))
+                .map_or_else(
+                    || {
+                        let unknown = if group_by == BlameGroupBy::Cgroup {
+                            "(unknown cgroup)"
+                        } else {
+                            "(no systemd unit)"
+                        };
+                        (unknown.to_string(), None)
+                    },
+                    |(key, cgroup)| (key, Some(cgroup)),
+                ),
+            BlameGroupBy::User => (
+                row.uid.map_or_else(
+                    || "(unknown user)".to_string(),
+                    |uid| {
+                        user_names
+                            .entry(uid)
+                            .or_insert_with(|| user_name_for_uid(uid))
+                            .clone()
+                    },
+                ),
+                None,
+            ),
+            BlameGroupBy::Exe => (
+                row.executable.as_ref().map_or_else(
+                    || format!("[{}]", row.name),
+                    |exe| exe.display().to_string(),
+                ),
+                None,
+            ),
+        };
+        let index = *index_by_key.entry(key.clone()).or_insert_with(|| {
+            groups.push(BlameGroup::new(key, cgroup));
+            groups.len() - 1
+        });
+        let group = &mut groups[index];
+        group.process_count += 1;
+        // Rows arrive heaviest writer first.
+        if group.pids.len() < 5 {
+            group.pids.push(row.pid);
+        }
+        group.recent_read_bytes = group
+            .recent_read_bytes
+            .saturating_add(row.recent_read_bytes);
+        group.recent_written_bytes = group
+            .recent_written_bytes
+            .saturating_add(row.recent_written_bytes);
+        group.open_file_count += row.open_files.len();
     }
 
-    if since == Duration::from_mins(15)
-        && let (Some(read), Some(written)) = (io.bytes_read_recent_15m, io.bytes_written_recent_15m)
-    {
-        return (read, written);
+    let by_cgroup = matches!(group_by, BlameGroupBy::Cgroup | BlameGroupBy::Unit);
+    if by_cgroup {
+        for leaf in platform.leaf_cgroups().unwrap_or_default() {
+            let Some((key, cgroup)) = cgroup_blame_key(&leaf, group_by) else {
+                continue;
+            };
+            if !index_by_key.contains_key(&key) {
+                index_by_key.insert(key.clone(), groups.len());
+                groups.push(BlameGroup::new(key, Some(cgroup)));
+            }
+        }
     }
 
-    (0, 0)
+    for group in &mut groups {
+        group.cgroup_io = group
+            .cgroup
+            .as_deref()
+            .and_then(|cgroup| platform.cgroup_io(cgroup).ok());
+    }
+    // Drop enumerated cgroups that never wrote anything.
+    groups.retain(|group| {
+        group.process_count > 0
+            || group
+                .cgroup_io
+                .as_ref()
+                .is_some_and(|io| io.bytes_written > 0)
+    });
+    let total_written = |group: &BlameGroup| group.cgroup_io.as_ref().map(|io| io.bytes_written);
+    groups.sort_by(|left, right| {
+        let recent = right.recent_written_bytes.cmp(&left.recent_written_bytes);
+        let total = total_written(right).cmp(&total_written(left));
+        if by_cgroup {
+            total.then(recent)
+        } else {
+            recent.then(total)
+        }
+        .then_with(|| left.key.cmp(&right.key))
+    });
+    groups.truncate(top);
+    groups
 }
 
-fn canonical_blame_roots(config: &Config) -> Vec<PathBuf> {
-    config
-        .scanner
-        .root_paths
-        .iter()
-        .filter_map(|path| path.canonicalize().ok())
-        .collect()
+/// Group key and backing cgroup of `cgroup` for cgroup or unit grouping.
+fn cgroup_blame_key(cgroup: &str, group_by: BlameGroupBy) -> Option<(String, String)> {
+    match group_by {
+        BlameGroupBy::Cgroup => Some((cgroup.to_string(), cgroup.to_string())),
+        BlameGroupBy::Unit => systemd_unit_cgroup(cgroup).map(|unit_cgroup| {
+            let unit = unit_cgroup.rsplit('/').next().unwrap_or(unit_cgroup);
+            (unit.to_string(), unit_cgroup.to_string())
+        }),
+        BlameGroupBy::User | BlameGroupBy::Exe => None,
+    }
 }
 
-fn collect_blame_open_files(
-    platform: &dyn Platform,
-    roots: &[PathBuf],
-) -> (HashMap<i32, Vec<PathBuf>>, usize) {
-    let mut by_pid: HashMap<i32, BTreeSet<PathBuf>> = HashMap::new();
-    let mut errors = 0;
-
-    for root in roots {
-        match platform.open_files_under(root) {
-            Ok(open_files) => {
-                for open_file in open_files {
-                    by_pid
-                        .entry(open_file.pid)
-                        .or_default()
-                        .insert(open_file.path);
-                }
-            }
-            Err(_) => errors += 1,
+/// The cgroup of the innermost systemd service or scope in `cgroup`, e.g.
+/// `/user.slice/user-1000.slice/user@1000.service/app.slice/tmux-1.scope`
+/// for any process below that scope.
+fn systemd_unit_cgroup(cgroup: &str) -> Option<&str> {
+    let mut unit_end = None;
+    let mut offset = 0;
+    for part in cgroup.split('/') {
+        let end = offset + part.len();
+        // Unit suffixes are case-sensitive in systemd.
+        if matches!(part.rsplit_once('.'), Some((_, "service" | "scope"))) {
+            unit_end = Some(end);
         }
+        offset = end + 1;
     }
-
-    (
-        by_pid
-            .into_iter()
-            .map(|(pid, paths)| (pid, paths.into_iter().collect()))
-            .collect(),
-        errors,
-    )
+    unit_end.map(|end| &cgroup[..end])
 }
 
-fn process_command(process: &ProcessInfo) -> String {
+#[cfg(unix)]
+fn user_name_for_uid(uid: u32) -> String {
+    nix::unistd::User::from_uid(nix::unistd::Uid::from_raw(uid))
+        .ok()
+        .flatten()
+        .map_or_else(|| format!("uid {uid}"), |user| user.name)
+}
+
+#[cfg(not(unix))]
+fn user_name_for_uid(uid: u32) -> String {
+    format!("uid {uid}")
+}
+
+fn blame_runs_json(runs: &[RunRecord]) -> Vec<Value> {
+    runs.iter()
+        .map(|run| {
+            json!({
+                "run_id": run.owner.run_id,
+                "command": run.owner.command,
+                "label": run.owner.label,
+                "cwd": run.owner.cwd.display().to_string(),
+                "started_at": run.owner.started_at,
+                "finished_at": run.owner.finished_at,
+                "exit_code": run.owner.exit_code,
+                "bytes_written": run.owner.bytes_written,
+                "produced": run.produced.iter().map(|path| path.display().to_string()).collect::<Vec<_>>(),
+            })
+        })
+        .collect()
+}
+
+fn blame_groups_json(groups: &[BlameGroup]) -> Vec<Value> {
+    groups
+        .iter()
+        .map(|group| {
+            json!({
+                "key": group.key,
+                "cgroup": group.cgroup,
+                "process_count": group.process_count,
+                "pids": group.pids,
+                "recent_bytes_written": group.recent_written_bytes,
+                "recent_bytes_read": group.recent_read_bytes,
+                "open_file_count": group.open_file_count,
+                "cgroup_bytes_written": group.cgroup_io.as_ref().map(|io| io.bytes_written),
+                "cgroup_bytes_read": group.cgroup_io.as_ref().map(|io| io.bytes_read),
+            })
+        })
+        .collect()
+}
+
+fn print_blame_groups_human(groups: &[BlameGroup], group_by: BlameGroupBy) {
+    let heading = match group_by {
+        BlameGroupBy::Cgroup => "cgroup",
+        BlameGroupBy::Unit => "Unit",
+        BlameGroupBy::User => "User",
+        BlameGroupBy::Exe => "Executable",
+    };
+    println!(
+        "  {:>12}  {:>12}  {:>14}  {:>5}  {heading}",
+        "Written", "Read", "cgroup total", "Procs"
+    );
+    println!("  {}", "-".repeat(72));
+    for group in groups {
+        println!(
+            "  {:>12}  {:>12}  {:>14}  {:>5}  {}",
+            format_bytes(group.recent_written_bytes),
+            format_bytes(group.recent_read_bytes),
+            group
+                .cgroup_io
+                .as_ref()
+                .map_or_else(|| "-".to_string(), |io| format_bytes(io.bytes_written)),
+            group.process_count,
+            group.key,
+        );
+        if let Some(cgroup) = group
+            .cgroup
+            .as_deref()
+            .filter(|cgroup| *cgroup != group.key)
+        {
+            println!("                   cgroup: {cgroup}");
+        }
+        let pids = group
+            .pids
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let more = group.process_count.saturating_sub(group.pids.len());
+        if more > 0 {
+            println!("                   pids: {pids}, +{more} more");
+        } else {
+            println!("                   pids: {pids}");
+        }
+    }
+    println!();
+    println!("  Written/Read cover the window for live processes; cgroup total is io.stat");
+    println!("  since the cgroup was created, including processes that have exited.");
+}
+
+/// Active `sbh run` wrapping `pid` or one of its ancestors.
+fn wrapping_run<'a>(
+    provenance: &'a ArtifactProvenance,
+    parents: &HashMap<i32, i32>,
+    pid: i32,
+) -> Option<&'a ArtifactOwner> {
+    let mut current = pid;
+    // Bounded walk: parent maps from a live process list can contain cycles
+    // after PID reuse.
+    for _ in 0..64 {
+        if let Some(owner) = provenance.active_run_for_pid(current) {
+            return Some(owner);
+        }
+        current = *parents.get(&current)?;
+    }
+    None
+}
+
+fn blame_recent_totals(
+    history: &ProcessIoHistory,
+    process: &ProcessInfo,
+    io: &ProcessIo,
+    since: Duration,
+    collected_at_unix_ms: i64,
+) -> (u64, u64) {
+    if let Some(recent) = history.recent_totals_for_process(
+        io,
+        process.start_time_unix_ms,
+        collected_at_unix_ms,
+        since,
+    ) {
+        return (recent.bytes_read, recent.bytes_written);
+    }
+
+    if since == Duration::from_mins(15)
+        && let (Some(read), Some(written)) = (io.bytes_read_recent_15m, io.bytes_written_recent_15m)
+    {
+        return (read, written);
+    }
+
+    (0, 0)
+}
+
+fn canonical_blame_roots(config: &Config) -> Vec<PathBuf> {
+    config
+        .scanner
+        .root_paths
+        .iter()
+        .filter_map(|path| path.canonicalize().ok())
+        .collect()
+}
+
+fn collect_blame_open_files(
+    platform: &dyn Platform,
+    roots: &[PathBuf],
+) -> (HashMap<i32, Vec<PathBuf>>, usize) {
+    let mut by_pid: HashMap<i32, BTreeSet<PathBuf>> = HashMap::new();
+    let mut errors = 0;
+
+    for root in roots {
+        match platform.open_files_under(root) {
+            Ok(open_files) => {
+                for open_file in open_files {
+                    by_pid
+                        .entry(open_file.pid)
+                        .or_default()
+                        .insert(open_file.path);
+                }
+            }
+            Err(_) => errors += 1,
+        }
+    }
+
+    (
+        by_pid
+            .into_iter()
+            .map(|(pid, paths)| (pid, paths.into_iter().collect()))
+            .collect(),
+        errors,
+    )
+}
+
+fn process_command(process: &ProcessInfo) -> String {
     if process.command_line.is_empty() {
         process.name.clone()
     } else {
@@ -2929,6 +3686,33 @@ fn print_blame_human(report: &BlameReport, tree: bool) {
     }
 }
 
+fn print_blame_runs_human(runs: &[RunRecord]) {
+    println!("  Finished `sbh run` commands:");
+    for run in runs {
+        println!(
+            "    {:>12}  exit {:<4} {}",
+            format_bytes(run.owner.bytes_written),
+            run.owner
+                .exit_code
+                .map_or_else(|| "?".to_string(), |code| code.to_string()),
+            run.owner.display_name(),
+        );
+        for dir in &run.produced {
+            println!("                  produced: {}", dir.display());
+        }
+    }
+}
+
+fn print_blame_deleted_open_human(files: &[HeldDeletedFile], config: &Config) {
+    println!("  Deleted but still open:");
+    for file in files {
+        println!(
+            "    {}",
+            deleted_open_line(&file.status_json(&config.scanner.deleted_open_files))
+        );
+    }
+}
+
 fn print_blame_row_human(row: &BlameRow, depth: usize) {
     let indent = "  ".repeat(depth);
     println!(
@@ -2947,6 +3731,9 @@ fn print_blame_row_human(row: &BlameRow, depth: usize) {
     if let Some(cwd) = &row.cwd {
         println!("  {indent}         cwd: {}", cwd.display());
     }
+    if let Some(run_command) = &row.run_command {
+        println!("  {indent}         run: {run_command}");
+    }
     if !row.open_files.is_empty() {
         let open_files = row
             .open_files
@@ -4203,6 +4990,22 @@ fn run_ballast(cli: &Cli, args: &BallastArgs) -> Result<(), CliError> {
     let config =
         Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
 
+    // A live daemon releases on our behalf so its release controller (and
+    // replenish cooldown) sees the release; otherwise act on the pool directly.
+    #[cfg(unix)]
+    if let Some(BallastCommand::Release(release_args)) = &args.command
+        && release_args.count > 0
+        && let Some(reply) = daemon_control_request(
+            &config,
+            &ControlRequest::ReleaseBallast {
+                mount: None,
+                count: release_args.count,
+            },
+        )?
+    {
+        return render_daemon_ballast_release(cli, release_args.count, reply);
+    }
+
     let mut manager = BallastManager::new(config.paths.ballast_dir.clone(), config.ballast.clone())
         .map_err(|e| CliError::Runtime(e.to_string()))?;
 
@@ -4823,9 +5626,9 @@ struct PalDoctorFollowUp {
 }
 
 fn run_doctor(cli: &Cli, args: &DoctorArgs) -> Result<(), CliError> {
-    if !args.pal && !args.release && !args.system {
+    if !args.pal && !args.release && !args.system && !args.catalog {
         return Err(CliError::User(
-            "specify a diagnostic target, for example: sbh doctor --pal, --system, or --release"
+            "specify a diagnostic target, for example: sbh doctor --pal, --system, --release, or --catalog"
                 .to_string(),
         ));
     }
@@ -4845,15 +5648,23 @@ fn run_doctor(cli: &Cli, args: &DoctorArgs) -> Result<(), CliError> {
     } else {
         None
     };
+    let catalog_rules = if args.catalog {
+        let config =
+            Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+        Some(catalog_rule_reports(&config))
+    } else {
+        None
+    };
 
     match output_mode(cli) {
         OutputMode::Json => {
             // Preserve the single-target top-level shapes; nest only when targets
             // are combined.
-            let payload = match (args.pal, args.release, args.system) {
-                (true, false, false) => serde_json::to_value(&pal_report)?,
-                (false, true, false) => serde_json::to_value(&release_report)?,
-                (false, false, true) => json!({ "system": { "checks": system_checks } }),
+            let payload = match (args.pal, args.release, args.system, args.catalog) {
+                (true, false, false, false) => serde_json::to_value(&pal_report)?,
+                (false, true, false, false) => serde_json::to_value(&release_report)?,
+                (false, false, true, false) => json!({ "system": { "checks": system_checks } }),
+                (false, false, false, true) => json!({ "catalog": { "rules": catalog_rules } }),
                 _ => {
                     let mut obj = serde_json::Map::new();
                     if let Some(report) = &pal_report {
@@ -4865,6 +5676,9 @@ fn run_doctor(cli: &Cli, args: &DoctorArgs) -> Result<(), CliError> {
                     if let Some(checks) = &system_checks {
                         obj.insert("system".to_string(), json!({ "checks": checks }));
                     }
+                    if let Some(rules) = &catalog_rules {
+                        obj.insert("catalog".to_string(), json!({ "rules": rules }));
+                    }
                     Value::Object(obj)
                 }
             };
@@ -4887,6 +5701,12 @@ fn run_doctor(cli: &Cli, args: &DoctorArgs) -> Result<(), CliError> {
                 println!("System tuning checks:");
                 print_doctor_checks(checks);
             }
+            if let Some(rules) = &catalog_rules {
+                if pal_report.is_some() || release_report.is_some() || system_checks.is_some() {
+                    println!();
+                }
+                print_catalog_rule_reports(rules);
+            }
         }
     }
 
@@ -4908,6 +5728,59 @@ fn run_doctor(cli: &Cli, args: &DoctorArgs) -> Result<(), CliError> {
     Ok(())
 }
 
+/// One active cleanup catalog rule, as listed by `sbh doctor --catalog`.
+#[derive(Debug, Serialize)]
+struct CatalogRuleReport {
+    name: Cow<'static, str>,
+    path_glob: Cow<'static, str>,
+    source: CleanupRuleSource,
+    /// `built-in`, or the config file that declared the rule.
+    origin: String,
+    min_age_hours: u64,
+    reclaim_command: ReclaimCommand,
+    confidence: CleanupConfidence,
+}
+
+fn catalog_rule_reports(config: &Config) -> Vec<CatalogRuleReport> {
+    platform_cleanup_rules()
+        .iter()
+        .map(|rule| {
+            let source = linux_cleanup_catalog::rule_source(rule);
+            let origin = match source {
+                CleanupRuleSource::Builtin => "built-in".to_string(),
+                CleanupRuleSource::UserConfig => config.paths.config_file.display().to_string(),
+            };
+            CatalogRuleReport {
+                name: rule.name.clone(),
+                path_glob: rule.path_glob.clone(),
+                source,
+                origin,
+                min_age_hours: rule.age_threshold.minimum_age.as_secs() / 3600,
+                reclaim_command: rule.reclaim_command,
+                confidence: rule.confidence,
+            }
+        })
+        .collect()
+}
+
+fn print_catalog_rule_reports(rules: &[CatalogRuleReport]) {
+    println!("Cleanup catalog ({} active rules):", rules.len());
+    println!(
+        "  {:<36}  {:<40}  {:>6}  {:<32}  Origin",
+        "Rule", "Path glob", "Age h", "Command"
+    );
+    for rule in rules {
+        println!(
+            "  {:<36}  {:<40}  {:>6}  {:<32}  {}",
+            truncate_str(&rule.name, 36),
+            truncate_str(&rule.path_glob, 40),
+            rule.min_age_hours,
+            truncate_str(&rule.reclaim_command.to_string(), 32),
+            rule.origin,
+        );
+    }
+}
+
 /// Host-level tuning diagnostics (kernel writeback / dirty-page limits) plus
 /// emergency-reserve integrity.
 fn system_doctor_checks(platform: &dyn Platform, config: &Config) -> Vec<DoctorCheck> {
@@ -6436,7 +7309,7 @@ fn count_sacred_scan_overlaps(
     let entries = walker
         .walk()
         .map_err(|e| CliError::Runtime(e.to_string()))?;
-    let patterns = ArtifactPatternRegistry::default();
+    let patterns = ArtifactPatternRegistry::from_config(&config.scanner);
 
     let mut candidate_count = 0usize;
     let mut overlap_count = 0usize;
@@ -6473,8 +7346,11 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
         .ok()
         .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok());
 
+    // Live snapshot over the control socket beats any file-based heuristic.
+    let live_status = daemon_live_status(&config);
+
     // I26: Check file modification time to detect stale state from a crashed daemon.
-    let daemon_running = {
+    let daemon_running = live_status.is_some() || {
         let state_file_fresh = daemon_state.is_some() && {
             let stale_threshold = std::time::Duration::from_secs(DAEMON_STATE_STALE_THRESHOLD_SECS);
             std::fs::metadata(&config.paths.state_file)
@@ -6497,6 +7373,15 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
             detect_daemon_running_fallback()
         }
     };
+    let policy_mode = live_status
+        .as_ref()
+        .and_then(|live| live["policy"]["mode"].as_str())
+        .or_else(|| {
+            daemon_state
+                .as_ref()
+                .and_then(|s| s.get("policy_mode"))
+                .and_then(Value::as_str)
+        });
 
     // Open SQLite database for recent activity (optional).
     let db_stats = if config.paths.sqlite_db.exists() {
@@ -6517,7 +7402,26 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
         OutputMode::Human => {
             println!("Storage Ballast Helper v{version}");
             println!("  Config: {}", config.paths.config_file.display());
-            if daemon_running {
+            if let Some(live) = &live_status {
+                println!(
+                    "  Daemon: running (pid {}, up {}s, live via control socket)",
+                    live["pid"],
+                    live["uptime_seconds"].as_u64().unwrap_or(0)
+                );
+                println!(
+                    "  Daemon view: pressure {} on {} (urgency {:.2}), policy {}",
+                    live["pressure"]["overall"]
+                        .as_str()
+                        .unwrap_or("unknown")
+                        .to_uppercase(),
+                    live["pressure"]["causing_mount"].as_str().unwrap_or("?"),
+                    live["pressure"]["urgency"].as_f64().unwrap_or(0.0),
+                    policy_mode.unwrap_or("unknown"),
+                );
+                if let Some(reason) = live["policy_diagnostics"]["fallback_reason"].as_str() {
+                    println!("  Policy fallback reason: {reason}");
+                }
+            } else if daemon_running {
                 println!("  Daemon: running");
             } else {
                 println!("  Daemon: not running (degraded mode)");
@@ -6534,6 +7438,8 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
             let mut overall_level = "green";
             let mut snapshot_warnings = Vec::new();
             let mut purgeable_notices = Vec::new();
+            let mut inode_lines = Vec::new();
+            let mut fs_snapshot_lines = Vec::new();
             for mount in &mounts {
                 let Ok(capacity) = platform.capacity(&mount.path) else {
                     continue;
@@ -6547,16 +7453,39 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
                 }
 
                 let free_pct = capacity_free_pct(&capacity);
-                let level = pressure_level_str(free_pct, &config);
+                let thresholds = config.pressure.thresholds_for(
+                    &capacity.mount_point.to_string_lossy(),
+                    capacity.total_bytes,
+                );
+                let level = pressure_level_str(free_pct, &thresholds);
                 if pressure_severity(level) > pressure_severity(overall_level) {
                     overall_level = level;
                 }
+                if let Some((inode_free_pct, inode_level)) =
+                    inode_pressure_level(&capacity, &config.pressure.inodes)
+                {
+                    if pressure_severity(inode_level) > pressure_severity(overall_level) {
+                        overall_level = inode_level;
+                    }
+                    inode_lines.push(format!(
+                        "{:<20}  {:>12}  {:>12}  {:>6.1}%  {:<10}",
+                        mount.path.display(),
+                        capacity.total_inodes,
+                        capacity.free_inodes,
+                        inode_free_pct,
+                        inode_level.to_uppercase(),
+                    ));
+                }
                 if let Some(warning) = local_snapshot_warning(&capacity) {
                     snapshot_warnings.push(warning);
                 }
                 if let Some(notice) = purgeable_storage_notice(&capacity) {
                     purgeable_notices.push(notice);
                 }
+                let fs_snapshots = mount_filesystem_snapshots(platform.as_ref(), mount);
+                if let Some(line) = fs_snapshot_summary(&mount.path, &fs_snapshots) {
+                    fs_snapshot_lines.push(line);
+                }
 
                 let ram_note = if platform.is_ram_backed(&mount.path).unwrap_or(false) {
                     " (tmpfs)"
@@ -6574,6 +7503,17 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
                 );
             }
 
+            if !inode_lines.is_empty() {
+                println!("\nInodes:");
+                println!(
+                    "  {:<20}  {:>12}  {:>12}  {:>7}  {:<10}",
+                    "Mount Point", "Total", "Free", "Free %", "Level"
+                );
+                for line in inode_lines {
+                    println!("  {line}");
+                }
+            }
+
             if !snapshot_warnings.is_empty() {
                 println!("\nLocal Snapshots:");
                 for warning in snapshot_warnings {
@@ -6581,6 +7521,16 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
                 }
             }
 
+            if !fs_snapshot_lines.is_empty() {
+                println!("\nFilesystem Snapshots:");
+                for line in fs_snapshot_lines {
+                    println!("  {line}");
+                }
+                println!(
+                    "  Snapshots keep deleted files' blocks allocated; cleanup frees nothing they still reference."
+                );
+            }
+
             if !purgeable_notices.is_empty() {
                 println!("\nPurgeable Storage:");
                 for notice in purgeable_notices {
@@ -6710,6 +7660,23 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
                 );
             }
 
+            let reservations = status_reservations(&config, live_status.as_ref());
+            if !reservations.is_empty() {
+                println!("\nReservations:");
+                for lease in &reservations {
+                    println!("  {}", reservation_line(lease));
+                }
+            }
+
+            let deleted_open =
+                status_deleted_open_files(&config, platform.as_ref(), live_status.as_ref());
+            if !deleted_open.is_empty() {
+                println!("\nDeleted but open:");
+                for file in &deleted_open {
+                    println!("  {}", deleted_open_line(file));
+                }
+            }
+
             // Recent activity from database.
             if let Some(stats) = &db_stats {
                 println!("\nRecent Activity (last hour):");
@@ -6744,12 +7711,33 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
                     continue;
                 }
                 let free_pct = capacity_free_pct(&capacity);
-                let level = pressure_level_str(free_pct, &config);
+                let thresholds = config.pressure.thresholds_for(
+                    &capacity.mount_point.to_string_lossy(),
+                    capacity.total_bytes,
+                );
+                let level = pressure_level_str(free_pct, &thresholds);
                 if pressure_severity(level) > pressure_severity(overall_level) {
                     overall_level = level;
                 }
+                if let Some((_, inode_level)) =
+                    inode_pressure_level(&capacity, &config.pressure.inodes)
+                    && pressure_severity(inode_level) > pressure_severity(overall_level)
+                {
+                    overall_level = inode_level;
+                }
 
-                mounts_json.push(status_mount_json(&capacity, level, free_pct));
+                let mut entry = status_mount_json(&capacity, level, free_pct);
+                entry["thresholds"] = json!({
+                    "green_min_free_pct": thresholds.green_min_free_pct,
+                    "yellow_min_free_pct": thresholds.yellow_min_free_pct,
+                    "orange_min_free_pct": thresholds.orange_min_free_pct,
+                    "red_min_free_pct": thresholds.red_min_free_pct,
+                });
+                entry["inodes"] = status_inode_json(&capacity, &config.pressure.inodes);
+                entry["filesystem_snapshots"] = filesystem_snapshots_json(
+                    &mount_filesystem_snapshots(platform.as_ref(), mount),
+                );
+                mounts_json.push(entry);
             }
 
             let recent = db_stats.as_ref().map(|s| {
@@ -6807,8 +7795,11 @@ fn render_status(cli: &Cli) -> Result<(), CliError> {
                 "process_attribution": {
                     "visibility": process_visibility.as_ref().map(process_attribution_visibility_json),
                 },
+                "reservations": status_reservations(&config, live_status.as_ref()),
+                "deleted_open_files": status_deleted_open_files(&config, platform.as_ref(), live_status.as_ref()),
                 "recent_hour": recent,
-                "policy_mode": daemon_state.as_ref().and_then(|s| s.get("policy_mode")).and_then(|v| v.as_str()),
+                "policy_mode": policy_mode,
+                "daemon_live": live_status,
             });
             write_json_line(&payload)?;
         }
@@ -6972,15 +7963,168 @@ fn detect_daemon_running_fallback() -> bool {
     false
 }
 
-/// Map free percentage to pressure level string.
-fn pressure_level_str(free_pct: f64, config: &Config) -> &'static str {
-    if free_pct >= config.pressure.green_min_free_pct {
+/// Send one request to the daemon's control socket.
+///
+/// `Ok(None)` means no daemon is listening, so the caller should act
+/// directly. A socket we may not open is an error: a daemon is running and
+/// acting behind its back would race it. Once connected, a broken exchange is
+/// an error rather than a silent fallback: the daemon may already be acting.
+#[cfg(unix)]
+fn daemon_control_request(
+    config: &Config,
+    request: &ControlRequest,
+) -> Result<Option<ControlResponse>, CliError> {
+    let socket = config.paths.control_socket();
+    match send_request(&socket, request, CONTROL_CLIENT_TIMEOUT) {
+        Ok(response) => Ok(Some(response)),
+        Err(e)
+            if matches!(
+                e.kind(),
+                io::ErrorKind::NotFound | io::ErrorKind::ConnectionRefused
+            ) =>
+        {
+            Ok(None)
+        }
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => Err(CliError::User(format!(
+            "permission denied on daemon control socket {}; run as the daemon's user or \
+             set [control] socket_group and socket_mode to grant access",
+            socket.display()
+        ))),
+        Err(e) => Err(CliError::Runtime(format!(
+            "daemon control socket {}: {e}",
+            socket.display()
+        ))),
+    }
+}
+
+/// Live status and policy diagnostics from a reachable daemon, if any.
+#[cfg(unix)]
+fn daemon_live_status(config: &Config) -> Option<Value> {
+    let status = daemon_control_request(config, &ControlRequest::Status)
+        .ok()
+        .flatten()
+        .filter(|reply| reply.ok)?;
+    let diagnostics = daemon_control_request(config, &ControlRequest::PolicyDiagnostics)
+        .ok()
+        .flatten()
+        .filter(|reply| reply.ok)
+        .map(|reply| reply.result);
+    let mut live = status.result;
+    if let Some(object) = live.as_object_mut() {
+        object.remove("event");
+        object.insert(
+            "policy_diagnostics".to_string(),
+            diagnostics.unwrap_or(Value::Null),
+        );
+    }
+    Some(live)
+}
+
+#[cfg(not(unix))]
+fn daemon_live_status(_config: &Config) -> Option<Value> {
+    None
+}
+
+#[cfg(unix)]
+fn render_daemon_ballast_release(
+    cli: &Cli,
+    count: usize,
+    reply: ControlResponse,
+) -> Result<(), CliError> {
+    if !reply.ok {
+        return Err(CliError::User(format!(
+            "daemon declined ballast release: {}",
+            reply.error.unwrap_or_default()
+        )));
+    }
+    let result = reply.result;
+    let released = result["files_released"].as_u64().unwrap_or(0);
+    let errors = result["errors"].as_array().cloned().unwrap_or_default();
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!("Ballast release complete (via daemon):");
+            println!("  Mount: {}", result["mount"].as_str().unwrap_or("unknown"));
+            println!("  Files released: {released} of {count} requested");
+            println!(
+                "  Bytes freed: {}",
+                format_bytes(result["bytes_freed"].as_u64().unwrap_or(0))
+            );
+            println!(
+                "  Remaining: {} files",
+                result["remaining"].as_u64().unwrap_or(0)
+            );
+            for (label, key) in [("Warnings", "warnings"), ("Errors", "errors")] {
+                let Some(items) = result[key].as_array().filter(|items| !items.is_empty()) else {
+                    continue;
+                };
+                println!("  {label}:");
+                for item in items {
+                    eprintln!("    {}", item.as_str().unwrap_or_default());
+                }
+            }
+        }
+        OutputMode::Json => {
+            let mut payload = json!({
+                "command": "ballast release",
+                "via": "daemon",
+                "requested": count,
+            });
+            if let (Some(payload), Some(result)) = (payload.as_object_mut(), result.as_object()) {
+                payload.extend(result.clone());
+            }
+            write_json_line(&payload)?;
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::Partial(format!(
+            "{} errors during release",
+            errors.len()
+        )))
+    }
+}
+
+#[cfg(unix)]
+fn render_daemon_scan(cli: &Cli, reply: ControlResponse) -> Result<(), CliError> {
+    if !reply.ok {
+        return Err(CliError::Runtime(format!(
+            "daemon declined scan request: {}",
+            reply.error.unwrap_or_default()
+        )));
+    }
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!("Scan queued by the running daemon:");
+            for path in reply.result["paths"].as_array().into_iter().flatten() {
+                println!("  {}", path.as_str().unwrap_or_default());
+            }
+            println!("Follow progress with `sbh log --follow` or `sbh explain --since 10m`.");
+        }
+        OutputMode::Json => {
+            let payload = json!({
+                "command": "scan",
+                "via": "daemon",
+                "queued": reply.result["queued"],
+                "paths": reply.result["paths"],
+            });
+            write_json_line(&payload)?;
+        }
+    }
+    Ok(())
+}
+
+/// Map free percentage to pressure level string using a mount's effective thresholds.
+fn pressure_level_str(free_pct: f64, thresholds: &PressureThresholds) -> &'static str {
+    if free_pct >= thresholds.green_min_free_pct {
         "green"
-    } else if free_pct >= config.pressure.yellow_min_free_pct {
+    } else if free_pct >= thresholds.yellow_min_free_pct {
         "yellow"
-    } else if free_pct >= config.pressure.orange_min_free_pct {
+    } else if free_pct >= thresholds.orange_min_free_pct {
         "orange"
-    } else if free_pct >= config.pressure.red_min_free_pct {
+    } else if free_pct >= thresholds.red_min_free_pct {
         "red"
     } else {
         "critical"
@@ -7002,9 +8146,33 @@ fn capacity_free_pct(capacity: &Capacity) -> f64 {
     bytes_to_pct(capacity.available_bytes, capacity.total_bytes)
 }
 
-fn status_mount_json(capacity: &Capacity, level: &str, free_pct: f64) -> Value {
-    json!({
-        "path": capacity.mount_point.to_string_lossy(),
+/// Inode free percentage and level, or `None` when the filesystem has no
+/// fixed inode table or inode pressure is disabled.
+fn inode_pressure_level(
+    capacity: &Capacity,
+    config: &InodePressureConfig,
+) -> Option<(f64, &'static str)> {
+    if !config.enabled || capacity.total_inodes == 0 {
+        return None;
+    }
+    let free_pct = bytes_to_pct(capacity.free_inodes, capacity.total_inodes);
+    Some((free_pct, pressure_level_str(free_pct, &config.thresholds())))
+}
+
+fn status_inode_json(capacity: &Capacity, config: &InodePressureConfig) -> Value {
+    inode_pressure_level(capacity, config).map_or(Value::Null, |(free_pct, level)| {
+        json!({
+            "total": capacity.total_inodes,
+            "free": capacity.free_inodes,
+            "free_pct": free_pct,
+            "level": level,
+        })
+    })
+}
+
+fn status_mount_json(capacity: &Capacity, level: &str, free_pct: f64) -> Value {
+    json!({
+        "path": capacity.mount_point.to_string_lossy(),
         "total": capacity.total_bytes,
         "free": capacity.available_bytes,
         "free_pct": free_pct,
@@ -7113,6 +8281,86 @@ fn local_snapshot_warning(capacity: &Capacity) -> Option<String> {
     ))
 }
 
+/// btrfs/ZFS snapshots of `mount`; empty on other filesystems or when the
+/// snapshot tools are missing or refuse an unprivileged caller.
+fn mount_filesystem_snapshots(platform: &dyn Platform, mount: &MountPoint) -> Vec<FsSnapshotInfo> {
+    if !matches!(mount.fs_type.as_str(), "btrfs" | "zfs") {
+        return Vec::new();
+    }
+    platform
+        .filesystem_snapshots(&mount.path)
+        .unwrap_or_default()
+}
+
+/// Snapshots on the btrfs/ZFS volumes holding `roots`, one entry per volume
+/// that has any.
+fn scan_root_filesystem_snapshots(roots: &[PathBuf]) -> Vec<(PathBuf, Vec<FsSnapshotInfo>)> {
+    let Ok(platform) = detect_platform() else {
+        return Vec::new();
+    };
+    let Ok(mounts) = platform.mount_points() else {
+        return Vec::new();
+    };
+    let mut volumes: Vec<&MountPoint> = Vec::new();
+    for root in roots {
+        let Some(mount) = mounts
+            .iter()
+            .filter(|mount| root.starts_with(&mount.path))
+            .max_by_key(|mount| mount.path.as_os_str().len())
+        else {
+            continue;
+        };
+        if !volumes.iter().any(|seen| seen.path == mount.path) {
+            volumes.push(mount);
+        }
+    }
+    volumes
+        .into_iter()
+        .map(|mount| {
+            (
+                mount.path.clone(),
+                mount_filesystem_snapshots(platform.as_ref(), mount),
+            )
+        })
+        .filter(|(_, snapshots)| !snapshots.is_empty())
+        .collect()
+}
+
+fn fs_snapshot_summary(mount: &Path, snapshots: &[FsSnapshotInfo]) -> Option<String> {
+    let first = snapshots.first()?;
+    let managed = snapshots
+        .iter()
+        .filter(|snapshot| snapshot.is_managed())
+        .count();
+    let exclusive = fs_snapshot_exclusive_bytes(snapshots).map_or_else(
+        || "exclusive size unknown (enable btrfs quotas)".to_string(),
+        |bytes| format!("~{} exclusive", format_bytes(bytes)),
+    );
+    Some(format!(
+        "{}: {} {} snapshot(s), {managed} managed by snapper/zfs-auto-snapshot, {exclusive}",
+        mount.display(),
+        snapshots.len(),
+        first.kind,
+    ))
+}
+
+/// Sum of exclusive bytes, or `None` when no snapshot reports a size.
+fn fs_snapshot_exclusive_bytes(snapshots: &[FsSnapshotInfo]) -> Option<u64> {
+    snapshots
+        .iter()
+        .filter_map(|snapshot| snapshot.exclusive_bytes)
+        .reduce(u64::saturating_add)
+}
+
+fn filesystem_snapshots_json(snapshots: &[FsSnapshotInfo]) -> Value {
+    json!({
+        "count": snapshots.len(),
+        "managed_count": snapshots.iter().filter(|snapshot| snapshot.is_managed()).count(),
+        "exclusive_bytes": fs_snapshot_exclusive_bytes(snapshots),
+        "snapshots": snapshots,
+    })
+}
+
 fn local_snapshot_reclaim_command(capacity: &Capacity) -> Option<String> {
     capacity.local_snapshot_bytes.filter(|bytes| *bytes > 0)?;
     Some(local_snapshot_thin_shell_command(&capacity.mount_point))
@@ -7418,6 +8666,9 @@ fn run_unprotect(cli: &Cli, args: &UnprotectArgs) -> Result<(), CliError> {
 #[derive(Debug, Clone)]
 struct ScoredScanEntry {
     score: CandidacyScore,
+    /// Logical size before hard-link and sparse-file accounting; `score.size_bytes`
+    /// is the reclaimable estimate.
+    apparent_bytes: u64,
     trace: ScanTrace,
 }
 
@@ -7621,6 +8872,7 @@ fn scan_entry_json(entry: &ScoredScanEntry, explain: bool) -> Value {
     let mut item = json!({
         "path": candidate.path.to_string_lossy(),
         "size_bytes": candidate.size_bytes,
+        "apparent_bytes": entry.apparent_bytes,
         "age_seconds": candidate.age.as_secs(),
         "total_score": candidate.total_score,
         "category": format!("{:?}", candidate.classification.category),
@@ -7683,6 +8935,22 @@ fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), CliError> {
     if root_paths.is_empty() {
         return Err(CliError::User("no valid scan paths found".to_string()));
     }
+
+    if args.daemon {
+        #[cfg(unix)]
+        if let Some(reply) = daemon_control_request(
+            &config,
+            &ControlRequest::Scan {
+                paths: root_paths,
+            },
+        )? {
+            return render_daemon_scan(cli, reply);
+        }
+        return Err(CliError::User(
+            "no daemon answered on the control socket; run `sbh scan` without --daemon for a local report"
+                .to_string(),
+        ));
+    }
     let scan_roots = root_paths.clone();
     let selected_scanner_engine = SelectedScannerEngine::for_mode(config.scanner.engine);
     let scanner_engine_mode = selected_scanner_engine.mode();
@@ -7722,7 +8990,7 @@ fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), CliError> {
     let dir_count = entries.len();
 
     // Classify and score each entry with active-reference evidence attached.
-    let registry = ArtifactPatternRegistry::default();
+    let registry = ArtifactPatternRegistry::from_config(&config.scanner);
     let engine = ScoringEngine::from_config(&config.scoring, config.scanner.min_file_age_minutes);
     let sacred_paths = active_sacred_paths(&config)?;
     let now = SystemTime::now();
@@ -7755,15 +9023,22 @@ fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), CliError> {
             let age = now
                 .duration_since(entry.metadata.effective_age_timestamp())
                 .unwrap_or_default();
+            let (size_bytes, shared_bytes) = cow_extents::discount_shared(
+                &entry.path,
+                entry.metadata.is_dir,
+                entry.metadata.content_size_bytes,
+            );
             let mut candidate = CandidateInput {
                 path: entry.path.clone(),
-                size_bytes: entry.metadata.content_size_bytes,
+                size_bytes,
                 age,
                 classification,
                 signals: entry.structural_signals,
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes,
             };
 
             let cheap_score = engine.score_candidate(&candidate, 0.0);
@@ -7806,7 +9081,11 @@ fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), CliError> {
                 active_reference_checked,
                 &sacred_overlaps,
             );
-            Some(ScoredScanEntry { score, trace })
+            Some(ScoredScanEntry {
+                score,
+                apparent_bytes: entry.metadata.apparent_size_bytes,
+                trace,
+            })
         })
         .collect::<Vec<_>>();
 
@@ -7851,7 +9130,9 @@ fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), CliError> {
         .zip(current_process_cpu_micros())
         .map(|(start, end)| end.saturating_sub(start));
     let total_reclaimable: u64 = candidates.iter().map(|entry| entry.score.size_bytes).sum();
+    let total_apparent: u64 = candidates.iter().map(|entry| entry.apparent_bytes).sum();
     let total_reported: u64 = report_only.iter().map(|entry| entry.score.size_bytes).sum();
+    let fs_snapshots = scan_root_filesystem_snapshots(&scan_roots);
 
     match output_mode(cli) {
         OutputMode::Human => {
@@ -7871,10 +9152,10 @@ fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), CliError> {
                 println!("  No candidates found above threshold.");
             } else {
                 println!(
-                    "  {:>3}  {:<50}  {:>10}  {:>10}  {:>6}  {:<12}",
-                    "#", "Path", "Size", "Age", "Score", "Type"
+                    "  {:>3}  {:<50}  {:>10}  {:>10}  {:>6}  {:<12}  {:<20}",
+                    "#", "Path", "Size", "Age", "Score", "Type", "Pattern"
                 );
-                println!("  {}", "-".repeat(100));
+                println!("  {}", "-".repeat(122));
 
                 for (i, entry) in candidates.iter().enumerate() {
                     let candidate = &entry.score;
@@ -7885,17 +9166,22 @@ fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), CliError> {
                     let path_str = truncate_path(&candidate.path, 50);
 
                     println!(
-                        "  {:>3}  {:<50}  {:>10}  {:>10}  {:>6.2}  {:<12}",
+                        "  {:>3}  {:<50}  {:>10}  {:>10}  {:>6.2}  {:<12}  {:<20}",
                         i + 1,
                         path_str,
                         size_str,
                         age_str,
                         candidate.total_score,
                         type_str,
+                        truncate_str(&candidate.classification.pattern_name, 20),
                     );
                 }
                 println!();
-                println!("  Total reclaimable: {}", format_bytes(total_reclaimable));
+                println!(
+                    "  Total reclaimable: {} (apparent {}; shared hard links and sparse holes excluded)",
+                    format_bytes(total_reclaimable),
+                    format_bytes(total_apparent),
+                );
                 println!("  Use 'sbh clean' to delete these candidates.");
             }
 
@@ -7924,6 +9210,18 @@ fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), CliError> {
                 println!("  Report-only total: {}", format_bytes(total_reported));
             }
 
+            if !fs_snapshots.is_empty() {
+                println!("\n  Filesystem snapshots on scanned volumes:");
+                for (mount, snapshots) in &fs_snapshots {
+                    if let Some(line) = fs_snapshot_summary(mount, snapshots) {
+                        println!("    {line}");
+                    }
+                }
+                println!(
+                    "  Space referenced by these snapshots is not freed by deleting candidates."
+                );
+            }
+
             if args.explain {
                 if !candidates.is_empty() {
                     println!("\n  Confidence trace:");
@@ -7980,10 +9278,19 @@ fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), CliError> {
                 "min_score": args.min_score,
                 "candidates_count": entries_json.len(),
                 "total_reclaimable_bytes": total_reclaimable,
+                "total_apparent_bytes": total_apparent,
                 "report_only_count": report_only_json.len(),
                 "report_only_bytes": total_reported,
                 "candidates": entries_json,
                 "report_only": report_only_json,
+                "filesystem_snapshots": fs_snapshots
+                    .iter()
+                    .map(|(mount, snapshots)| {
+                        let mut entry = filesystem_snapshots_json(snapshots);
+                        entry["mount"] = json!(mount.to_string_lossy());
+                        entry
+                    })
+                    .collect::<Vec<_>>(),
             });
 
             if args.explain {
@@ -8110,7 +9417,7 @@ fn run_clean(cli: &Cli, args: &CleanArgs) -> Result<(), CliError> {
     // Classify and score each entry with active-reference evidence already
     // attached, so in-use artifacts are vetoed before the deletion plan.
     // Also apply CLI min_score override to the engine config.
-    let registry = ArtifactPatternRegistry::default();
+    let registry = ArtifactPatternRegistry::from_config(&config.scanner);
     let mut scoring_config = config.scoring.clone();
     scoring_config.min_score = args.min_score;
     let engine = ScoringEngine::from_config(&scoring_config, config.scanner.min_file_age_minutes);
@@ -8127,15 +9434,22 @@ fn run_clean(cli: &Cli, args: &CleanArgs) -> Result<(), CliError> {
             let age = now
                 .duration_since(entry.metadata.effective_age_timestamp())
                 .unwrap_or_default();
+            let (size_bytes, shared_bytes) = cow_extents::discount_shared(
+                &entry.path,
+                entry.metadata.is_dir,
+                entry.metadata.content_size_bytes,
+            );
             let mut candidate = CandidateInput {
                 path: entry.path.clone(),
-                size_bytes: entry.metadata.content_size_bytes,
+                size_bytes,
                 age,
                 classification,
                 signals: entry.structural_signals,
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes,
             };
             let cheap_score = engine.score_candidate(&candidate, 0.0);
             if !cheap_score.vetoed && cheap_score.total_score >= args.min_score {
@@ -8963,18 +10277,35 @@ fn run_check(cli: &Cli, args: &CheckArgs) -> Result<(), CliError> {
 
     let free_pct = capacity_free_pct(&capacity);
     let config = Config::load(cli.config.as_deref()).unwrap_or_default();
-    let threshold_pct = args
-        .target_free
-        .unwrap_or(config.pressure.yellow_min_free_pct);
+    let threshold_pct = args.target_free.unwrap_or_else(|| {
+        config
+            .pressure
+            .thresholds_for(
+                &capacity.mount_point.to_string_lossy(),
+                capacity.total_bytes,
+            )
+            .yellow_min_free_pct
+    });
+
+    // Space already promised to `sbh reserve` leases on this volume is not
+    // free for us: `--need` is a non-blocking, lease-free reservation check.
+    let reserved_bytes =
+        reserved_bytes_on_mount(&config, &capacity.mount_point, capacity.available_bytes);
+    let unreserved_bytes = capacity.available_bytes.saturating_sub(reserved_bytes);
 
     // Check 1: absolute free space requirement.
     if let Some(need_bytes) = args.need
-        && capacity.available_bytes < need_bytes
+        && unreserved_bytes < need_bytes
     {
         match output_mode(cli) {
             OutputMode::Human => {
+                let reserved_note = if reserved_bytes > 0 {
+                    format!(" ({} reserved)", format_bytes(reserved_bytes))
+                } else {
+                    String::new()
+                };
                 eprintln!(
-                    "sbh: {} has {} free but {} required. Run: sbh emergency {}",
+                    "sbh: {} has {} free{reserved_note} but {} required. Run: sbh emergency {}",
                     capacity.mount_point.display(),
                     format_bytes(capacity.available_bytes),
                     format_bytes(need_bytes),
@@ -8988,6 +10319,7 @@ fn run_check(cli: &Cli, args: &CheckArgs) -> Result<(), CliError> {
                     "path": check_path.to_string_lossy(),
                     "mount_point": capacity.mount_point.to_string_lossy(),
                     "free_bytes": capacity.available_bytes,
+                    "reserved_bytes": reserved_bytes,
                     "total_bytes": capacity.total_bytes,
                     "need_bytes": need_bytes,
                     "free_pct": free_pct,
@@ -9080,106 +10412,657 @@ fn run_check(cli: &Cli, args: &CheckArgs) -> Result<(), CliError> {
                 ballast.unreadable_count, ballast.configured_count,
             );
         }
-    }
+    }
+
+    // Check 3: prediction from daemon state.json (if available and --predict requested).
+    if let Some(predict_minutes) = args.predict {
+        match read_daemon_prediction(&config.paths.state_file, &capacity.mount_point) {
+            Some(rate_bps) if rate_bps > 0.0 => {
+                // Positive rate means filling; estimate time to threshold.
+                let bytes_until_threshold = capacity
+                    .available_bytes
+                    .saturating_sub((threshold_pct / 100.0 * capacity.total_bytes as f64) as u64);
+                let seconds_left = bytes_until_threshold as f64 / rate_bps;
+                let minutes_left = seconds_left / 60.0;
+
+                if minutes_left < predict_minutes as f64 {
+                    match output_mode(cli) {
+                        OutputMode::Human => {
+                            eprintln!(
+                                "sbh: {} has {} free but predicted full in {:.0} min (need {} min)",
+                                capacity.mount_point.display(),
+                                format_bytes(capacity.available_bytes),
+                                minutes_left,
+                                predict_minutes,
+                            );
+                        }
+                        OutputMode::Json => {
+                            let payload = json!({
+                                "command": "check",
+                                "status": "warning",
+                                "path": check_path.to_string_lossy(),
+                                "mount_point": capacity.mount_point.to_string_lossy(),
+                                "free_bytes": capacity.available_bytes,
+                                "total_bytes": capacity.total_bytes,
+                                "free_pct": free_pct,
+                                "rate_bytes_per_sec": rate_bps,
+                                "minutes_until_full": minutes_left,
+                                "predict_minutes": predict_minutes,
+                                "container_id": capacity.container_id.as_deref(),
+                                "container_total_bytes": capacity.container_total_bytes,
+                                "container_available_bytes": capacity.container_available_bytes,
+                                "volume_total_bytes": capacity.volume_total_bytes,
+                                "volume_available_bytes": capacity.volume_available_bytes,
+                                "volume_role": capacity.volume_role.as_deref(),
+                                "free_excludes_purgeable": true,
+                                "platform": capacity_platform_json(&capacity),
+                                "exit_code": 1,
+                            });
+                            write_json_line(&payload)?;
+                        }
+                    }
+                    return Err(CliError::User(
+                        "predicted disk full within window".to_string(),
+                    ));
+                }
+            }
+            _ => {
+                // No prediction available — daemon not running or not filling.
+                // This is not an error, just degraded mode.
+            }
+        }
+    }
+
+    // All checks passed — silent success on human mode.
+    if output_mode(cli) == OutputMode::Json {
+        let payload = json!({
+            "command": "check",
+            "status": "ok",
+            "path": check_path.to_string_lossy(),
+            "mount_point": capacity.mount_point.to_string_lossy(),
+            "free_bytes": capacity.available_bytes,
+            "reserved_bytes": reserved_bytes,
+            "total_bytes": capacity.total_bytes,
+            "free_pct": free_pct,
+            "container_id": capacity.container_id.as_deref(),
+            "container_total_bytes": capacity.container_total_bytes,
+            "container_available_bytes": capacity.container_available_bytes,
+            "volume_total_bytes": capacity.volume_total_bytes,
+            "volume_available_bytes": capacity.volume_available_bytes,
+            "volume_role": capacity.volume_role.as_deref(),
+            "free_excludes_purgeable": true,
+            "platform": capacity_platform_json(&capacity),
+            // #16: machine-readable reserve state so automation can flag a
+            // configured-but-empty emergency reserve.
+            "ballast": {
+                "configured_pool_bytes": ballast.configured_pool_bytes,
+                "releasable_bytes": ballast.releasable_bytes,
+                "available_count": ballast.available_count,
+                "missing_count": ballast.missing_count,
+                // Non-zero means this snapshot is NOT authoritative (usually an
+                // unprivileged caller against a root-owned, mode-700 dir).
+                // Automation must not treat `missing_count` as a real absence
+                // while this is > 0.
+                "unreadable_count": ballast.unreadable_count,
+                "authoritative": ballast.is_authoritative(),
+                "health": ballast.health.as_str(),
+            },
+            "exit_code": 0,
+        });
+        write_json_line(&payload)?;
+    }
+
+    Ok(())
+}
+
+/// Outstanding bytes of unexpired `sbh reserve` leases on `mount`, from the
+/// ledger the daemon persists on every change.
+fn reserved_bytes_on_mount(config: &Config, mount: &Path, current_free: u64) -> u64 {
+    let now = reservations::unix_now();
+    read_reservations(&config.paths.reservations_file())
+        .iter()
+        .filter(|lease| lease.mount == mount && !lease.is_expired(now))
+        .map(|lease| lease.outstanding_bytes(current_free))
+        .fold(0, u64::saturating_add)
+}
+
+/// Active leases for `sbh status`: the daemon's live view when reachable,
+/// otherwise the persisted ledger (outstanding bytes unknown).
+fn status_reservations(config: &Config, live_status: Option<&Value>) -> Vec<Value> {
+    if let Some(live) = live_status.and_then(|live| live["reservations"].as_array()) {
+        return live.clone();
+    }
+    let now = reservations::unix_now();
+    read_reservations(&config.paths.reservations_file())
+        .iter()
+        .filter(|lease| !lease.is_expired(now))
+        .map(|lease| lease.status_json(None, now))
+        .collect()
+}
+
+/// Deleted-but-open files for `sbh status`: the daemon's latest sweep when
+/// reachable, otherwise a local enumeration (complete only as root).
+fn status_deleted_open_files(
+    config: &Config,
+    platform: &dyn Platform,
+    live_status: Option<&Value>,
+) -> Vec<Value> {
+    if let Some(live) = live_status.and_then(|live| live["deleted_open_files"].as_array()) {
+        return live.clone();
+    }
+    local_deleted_open_files(platform)
+        .iter()
+        .take(STATUS_DELETED_OPEN_LIMIT)
+        .map(|file| file.status_json(&config.scanner.deleted_open_files))
+        .collect()
+}
+
+/// Deleted-but-open files visible to this process, attributed to mounts.
+fn local_deleted_open_files(platform: &dyn Platform) -> Vec<HeldDeletedFile> {
+    let Ok(files) = platform.deleted_open_files() else {
+        return Vec::new();
+    };
+    let mounts: Vec<PathBuf> = platform
+        .mount_points()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|mount| mount.path)
+        .collect();
+    group_deleted_open_files(files, &mounts)
+}
+
+/// One human-readable line for a deleted-but-open file's status JSON.
+fn deleted_open_line(file: &Value) -> String {
+    let holders: Vec<String> = file["holders"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|holder| {
+            let pid = holder["pid"].as_i64().unwrap_or(0);
+            holder["process"].as_str().map_or_else(
+                || format!("pid {pid}"),
+                |name| format!("pid {pid} ({name})"),
+            )
+        })
+        .collect();
+    let verdict = file["truncation_veto"]
+        .as_str()
+        .map_or_else(|| "truncatable".to_string(), |veto| format!("kept: {veto}"));
+    format!(
+        "{:>10}  {}  held by {}  [{verdict}]",
+        format_bytes(file["allocated_bytes"].as_u64().unwrap_or(0)),
+        file["path"].as_str().unwrap_or("?"),
+        holders.join(", "),
+    )
+}
+
+/// One human-readable line for a reservation's status JSON.
+fn reservation_line(lease: &Value) -> String {
+    let outstanding = lease["outstanding_bytes"]
+        .as_u64()
+        .map(|bytes| format!(", {} outstanding", format_bytes(bytes)))
+        .unwrap_or_default();
+    let holder = lease["holder"]
+        .as_str()
+        .map(|holder| format!(", holder {holder}"))
+        .unwrap_or_default();
+    format!(
+        "{}  {:<7}  {} on {}{outstanding}{holder}, expires in {}",
+        lease["id"].as_str().unwrap_or("?"),
+        lease["state"].as_str().unwrap_or("?"),
+        format_bytes(lease["bytes"].as_u64().unwrap_or(0)),
+        lease["mount"].as_str().unwrap_or("?"),
+        format_duration(std::time::Duration::from_secs(
+            lease["expires_in_secs"].as_u64().unwrap_or(0)
+        )),
+    )
+}
+
+#[cfg(unix)]
+fn reservation_control_request(
+    config: &Config,
+    request: &ControlRequest,
+) -> Result<Value, CliError> {
+    let reply = daemon_control_request(config, request)?.ok_or_else(|| {
+        CliError::User(format!(
+            "no sbh daemon is listening on {}; reservations need a running daemon \
+             (`sbh check --need SIZE` performs a one-off check without one)",
+            config.paths.control_socket().display()
+        ))
+    })?;
+    if reply.ok {
+        Ok(reply.result)
+    } else {
+        Err(CliError::User(format!(
+            "daemon declined reservation request: {}",
+            reply.error.unwrap_or_default()
+        )))
+    }
+}
+
+/// Poll a pending lease until the daemon grants it or `wait` runs out.
+/// Returns the lease as last reported, granted or not.
+#[cfg(unix)]
+fn wait_for_reservation_grant(
+    cli: &Cli,
+    config: &Config,
+    mut lease: Value,
+    wait: Duration,
+) -> Result<Value, CliError> {
+    let id = lease["id"].as_str().unwrap_or_default().to_string();
+    let deadline = std::time::Instant::now() + wait;
+    let mut announced = false;
+    while lease["state"] != "granted" && std::time::Instant::now() < deadline {
+        if !announced && output_mode(cli) == OutputMode::Human {
+            eprintln!(
+                "sbh: waiting up to {} for the daemon to free space on {}...",
+                format_duration(wait),
+                lease["mount"].as_str().unwrap_or("?")
+            );
+            announced = true;
+        }
+        std::thread::sleep(RESERVE_POLL_INTERVAL);
+        let result = reservation_control_request(config, &ControlRequest::Reservations)?;
+        let Some(current) = result["reservations"]
+            .as_array()
+            .and_then(|leases| leases.iter().find(|lease| lease["id"] == id.as_str()))
+        else {
+            return Err(CliError::Runtime(format!(
+                "reservation {id} expired or was released while waiting"
+            )));
+        };
+        lease = current.clone();
+    }
+    Ok(lease)
+}
+
+/// `sbh reserve`: lease free space from the daemon, waiting (up to `--wait`)
+/// for it to free enough space to grant the lease.
+#[cfg(unix)]
+#[allow(clippy::too_many_lines)]
+fn run_reserve(cli: &Cli, args: &ReserveArgs) -> Result<(), CliError> {
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+
+    if args.list {
+        let result = reservation_control_request(&config, &ControlRequest::Reservations)?;
+        let leases = result["reservations"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        match output_mode(cli) {
+            OutputMode::Human if leases.is_empty() => println!("No active reservations."),
+            OutputMode::Human => {
+                for lease in &leases {
+                    println!("{}", reservation_line(lease));
+                }
+            }
+            OutputMode::Json => write_json_line(&json!({
+                "command": "reserve",
+                "action": "list",
+                "reservations": leases,
+            }))?,
+        }
+        return Ok(());
+    }
+
+    if let Some(id) = &args.release {
+        let lease = reservation_control_request(
+            &config,
+            &ControlRequest::ReleaseReservation { id: id.clone() },
+        )?;
+        match output_mode(cli) {
+            OutputMode::Human => println!(
+                "Released reservation {id} ({} on {}).",
+                format_bytes(lease["bytes"].as_u64().unwrap_or(0)),
+                lease["mount"].as_str().unwrap_or("?")
+            ),
+            OutputMode::Json => write_json_line(&json!({
+                "command": "reserve",
+                "action": "release",
+                "reservation": lease,
+            }))?,
+        }
+        return Ok(());
+    }
+
+    let Some(need_bytes) = args.need else {
+        return Err(CliError::User(
+            "--need is required to create a reservation".to_string(),
+        ));
+    };
+    let ttl = parse_window_duration(&args.ttl)?;
+    if ttl.is_zero() {
+        return Err(CliError::User(
+            "--ttl must be greater than zero".to_string(),
+        ));
+    }
+    let wait = parse_window_duration(&args.wait)?;
+    let requested_path = args
+        .path
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/")));
+    // The build may not have created its output directory yet; any existing
+    // ancestor resolves to the same volume.
+    let path =
+        nearest_existing_ancestor(&std::path::absolute(&requested_path).unwrap_or(requested_path));
+
+    let lease = reservation_control_request(
+        &config,
+        &ControlRequest::Reserve {
+            path,
+            bytes: need_bytes,
+            ttl_secs: ttl.as_secs(),
+            holder: args.holder.clone(),
+        },
+    )?;
+    let id = lease["id"].as_str().unwrap_or_default().to_string();
+    let lease = wait_for_reservation_grant(cli, &config, lease, wait)?;
+
+    let granted = lease["state"] == "granted";
+    if !granted && !wait.is_zero() {
+        // Don't leave a lease the caller has given up on inflating pressure.
+        let _ = reservation_control_request(&config, &ControlRequest::ReleaseReservation { id });
+        if output_mode(cli) == OutputMode::Json {
+            write_json_line(&json!({
+                "command": "reserve",
+                "status": "timeout",
+                "reservation": lease,
+                "exit_code": 2,
+            }))?;
+        }
+        return Err(CliError::Runtime(format!(
+            "could not reserve {} within {}; reservation released",
+            format_bytes(need_bytes),
+            format_duration(wait)
+        )));
+    }
+
+    match output_mode(cli) {
+        OutputMode::Human => {
+            println!(
+                "{} {} on {} for {} (id {id}).",
+                if granted { "Reserved" } else { "Requested" },
+                format_bytes(need_bytes),
+                lease["mount"].as_str().unwrap_or("?"),
+                format_duration(ttl),
+            );
+            if !granted {
+                println!("  Pending: the daemon grants it once enough space is free.");
+            }
+            println!("  Release early with: sbh reserve --release {id}");
+        }
+        OutputMode::Json => write_json_line(&json!({
+            "command": "reserve",
+            "status": if granted { "granted" } else { "pending" },
+            "reservation": lease,
+            "exit_code": 0,
+        }))?,
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn run_reserve(_cli: &Cli, _args: &ReserveArgs) -> Result<(), CliError> {
+    Err(CliError::User(
+        "sbh reserve needs the daemon control socket, which is only available on Unix; \
+         use `sbh check --need SIZE` instead"
+            .to_string(),
+    ))
+}
+
+/// Lease `--need` bytes for a wrapped command; fails if the daemon cannot
+/// grant it within `--wait`. Returns the lease id.
+#[cfg(unix)]
+fn acquire_run_reservation(
+    cli: &Cli,
+    config: &Config,
+    args: &RunArgs,
+    cwd: &Path,
+    holder: &str,
+) -> Result<String, CliError> {
+    let need_bytes = args.need.unwrap_or_default();
+    let ttl = parse_window_duration(&args.ttl)?;
+    if ttl.is_zero() {
+        return Err(CliError::User(
+            "--ttl must be greater than zero".to_string(),
+        ));
+    }
+    let wait = parse_window_duration(&args.wait)?;
+    let lease = reservation_control_request(
+        config,
+        &ControlRequest::Reserve {
+            path: cwd.to_path_buf(),
+            bytes: need_bytes,
+            ttl_secs: ttl.as_secs(),
+            holder: Some(holder.to_string()),
+        },
+    )?;
+    let id = lease["id"].as_str().unwrap_or_default().to_string();
+    let lease = wait_for_reservation_grant(cli, config, lease, wait)?;
+    if lease["state"] != "granted" {
+        release_run_reservation(config, Some(&id));
+        return Err(CliError::Runtime(format!(
+            "could not reserve {} within {}; not starting the command",
+            format_bytes(need_bytes),
+            format_duration(wait)
+        )));
+    }
+    Ok(id)
+}
+
+#[cfg(not(unix))]
+fn acquire_run_reservation(
+    _cli: &Cli,
+    _config: &Config,
+    _args: &RunArgs,
+    _cwd: &Path,
+    _holder: &str,
+) -> Result<String, CliError> {
+    Err(CliError::User(
+        "--reserve needs the daemon control socket, which is only available on Unix".to_string(),
+    ))
+}
+
+/// Best-effort early release; the lease expires on its own otherwise.
+fn release_run_reservation(config: &Config, id: Option<&str>) {
+    #[cfg(unix)]
+    if let Some(id) = id {
+        let _ = reservation_control_request(
+            config,
+            &ControlRequest::ReleaseReservation { id: id.to_string() },
+        );
+    }
+    #[cfg(not(unix))]
+    let _ = (config, id);
+}
+
+/// Shell-style exit code: the process's own code, or 128 + signal number.
+fn wrapped_exit_code(status: std::process::ExitStatus) -> i32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt as _;
+        if let Some(signal) = status.signal() {
+            return 128 + signal;
+        }
+    }
+    status.code().unwrap_or(1)
+}
+
+/// `sbh run -- <command>`: pre-flight pressure check (or a held reservation),
+/// run the command while sampling its process tree's writes, then record the
+/// directories it created in the artifact-provenance ledger so the scanner
+/// and `sbh blame` can name the command that produced them.
+#[allow(clippy::too_many_lines)]
+fn run_wrapped_command(cli: &Cli, args: &RunArgs) -> Result<(), CliError> {
+    let config =
+        Config::load(cli.config.as_deref()).map_err(|e| CliError::Runtime(e.to_string()))?;
+    let cwd = std::env::current_dir()
+        .map_err(|e| CliError::Runtime(format!("cannot determine working directory: {e}")))?;
+    let command_line = args.command.join(" ");
+    let display_name = args.label.clone().unwrap_or_else(|| command_line.clone());
+
+    let reservation = if args.reserve {
+        Some(acquire_run_reservation(
+            cli,
+            &config,
+            args,
+            &cwd,
+            &display_name,
+        )?)
+    } else {
+        if !args.no_check {
+            run_check(
+                cli,
+                &CheckArgs {
+                    path: Some(cwd.clone()),
+                    target_free: args.target_free,
+                    need: args.need,
+                    predict: None,
+                },
+            )?;
+        }
+        None
+    };
+
+    let platform = match detect_platform() {
+        Ok(platform) => platform,
+        Err(e) => {
+            release_run_reservation(&config, reservation.as_deref());
+            return Err(CliError::Runtime(e.to_string()));
+        }
+    };
+    let before = directory_snapshot(&cwd, PRODUCED_DIR_DEPTH);
+    let started = std::time::Instant::now();
+    let mut child = match std::process::Command::new(&args.command[0])
+        .args(&args.command[1..])
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            release_run_reservation(&config, reservation.as_deref());
+            return Err(CliError::User(format!(
+                "failed to start {}: {e}",
+                args.command[0]
+            )));
+        }
+    };
+    // Ctrl-C reaches the whole foreground process group; stay alive long
+    // enough to record how the command ended.
+    #[cfg(unix)]
+    let _ = signal_hook::flag::register(
+        signal_hook::consts::SIGINT,
+        std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
+    );
 
-    // Check 3: prediction from daemon state.json (if available and --predict requested).
-    if let Some(predict_minutes) = args.predict {
-        match read_daemon_prediction(&config.paths.state_file, &capacity.mount_point) {
-            Some(rate_bps) if rate_bps > 0.0 => {
-                // Positive rate means filling; estimate time to threshold.
-                let bytes_until_threshold = capacity
-                    .available_bytes
-                    .saturating_sub((threshold_pct / 100.0 * capacity.total_bytes as f64) as u64);
-                let seconds_left = bytes_until_threshold as f64 / rate_bps;
-                let minutes_left = seconds_left / 60.0;
+    let pid = i32::try_from(child.id()).ok();
+    let ledger_path = config.paths.artifact_provenance_file();
+    let started_at = reservations::unix_now();
+    let mut owner = ArtifactOwner {
+        run_id: format!("run-{started_at:x}-{:x}", std::process::id()),
+        command: command_line,
+        label: args.label.clone(),
+        cwd: cwd.clone(),
+        pid,
+        started_at,
+        finished_at: None,
+        exit_code: None,
+        bytes_written: 0,
+    };
+    let active = RunRecord {
+        owner: owner.clone(),
+        produced: Vec::new(),
+    };
+    if let Err(e) = ArtifactProvenance::update(&ledger_path, started_at, |runs| runs.push(active)) {
+        eprintln!("sbh: warning: could not record run: {e}");
+    }
 
-                if minutes_left < predict_minutes as f64 {
-                    match output_mode(cli) {
-                        OutputMode::Human => {
-                            eprintln!(
-                                "sbh: {} has {} free but predicted full in {:.0} min (need {} min)",
-                                capacity.mount_point.display(),
-                                format_bytes(capacity.available_bytes),
-                                minutes_left,
-                                predict_minutes,
-                            );
-                        }
-                        OutputMode::Json => {
-                            let payload = json!({
-                                "command": "check",
-                                "status": "warning",
-                                "path": check_path.to_string_lossy(),
-                                "mount_point": capacity.mount_point.to_string_lossy(),
-                                "free_bytes": capacity.available_bytes,
-                                "total_bytes": capacity.total_bytes,
-                                "free_pct": free_pct,
-                                "rate_bytes_per_sec": rate_bps,
-                                "minutes_until_full": minutes_left,
-                                "predict_minutes": predict_minutes,
-                                "container_id": capacity.container_id.as_deref(),
-                                "container_total_bytes": capacity.container_total_bytes,
-                                "container_available_bytes": capacity.container_available_bytes,
-                                "volume_total_bytes": capacity.volume_total_bytes,
-                                "volume_available_bytes": capacity.volume_available_bytes,
-                                "volume_role": capacity.volume_role.as_deref(),
-                                "free_excludes_purgeable": true,
-                                "platform": capacity_platform_json(&capacity),
-                                "exit_code": 1,
-                            });
-                            write_json_line(&payload)?;
-                        }
-                    }
-                    return Err(CliError::User(
-                        "predicted disk full within window".to_string(),
-                    ));
-                }
-            }
-            _ => {
-                // No prediction available — daemon not running or not filling.
-                // This is not an error, just degraded mode.
+    let mut writes = pid.map(ProcessTreeWrites::new);
+    let mut next_sample = std::time::Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Ok(status),
+            Ok(None) => {}
+            Err(_) => break child.wait(),
+        }
+        if std::time::Instant::now() >= next_sample {
+            if let Some(writes) = writes.as_mut() {
+                writes.sample(platform.as_ref());
             }
+            next_sample += RUN_SAMPLE_INTERVAL;
         }
+        std::thread::sleep(RUN_EXIT_POLL_INTERVAL);
+    };
+    let elapsed = started.elapsed();
+    release_run_reservation(&config, reservation.as_deref());
+
+    let mut produced = produced_directories(&before, &directory_snapshot(&cwd, PRODUCED_DIR_DEPTH));
+    // sbh's own data directory may be created under cwd by the first ledger write.
+    produced.retain(|dir| !ledger_path.starts_with(dir));
+    let finished_at = reservations::unix_now();
+    let exit_code = status
+        .as_ref()
+        .ok()
+        .map(|status| wrapped_exit_code(*status));
+    owner.finished_at = Some(finished_at);
+    owner.exit_code = exit_code;
+    owner.bytes_written = writes.as_ref().map_or(0, ProcessTreeWrites::bytes_written);
+    let finished = RunRecord {
+        owner: owner.clone(),
+        produced: produced.clone(),
+    };
+    if let Err(e) = ArtifactProvenance::update(&ledger_path, finished_at, |runs| {
+        runs.retain(|run| run.owner.run_id != finished.owner.run_id);
+        runs.push(finished);
+    }) {
+        eprintln!("sbh: warning: could not record produced directories: {e}");
     }
 
-    // All checks passed — silent success on human mode.
-    if output_mode(cli) == OutputMode::Json {
-        let payload = json!({
-            "command": "check",
-            "status": "ok",
-            "path": check_path.to_string_lossy(),
-            "mount_point": capacity.mount_point.to_string_lossy(),
-            "free_bytes": capacity.available_bytes,
-            "total_bytes": capacity.total_bytes,
-            "free_pct": free_pct,
-            "container_id": capacity.container_id.as_deref(),
-            "container_total_bytes": capacity.container_total_bytes,
-            "container_available_bytes": capacity.container_available_bytes,
-            "volume_total_bytes": capacity.volume_total_bytes,
-            "volume_available_bytes": capacity.volume_available_bytes,
-            "volume_role": capacity.volume_role.as_deref(),
-            "free_excludes_purgeable": true,
-            "platform": capacity_platform_json(&capacity),
-            // #16: machine-readable reserve state so automation can flag a
-            // configured-but-empty emergency reserve.
-            "ballast": {
-                "configured_pool_bytes": ballast.configured_pool_bytes,
-                "releasable_bytes": ballast.releasable_bytes,
-                "available_count": ballast.available_count,
-                "missing_count": ballast.missing_count,
-                // Non-zero means this snapshot is NOT authoritative (usually an
-                // unprivileged caller against a root-owned, mode-700 dir).
-                // Automation must not treat `missing_count` as a real absence
-                // while this is > 0.
-                "unreadable_count": ballast.unreadable_count,
-                "authoritative": ballast.is_authoritative(),
-                "health": ballast.health.as_str(),
-            },
-            "exit_code": 0,
-        });
-        write_json_line(&payload)?;
+    match output_mode(cli) {
+        OutputMode::Human => {
+            // stdout belongs to the wrapped command.
+            let produced_note = if produced.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    "; produced {}",
+                    produced
+                        .iter()
+                        .map(|dir| dir.strip_prefix(&cwd).unwrap_or(dir).display().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            };
+            eprintln!(
+                "sbh: {display_name} exited {} after {}; wrote {}{produced_note}",
+                exit_code.map_or_else(|| "?".to_string(), |code| code.to_string()),
+                format_duration(elapsed),
+                format_bytes(owner.bytes_written),
+            );
+        }
+        OutputMode::Json => write_json_line(&json!({
+            "command": "run",
+            "run_id": owner.run_id,
+            "wrapped_command": owner.command,
+            "label": owner.label,
+            "cwd": cwd.to_string_lossy(),
+            "pid": owner.pid,
+            "reservation_id": reservation,
+            "elapsed_ms": u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+            "bytes_written": owner.bytes_written,
+            "produced": produced.iter().map(|dir| dir.display().to_string()).collect::<Vec<_>>(),
+            "exit_code": exit_code,
+        }))?,
     }
 
-    Ok(())
+    match (status, exit_code) {
+        (Err(e), _) => Err(CliError::Runtime(format!(
+            "lost track of {display_name}: {e}"
+        ))),
+        (Ok(_), Some(0)) => Ok(()),
+        (Ok(_), code) => Err(CliError::Wrapped(code.unwrap_or(1))),
+    }
 }
 
 /// Read EWMA rate prediction from daemon state.json if available and fresh.
@@ -9351,7 +11234,7 @@ fn run_emergency(cli: &Cli, args: &EmergencyArgs) -> Result<(), CliError> {
     let mut active_reference_index = None;
 
     // Classify and score using default weights.
-    let registry = ArtifactPatternRegistry::default();
+    let registry = ArtifactPatternRegistry::from_config(&config.scanner);
     let engine = ScoringEngine::from_config(&config.scoring, config.scanner.min_file_age_minutes);
     let sacred_paths = active_sacred_paths(&config)?;
     let now = SystemTime::now();
@@ -9363,15 +11246,22 @@ fn run_emergency(cli: &Cli, args: &EmergencyArgs) -> Result<(), CliError> {
             let age = now
                 .duration_since(entry.metadata.effective_age_timestamp())
                 .unwrap_or_default();
+            let (size_bytes, shared_bytes) = cow_extents::discount_shared(
+                &entry.path,
+                entry.metadata.is_dir,
+                entry.metadata.content_size_bytes,
+            );
             let mut candidate = CandidateInput {
                 path: entry.path.clone(),
-                size_bytes: entry.metadata.content_size_bytes,
+                size_bytes,
                 age,
                 classification,
                 signals: entry.structural_signals,
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes,
             };
             let cheap_score = engine.score_candidate(&candidate, 0.8);
             if !cheap_score.vetoed && cheap_score.total_score >= config.scoring.min_score {
@@ -10514,10 +12404,10 @@ mod tests {
     use std::collections::HashMap;
     use std::sync::atomic::{AtomicUsize, Ordering};
     use storage_ballast_helper::core::config::SacredConfig;
-    use storage_ballast_helper::platform::pal::{FsStats, MockPlatform, MountPoint, PlatformPaths};
+    use storage_ballast_helper::platform::pal::{FsStats, MockPlatform, PlatformPaths};
     use storage_ballast_helper::platform::types::{
-        FullDiskAccessState, FullDiskAccessStatus, OpenFile, OpenFileKind, OpenFileMode,
-        ProcessInfo, ProcessIo,
+        DeletedOpenFile, FullDiskAccessState, FullDiskAccessStatus, OpenFile, OpenFileKind,
+        OpenFileMode, ProcessInfo, ProcessIo,
     };
     use tempfile::TempDir;
 
@@ -10613,6 +12503,8 @@ mod tests {
             resident_memory_bytes: None,
             cpu_user_micros: None,
             cpu_system_micros: None,
+            uid: None,
+            cgroup: None,
         }
     }
 
@@ -10695,6 +12587,45 @@ mod tests {
         assert_eq!(args.need, Some(5 * 1024_u64.pow(3)));
     }
 
+    #[test]
+    fn reserve_command_requires_need_unless_listing_or_releasing() {
+        let parsed = Cli::try_parse_from([
+            "sbh", "reserve", "--need", "20G", "--ttl", "2h", "--path", ".", "--holder", "ci",
+        ])
+        .expect("documented reserve invocation should parse");
+        let Command::Reserve(args) = parsed.command else {
+            panic!("expected reserve command");
+        };
+        assert_eq!(args.need, Some(20 * 1024_u64.pow(3)));
+        assert_eq!(args.ttl, "2h");
+        assert_eq!(args.wait, "5m");
+        assert_eq!(args.holder.as_deref(), Some("ci"));
+
+        assert!(Cli::try_parse_from(["sbh", "reserve"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "reserve", "--list"]).is_ok());
+        assert!(Cli::try_parse_from(["sbh", "reserve", "--release", "rsv-1"]).is_ok());
+        assert!(
+            Cli::try_parse_from(["sbh", "reserve", "--need", "1G", "--release", "rsv-1"]).is_err()
+        );
+    }
+
+    #[test]
+    fn reservation_line_summarizes_status_json() {
+        let line = reservation_line(&json!({
+            "id": "rsv-1",
+            "state": "granted",
+            "bytes": 2048,
+            "outstanding_bytes": 1024,
+            "mount": "/data",
+            "holder": "cargo",
+            "expires_in_secs": 90,
+        }));
+        assert!(line.starts_with("rsv-1  granted"));
+        assert!(line.contains("on /data"));
+        assert!(line.contains("holder cargo"));
+        assert!(line.ends_with("expires in 1m 30s"));
+    }
+
     #[test]
     fn parse_byte_count_accepts_binary_suffixes_and_decimals() {
         let cases = [
@@ -10787,6 +12718,8 @@ mod tests {
             fs_type: "apfs".to_string(),
             mount_point: mount.clone(),
             is_readonly: false,
+            total_inodes: 0,
+            free_inodes: 0,
         };
         let mut stats_by_mount = HashMap::new();
         stats_by_mount.insert(mount.clone(), stats);
@@ -11651,6 +13584,8 @@ mod tests {
             active_references,
             is_open: false,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         };
         let engine = ScoringEngine::from_config(
             &storage_ballast_helper::core::config::ScoringConfig::default(),
@@ -11683,6 +13618,8 @@ mod tests {
                 storage_ballast_helper::scanner::scoring::ActiveReferenceSummary::default(),
             is_open: false,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         };
         let engine = ScoringEngine::from_config(
             &storage_ballast_helper::core::config::ScoringConfig::default(),
@@ -11723,6 +13660,8 @@ mod tests {
             active_references,
             is_open: false,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         };
         let engine = ScoringEngine::from_config(
             &storage_ballast_helper::core::config::ScoringConfig::default(),
@@ -12834,6 +14773,103 @@ mod tests {
         assert!(parse_window_duration("10x").is_err());
     }
 
+    #[test]
+    fn explain_command_requires_a_lookup_target() {
+        assert!(Cli::try_parse_from(["sbh", "explain"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "explain", "--id", "4", "--level", "4"]).is_err());
+
+        let parsed = Cli::try_parse_from([
+            "sbh", "explain", "--path", "/data", "--since", "24h", "--level", "2",
+        ])
+        .expect("explain flags should parse");
+        let Command::Explain(args) = parsed.command else {
+            panic!("expected explain command");
+        };
+        assert_eq!(args.path, Some(PathBuf::from("/data")));
+        assert_eq!(args.since.as_deref(), Some("24h"));
+        assert_eq!(args.level, Some(2));
+        assert_eq!(args.limit, 20);
+    }
+
+    #[test]
+    fn decompress_command_requires_a_path() {
+        assert!(Cli::try_parse_from(["sbh", "decompress"]).is_err());
+
+        let parsed = Cli::try_parse_from(["sbh", "decompress", "/tmp/a.log.zst", "--dry-run"])
+            .expect("decompress flags should parse");
+        let Command::Decompress(args) = parsed.command else {
+            panic!("expected decompress command");
+        };
+        assert_eq!(args.paths, vec![PathBuf::from("/tmp/a.log.zst")]);
+        assert!(args.dry_run);
+    }
+
+    #[test]
+    fn parse_decision_id_accepts_numbers_and_trace_ids() {
+        assert_eq!(parse_decision_id("42").unwrap(), 42);
+        assert_eq!(parse_decision_id("sbh-0000002a").unwrap(), 42);
+        assert_eq!(parse_decision_id(" sbh-2A ").unwrap(), 42);
+        assert!(parse_decision_id("sbh-").is_err());
+        assert!(parse_decision_id("abc").is_err());
+    }
+
+    #[test]
+    fn scan_daemon_flag_excludes_local_report_options() {
+        let daemon = |argv: &[&str]| {
+            let parsed = Cli::try_parse_from(argv).expect("scan args should parse");
+            let Command::Scan(args) = parsed.command else {
+                panic!("expected scan command");
+            };
+            args.daemon
+        };
+        assert!(!daemon(&["sbh", "scan", "/data"]));
+        assert!(daemon(&["sbh", "scan", "--daemon", "/data"]));
+        for option in [
+            &["--top", "5"][..],
+            &["--min-score", "0.5"],
+            &["--explain"],
+            &["--show-protected"],
+        ] {
+            let argv = [&["sbh", "scan", "--daemon"][..], option].concat();
+            assert!(
+                Cli::try_parse_from(argv).is_err(),
+                "--daemon should refuse {option:?}"
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn daemon_control_request_without_socket_falls_back() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut config = Config::default();
+        config.paths.state_file = dir.path().join("state.json");
+        let reply = daemon_control_request(&config, &ControlRequest::Status)
+            .expect("missing socket is not an error");
+        assert!(reply.is_none());
+        assert!(daemon_live_status(&config).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn daemon_control_request_reports_unreadable_socket() {
+        use std::os::unix::fs::PermissionsExt as _;
+
+        if nix::unistd::geteuid().is_root() {
+            return;
+        }
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut config = Config::default();
+        config.paths.state_file = dir.path().join("state.json");
+        let socket = config.paths.control_socket();
+        let _listener = std::os::unix::net::UnixListener::bind(&socket).expect("bind");
+        std::fs::set_permissions(&socket, std::fs::Permissions::from_mode(0o000)).expect("chmod");
+
+        let err = daemon_control_request(&config, &ControlRequest::Status)
+            .expect_err("an unreadable socket is not a missing daemon");
+        assert!(err.to_string().contains("permission denied"));
+    }
+
     #[test]
     fn blame_command_parses_since_and_tree_flags() {
         let parsed = Cli::try_parse_from(["sbh", "blame", "--top", "5", "--since", "1h", "--tree"])
@@ -12848,6 +14884,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::too_many_lines)]
     fn blame_report_ranks_processes_by_recent_writes_and_open_files() {
         let dir = TempDir::new().expect("temp dir should be created");
         let raw_root = dir.path().join("work");
@@ -12890,14 +14927,57 @@ mod tests {
                 fd: Some(3),
                 kind: OpenFileKind::Regular,
                 mode: OpenFileMode::ReadWrite,
+            })
+            .with_deleted_open_file(DeletedOpenFile {
+                pid: 42,
+                process_name: Some("rustc".to_string()),
+                fd: 9,
+                path: root.join("build.log"),
+                device: 1,
+                inode: 77,
+                size_bytes: 8_192,
+                allocated_bytes: 8_192,
+                mode: OpenFileMode::Write,
+                append: true,
+                mapped: false,
             });
 
+        let wrapper = ArtifactOwner {
+            run_id: "run-1".to_string(),
+            command: "cargo test".to_string(),
+            label: Some("nightly tests".to_string()),
+            cwd: root.clone(),
+            pid: Some(7),
+            started_at: 1_699_990_000,
+            finished_at: None,
+            exit_code: None,
+            bytes_written: 0,
+        };
+        let earlier = ArtifactOwner {
+            run_id: "run-0".to_string(),
+            pid: None,
+            finished_at: Some(1_699_999_900),
+            exit_code: Some(0),
+            bytes_written: 4_096,
+            ..wrapper.clone()
+        };
+        let provenance = ArtifactProvenance::from_runs(vec![
+            RunRecord {
+                owner: earlier,
+                produced: vec![root.join("target")],
+            },
+            RunRecord {
+                owner: wrapper,
+                produced: Vec::new(),
+            },
+        ]);
+
         let report = collect_blame_report_at(
             &config,
             &platform,
             &history,
+            &provenance,
             Duration::from_mins(15),
-            10,
             now,
         )
         .expect("blame report should collect");
@@ -12906,6 +14986,119 @@ mod tests {
         assert_eq!(report.rows[0].recent_written_bytes, 100_000);
         assert_eq!(report.rows[0].recent_read_bytes, 500);
         assert_eq!(report.rows[0].open_files, vec![open_path]);
+        // rustc is a child of the wrapped cargo, so blame names the run.
+        assert_eq!(report.rows[0].run_command.as_deref(), Some("nightly tests"));
+        assert_eq!(report.runs.len(), 1);
+        assert_eq!(report.runs[0].owner.run_id, "run-0");
+        assert_eq!(report.deleted_open.len(), 1);
+        let line = deleted_open_line(
+            &report.deleted_open[0].status_json(&config.scanner.deleted_open_files),
+        );
+        assert!(line.contains("build.log"), "{line}");
+        assert!(line.contains("pid 42 (rustc)"), "{line}");
+        assert!(line.ends_with("[kept: below_min_size]"), "{line}");
+    }
+
+    #[test]
+    fn run_command_parses_trailing_command_and_reservation_flags() {
+        let parsed = Cli::try_parse_from([
+            "sbh",
+            "run",
+            "--need",
+            "20G",
+            "--reserve",
+            "--label",
+            "release",
+            "--",
+            "cargo",
+            "build",
+            "--release",
+        ])
+        .expect("run flags should parse");
+        let Command::Run(args) = parsed.command else {
+            panic!("expected run command");
+        };
+        assert_eq!(args.need, Some(20 * 1024_u64.pow(3)));
+        assert!(args.reserve);
+        assert_eq!(args.label.as_deref(), Some("release"));
+        assert_eq!(args.command, vec!["cargo", "build", "--release"]);
+
+        assert!(Cli::try_parse_from(["sbh", "run"]).is_err());
+        assert!(Cli::try_parse_from(["sbh", "run", "--reserve", "--", "make"]).is_err());
+        assert!(
+            Cli::try_parse_from(["sbh", "run", "--no-check", "--need", "1G", "--", "make"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn blame_groups_aggregate_by_unit_with_cgroup_io_totals() {
+        let scope = "/user.slice/user-1000.slice/user@1000.service/app.slice/agent-7.scope";
+        let row = |pid: i32, cgroup: Option<&str>, written: u64| BlameRow {
+            pid,
+            parent_pid: None,
+            name: "rustc".to_string(),
+            command: "rustc".to_string(),
+            executable: Some(PathBuf::from("/usr/bin/rustc")),
+            cwd: None,
+            recent_read_bytes: 1,
+            recent_written_bytes: written,
+            open_files: Vec::new(),
+            run_command: None,
+            uid: Some(1000),
+            cgroup: cgroup.map(str::to_string),
+        };
+        let rows = vec![
+            row(10, Some("/system.slice/docker-abc.scope"), 900),
+            row(11, Some(&format!("{scope}/sub")), 500),
+            row(12, Some(scope), 450),
+            row(13, None, 5),
+        ];
+        let written = |bytes_written: u64| CgroupIo {
+            bytes_written,
+            ..CgroupIo::default()
+        };
+        // Every process of the build scope has exited; only io.stat remembers it.
+        let platform = MockPlatform::healthy()
+            .with_cgroup_io(scope, written(8_000_000))
+            .with_cgroup_io("/system.slice/build-9.scope", written(20_000_000))
+            .with_cgroup_io("/system.slice/idle.service", written(0));
+
+        let groups = group_blame_rows(&platform, &rows, BlameGroupBy::Unit, 10);
+        let keys: Vec<&str> = groups.iter().map(|group| group.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "build-9.scope",
+                "agent-7.scope",
+                "docker-abc.scope",
+                "(no systemd unit)"
+            ]
+        );
+        assert_eq!(groups[0].process_count, 0);
+        assert!(groups[0].pids.is_empty());
+        assert_eq!(groups[1].process_count, 2);
+        assert_eq!(groups[1].pids, vec![11, 12]);
+        assert_eq!(groups[1].recent_written_bytes, 950);
+        assert_eq!(groups[1].cgroup.as_deref(), Some(scope));
+        assert_eq!(
+            groups[1].cgroup_io.as_ref().map(|io| io.bytes_written),
+            Some(8_000_000)
+        );
+        assert_eq!(groups[2].cgroup_io, None);
+
+        let by_cgroup = group_blame_rows(&platform, &rows, BlameGroupBy::Cgroup, 2);
+        assert_eq!(by_cgroup[0].key, "/system.slice/build-9.scope");
+        assert_eq!(by_cgroup[1].key, scope);
+
+        let by_exe = group_blame_rows(&platform, &rows, BlameGroupBy::Exe, 1);
+        assert_eq!(by_exe.len(), 1);
+        assert_eq!(by_exe[0].key, "/usr/bin/rustc");
+        assert_eq!(by_exe[0].process_count, 4);
+
+        assert_eq!(systemd_unit_cgroup("/init.scope"), Some("/init.scope"));
+        assert_eq!(systemd_unit_cgroup("/user.slice"), None);
+        assert!(Cli::try_parse_from(["sbh", "blame", "--group-by", "unit", "--tree"]).is_err());
     }
 
     #[test]
@@ -12921,6 +15114,9 @@ mod tests {
                 recent_read_bytes: 0,
                 recent_written_bytes: 20,
                 open_files: Vec::new(),
+                run_command: None,
+                uid: None,
+                cgroup: None,
             },
             BlameRow {
                 pid: 42,
@@ -12932,6 +15128,9 @@ mod tests {
                 recent_read_bytes: 0,
                 recent_written_bytes: 10,
                 open_files: Vec::new(),
+                run_command: None,
+                uid: None,
+                cgroup: None,
             },
         ];
 
@@ -13395,6 +15594,8 @@ mod tests {
             is_primary: true,
             purgeable_bytes: None,
             local_snapshot_bytes: None,
+            total_inodes: 0,
+            free_inodes: 0,
         };
 
         assert!((capacity_free_pct(&capacity) - 25.0).abs() < f64::EPSILON);
@@ -13419,11 +15620,56 @@ mod tests {
             is_primary: true,
             purgeable_bytes: Some(500),
             local_snapshot_bytes: None,
+            total_inodes: 0,
+            free_inodes: 0,
         };
 
         assert!((capacity_free_pct(&capacity) - 10.0).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn catalog_rule_reports_mark_builtins() {
+        let reports = catalog_rule_reports(&Config::default());
+        assert_eq!(reports.len(), platform_cleanup_rules().len());
+        assert!(reports.iter().all(|report| {
+            report.source == CleanupRuleSource::Builtin && report.origin == "built-in"
+        }));
+    }
+
+    #[test]
+    fn status_inode_json_reports_level_for_fixed_inode_tables() {
+        let mut capacity = Capacity {
+            mount_point: PathBuf::from("/data"),
+            fs_type: "ext4".to_string(),
+            total_bytes: 1_000,
+            free_bytes: 900,
+            available_bytes: 900,
+            is_readonly: false,
+            container_id: None,
+            container_total_bytes: None,
+            container_available_bytes: None,
+            volume_total_bytes: None,
+            volume_available_bytes: None,
+            volume_role: None,
+            shared_volumes: Vec::new(),
+            is_primary: false,
+            purgeable_bytes: None,
+            local_snapshot_bytes: None,
+            total_inodes: 1_000,
+            free_inodes: 50,
+        };
+        let config = InodePressureConfig::default();
+
+        let inodes = status_inode_json(&capacity, &config);
+        assert_eq!(inodes["total"], 1_000);
+        assert_eq!(inodes["free"], 50);
+        assert_eq!(inodes["level"], "critical");
+
+        capacity.total_inodes = 0;
+        capacity.free_inodes = 0;
+        assert!(status_inode_json(&capacity, &config).is_null());
+    }
+
     #[test]
     fn status_mount_json_exposes_apfs_container_metadata() {
         let capacity = Capacity {
@@ -13443,6 +15689,8 @@ mod tests {
             is_primary: true,
             purgeable_bytes: Some(32),
             local_snapshot_bytes: Some(64),
+            total_inodes: 0,
+            free_inodes: 0,
         };
 
         let payload = status_mount_json(&capacity, "yellow", 25.0);
@@ -13570,6 +15818,8 @@ mod tests {
             is_primary: true,
             purgeable_bytes: Some(64),
             local_snapshot_bytes: None,
+            total_inodes: 0,
+            free_inodes: 0,
         };
 
         let notice = purgeable_storage_notice(&capacity).expect("notice should be present");
@@ -13596,6 +15846,8 @@ mod tests {
             is_primary: true,
             purgeable_bytes: None,
             local_snapshot_bytes: Some(64),
+            total_inodes: 0,
+            free_inodes: 0,
         };
 
         let warning = local_snapshot_warning(&capacity).expect("warning should be present");
@@ -13604,6 +15856,82 @@ mod tests {
         assert!(warning.contains("sudo tmutil thinlocalsnapshots / 9999999999999999 4"));
     }
 
+    #[test]
+    fn filesystem_snapshots_are_listed_only_for_btrfs_and_zfs_mounts() {
+        use storage_ballast_helper::platform::types::{FsSnapshotKind, SnapshotManager};
+
+        let snapshot = |sequence: u64, exclusive_bytes: Option<u64>, manager| FsSnapshotInfo {
+            kind: FsSnapshotKind::Zfs,
+            name: format!("tank/home@snap{sequence}"),
+            created: None,
+            sequence,
+            exclusive_bytes,
+            manager,
+            mount_path: PathBuf::from("/home"),
+        };
+        let snapshots = vec![
+            snapshot(
+                1,
+                Some(2048),
+                SnapshotManager::ZfsAutoSnapshot {
+                    label: "daily".to_string(),
+                },
+            ),
+            snapshot(2, None, SnapshotManager::Unmanaged),
+        ];
+        let zfs = MountPoint {
+            path: PathBuf::from("/home"),
+            device: "tank/home".to_string(),
+            fs_type: "zfs".to_string(),
+            is_ram_backed: false,
+        };
+        let ext4 = MountPoint {
+            path: PathBuf::from("/"),
+            device: "/dev/sda1".to_string(),
+            fs_type: "ext4".to_string(),
+            is_ram_backed: false,
+        };
+        let platform = MockPlatform::new(
+            vec![ext4.clone(), zfs.clone()],
+            HashMap::new(),
+            MemoryInfo {
+                total_bytes: 0,
+                available_bytes: 0,
+                swap_total_bytes: 0,
+                swap_free_bytes: 0,
+            },
+            PlatformPaths {
+                ballast_dir: PathBuf::from("/var/lib/sbh/ballast"),
+                state_file: PathBuf::from("/var/lib/sbh/state.json"),
+                sqlite_db: PathBuf::from("/var/lib/sbh/activity.sqlite3"),
+                jsonl_log: PathBuf::from("/var/log/sbh/activity.jsonl"),
+            },
+        )
+        .with_filesystem_snapshots("/home", snapshots)
+        .with_filesystem_snapshots("/", vec![snapshot(3, Some(1), SnapshotManager::Unmanaged)]);
+
+        assert!(mount_filesystem_snapshots(&platform, &ext4).is_empty());
+        let listed = mount_filesystem_snapshots(&platform, &zfs);
+        assert_eq!(listed.len(), 2);
+
+        let summary = fs_snapshot_summary(&zfs.path, &listed).expect("summary");
+        assert!(
+            summary.contains("/home: 2 zfs snapshot(s), 1 managed"),
+            "{summary}"
+        );
+        assert!(summary.ends_with("~2.0 KiB exclusive"), "{summary}");
+
+        let json = filesystem_snapshots_json(&listed);
+        assert_eq!(json["count"], 2);
+        assert_eq!(json["managed_count"], 1);
+        assert_eq!(json["exclusive_bytes"], 2048);
+        assert_eq!(json["snapshots"][0]["manager"]["tool"], "zfs_auto_snapshot");
+        assert_eq!(
+            filesystem_snapshots_json(&[])["exclusive_bytes"],
+            Value::Null
+        );
+    }
+
     #[test]
     fn swap_thrash_risk_requires_high_swap_and_low_ram() {
         // High swap + ample RAM → NOT risky (cold pages swapped, normal).
@@ -2,7 +2,7 @@
 
 #![allow(missing_docs)]
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -12,6 +12,9 @@ use serde::{Deserialize, Serialize};
 use crate::core::errors::{Result, SbhError};
 use crate::daemon::notifications::NotificationConfig;
 use crate::daemon::policy::PolicyConfig;
+use crate::platform::cleanup_catalog::OwnedCleanupRule;
+use crate::platform::{linux, macos};
+use crate::scanner::patterns::{ArtifactCategory, CustomPattern};
 
 /// Supplemental protection file written by `sbh protect`.
 pub const SACRED_CONFIG_FILENAME: &str = "sacred.toml";
@@ -32,6 +35,8 @@ pub struct Config {
     pub dashboard: DashboardConfig,
     pub policy: PolicyConfig,
     pub system_tuning: SystemTuningConfig,
+    pub metrics: MetricsConfig,
+    pub control: ControlConfig,
 }
 
 /// Pressure thresholds and control knobs.
@@ -47,6 +52,197 @@ pub struct PressureConfig {
     pub behavior_hysteresis_secs: u64,
     /// Predictive pre-emption settings.
     pub prediction: PredictionConfig,
+    /// Per-volume threshold/prediction overrides keyed by mount-point path
+    /// (e.g., "/data"). Uses BTreeMap for stable ordering in hash generation.
+    #[serde(default)]
+    pub overrides: BTreeMap<String, PressureVolumeOverride>,
+    /// Inode exhaustion thresholds (`[pressure.inodes]`).
+    pub inodes: InodePressureConfig,
+}
+
+/// Free-inode thresholds, tracked alongside free bytes on filesystems with a
+/// fixed inode table (ext2/3/4, XFS with `imaxpct`, ...). A volume's pressure
+/// is the worse of its byte and inode levels.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct InodePressureConfig {
+    pub enabled: bool,
+    pub green_min_free_pct: f64,
+    pub yellow_min_free_pct: f64,
+    pub orange_min_free_pct: f64,
+    pub red_min_free_pct: f64,
+}
+
+impl InodePressureConfig {
+    #[must_use]
+    pub const fn thresholds(&self) -> PressureThresholds {
+        PressureThresholds {
+            green_min_free_pct: self.green_min_free_pct,
+            yellow_min_free_pct: self.yellow_min_free_pct,
+            orange_min_free_pct: self.orange_min_free_pct,
+            red_min_free_pct: self.red_min_free_pct,
+        }
+    }
+}
+
+/// Per-volume override for pressure thresholds and prediction settings.
+///
+/// Each level may be set as a percentage of capacity or as absolute free
+/// bytes, but not both; unset levels inherit the global `[pressure]` value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PressureVolumeOverride {
+    pub green_min_free_pct: Option<f64>,
+    pub yellow_min_free_pct: Option<f64>,
+    pub orange_min_free_pct: Option<f64>,
+    pub red_min_free_pct: Option<f64>,
+    pub green_min_free_bytes: Option<u64>,
+    pub yellow_min_free_bytes: Option<u64>,
+    pub orange_min_free_bytes: Option<u64>,
+    pub red_min_free_bytes: Option<u64>,
+    /// Prediction settings for this volume; unset fields inherit `[pressure.prediction]`.
+    pub prediction: PredictionOverride,
+}
+
+/// Partial `PredictionConfig` used by per-volume pressure overrides.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PredictionOverride {
+    pub enabled: Option<bool>,
+    pub action_horizon_minutes: Option<f64>,
+    pub warning_horizon_minutes: Option<f64>,
+    pub min_confidence: Option<f64>,
+    pub min_samples: Option<u64>,
+    pub imminent_danger_minutes: Option<f64>,
+    pub critical_danger_minutes: Option<f64>,
+    pub burst_min_confidence: Option<f64>,
+}
+
+/// Effective free-space thresholds for one volume, in percent of capacity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PressureThresholds {
+    pub green_min_free_pct: f64,
+    pub yellow_min_free_pct: f64,
+    pub orange_min_free_pct: f64,
+    pub red_min_free_pct: f64,
+}
+
+impl PressureThresholds {
+    /// Free bytes below which a volume of `total_bytes` turns critical.
+    #[must_use]
+    pub fn red_min_free_bytes(&self, total_bytes: u64) -> u64 {
+        pct_to_bytes(self.red_min_free_pct, total_bytes)
+    }
+
+    /// Free bytes below which a volume of `total_bytes` is at red pressure.
+    #[must_use]
+    pub fn orange_min_free_bytes(&self, total_bytes: u64) -> u64 {
+        pct_to_bytes(self.orange_min_free_pct, total_bytes)
+    }
+}
+
+impl PressureConfig {
+    /// Resolve effective thresholds for a mount point, applying overrides.
+    ///
+    /// Byte thresholds are converted against `total_bytes` (and ignored when it
+    /// is zero). A stricter lower level raises the levels above it, so the
+    /// result always satisfies green >= yellow >= orange >= red.
+    #[must_use]
+    pub fn thresholds_for(&self, mount_path: &str, total_bytes: u64) -> PressureThresholds {
+        let key = strip_trailing_separator(mount_path);
+        let Some(ovr) = self.overrides.get(key) else {
+            return self.global_thresholds();
+        };
+        let level = |bytes: Option<u64>, pct: Option<f64>, global: f64| {
+            bytes
+                .filter(|_| total_bytes > 0)
+                .map(|bytes| bytes_to_pct(bytes, total_bytes))
+                .or(pct)
+                .unwrap_or(global)
+        };
+        let red = level(
+            ovr.red_min_free_bytes,
+            ovr.red_min_free_pct,
+            self.red_min_free_pct,
+        );
+        let orange = level(
+            ovr.orange_min_free_bytes,
+            ovr.orange_min_free_pct,
+            self.orange_min_free_pct,
+        )
+        .max(red);
+        let yellow = level(
+            ovr.yellow_min_free_bytes,
+            ovr.yellow_min_free_pct,
+            self.yellow_min_free_pct,
+        )
+        .max(orange);
+        let green = level(
+            ovr.green_min_free_bytes,
+            ovr.green_min_free_pct,
+            self.green_min_free_pct,
+        )
+        .max(yellow);
+        PressureThresholds {
+            green_min_free_pct: green,
+            yellow_min_free_pct: yellow,
+            orange_min_free_pct: orange,
+            red_min_free_pct: red,
+        }
+    }
+
+    /// Resolve effective prediction settings for a mount point, applying overrides.
+    #[must_use]
+    pub fn prediction_for(&self, mount_path: &str) -> PredictionConfig {
+        let key = strip_trailing_separator(mount_path);
+        let base = self.prediction.clone();
+        let Some(ovr) = self.overrides.get(key).map(|o| &o.prediction) else {
+            return base;
+        };
+        PredictionConfig {
+            enabled: ovr.enabled.unwrap_or(base.enabled),
+            action_horizon_minutes: ovr
+                .action_horizon_minutes
+                .unwrap_or(base.action_horizon_minutes),
+            warning_horizon_minutes: ovr
+                .warning_horizon_minutes
+                .unwrap_or(base.warning_horizon_minutes),
+            min_confidence: ovr.min_confidence.unwrap_or(base.min_confidence),
+            min_samples: ovr.min_samples.unwrap_or(base.min_samples),
+            imminent_danger_minutes: ovr
+                .imminent_danger_minutes
+                .unwrap_or(base.imminent_danger_minutes),
+            critical_danger_minutes: ovr
+                .critical_danger_minutes
+                .unwrap_or(base.critical_danger_minutes),
+            burst_min_confidence: ovr
+                .burst_min_confidence
+                .unwrap_or(base.burst_min_confidence),
+        }
+    }
+
+    const fn global_thresholds(&self) -> PressureThresholds {
+        PressureThresholds {
+            green_min_free_pct: self.green_min_free_pct,
+            yellow_min_free_pct: self.yellow_min_free_pct,
+            orange_min_free_pct: self.orange_min_free_pct,
+            red_min_free_pct: self.red_min_free_pct,
+        }
+    }
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn pct_to_bytes(pct: f64, total_bytes: u64) -> u64 {
+    (total_bytes as f64 * pct / 100.0) as u64
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn bytes_to_pct(bytes: u64, total_bytes: u64) -> f64 {
+    (bytes as f64 * 100.0 / total_bytes as f64).min(100.0)
 }
 
 /// Knobs for predictive pre-emptive action (EWMA → graduated response).
@@ -146,7 +342,7 @@ impl std::str::FromStr for ScannerEventSourceMode {
 }
 
 /// Scanner behavior and safety constraints.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(default)]
 pub struct ScannerConfig {
     pub engine: ScannerEngineMode,
@@ -187,6 +383,25 @@ pub struct ScannerConfig {
     /// `ftruncate`-ing matching files in place, which preserves the open fd so
     /// the writer keeps logging into the same (now sparse) file.
     pub log_truncation: LogTruncationConfig,
+    /// Reclaim of space held by deleted-but-open files.
+    ///
+    /// An unlinked file keeps its blocks until every fd on it closes, so no
+    /// scan can find it. The daemon lists such files per mount and, when
+    /// allowed, truncates them through `/proc/<pid>/fd/<n>`.
+    pub deleted_open_files: DeletedOpenFilesConfig,
+    /// In-place sweep of Cargo target directories too recent or busy to
+    /// delete whole.
+    pub target_sweep: TargetSweepConfig,
+    /// Access-time pruning of Bazel's shared repository and disk caches.
+    pub bazel: BazelCacheConfig,
+    /// Reclaim of idle git worktrees and agent clones with nothing unsaved.
+    pub git_workspaces: GitWorkspaceConfig,
+    /// Site-specific artifact name patterns (`[[scanner.custom_patterns]]`),
+    /// consulted alongside the built-in registry.
+    pub custom_patterns: Vec<CustomPattern>,
+    /// Extra Linux cleanup catalog rules (`[[scanner.cleanup_rules]]`),
+    /// merged after the built-in rules when the config is loaded.
+    pub cleanup_rules: Vec<OwnedCleanupRule>,
 }
 
 /// Active-log truncate-in-place policy.
@@ -219,6 +434,78 @@ pub struct LogTruncationConfig {
     pub min_age_minutes: u64,
 }
 
+/// Deleted-but-open file reclaim policy (`[scanner.deleted_open_files]`).
+///
+/// Held files are always reported. Truncation follows the log truncator's
+/// safety model: only regular files whose every holder is an `O_APPEND`
+/// writer, so writers carry on at the new end of file and nobody reads the
+/// lost bytes, and never a file some process has memory-mapped.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct DeletedOpenFilesConfig {
+    /// Allow truncating held files. Off by default; reporting is always on.
+    pub truncate: bool,
+    /// Files holding fewer allocated bytes than this are left alone.
+    pub min_size_bytes: u64,
+    /// Only truncate while the file's mount is at or below this free percent.
+    pub pressure_free_pct_ceiling: u8,
+}
+
+/// Partial sweep of live Cargo target directories (`[scanner.target_sweep]`).
+///
+/// A target dir that is recent or held open is never a whole candidate, yet
+/// stale incremental sessions and superseded dependency builds can dwarf the
+/// live build. When enabled, such targets are swept in place: incremental
+/// crate dirs and fingerprinted units untouched for `stale_after_days` are
+/// removed while the target dir and its current build stay.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct TargetSweepConfig {
+    /// Off by default; whole-target deletion is unaffected either way.
+    pub enabled: bool,
+    /// Units whose newest file is younger than this are kept.
+    pub stale_after_days: u64,
+    /// Targets smaller than this are not worth a sweep.
+    pub min_target_size_bytes: u64,
+}
+
+/// Bazel cache pruning (`[scanner.bazel]`).
+///
+/// Output bases are scored like any other candidate. The repository cache
+/// next to them and any `--disk_cache` directory are shared by every
+/// workspace, so instead of deleting them whole the daemon removes entries
+/// nobody has read for `max_access_age_days`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct BazelCacheConfig {
+    /// Off by default; output base scoring is unaffected either way.
+    pub prune_by_access: bool,
+    /// Cache entries read more recently than this are kept.
+    pub max_access_age_days: u64,
+    /// `--disk_cache` directories. Repository caches are found from the
+    /// output bases the scan encounters.
+    pub disk_cache_paths: Vec<PathBuf>,
+}
+
+/// Abandoned git worktree and agent clone reclaim (`[scanner.git_workspaces]`).
+///
+/// Scored candidates containing `.git` are always refused. This is a separate
+/// path: a linked worktree, or a clone whose name matches an agent workspace
+/// pattern, is removed once git shows no uncommitted, stashed or unpushed
+/// work, its branch is merged or gone upstream, and it has been idle for
+/// `min_idle_days`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct GitWorkspaceConfig {
+    /// Off by default.
+    pub enabled: bool,
+    /// Workspaces git touched more recently than this are not inspected.
+    pub min_idle_days: u64,
+    /// Branches a workspace's HEAD may be merged into, matched locally and
+    /// on every remote (`refs/heads/<name>`, `refs/remotes/*/<name>`).
+    pub base_branches: Vec<String>,
+}
+
 /// Multi-factor score weights and decision-theoretic losses.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(default)]
@@ -430,6 +717,69 @@ impl Default for SystemTuningConfig {
     }
 }
 
+/// How the daemon exposes Prometheus metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricsMode {
+    /// No exporter (default).
+    #[default]
+    Off,
+    /// Serve `/metrics` on a loopback HTTP listener.
+    Http,
+    /// Write a node_exporter textfile-collector `.prom` file.
+    Textfile,
+}
+
+/// Optional Prometheus exporter for daemon state.
+///
+/// Metrics are rendered from the same payload as the SIGINFO status dump, so
+/// the two views always agree.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct MetricsConfig {
+    pub mode: MetricsMode,
+    /// Listen address for `http` mode; must be a loopback address.
+    pub listen_addr: String,
+    /// Output file for `textfile` mode; node_exporter only reads `*.prom`.
+    pub textfile_path: PathBuf,
+    /// Minimum seconds between metric snapshots.
+    pub refresh_interval_secs: u64,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            mode: MetricsMode::Off,
+            listen_addr: "127.0.0.1:9477".to_string(),
+            textfile_path: PathBuf::from("/var/lib/node_exporter/textfile_collector/sbh.prom"),
+            refresh_interval_secs: 15,
+        }
+    }
+}
+
+/// Access to the daemon control socket (`[control]`).
+///
+/// The socket is owner-only by default, so only the daemon's user can send
+/// requests. Naming a group and a group-accessible mode lets non-root agents
+/// reserve space and hand scans to the daemon.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ControlConfig {
+    /// Group given ownership of the socket; unset keeps the daemon's group.
+    pub socket_group: Option<String>,
+    /// Permission bits of the socket, e.g. `0o660` together with `socket_group`.
+    pub socket_mode: u32,
+}
+
+impl Default for ControlConfig {
+    fn default() -> Self {
+        Self {
+            socket_group: None,
+            socket_mode: 0o600,
+        }
+    }
+}
+
 /// Dashboard runtime selection mode.
 ///
 /// Controls which TUI implementation `sbh dashboard` uses during phased rollout.
@@ -553,6 +903,36 @@ impl PathsConfig {
     pub fn scanner_index_file(&self) -> PathBuf {
         data_dir_for_paths(self).join("scanner-index-v2.json")
     }
+
+    /// Unix-domain control socket served by a running daemon.
+    #[must_use]
+    pub fn control_socket(&self) -> PathBuf {
+        data_dir_for_paths(self).join("control.sock")
+    }
+
+    /// Persisted disk-space reservation leases (`sbh reserve`).
+    #[must_use]
+    pub fn reservations_file(&self) -> PathBuf {
+        data_dir_for_paths(self).join("reservations.json")
+    }
+
+    /// Cgroup write limits the daemon currently holds (lifted after a crash).
+    #[must_use]
+    pub fn write_throttles_file(&self) -> PathBuf {
+        data_dir_for_paths(self).join("write-throttles.json")
+    }
+
+    /// Cgroups the daemon currently holds frozen (thawed after a crash).
+    #[must_use]
+    pub fn frozen_writers_file(&self) -> PathBuf {
+        data_dir_for_paths(self).join("frozen-writers.json")
+    }
+
+    /// Directories produced by `sbh run` commands, keyed by command.
+    #[must_use]
+    pub fn artifact_provenance_file(&self) -> PathBuf {
+        data_dir_for_paths(self).join("artifact-provenance.json")
+    }
 }
 
 /// User-managed protection paths kept separate from the generated main config.
@@ -609,6 +989,20 @@ impl Default for PressureConfig {
             poll_interval_ms: 5_000,
             behavior_hysteresis_secs: 5,
             prediction: PredictionConfig::default(),
+            overrides: BTreeMap::new(),
+            inodes: InodePressureConfig::default(),
+        }
+    }
+}
+
+impl Default for InodePressureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            green_min_free_pct: 20.0,
+            yellow_min_free_pct: 14.0,
+            orange_min_free_pct: 10.0,
+            red_min_free_pct: 6.0,
         }
     }
 }
@@ -698,6 +1092,12 @@ impl Default for ScannerConfig {
             active_reference_cache_ttl_secs: 30,
             active_reference_min_size_bytes: 100 * 1024 * 1024,
             log_truncation: LogTruncationConfig::default(),
+            deleted_open_files: DeletedOpenFilesConfig::default(),
+            target_sweep: TargetSweepConfig::default(),
+            bazel: BazelCacheConfig::default(),
+            git_workspaces: GitWorkspaceConfig::default(),
+            custom_patterns: Vec::new(),
+            cleanup_rules: Vec::new(),
         }
     }
 }
@@ -723,6 +1123,46 @@ impl Default for LogTruncationConfig {
     }
 }
 
+impl Default for DeletedOpenFilesConfig {
+    fn default() -> Self {
+        Self {
+            truncate: false,
+            min_size_bytes: 100 * 1_048_576, // 100 MiB
+            pressure_free_pct_ceiling: 10,
+        }
+    }
+}
+
+impl Default for TargetSweepConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            stale_after_days: 7,
+            min_target_size_bytes: 1_073_741_824, // 1 GiB
+        }
+    }
+}
+
+impl Default for BazelCacheConfig {
+    fn default() -> Self {
+        Self {
+            prune_by_access: false,
+            max_access_age_days: 30,
+            disk_cache_paths: Vec::new(),
+        }
+    }
+}
+
+impl Default for GitWorkspaceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_idle_days: 14,
+            base_branches: vec!["main".to_string(), "master".to_string()],
+        }
+    }
+}
+
 impl Default for ScoringConfig {
     fn default() -> Self {
         Self {
@@ -1072,6 +1512,7 @@ impl Config {
         cfg.merge_sacred_config()?;
         cfg.normalize_paths();
         cfg.validate()?;
+        linux::cleanup_catalog::install_configured_rules(&cfg.scanner.cleanup_rules);
         Ok(cfg)
     }
 
@@ -1108,6 +1549,10 @@ impl Config {
             "SBH_PRESSURE_RED_MIN_FREE_PCT",
             &mut self.pressure.red_min_free_pct,
         )?;
+        set_env_bool(
+            "SBH_PRESSURE_INODES_ENABLED",
+            &mut self.pressure.inodes.enabled,
+        )?;
         set_env_u64(
             "SBH_PRESSURE_POLL_INTERVAL_MS",
             &mut self.pressure.poll_interval_ms,
@@ -1378,6 +1823,11 @@ impl Config {
             })
             .collect();
         self.ballast.overrides = normalized;
+        let old_overrides = std::mem::take(&mut self.pressure.overrides);
+        self.pressure.overrides = old_overrides
+            .into_iter()
+            .map(|(k, v)| (strip_trailing_separator(&k).to_string(), v))
+            .collect();
 
         // Strip trailing slashes from scanner root_paths.
         for path in &mut self.scanner.root_paths {
@@ -1431,47 +1881,9 @@ impl Config {
             });
         }
 
-        if self.pressure.prediction.enabled {
-            let pred = &self.pressure.prediction;
-
-            // All horizon minutes must be positive (used in division/comparison).
-            for (name, val) in [
-                ("action_horizon_minutes", pred.action_horizon_minutes),
-                ("warning_horizon_minutes", pred.warning_horizon_minutes),
-                ("imminent_danger_minutes", pred.imminent_danger_minutes),
-            ] {
-                if val <= 0.0 {
-                    return Err(SbhError::InvalidConfig {
-                        details: format!("prediction.{name} must be > 0, got {val}"),
-                    });
-                }
-            }
-
-            if pred.warning_horizon_minutes <= pred.action_horizon_minutes {
-                return Err(SbhError::InvalidConfig {
-                    details: "prediction.warning_horizon_minutes must be > action_horizon_minutes"
-                        .to_string(),
-                });
-            }
-            if pred.action_horizon_minutes <= pred.imminent_danger_minutes {
-                return Err(SbhError::InvalidConfig {
-                    details: "prediction.action_horizon_minutes must be > imminent_danger_minutes"
-                        .to_string(),
-                });
-            }
-            if pred.imminent_danger_minutes <= pred.critical_danger_minutes {
-                return Err(SbhError::InvalidConfig {
-                    details: "prediction.imminent_danger_minutes must be > critical_danger_minutes"
-                        .to_string(),
-                });
-            }
-            if pred.critical_danger_minutes < 0.0 {
-                return Err(SbhError::InvalidConfig {
-                    details: "prediction.critical_danger_minutes must be >= 0".to_string(),
-                });
-            }
-            validate_prob("prediction.min_confidence", pred.min_confidence)?;
-        }
+        validate_prediction("prediction", &self.pressure.prediction)?;
+        self.validate_pressure_overrides()?;
+        self.validate_inode_pressure()?;
 
         if self.scanner.parallelism == 0 {
             return Err(SbhError::InvalidConfig {
@@ -1505,6 +1917,79 @@ impl Config {
                 details: "scanner.repeat_deletion_max_cooldown_secs must be >= scanner.repeat_deletion_base_cooldown_secs".to_string(),
             });
         }
+        self.validate_custom_patterns()?;
+        self.validate_cleanup_rules()?;
+        if self.scanner.target_sweep.stale_after_days == 0 {
+            return Err(SbhError::InvalidConfig {
+                details: "scanner.target_sweep.stale_after_days must be >= 1".to_string(),
+            });
+        }
+        if self.scanner.bazel.max_access_age_days == 0 {
+            return Err(SbhError::InvalidConfig {
+                details: "scanner.bazel.max_access_age_days must be >= 1".to_string(),
+            });
+        }
+        if self.scanner.git_workspaces.min_idle_days == 0 {
+            return Err(SbhError::InvalidConfig {
+                details: "scanner.git_workspaces.min_idle_days must be >= 1".to_string(),
+            });
+        }
+        if self
+            .scanner
+            .git_workspaces
+            .base_branches
+            .iter()
+            .any(|branch| branch.is_empty() || branch.contains(['*', '?', '[']))
+        {
+            return Err(SbhError::InvalidConfig {
+                details: "scanner.git_workspaces.base_branches must be plain branch names"
+                    .to_string(),
+            });
+        }
+
+        if self.policy.write_throttle.limit_bytes_per_sec == 0 {
+            return Err(SbhError::InvalidConfig {
+                details: "policy.write_throttle.limit_bytes_per_sec must be >= 1".to_string(),
+            });
+        }
+
+        if self.policy.freeze.max_freeze_secs == 0 {
+            return Err(SbhError::InvalidConfig {
+                details: "policy.freeze.max_freeze_secs must be >= 1".to_string(),
+            });
+        }
+
+        if self.policy.snapshot_thinning.keep_latest == 0 {
+            return Err(SbhError::InvalidConfig {
+                details: "policy.snapshot_thinning.keep_latest must be >= 1".to_string(),
+            });
+        }
+
+        let compress = &self.policy.compress_in_place;
+        if !(1..=19).contains(&compress.level) {
+            return Err(SbhError::InvalidConfig {
+                details: format!(
+                    "policy.compress_in_place.level must be in [1, 19], got {}",
+                    compress.level
+                ),
+            });
+        }
+        if compress.min_savings_pct > 90 {
+            return Err(SbhError::InvalidConfig {
+                details: "policy.compress_in_place.min_savings_pct must be <= 90".to_string(),
+            });
+        }
+        if compress.extensions.iter().any(|ext| {
+            ext.is_empty()
+                || ext.contains(['.', '/'])
+                || ext.eq_ignore_ascii_case(crate::scanner::compress::COMPRESSED_EXTENSION)
+        }) {
+            return Err(SbhError::InvalidConfig {
+                details:
+                    "policy.compress_in_place.extensions must be bare extensions other than zst"
+                        .to_string(),
+            });
+        }
 
         validate_prob("scoring.min_score", self.scoring.min_score)?;
         validate_prob("scoring.calibration_floor", self.scoring.calibration_floor)?;
@@ -1759,6 +2244,241 @@ impl Config {
             });
         }
 
+        self.validate_metrics()?;
+        self.validate_control()
+    }
+
+    fn validate_custom_patterns(&self) -> Result<()> {
+        let mut names = HashSet::new();
+        for pattern in &self.scanner.custom_patterns {
+            let invalid = |reason: String| SbhError::InvalidConfig {
+                details: format!("scanner.custom_patterns \"{}\": {reason}", pattern.name),
+            };
+            if pattern.name.trim().is_empty() {
+                return Err(SbhError::InvalidConfig {
+                    details: "scanner.custom_patterns entries need a non-empty name".to_string(),
+                });
+            }
+            if !names.insert(pattern.name.as_str()) {
+                return Err(invalid("name is used by more than one pattern".to_string()));
+            }
+            if pattern.needle.is_empty() {
+                return Err(invalid("pattern must not be empty".to_string()));
+            }
+            if !(pattern.confidence > 0.0 && pattern.confidence <= 1.0) {
+                return Err(invalid(format!(
+                    "confidence must be in (0, 1], got {}",
+                    pattern.confidence
+                )));
+            }
+            if pattern.category == ArtifactCategory::Unknown {
+                return Err(invalid("category must not be \"unknown\"".to_string()));
+            }
+            if let Some(Err(err)) = pattern.compile_regex() {
+                return Err(invalid(format!("invalid regex: {err}")));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_cleanup_rules(&self) -> Result<()> {
+        let mut names = HashSet::new();
+        for rule in &self.scanner.cleanup_rules {
+            let invalid = |reason: String| SbhError::InvalidConfig {
+                details: format!("scanner.cleanup_rules \"{}\": {reason}", rule.name),
+            };
+            if let Some(reason) = rule.validation_error() {
+                return Err(invalid(reason));
+            }
+            if !names.insert(rule.name.as_str()) {
+                return Err(invalid("name is used by more than one rule".to_string()));
+            }
+            if linux::cleanup_catalog::LINUX_CLEANUP_RULES
+                .iter()
+                .chain(macos::cleanup_catalog::MAC_CLEANUP_RULES)
+                .any(|builtin| builtin.name == rule.name || builtin.scanner_label() == rule.name)
+            {
+                return Err(invalid(
+                    "name collides with a built-in cleanup rule".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_inode_pressure(&self) -> Result<()> {
+        let inodes = &self.pressure.inodes;
+        for (name, val) in [
+            ("green_min_free_pct", inodes.green_min_free_pct),
+            ("yellow_min_free_pct", inodes.yellow_min_free_pct),
+            ("orange_min_free_pct", inodes.orange_min_free_pct),
+            ("red_min_free_pct", inodes.red_min_free_pct),
+        ] {
+            if !(0.0..=100.0).contains(&val) {
+                return Err(SbhError::InvalidConfig {
+                    details: format!("pressure.inodes.{name} must be in [0, 100], got {val}"),
+                });
+            }
+        }
+        if !(inodes.green_min_free_pct > inodes.yellow_min_free_pct
+            && inodes.yellow_min_free_pct > inodes.orange_min_free_pct
+            && inodes.orange_min_free_pct > inodes.red_min_free_pct)
+        {
+            return Err(SbhError::InvalidConfig {
+                details:
+                    "pressure.inodes thresholds must strictly descend: green > yellow > orange > red"
+                        .to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn validate_pressure_overrides(&self) -> Result<()> {
+        for (mount, ovr) in &self.pressure.overrides {
+            let prefix = format!("pressure.overrides[\"{mount}\"]");
+            let levels = [
+                (
+                    "green",
+                    ovr.green_min_free_pct,
+                    ovr.green_min_free_bytes,
+                    self.pressure.green_min_free_pct,
+                ),
+                (
+                    "yellow",
+                    ovr.yellow_min_free_pct,
+                    ovr.yellow_min_free_bytes,
+                    self.pressure.yellow_min_free_pct,
+                ),
+                (
+                    "orange",
+                    ovr.orange_min_free_pct,
+                    ovr.orange_min_free_bytes,
+                    self.pressure.orange_min_free_pct,
+                ),
+                (
+                    "red",
+                    ovr.red_min_free_pct,
+                    ovr.red_min_free_bytes,
+                    self.pressure.red_min_free_pct,
+                ),
+            ];
+            for (name, pct, bytes, _) in levels {
+                if pct.is_some() && bytes.is_some() {
+                    return Err(SbhError::InvalidConfig {
+                        details: format!(
+                            "{prefix} sets both {name}_min_free_pct and {name}_min_free_bytes"
+                        ),
+                    });
+                }
+                if let Some(val) = pct
+                    && !(0.0..=100.0).contains(&val)
+                {
+                    return Err(SbhError::InvalidConfig {
+                        details: format!(
+                            "{prefix}.{name}_min_free_pct must be in [0, 100], got {val}"
+                        ),
+                    });
+                }
+            }
+
+            // Percentage levels (explicit or inherited) and byte levels must each
+            // descend; the two kinds are only comparable once capacity is known.
+            let pct_levels: Vec<f64> = levels
+                .iter()
+                .filter(|(_, _, bytes, _)| bytes.is_none())
+                .map(|(_, pct, _, global)| pct.unwrap_or(*global))
+                .collect();
+            let byte_levels: Vec<u64> = levels
+                .iter()
+                .filter_map(|(_, _, bytes, _)| *bytes)
+                .collect();
+            if pct_levels.windows(2).any(|w| w[0] <= w[1])
+                || byte_levels.windows(2).any(|w| w[0] <= w[1])
+            {
+                return Err(SbhError::InvalidConfig {
+                    details: format!(
+                        "{prefix} thresholds must strictly descend: green > yellow > orange > red"
+                    ),
+                });
+            }
+
+            validate_prediction(
+                &format!("{prefix}.prediction"),
+                &self.pressure.prediction_for(mount),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn validate_metrics(&self) -> Result<()> {
+        let metrics = &self.metrics;
+        if metrics.refresh_interval_secs == 0 {
+            return Err(SbhError::InvalidConfig {
+                details: "metrics.refresh_interval_secs must be > 0".to_string(),
+            });
+        }
+        match metrics.mode {
+            MetricsMode::Off => {}
+            MetricsMode::Http => {
+                // The exporter has no auth; keep it off the network.
+                let addr = metrics
+                    .listen_addr
+                    .parse::<std::net::SocketAddr>()
+                    .map_err(|e| SbhError::InvalidConfig {
+                        details: format!(
+                            "metrics.listen_addr {:?} is not a socket address: {e}",
+                            metrics.listen_addr
+                        ),
+                    })?;
+                if !addr.ip().is_loopback() {
+                    return Err(SbhError::InvalidConfig {
+                        details: format!(
+                            "metrics.listen_addr must be a loopback address, got {addr}"
+                        ),
+                    });
+                }
+            }
+            MetricsMode::Textfile => {
+                if !metrics.textfile_path.is_absolute()
+                    || metrics
+                        .textfile_path
+                        .extension()
+                        .and_then(|ext| ext.to_str())
+                        != Some("prom")
+                {
+                    return Err(SbhError::InvalidConfig {
+                        details: format!(
+                            "metrics.textfile_path must be an absolute *.prom path, got {}",
+                            metrics.textfile_path.display()
+                        ),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_control(&self) -> Result<()> {
+        let control = &self.control;
+        // Anyone who can connect can release ballast and switch policy modes,
+        // so access is granted through a group, never to all users.
+        if control.socket_mode & !0o770 != 0 || control.socket_mode & 0o600 != 0o600 {
+            return Err(SbhError::InvalidConfig {
+                details: format!(
+                    "control.socket_mode must keep owner read/write and grant nothing to other users, got {:#o}",
+                    control.socket_mode
+                ),
+            });
+        }
+        if control
+            .socket_group
+            .as_deref()
+            .is_some_and(|group| group.trim().is_empty())
+        {
+            return Err(SbhError::InvalidConfig {
+                details: "control.socket_group must not be empty".to_string(),
+            });
+        }
         Ok(())
     }
 
@@ -1825,6 +2545,52 @@ pub fn write_sacred_config(path: &Path, sacred: &SacredConfig) -> Result<()> {
     })
 }
 
+fn validate_prediction(prefix: &str, pred: &PredictionConfig) -> Result<()> {
+    if pred.enabled {
+        // All horizon minutes must be positive (used in division/comparison).
+        for (name, val) in [
+            ("action_horizon_minutes", pred.action_horizon_minutes),
+            ("warning_horizon_minutes", pred.warning_horizon_minutes),
+            ("imminent_danger_minutes", pred.imminent_danger_minutes),
+        ] {
+            if val <= 0.0 {
+                return Err(SbhError::InvalidConfig {
+                    details: format!("{prefix}.{name} must be > 0, got {val}"),
+                });
+            }
+        }
+
+        if pred.warning_horizon_minutes <= pred.action_horizon_minutes {
+            return Err(SbhError::InvalidConfig {
+                details: format!(
+                    "{prefix}.warning_horizon_minutes must be > action_horizon_minutes"
+                ),
+            });
+        }
+        if pred.action_horizon_minutes <= pred.imminent_danger_minutes {
+            return Err(SbhError::InvalidConfig {
+                details: format!(
+                    "{prefix}.action_horizon_minutes must be > imminent_danger_minutes"
+                ),
+            });
+        }
+        if pred.imminent_danger_minutes <= pred.critical_danger_minutes {
+            return Err(SbhError::InvalidConfig {
+                details: format!(
+                    "{prefix}.imminent_danger_minutes must be > critical_danger_minutes"
+                ),
+            });
+        }
+        if pred.critical_danger_minutes < 0.0 {
+            return Err(SbhError::InvalidConfig {
+                details: format!("{prefix}.critical_danger_minutes must be >= 0"),
+            });
+        }
+        validate_prob(&format!("{prefix}.min_confidence"), pred.min_confidence)?;
+    }
+    Ok(())
+}
+
 fn validate_prob(name: &str, value: f64) -> Result<()> {
     if !(0.0..=1.0).contains(&value) {
         return Err(SbhError::InvalidConfig {
@@ -1910,6 +2676,7 @@ mod tests {
         Config, PathsConfig, SacredConfig, SbhError, ScannerEngineMode, load_sacred_config,
         sacred_config_path_for, write_sacred_config,
     };
+    use crate::platform::cleanup_catalog::CheckRequirement;
     use std::collections::HashMap;
     use std::path::{Path, PathBuf};
     use tempfile::TempDir;
@@ -2077,6 +2844,92 @@ mod tests {
         assert!(err.to_string().contains("unknown variant"));
     }
 
+    #[test]
+    fn custom_patterns_parse_and_validate() {
+        let mut cfg: Config = toml::from_str(
+            "[[scanner.custom_patterns]]\n\
+             name = \"ci-out\"\n\
+             match = \"regex\"\n\
+             pattern = \"^out-ci-[0-9]+$\"\n\
+             category = \"build_output\"\n\
+             confidence = 0.9\n",
+        )
+        .expect("custom patterns should parse");
+        assert_eq!(cfg.scanner.custom_patterns.len(), 1);
+        cfg.validate().expect("valid custom pattern");
+
+        let mut duplicate = cfg.scanner.custom_patterns[0].clone();
+        duplicate.needle = "out-ci-".to_string();
+        cfg.scanner.custom_patterns.push(duplicate);
+        let err = cfg.validate().expect_err("duplicate names rejected");
+        assert!(err.to_string().contains("more than one pattern"));
+
+        cfg.scanner.custom_patterns.truncate(1);
+        cfg.scanner.custom_patterns[0].needle = "out-ci-(".to_string();
+        let err = cfg.validate().expect_err("bad regex rejected");
+        assert!(err.to_string().contains("invalid regex"));
+    }
+
+    #[test]
+    fn disruptive_policy_steps_are_opt_in() {
+        let policy = Config::default().policy;
+        assert!(!policy.write_throttle.enabled);
+        assert!(!policy.freeze.enabled);
+        assert!(!policy.snapshot_thinning.enabled);
+        assert!(!policy.compress_in_place.enabled);
+    }
+
+    #[test]
+    fn compress_in_place_parses_and_validates() {
+        let mut cfg: Config = toml::from_str(
+            "[policy.compress_in_place]\n\
+             enabled = true\n\
+             categories = [\"cache_dir\", \"agent_workspace\"]\n\
+             extensions = [\"log\", \"jsonl\"]\n",
+        )
+        .expect("compress_in_place should parse");
+        let compress = &cfg.policy.compress_in_place;
+        assert!(compress.enabled);
+        assert_eq!(compress.categories.len(), 2);
+        assert_eq!(compress.level, 3);
+        cfg.validate().expect("valid compress_in_place");
+
+        cfg.policy
+            .compress_in_place
+            .extensions
+            .push("zst".to_string());
+        let err = cfg.validate().expect_err("zst extension rejected");
+        assert!(err.to_string().contains("extensions"));
+
+        cfg.policy.compress_in_place.extensions.pop();
+        cfg.policy.compress_in_place.level = 22;
+        let err = cfg.validate().expect_err("level out of range rejected");
+        assert!(err.to_string().contains("level"));
+    }
+
+    #[test]
+    fn cleanup_rules_parse_and_reject_builtin_names() {
+        let mut cfg: Config = toml::from_str(
+            "[[scanner.cleanup_rules]]\n\
+             name = \"ci-scratch\"\n\
+             path_glob = \"/scratch/ci-*\"\n\
+             age_threshold_hours = 12\n\
+             parent_check = \"not_required\"\n\
+             reclaim_command = \"remove_tree\"\n\
+             confidence = \"likely\"\n\
+             category = \"build_output\"\n",
+        )
+        .expect("cleanup rules should parse");
+        let rule = &cfg.scanner.cleanup_rules[0];
+        assert_eq!(rule.fd_check, CheckRequirement::Required);
+        assert_eq!(rule.parent_check, CheckRequirement::NotRequired);
+        cfg.validate().expect("valid cleanup rule");
+
+        cfg.scanner.cleanup_rules[0].name = "linux-tmp-dash-target".to_string();
+        let err = cfg.validate().expect_err("built-in name rejected");
+        assert!(err.to_string().contains("built-in cleanup rule"));
+    }
+
     #[test]
     fn scanner_engine_env_override_parses() {
         let mut cfg = Config::default();
@@ -2138,6 +2991,19 @@ mod tests {
         assert!(err.to_string().contains("strictly descend"));
     }
 
+    #[test]
+    fn inode_pressure_defaults_and_ordering() {
+        let cfg = Config::default();
+        assert!(cfg.pressure.inodes.enabled);
+        let thresholds = cfg.pressure.inodes.thresholds();
+        assert!(thresholds.green_min_free_pct > thresholds.red_min_free_pct);
+
+        let mut cfg = Config::default();
+        cfg.pressure.inodes.orange_min_free_pct = cfg.pressure.inodes.yellow_min_free_pct;
+        let err = cfg.validate().expect_err("expected inode ordering error");
+        assert!(err.to_string().contains("pressure.inodes"));
+    }
+
     #[test]
     fn ewma_alpha_ordering_enforced() {
         let mut cfg = Config::default();
@@ -2352,6 +3218,58 @@ mod tests {
         assert!(err.to_string().contains("writeback_sysctl_path"));
     }
 
+    #[test]
+    fn control_socket_access_parses_and_rejects_world_access() {
+        let mut cfg: Config = toml::from_str(
+            "[control]\n\
+             socket_group = \"sbh\"\n\
+             socket_mode = 0o660\n",
+        )
+        .expect("control section should parse");
+        assert_eq!(cfg.control.socket_group.as_deref(), Some("sbh"));
+        assert_eq!(cfg.control.socket_mode, 0o660);
+        cfg.validate().expect("group-accessible socket is valid");
+
+        cfg.control.socket_mode = 0o666;
+        let err = cfg.validate().expect_err("world access rejected");
+        assert!(err.to_string().contains("control.socket_mode"));
+
+        cfg.control.socket_mode = 0o060;
+        assert!(cfg.validate().is_err());
+        assert_eq!(Config::default().control.socket_mode, 0o600);
+    }
+
+    #[test]
+    fn metrics_http_listener_must_be_loopback() {
+        let mut cfg = Config::default();
+        cfg.metrics.mode = super::MetricsMode::Http;
+        assert!(cfg.validate().is_ok());
+
+        cfg.metrics.listen_addr = "0.0.0.0:9477".to_string();
+        let err = cfg.validate().expect_err("expected loopback error");
+        assert!(err.to_string().contains("metrics.listen_addr"));
+
+        cfg.metrics.listen_addr = "[::1]:9477".to_string();
+        assert!(cfg.validate().is_ok());
+    }
+
+    #[test]
+    fn metrics_textfile_path_must_be_absolute_prom() {
+        let mut cfg = Config::default();
+        cfg.metrics.mode = super::MetricsMode::Textfile;
+        assert!(cfg.validate().is_ok());
+
+        cfg.metrics.textfile_path = PathBuf::from("/var/lib/node_exporter/sbh.txt");
+        let err = cfg.validate().expect_err("expected .prom error");
+        assert!(err.to_string().contains("metrics.textfile_path"));
+
+        let parsed: Config =
+            toml::from_str("[metrics]\nmode = \"textfile\"\ntextfile_path = \"/tmp/sbh.prom\"\n")
+                .expect("metrics section should parse");
+        assert_eq!(parsed.metrics.mode, super::MetricsMode::Textfile);
+        assert_eq!(parsed.metrics.refresh_interval_secs, 15);
+    }
+
     #[test]
     fn scanner_repeat_deletion_max_cooldown_must_not_be_lower_than_base() {
         let mut cfg = Config::default();
@@ -2419,6 +3337,94 @@ mod tests {
         assert!(cfg.is_volume_enabled("/data"));
     }
 
+    #[test]
+    fn pressure_volume_override_resolves_bytes_and_percentages() {
+        let mut cfg: Config = toml::from_str(
+            r#"
+[pressure.overrides."/data/"]
+red_min_free_bytes = 50_000_000_000
+orange_min_free_pct = 4.0
+
+[pressure.overrides."/data/".prediction]
+action_horizon_minutes = 90.0
+warning_horizon_minutes = 180.0
+"#,
+        )
+        .expect("parse pressure overrides");
+        cfg.normalize_paths();
+        cfg.validate().expect("overrides are valid");
+
+        // 50 GB of a 4 TB volume is 1.25%; unset levels inherit the globals.
+        let data = cfg.pressure.thresholds_for("/data", 4_000_000_000_000);
+        assert!((data.red_min_free_pct - 1.25).abs() < 1e-9);
+        assert!((data.orange_min_free_pct - 4.0).abs() < f64::EPSILON);
+        assert!((data.yellow_min_free_pct - 14.0).abs() < f64::EPSILON);
+        assert_eq!(data.red_min_free_bytes(4_000_000_000_000), 50_000_000_000);
+
+        // On a small volume the byte threshold dominates and lifts the levels above it.
+        let small = cfg.pressure.thresholds_for("/data/", 100_000_000_000);
+        assert!((small.red_min_free_pct - 50.0).abs() < f64::EPSILON);
+        assert!((small.green_min_free_pct - 50.0).abs() < f64::EPSILON);
+
+        assert_eq!(
+            cfg.pressure.thresholds_for("/", 4_000_000_000_000),
+            cfg.pressure.thresholds_for("/unknown", 1)
+        );
+        let prediction = cfg.pressure.prediction_for("/data");
+        assert!((prediction.action_horizon_minutes - 90.0).abs() < f64::EPSILON);
+        assert!((prediction.imminent_danger_minutes - 5.0).abs() < f64::EPSILON);
+        assert!(
+            (cfg.pressure.prediction_for("/").action_horizon_minutes - 30.0).abs() < f64::EPSILON
+        );
+    }
+
+    #[test]
+    fn pressure_volume_override_validation() {
+        let mut cfg = Config::default();
+        cfg.pressure.overrides.insert(
+            "/data".to_string(),
+            super::PressureVolumeOverride {
+                red_min_free_pct: Some(2.0),
+                red_min_free_bytes: Some(1 << 30),
+                ..Default::default()
+            },
+        );
+        let err = cfg.validate().expect_err("pct and bytes for one level");
+        assert!(
+            err.to_string()
+                .contains("red_min_free_pct and red_min_free_bytes")
+        );
+
+        cfg.pressure.overrides.insert(
+            "/data".to_string(),
+            super::PressureVolumeOverride {
+                yellow_min_free_bytes: Some(1 << 30),
+                orange_min_free_bytes: Some(2 << 30),
+                ..Default::default()
+            },
+        );
+        let err = cfg.validate().expect_err("byte levels out of order");
+        assert!(err.to_string().contains("strictly descend"));
+
+        cfg.pressure.overrides.insert(
+            "/data".to_string(),
+            super::PressureVolumeOverride {
+                prediction: super::PredictionOverride {
+                    action_horizon_minutes: Some(120.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        let err = cfg
+            .validate()
+            .expect_err("prediction horizons out of order");
+        assert!(
+            err.to_string()
+                .contains("pressure.overrides[\"/data\"].prediction.warning_horizon_minutes")
+        );
+    }
+
     #[test]
     fn normalize_paths_trims_trailing_slashes_and_keeps_root() {
         let mut cfg = Config::default();
@@ -3,6 +3,7 @@
 pub mod config;
 pub mod errors;
 pub mod paths;
+pub mod state_file;
 pub mod update_cache;
 
 /// Render bytes as lowercase hexadecimal without relying on digest display traits.
@@ -0,0 +1,47 @@
+//! Atomic writes for the JSON state files the daemon keeps under its state dir.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+/// Write `value` as pretty JSON to `path` atomically: write to `.json.tmp`,
+/// fsync, then rename over `path`. The temp file is removed on any failure.
+///
+/// Sets 0o644 permissions on the temp file (Unix only) so the file is
+/// world-readable. State files hold only operational data and must be readable
+/// by the CLI running as a non-root user (e.g. `sbh status` run by ubuntu while
+/// the daemon runs as root).
+pub fn write_json_atomic(path: &Path, value: &impl Serialize) -> io::Result<()> {
+    let tmp_path = path.with_extension("json.tmp");
+
+    // Ensure parent directory exists.
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let json = serde_json::to_vec_pretty(value).map_err(io::Error::other)?;
+
+    let result = (|| {
+        {
+            let mut opts = OpenOptions::new();
+            opts.write(true).create(true).truncate(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::OpenOptionsExt as _;
+                opts.mode(0o644);
+            }
+            let mut file = opts.open(&tmp_path)?;
+            file.write_all(&json)?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
@@ -0,0 +1,474 @@
+//! Local control socket: typed JSON requests to a running daemon.
+//!
+//! Signals (see `signals.rs`) can nudge the daemon but carry no arguments and
+//! return no results. The control socket sits next to the state file and
+//! speaks one JSON request line per connection, answered with one JSON
+//! response line.
+//!
+//! A listener thread accepts connections and forwards each request to the
+//! main loop, which owns all mutable daemon state, answers at the start of
+//! its next tick, and wakes early from its inter-tick sleep when a request is
+//! pending.
+
+#![allow(missing_docs)]
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use crossbeam_channel::{Receiver, Sender, TrySendError, bounded};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::config::ControlConfig;
+use crate::core::errors::{Result, SbhError};
+use crate::daemon::policy::ActiveMode;
+
+/// How long a connection waits for the main loop to answer a request.
+///
+/// The main loop wakes within its 500ms sleep slice when a request is queued,
+/// so this only trips when a tick is stuck in expensive work.
+pub const CONTROL_REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Client-side default for a full request/response round trip.
+pub const CONTROL_CLIENT_TIMEOUT: Duration = Duration::from_secs(15);
+
+const CONTROL_CHANNEL_CAP: usize = 16;
+const CONTROL_READ_TIMEOUT: Duration = Duration::from_secs(2);
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const MAX_REQUEST_BYTES: u64 = 64 * 1024;
+
+// ──────────────────── protocol ────────────────────
+
+/// A typed request sent to the daemon over the control socket.
+///
+/// Serialized as an object tagged by `op`, e.g.
+/// `{"op":"release_ballast","count":2}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ControlRequest {
+    /// Live status snapshot (same shape as the SIGINFO status dump).
+    Status,
+    /// Live policy engine diagnostics.
+    PolicyDiagnostics,
+    /// Forced scan of specific absolute paths; empty scans the configured roots.
+    Scan {
+        #[serde(default)]
+        paths: Vec<PathBuf>,
+    },
+    /// Release up to `count` ballast files. `mount` may be any path on the
+    /// target volume; when omitted the pool holding `paths.ballast_dir` is used.
+    ReleaseBallast {
+        #[serde(default)]
+        mount: Option<PathBuf>,
+        count: usize,
+    },
+    /// Switch the policy engine to a specific active mode.
+    SetMode { mode: ActiveMode },
+    /// Lease `bytes` of free space on the volume holding `path` for `ttl_secs`.
+    Reserve {
+        path: PathBuf,
+        bytes: u64,
+        ttl_secs: u64,
+        #[serde(default)]
+        holder: Option<String>,
+    },
+    /// Drop a reservation before it expires.
+    ReleaseReservation { id: String },
+    /// Active reservations with their outstanding bytes.
+    Reservations,
+}
+
+/// Structured reply to a [`ControlRequest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ControlResponse {
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub result: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ControlResponse {
+    #[must_use]
+    pub fn success(result: Value) -> Self {
+        Self {
+            ok: true,
+            result,
+            error: None,
+        }
+    }
+
+    #[must_use]
+    pub fn failure(error: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            result: Value::Null,
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// A request handed to the main loop together with its reply slot.
+pub struct ControlCommand {
+    pub request: ControlRequest,
+    reply: Sender<ControlResponse>,
+}
+
+impl ControlCommand {
+    /// Send the reply back to the waiting connection. A client that already
+    /// gave up is not an error.
+    pub fn respond(self, response: ControlResponse) {
+        let _ = self.reply.send(response);
+    }
+}
+
+// ──────────────────── server ────────────────────
+
+/// Listener thread plus the queue of requests awaiting the main loop.
+///
+/// Dropping the server stops the listener and removes the socket file.
+pub struct ControlServer {
+    path: PathBuf,
+    commands: Receiver<ControlCommand>,
+    stop: Arc<AtomicBool>,
+    join: Option<thread::JoinHandle<()>>,
+}
+
+impl ControlServer {
+    /// Bind the control socket at `path` and start the listener thread.
+    ///
+    /// A leftover socket file from a crashed daemon is replaced; a socket
+    /// that still accepts connections belongs to another live daemon and is
+    /// left alone. The socket gets `access.socket_mode` (0600 by default) and,
+    /// if configured, `access.socket_group`.
+    pub fn bind(path: &Path, access: &ControlConfig) -> Result<Self> {
+        if path.exists() {
+            if UnixStream::connect(path).is_ok() {
+                return Err(SbhError::Runtime {
+                    details: format!(
+                        "control socket {} is already served by another daemon",
+                        path.display()
+                    ),
+                });
+            }
+            std::fs::remove_file(path).map_err(|e| SbhError::io(path, e))?;
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| SbhError::io(parent, e))?;
+        }
+
+        let listener = UnixListener::bind(path).map_err(|e| SbhError::io(path, e))?;
+        if let Err(e) = apply_socket_access(path, access) {
+            let _ = std::fs::remove_file(path);
+            return Err(e);
+        }
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| SbhError::io(path, e))?;
+
+        let (tx, rx) = bounded::<ControlCommand>(CONTROL_CHANNEL_CAP);
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+        let join = thread::Builder::new()
+            .name("sbh-control".to_string())
+            .spawn(move || listener_thread_main(&listener, &tx, &thread_stop))
+            .map_err(|source| SbhError::Runtime {
+                details: format!("failed to spawn control socket thread: {source}"),
+            })?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            commands: rx,
+            stop,
+            join: Some(join),
+        })
+    }
+
+    /// Socket path this server is bound to.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether a request is waiting for the main loop.
+    #[must_use]
+    pub fn has_pending(&self) -> bool {
+        !self.commands.is_empty()
+    }
+
+    /// Take the next pending request, if any.
+    #[must_use]
+    pub fn try_recv(&self) -> Option<ControlCommand> {
+        self.commands.try_recv().ok()
+    }
+}
+
+impl Drop for ControlServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(join) = self.join.take() {
+            let _ = join.join();
+        }
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Set the socket's group (if configured) before widening its mode, so the
+/// group bits never apply to the daemon's own group.
+fn apply_socket_access(path: &Path, access: &ControlConfig) -> Result<()> {
+    if let Some(name) = access.socket_group.as_deref() {
+        let group = nix::unistd::Group::from_name(name)
+            .map_err(|e| SbhError::Runtime {
+                details: format!("control socket group {name}: {e}"),
+            })?
+            .ok_or_else(|| SbhError::Runtime {
+                details: format!("control socket group {name} does not exist"),
+            })?;
+        std::os::unix::fs::chown(path, None, Some(group.gid.as_raw()))
+            .map_err(|e| SbhError::io(path, e))?;
+    }
+    std::fs::set_permissions(
+        path,
+        std::fs::Permissions::from_mode(access.socket_mode & 0o777),
+    )
+    .map_err(|e| SbhError::io(path, e))
+}
+
+fn listener_thread_main(listener: &UnixListener, tx: &Sender<ControlCommand>, stop: &AtomicBool) {
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                if let Err(e) = serve_connection(&stream, tx) {
+                    eprintln!("[SBH-DAEMON] control socket connection failed: {e}");
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(ACCEPT_POLL_INTERVAL);
+            }
+            Err(e) => {
+                eprintln!("[SBH-DAEMON] control socket accept failed: {e}");
+                thread::sleep(ACCEPT_POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+fn serve_connection(stream: &UnixStream, tx: &Sender<ControlCommand>) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(CONTROL_READ_TIMEOUT))?;
+    stream.set_write_timeout(Some(CONTROL_READ_TIMEOUT))?;
+
+    let mut line = String::new();
+    BufReader::new(stream.take(MAX_REQUEST_BYTES)).read_line(&mut line)?;
+
+    let response = match serde_json::from_str::<ControlRequest>(line.trim()) {
+        Ok(request) => dispatch(request, tx),
+        Err(e) => ControlResponse::failure(format!("invalid control request: {e}")),
+    };
+    write_message(stream, &response)
+}
+
+fn dispatch(request: ControlRequest, tx: &Sender<ControlCommand>) -> ControlResponse {
+    let (reply_tx, reply_rx) = bounded(1);
+    let command = ControlCommand {
+        request,
+        reply: reply_tx,
+    };
+    match tx.try_send(command) {
+        Ok(()) => {}
+        Err(TrySendError::Full(_)) => {
+            return ControlResponse::failure("daemon busy: too many pending control requests");
+        }
+        Err(TrySendError::Disconnected(_)) => {
+            return ControlResponse::failure("daemon is shutting down");
+        }
+    }
+    reply_rx
+        .recv_timeout(CONTROL_REPLY_TIMEOUT)
+        .unwrap_or_else(|_| ControlResponse::failure("timed out waiting for the daemon main loop"))
+}
+
+fn write_message<T: Serialize>(mut stream: &UnixStream, message: &T) -> io::Result<()> {
+    let mut encoded = serde_json::to_vec(message).map_err(io::Error::other)?;
+    encoded.push(b'\n');
+    stream.write_all(&encoded)?;
+    stream.flush()
+}
+
+// ──────────────────── client ────────────────────
+
+/// Send one request to the daemon listening at `path` and wait for its reply.
+///
+/// Connection errors (`NotFound`, `ConnectionRefused`, `PermissionDenied`)
+/// mean no reachable daemon; callers fall back to acting directly.
+pub fn send_request(
+    path: &Path,
+    request: &ControlRequest,
+    timeout: Duration,
+) -> io::Result<ControlResponse> {
+    let stream = UnixStream::connect(path)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    write_message(&stream, request)?;
+
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    if line.trim().is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "daemon closed the control connection without replying",
+        ));
+    }
+    serde_json::from_str(line.trim()).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// ──────────────────── tests ────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn requests_use_op_tag_and_snake_case() {
+        let encoded = serde_json::to_value(ControlRequest::ReleaseBallast {
+            mount: None,
+            count: 2,
+        })
+        .unwrap();
+        assert_eq!(
+            encoded,
+            json!({"op": "release_ballast", "mount": null, "count": 2})
+        );
+
+        let decoded: ControlRequest =
+            serde_json::from_str(r#"{"op":"set_mode","mode":"fallback_safe"}"#).unwrap();
+        assert_eq!(
+            decoded,
+            ControlRequest::SetMode {
+                mode: ActiveMode::FallbackSafe
+            }
+        );
+
+        let decoded: ControlRequest = serde_json::from_str(r#"{"op":"scan"}"#).unwrap();
+        assert_eq!(decoded, ControlRequest::Scan { paths: Vec::new() });
+
+        let decoded: ControlRequest =
+            serde_json::from_str(r#"{"op":"reserve","path":"/data","bytes":1024,"ttl_secs":60}"#)
+                .unwrap();
+        assert_eq!(
+            decoded,
+            ControlRequest::Reserve {
+                path: PathBuf::from("/data"),
+                bytes: 1024,
+                ttl_secs: 60,
+                holder: None,
+            }
+        );
+    }
+
+    #[test]
+    fn round_trip_through_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("control.sock");
+        let server = ControlServer::bind(&path, &ControlConfig::default()).unwrap();
+
+        let client_path = path.clone();
+        let client = thread::spawn(move || {
+            send_request(
+                &client_path,
+                &ControlRequest::Status,
+                CONTROL_CLIENT_TIMEOUT,
+            )
+        });
+
+        let command = loop {
+            if let Some(command) = server.try_recv() {
+                break command;
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!(command.request, ControlRequest::Status);
+        command.respond(ControlResponse::success(json!({"pid": 42})));
+
+        let response = client.join().unwrap().unwrap();
+        assert!(response.ok);
+        assert_eq!(response.result["pid"], 42);
+
+        drop(server);
+        assert!(!path.exists(), "socket file should be removed on drop");
+    }
+
+    #[test]
+    fn malformed_request_gets_structured_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("control.sock");
+        let _server = ControlServer::bind(&path, &ControlConfig::default()).unwrap();
+
+        let mut stream = UnixStream::connect(&path).unwrap();
+        stream.write_all(b"{\"op\":\"reboot\"}\n").unwrap();
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line).unwrap();
+        let response: ControlResponse = serde_json::from_str(&line).unwrap();
+        assert!(!response.ok);
+        assert!(response.error.unwrap().contains("invalid control request"));
+    }
+
+    #[test]
+    fn bind_replaces_stale_socket_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("control.sock");
+        drop(UnixListener::bind(&path).unwrap());
+        assert!(path.exists());
+
+        let server = ControlServer::bind(&path, &ControlConfig::default()).unwrap();
+        assert_eq!(server.path(), path.as_path());
+        assert!(ControlServer::bind(&path, &ControlConfig::default()).is_err());
+    }
+
+    #[test]
+    fn bind_applies_configured_group_and_mode() {
+        use std::os::unix::fs::MetadataExt as _;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("control.sock");
+        let gid = nix::unistd::getegid();
+        let Some(group) = nix::unistd::Group::from_gid(gid).ok().flatten() else {
+            return;
+        };
+        let access = ControlConfig {
+            socket_group: Some(group.name),
+            socket_mode: 0o660,
+        };
+        let server = ControlServer::bind(&path, &access).unwrap();
+        let meta = std::fs::metadata(&path).unwrap();
+        assert_eq!(meta.mode() & 0o777, 0o660);
+        assert_eq!(meta.gid(), gid.as_raw());
+        drop(server);
+
+        let access = ControlConfig {
+            socket_group: Some("sbh-no-such-group".to_string()),
+            socket_mode: 0o660,
+        };
+        assert!(ControlServer::bind(&path, &access).is_err());
+        assert!(!path.exists(), "a socket with unapplied access is removed");
+    }
+
+    #[test]
+    fn send_request_without_daemon_reports_connect_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = send_request(
+            &dir.path().join("missing.sock"),
+            &ControlRequest::Status,
+            CONTROL_CLIENT_TIMEOUT,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,252 @@
+//! Last-resort freeze of the top disk writer.
+//!
+//! Write throttling slows a runaway writer; it cannot stop one. When a volume
+//! is Critical, its ballast pool is spent and the cleanup pipeline has freed
+//! nothing for `policy.freeze.stall_secs`, the daemon freezes the cgroup
+//! writing fastest (`cgroup.freeze`, or SIGSTOP of its processes on kernels
+//! without the v2 freezer) so the volume cannot fill while an operator steps
+//! in. Freezing is opt-in (`policy.freeze.enabled`) and only happens in
+//! enforce mode, after the emergency escalation rung.
+//!
+//! A frozen writer is thawed as soon as its volume drops below Red, or after
+//! `policy.freeze.max_freeze_secs`; a timed-out freeze puts the volume in a
+//! cooldown of the same length before anything there is frozen again. Frozen
+//! cgroups live in `frozen-writers.json` next to the state file so a daemon
+//! restarted after a crash thaws what it left behind.
+
+#![allow(missing_docs)]
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::state_file::write_json_atomic;
+
+/// One frozen cgroup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrozenWriter {
+    pub cgroup: String,
+    /// Critical mount the writer was filling.
+    pub mount: PathBuf,
+    /// Write rate that triggered the freeze.
+    pub observed_bytes_per_sec: u64,
+    /// Unix seconds.
+    pub frozen_at: u64,
+}
+
+impl FrozenWriter {
+    #[must_use]
+    pub const fn frozen_secs(&self, now: u64) -> u64 {
+        now.saturating_sub(self.frozen_at)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FreezeFile {
+    frozen: Vec<FrozenWriter>,
+}
+
+/// Frozen cgroups plus per-mount cooldowns after timed-out freezes.
+#[derive(Debug)]
+pub struct FreezeLedger {
+    path: PathBuf,
+    frozen: Vec<FrozenWriter>,
+    /// Mount → Unix seconds until which nothing there is frozen again.
+    cooldowns: HashMap<PathBuf, u64>,
+    notices: HashSet<String>,
+}
+
+impl FreezeLedger {
+    /// Load writers frozen by a previous daemon. A missing or unreadable file
+    /// starts empty.
+    #[must_use]
+    pub fn load(path: PathBuf) -> Self {
+        let frozen = fs::read(&path)
+            .ok()
+            .and_then(|raw| serde_json::from_slice::<FreezeFile>(&raw).ok())
+            .map(|file| file.frozen)
+            .unwrap_or_default();
+        Self {
+            path,
+            frozen,
+            cooldowns: HashMap::new(),
+            notices: HashSet::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn frozen(&self) -> &[FrozenWriter] {
+        &self.frozen
+    }
+
+    /// Every frozen cgroup, on any mount.
+    #[must_use]
+    pub fn frozen_cgroups(&self) -> Vec<&str> {
+        self.frozen
+            .iter()
+            .map(|writer| writer.cgroup.as_str())
+            .collect()
+    }
+
+    #[must_use]
+    pub fn has_frozen_on(&self, mount: &Path) -> bool {
+        self.frozen.iter().any(|writer| writer.mount == mount)
+    }
+
+    /// Keep `mount` from being frozen again until `until` (Unix seconds).
+    pub fn start_cooldown(&mut self, mount: &Path, until: u64) {
+        self.cooldowns.insert(mount.to_path_buf(), until);
+    }
+
+    #[must_use]
+    pub fn in_cooldown(&self, mount: &Path, now: u64) -> bool {
+        self.cooldowns.get(mount).is_some_and(|until| now < *until)
+    }
+
+    /// True the first time `key` comes up in this Critical episode.
+    pub fn first_notice(&mut self, key: &str) -> bool {
+        self.notices.insert(key.to_string())
+    }
+
+    /// Forget logged notices once no volume is Critical.
+    pub fn reset_notices(&mut self) {
+        self.notices.clear();
+    }
+
+    pub fn record(&mut self, writer: FrozenWriter) {
+        self.frozen.push(writer);
+    }
+
+    /// Remove and return every frozen writer for which `keep` is false.
+    pub fn release_unless(&mut self, keep: impl Fn(&FrozenWriter) -> bool) -> Vec<FrozenWriter> {
+        let (kept, released): (Vec<_>, Vec<_>) = std::mem::take(&mut self.frozen)
+            .into_iter()
+            .partition(|writer| keep(writer));
+        self.frozen = kept;
+        released
+    }
+
+    /// Write the frozen set atomically (`.tmp` + rename, mode 0644).
+    pub fn persist(&self) -> io::Result<()> {
+        write_json_atomic(
+            &self.path,
+            &FreezeFile {
+                frozen: self.frozen.clone(),
+            },
+        )
+    }
+}
+
+/// How long cleanup has gone without freeing anything while Critical.
+///
+/// The clock starts when pressure turns Critical and restarts after every
+/// deletion batch that freed space; a batch that freed nothing
+/// (`DeletionReport::stalled`) leaves it running, as does having no batch to
+/// run at all.
+#[derive(Debug, Default)]
+pub struct CleanupStallTracker {
+    since: Option<Instant>,
+}
+
+impl CleanupStallTracker {
+    pub fn observe_pressure(&mut self, critical: bool, now: Instant) {
+        if !critical {
+            self.since = None;
+        } else if self.since.is_none() {
+            self.since = Some(now);
+        }
+    }
+
+    pub fn observe_batch(&mut self, stalled: bool, now: Instant) {
+        if !stalled && self.since.is_some() {
+            self.since = Some(now);
+        }
+    }
+
+    #[must_use]
+    pub fn stalled_for(&self, now: Instant) -> Duration {
+        self.since
+            .map_or(Duration::ZERO, |since| now.saturating_duration_since(since))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn writer(cgroup: &str, mount: &str) -> FrozenWriter {
+        FrozenWriter {
+            cgroup: cgroup.to_string(),
+            mount: PathBuf::from(mount),
+            observed_bytes_per_sec: 1_000,
+            frozen_at: 1_000,
+        }
+    }
+
+    #[test]
+    fn stall_clock_runs_only_while_critical_and_unproductive() {
+        let start = Instant::now();
+        let mut tracker = CleanupStallTracker::default();
+        tracker.observe_batch(true, start);
+        assert_eq!(tracker.stalled_for(start), Duration::ZERO);
+
+        tracker.observe_pressure(true, start);
+        tracker.observe_batch(true, start + Duration::from_secs(10));
+        tracker.observe_pressure(true, start + Duration::from_secs(20));
+        assert_eq!(
+            tracker.stalled_for(start + Duration::from_secs(30)),
+            Duration::from_secs(30)
+        );
+
+        tracker.observe_batch(false, start + Duration::from_secs(30));
+        assert_eq!(
+            tracker.stalled_for(start + Duration::from_secs(45)),
+            Duration::from_secs(15)
+        );
+
+        tracker.observe_pressure(false, start + Duration::from_secs(50));
+        assert_eq!(
+            tracker.stalled_for(start + Duration::from_mins(1)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn frozen_writers_persist_release_and_cool_down() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("frozen-writers.json");
+        let mut ledger = FreezeLedger::load(path.clone());
+        ledger.record(writer("/user.slice/a.scope", "/data"));
+        ledger.record(writer("/user.slice/b.scope", "/"));
+        ledger.persist().unwrap();
+        assert!(!dir.path().join("frozen-writers.json.tmp").exists());
+
+        let mut reloaded = FreezeLedger::load(path);
+        assert_eq!(reloaded.frozen(), ledger.frozen());
+        assert!(reloaded.has_frozen_on(Path::new("/data")));
+        assert_eq!(
+            reloaded.frozen_cgroups(),
+            vec!["/user.slice/a.scope", "/user.slice/b.scope"]
+        );
+        assert_eq!(reloaded.frozen()[0].frozen_secs(1_300), 300);
+
+        let released = reloaded.release_unless(|writer| writer.mount == Path::new("/"));
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].cgroup, "/user.slice/a.scope");
+        assert!(!reloaded.has_frozen_on(Path::new("/data")));
+
+        reloaded.start_cooldown(Path::new("/data"), 1_600);
+        assert!(reloaded.in_cooldown(Path::new("/data"), 1_599));
+        assert!(!reloaded.in_cooldown(Path::new("/data"), 1_600));
+        assert!(!reloaded.in_cooldown(Path::new("/"), 1_000));
+
+        assert!(reloaded.first_notice("veto:/init.scope"));
+        assert!(!reloaded.first_notice("veto:/init.scope"));
+        reloaded.reset_notices();
+        assert!(reloaded.first_notice("veto:/init.scope"));
+    }
+}
@@ -14,7 +14,7 @@
 #![allow(clippy::cast_precision_loss)]
 
 use std::borrow::Cow;
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
@@ -25,18 +25,30 @@ use crossbeam_channel::{Receiver, RecvTimeoutError, Sender, TryRecvError, TrySen
 use parking_lot::{Mutex, RwLock};
 use serde_json::{Value, json};
 
-use crate::ballast::coordinator::BallastPoolCoordinator;
+use crate::ballast::coordinator::{BallastPoolCoordinator, PoolInventory};
 use crate::ballast::release::BallastReleaseController;
-use crate::core::config::{Config, ScannerConfig, ScannerEngineMode};
+use crate::core::config::{
+    Config, InodePressureConfig, PredictionConfig, PressureThresholds, ScannerConfig,
+    ScannerEngineMode,
+};
 use crate::core::errors::{Result, SbhError};
+#[cfg(unix)]
+use crate::daemon::control::{ControlRequest, ControlResponse, ControlServer};
+use crate::daemon::freeze::{CleanupStallTracker, FreezeLedger, FrozenWriter};
+use crate::daemon::metrics::MetricsExporter;
 use crate::daemon::notifications::{NotificationEvent, NotificationLevel, NotificationManager};
 use crate::daemon::policy::{
     ActiveMode, BallastAction, BehaviorDispatchTable, BehaviorMode, BehaviorPressureLevel,
-    CleanupAction, NotificationPriority, PolicyEngine, ScanAggressiveness,
+    CleanupAction, CompressInPlaceConfig, FreezeConfig, NotificationPriority, PolicyEngine,
+    ScanAggressiveness, WriteThrottleAction, cgroup_contains,
 };
 use crate::daemon::process_io_history::ProcessIoHistory;
+use crate::daemon::reservations::{self, ReservationLedger};
 use crate::daemon::self_monitor::{SelfMonitor, SelfMonitorTick, ThreadHeartbeat, ThreadStatus};
 use crate::daemon::signals::{SignalHandler, WatchdogHeartbeat};
+use crate::daemon::write_throttle::{
+    CgroupWriteRate, WriteThrottle, WriteThrottler, select_throttle_target, select_top_writer,
+};
 use crate::logger::dual::{
     ActivityEvent, ActivityLoggerHandle, DualLoggerConfig, ScanCompletionTelemetry, spawn_logger,
 };
@@ -47,18 +59,29 @@ use crate::monitor::guardrails::{
     AdaptiveGuard, CalibrationObservation, GuardDiagnostics, GuardStatus, PredictionScorecard,
 };
 use crate::monitor::pid::{
-    PidPressureController, PressureLevel, PressureReading, PressureResponse,
+    PidPressureController, PressureDimension, PressureLevel, PressureReading, PressureResponse,
 };
 use crate::monitor::predictive::{PredictiveAction, PredictiveActionPolicy};
 use crate::monitor::special_locations::SpecialLocationRegistry;
 use crate::monitor::voi_scheduler::VoiScheduler;
 use crate::platform::pal::{MemoryInfo, Platform, detect_platform};
 use crate::platform::types::{
-    FullDiskAccessState, FullDiskAccessStatus, MemoryPressure, MemoryPressureLevel,
+    CgroupIo, FsSnapshotInfo, FullDiskAccessState, FullDiskAccessStatus, MemoryPressure,
+    MemoryPressureLevel,
+};
+use crate::scanner::bazel;
+use crate::scanner::compress;
+use crate::scanner::decision_record::{
+    ActionRecord, DecisionRecord, GitWorkspaceEvidence, PolicyMode, SnapshotEvidence,
+    ThrottleEvidence,
+};
+use crate::scanner::deleted_open::{
+    HeldDeletedFile, group_deleted_open_files, truncate_held_files,
 };
 use crate::scanner::deletion::{DeletionConfig, DeletionExecutor};
 use crate::scanner::engine::{ScannerEngine, SelectedScannerEngine};
 use crate::scanner::events::{EventSourceConfig, ScannerEventSource};
+use crate::scanner::git_workspace::{self, GitWorkspace};
 use crate::scanner::index::{
     CandidateIndexRecord, IndexedIdentity, ScannerCandidateIndex, ScannerIndexContext,
     ScannerIndexLoadStatus,
@@ -68,10 +91,13 @@ use crate::scanner::patterns::{
     StructuralSignals,
 };
 use crate::scanner::protection::{self, ProtectionRegistry};
+use crate::scanner::provenance::ArtifactProvenance;
 use crate::scanner::scoring::{ActiveReferenceSummary, CandidacyScore, ScoringEngine};
+use crate::scanner::target_sweep;
 use crate::scanner::walker::{
     ActiveReferenceIndex, ActiveReferenceScanConfig, DirectoryWalker, WalkerConfig,
-    collect_active_reference_index_cached, collect_open_path_ancestors_cached,
+    collect_active_reference_index_cached, collect_open_path_ancestors,
+    collect_open_path_ancestors_cached,
 };
 
 // ──────────────────── channel capacities ────────────────────
@@ -112,6 +138,13 @@ const SWAP_THRASH_MIN_AVAILABLE_RAM_BYTES: u64 = 8 * 1024 * 1024 * 1024;
 const TEMP_FAST_TRACK_MIN_OBSERVED_AGE: Duration = Duration::from_mins(2);
 /// Recheck macOS Full Disk Access grants without adding pressure-loop noise.
 const FULL_DISK_ACCESS_RECHECK_INTERVAL: Duration = Duration::from_mins(5);
+/// Re-enumerate deleted-but-open files this often while some volume is at or
+/// below `scanner.deleted_open_files.pressure_free_pct_ceiling`...
+const DELETED_OPEN_PRESSURE_SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+/// ...and this often otherwise (the list only feeds `sbh status`).
+const DELETED_OPEN_IDLE_SWEEP_INTERVAL: Duration = Duration::from_mins(5);
+/// Deleted-but-open files listed in the status dump, largest first.
+const DELETED_OPEN_STATUS_LIMIT: usize = 50;
 /// Memory pressure callbacks wake the monitor loop instead of waiting for the
 /// next disk-pressure poll.
 const MEMORY_PRESSURE_CHANNEL_CAP: usize = 16;
@@ -301,6 +334,8 @@ pub struct ScanRequest {
     /// Actual free percentage for the mount/root that triggered this scan.
     /// `None` is allowed for synthetic unit-test requests and degraded callers.
     pub free_pct: Option<f64>,
+    /// Whether free bytes or free inodes drove the pressure behind this scan.
+    pub pressure_dimension: PressureDimension,
     pub max_delete_batch: usize,
     /// Explicit operator/service request that must reconcile the configured roots
     /// even when v2 has no dirty event roots under green/yellow pressure.
@@ -481,6 +516,8 @@ enum WorkerReport {
         deleted: u64,
         bytes_freed: u64,
         failed: u64,
+        /// The batch freed nothing (see `DeletionReport::stalled`).
+        stalled: bool,
     },
 }
 
@@ -745,8 +782,16 @@ impl Default for DaemonArgs {
 struct MountMonitor {
     rate_estimator: DiskRateEstimator,
     pressure_controller: PidPressureController,
+    /// Effective `[pressure.prediction]` for this mount (after overrides).
+    prediction: PredictionConfig,
+    predictive_policy: PredictiveActionPolicy,
+    /// Level this mount last reported, for per-mount transition records.
+    level: PressureLevel,
     guard: AdaptiveGuard,
     last_guard_sample: Option<GuardSample>,
+    /// Free-inode tracking (`[pressure.inodes]`), fed only on filesystems
+    /// with a fixed inode table.
+    inodes: InodeMonitor,
 }
 
 struct GuardSample {
@@ -756,8 +801,132 @@ struct GuardSample {
     predicted_tte: f64,
 }
 
-impl MountMonitor {
+/// Second EWMA + PID pair for one mount, driven by free inodes instead of
+/// free bytes.
+struct InodeMonitor {
+    rate_estimator: DiskRateEstimator,
+    pressure_controller: PidPressureController,
+}
+
+/// One inode observation: the controller's response plus the readings that
+/// fed it.
+struct InodeReading {
+    response: PressureResponse,
+    snapshot: InodeSnapshot,
+}
+
+/// Inode state of one mount, as shown in the status dump.
+#[derive(Debug, Clone, PartialEq)]
+struct InodeSnapshot {
+    level: PressureLevel,
+    free_inodes: u64,
+    total_inodes: u64,
+    free_pct: f64,
+    rate_per_sec: f64,
+    seconds_to_exhaustion: f64,
+}
+
+impl InodeMonitor {
     fn new(config: &Config) -> Self {
+        let thresholds = config.pressure.inodes.thresholds();
+        let mut pressure_controller = PidPressureController::new(
+            0.25,  // kp
+            0.08,  // ki
+            0.02,  // kd
+            100.0, // integral_cap
+            thresholds.green_min_free_pct,
+            1.0, // hysteresis_pct
+            thresholds.green_min_free_pct,
+            thresholds.yellow_min_free_pct,
+            thresholds.orange_min_free_pct,
+            thresholds.red_min_free_pct,
+            Duration::from_millis(config.pressure.poll_interval_ms),
+        )
+        .with_dimension(PressureDimension::Inodes);
+        if config.pressure.prediction.enabled {
+            pressure_controller
+                .set_action_horizon_minutes(config.pressure.prediction.action_horizon_minutes);
+        }
+        Self {
+            rate_estimator: DiskRateEstimator::with_history_cap(
+                config.telemetry.ewma_base_alpha,
+                config.telemetry.ewma_min_alpha,
+                config.telemetry.ewma_max_alpha,
+                config.telemetry.ewma_min_samples,
+                config.telemetry.ewma_rate_history_size,
+            ),
+            pressure_controller,
+        }
+    }
+
+    fn update_config(&mut self, config: &Config) {
+        self.rate_estimator.update_params(
+            config.telemetry.ewma_base_alpha,
+            config.telemetry.ewma_min_alpha,
+            config.telemetry.ewma_max_alpha,
+            config.telemetry.ewma_min_samples,
+        );
+        let thresholds = config.pressure.inodes.thresholds();
+        self.pressure_controller
+            .set_target_free_pct(thresholds.green_min_free_pct);
+        self.pressure_controller.set_pressure_thresholds(
+            thresholds.green_min_free_pct,
+            thresholds.yellow_min_free_pct,
+            thresholds.orange_min_free_pct,
+            thresholds.red_min_free_pct,
+        );
+        self.pressure_controller
+            .set_base_poll_interval(Duration::from_millis(config.pressure.poll_interval_ms));
+        if config.pressure.prediction.enabled {
+            self.pressure_controller
+                .set_action_horizon_minutes(config.pressure.prediction.action_horizon_minutes);
+        } else {
+            self.pressure_controller.disable_urgency_boost();
+        }
+    }
+
+    /// Feed one sample. `None` when inode tracking is disabled or the
+    /// filesystem has no fixed inode table.
+    fn observe(
+        &mut self,
+        settings: &InodePressureConfig,
+        stats: &crate::platform::pal::FsStats,
+        now: Instant,
+    ) -> Option<InodeReading> {
+        let free_pct = stats.inode_free_pct().filter(|_| settings.enabled)?;
+        // Percent thresholds convert the same way for any count.
+        let red_threshold = settings.thresholds().red_min_free_bytes(stats.total_inodes);
+        let estimate = self
+            .rate_estimator
+            .update(stats.free_inodes, now, red_threshold);
+        let predicted_seconds = (estimate.seconds_to_threshold.is_finite()
+            && estimate.seconds_to_threshold > 0.0)
+            .then_some(estimate.seconds_to_threshold);
+        let response = self.pressure_controller.update(
+            PressureReading {
+                free_bytes: stats.free_inodes,
+                total_bytes: stats.total_inodes,
+                mount: stats.mount_point.clone(),
+            },
+            predicted_seconds,
+            now,
+        );
+        Some(InodeReading {
+            snapshot: InodeSnapshot {
+                level: response.level,
+                free_inodes: stats.free_inodes,
+                total_inodes: stats.total_inodes,
+                free_pct,
+                rate_per_sec: estimate.bytes_per_second,
+                seconds_to_exhaustion: estimate.seconds_to_exhaustion,
+            },
+            response,
+        })
+    }
+}
+
+impl MountMonitor {
+    fn new(config: &Config, mount: &str) -> Self {
         let rate_estimator = DiskRateEstimator::with_history_cap(
             config.telemetry.ewma_base_alpha,
             config.telemetry.ewma_min_alpha,
@@ -766,22 +935,25 @@ impl MountMonitor {
             config.telemetry.ewma_rate_history_size,
         );
 
+        // Byte-based overrides need the mount's capacity; `apply_thresholds`
+        // resolves them on every pressure check.
+        let thresholds = config.pressure.thresholds_for(mount, 0);
+        let prediction = config.pressure.prediction_for(mount);
         let mut pressure_controller = PidPressureController::new(
             0.25,  // kp
             0.08,  // ki
             0.02,  // kd
             100.0, // integral_cap
-            config.pressure.green_min_free_pct,
+            thresholds.green_min_free_pct,
             1.0, // hysteresis_pct
-            config.pressure.green_min_free_pct,
-            config.pressure.yellow_min_free_pct,
-            config.pressure.orange_min_free_pct,
-            config.pressure.red_min_free_pct,
+            thresholds.green_min_free_pct,
+            thresholds.yellow_min_free_pct,
+            thresholds.orange_min_free_pct,
+            thresholds.red_min_free_pct,
             Duration::from_millis(config.pressure.poll_interval_ms),
         );
-        if config.pressure.prediction.enabled {
-            pressure_controller
-                .set_action_horizon_minutes(config.pressure.prediction.action_horizon_minutes);
+        if prediction.enabled {
+            pressure_controller.set_action_horizon_minutes(prediction.action_horizon_minutes);
         }
 
         let guard_config = crate::monitor::guardrails::GuardrailConfig {
@@ -793,12 +965,17 @@ impl MountMonitor {
         Self {
             rate_estimator,
             pressure_controller,
+            predictive_policy: PredictiveActionPolicy::from_config(prediction.clone()),
+            prediction,
+            level: PressureLevel::Green,
             guard: AdaptiveGuard::new(guard_config),
             last_guard_sample: None,
+            inodes: InodeMonitor::new(config),
         }
     }
 
-    fn update_config(&mut self, config: &Config) {
+    fn update_config(&mut self, config: &Config, mount: &str) {
+        self.inodes.update_config(config);
         self.rate_estimator.update_params(
             config.telemetry.ewma_base_alpha,
             config.telemetry.ewma_min_alpha,
@@ -806,25 +983,32 @@ impl MountMonitor {
             config.telemetry.ewma_min_samples,
         );
 
-        self.pressure_controller
-            .set_target_free_pct(config.pressure.green_min_free_pct);
-        self.pressure_controller.set_pressure_thresholds(
-            config.pressure.green_min_free_pct,
-            config.pressure.yellow_min_free_pct,
-            config.pressure.orange_min_free_pct,
-            config.pressure.red_min_free_pct,
-        );
+        // Thresholds are re-resolved against live capacity in `check_pressure`.
+        self.apply_thresholds(config.pressure.thresholds_for(mount, 0));
         self.pressure_controller
             .set_base_poll_interval(Duration::from_millis(config.pressure.poll_interval_ms));
 
-        if config.pressure.prediction.enabled {
+        self.prediction = config.pressure.prediction_for(mount);
+        self.predictive_policy = PredictiveActionPolicy::from_config(self.prediction.clone());
+        if self.prediction.enabled {
             self.pressure_controller
-                .set_action_horizon_minutes(config.pressure.prediction.action_horizon_minutes);
+                .set_action_horizon_minutes(self.prediction.action_horizon_minutes);
         } else {
             self.pressure_controller.disable_urgency_boost();
         }
     }
 
+    fn apply_thresholds(&mut self, thresholds: PressureThresholds) {
+        self.pressure_controller
+            .set_target_free_pct(thresholds.green_min_free_pct);
+        self.pressure_controller.set_pressure_thresholds(
+            thresholds.green_min_free_pct,
+            thresholds.yellow_min_free_pct,
+            thresholds.orange_min_free_pct,
+            thresholds.red_min_free_pct,
+        );
+    }
+
     fn observe_guard(
         &mut self,
         now: Instant,
@@ -905,7 +1089,6 @@ pub struct MonitoringDaemon {
     shared_executor_config: Arc<SharedExecutorConfig>,
     shared_scoring_config: Arc<RwLock<crate::core::config::ScoringConfig>>,
     shared_scanner_config: Arc<RwLock<crate::core::config::ScannerConfig>>,
-    cached_primary_path: PathBuf,
     start_time: Instant,
     last_pressure_level: PressureLevel,
     /// Highest pressure level that was notified within the cooldown window.
@@ -920,7 +1103,6 @@ pub struct MonitoringDaemon {
     last_predictive_warning: Option<Instant>,
     last_predictive_level: Option<NotificationLevel>,
     last_ewma_confidence: f64,
-    predictive_policy: PredictiveActionPolicy,
     last_predictive_action: PredictiveAction,
     /// Whether any cleanup was dispatched in the previous tick (scan or ballast release).
     /// Used by the prediction scorecard to distinguish interventions from false alarms.
@@ -951,15 +1133,26 @@ pub struct MonitoringDaemon {
     scanner_heartbeat: Arc<ThreadHeartbeat>,
     executor_heartbeat: Arc<ThreadHeartbeat>,
     prediction_scorecard: PredictionScorecard,
-}
-
-fn compute_primary_path(config: &Config) -> PathBuf {
-    config
-        .scanner
-        .root_paths
-        .first()
-        .cloned()
-        .unwrap_or_else(|| PathBuf::from("/"))
+    /// Per-mount readings from the latest pressure check (status dump, metrics).
+    mount_snapshots: Vec<MountPressureSnapshot>,
+    /// Prometheus exporter (`[metrics]`); started in `run()`.
+    metrics_exporter: Option<MetricsExporter>,
+    /// Disk-space leases from `sbh reserve`, counted as committed usage.
+    reservations: ReservationLedger,
+    /// `io.max` write limits on runaway writers (`[policy.write_throttle]`).
+    write_throttler: WriteThrottler,
+    /// Writers frozen as a last resort (`[policy.freeze]`).
+    freeze_ledger: FreezeLedger,
+    /// Time since cleanup last freed space while a volume was Critical.
+    cleanup_stall: CleanupStallTracker,
+    /// Deleted files still held open, from the latest sweep.
+    held_deleted_files: Vec<HeldDeletedFile>,
+    last_deleted_open_sweep: Option<Instant>,
+    /// Last snapshot-thinning pass per volume (`[policy.snapshot_thinning]`).
+    last_snapshot_thinning: HashMap<PathBuf, Instant>,
+    /// Local control socket; bound in `run()`, dropped (and unlinked) on shutdown.
+    #[cfg(unix)]
+    control_server: Option<ControlServer>,
 }
 
 fn bytes_to_pct(value: u64, total: u64) -> f64 {
@@ -1089,6 +1282,8 @@ fn should_fast_track_temp_age(
             | ArtifactCategory::BuildOutput
             | ArtifactCategory::CacheDir
             | ArtifactCategory::GoCache
+            | ArtifactCategory::BazelOutputBase
+            | ArtifactCategory::JvmBuild
             | ArtifactCategory::AgentWorkspace
             | ArtifactCategory::TempDir
     ) {
@@ -1256,6 +1451,327 @@ fn log_truncation_free_pct_for_request(request: &ScanRequest) -> f64 {
         .unwrap_or_else(|| fallback_log_truncation_free_pct(request.pressure_level))
 }
 
+/// Sweep stale units out of Rust targets that were kept whole.
+fn run_target_sweep(
+    targets: &[PathBuf],
+    scanner_config: &ScannerConfig,
+    pressure_level: PressureLevel,
+    logger: &ActivityLoggerHandle,
+) {
+    // Same fail-safe as the deletion executor: without a complete open-file
+    // view nothing inside a live target is safe to remove.
+    let (open_paths, complete) = collect_open_path_ancestors(targets);
+    if !complete {
+        logger.send(ActivityEvent::Error {
+            code: "SBH-3003".to_string(),
+            message: "target_sweep: open file scan incomplete - skipping sweep for safety"
+                .to_string(),
+        });
+        return;
+    }
+    let report = target_sweep::sweep_targets(
+        targets,
+        &scanner_config.target_sweep,
+        &open_paths,
+        SystemTime::now(),
+        scanner_config.dry_run,
+    );
+    let (verb, bytes, units) = if report.dry_run {
+        (
+            "would_free",
+            report.bytes_would_free,
+            report.units_would_remove,
+        )
+    } else {
+        ("freed", report.bytes_freed, report.units_removed)
+    };
+    if units == 0 && report.errors.is_empty() {
+        return;
+    }
+    eprintln!(
+        "[sbh-target-sweep] pressure={pressure_level:?} {verb}={bytes}B units={units} \
+         profiles={} building={} open={} errors={} dur={}ms",
+        report.profiles_swept,
+        report.profiles_building,
+        report.units_open,
+        report.errors.len(),
+        report.duration.as_millis(),
+    );
+    logger.send(ActivityEvent::Info {
+        message: format!(
+            "target_sweep: {verb} {bytes} bytes across {units} stale unit(s) in {} target(s) at pressure={pressure_level:?}",
+            targets.len(),
+        ),
+    });
+    for (path, err) in &report.errors {
+        logger.send(ActivityEvent::Error {
+            code: "SBH-TARGETSWEEP".to_string(),
+            message: format!("target_sweep error on {}: {err}", path.display()),
+        });
+    }
+}
+
+/// Prune unused entries from the Bazel repository and disk caches.
+fn run_bazel_prune(
+    caches: &[PathBuf],
+    scanner_config: &ScannerConfig,
+    sacred_paths: &[crate::platform::types::SacredPath],
+    pressure_level: PressureLevel,
+    logger: &ActivityLoggerHandle,
+) {
+    let caches: Vec<PathBuf> = caches
+        .iter()
+        .filter(|cache| {
+            protection::find_sacred_overlaps(cache, sacred_paths)
+                .is_ok_and(|overlaps| overlaps.is_empty())
+        })
+        .cloned()
+        .collect();
+    if caches.is_empty() {
+        return;
+    }
+    let (open_paths, complete) = collect_open_path_ancestors(&caches);
+    if !complete {
+        logger.send(ActivityEvent::Error {
+            code: "SBH-3003".to_string(),
+            message: "bazel_prune: open file scan incomplete - skipping prune for safety"
+                .to_string(),
+        });
+        return;
+    }
+    let report = bazel::prune_caches(
+        &caches,
+        &scanner_config.bazel,
+        &open_paths,
+        SystemTime::now(),
+        scanner_config.dry_run,
+    );
+    let (verb, bytes, files) = if report.dry_run {
+        (
+            "would_free",
+            report.bytes_would_free,
+            report.files_would_remove,
+        )
+    } else {
+        ("freed", report.bytes_freed, report.files_removed)
+    };
+    if files == 0 && report.errors.is_empty() {
+        return;
+    }
+    eprintln!(
+        "[sbh-bazel-prune] pressure={pressure_level:?} {verb}={bytes}B files={files} \
+         caches={} open={} errors={} dur={}ms",
+        report.caches_pruned,
+        report.files_open,
+        report.errors.len(),
+        report.duration.as_millis(),
+    );
+    logger.send(ActivityEvent::Info {
+        message: format!(
+            "bazel_prune: {verb} {bytes} bytes across {files} unused cache file(s) in {} cache(s) at pressure={pressure_level:?}",
+            report.caches_pruned,
+        ),
+    });
+    for (path, err) in &report.errors {
+        logger.send(ActivityEvent::Error {
+            code: "SBH-BAZELPRUNE".to_string(),
+            message: format!("bazel_prune error on {}: {err}", path.display()),
+        });
+    }
+}
+
+/// Compress cold files below candidates the policy engine chose to keep
+/// on disk in a reversible form instead of deleting.
+///
+/// Returns the candidates whose pass compressed nothing, so the caller can
+/// delete them as it would have without compression.
+fn run_compression(
+    candidates: &[CandidacyScore],
+    config: &CompressInPlaceConfig,
+    scanner_config: &ScannerConfig,
+    pressure_level: PressureLevel,
+    dry_run: bool,
+    logger: &ActivityLoggerHandle,
+) -> Vec<CandidacyScore> {
+    let sacred_paths =
+        protection::sacred_paths_from_protected_patterns(&scanner_config.protected_paths);
+    let candidates: Vec<&CandidacyScore> = candidates
+        .iter()
+        .filter(|candidate| {
+            protection::find_sacred_overlaps(&candidate.path, &sacred_paths)
+                .is_ok_and(|overlaps| overlaps.is_empty())
+        })
+        .collect();
+    let mut uncompressed = Vec::new();
+    if candidates.is_empty() {
+        return uncompressed;
+    }
+    let roots: Vec<PathBuf> = candidates.iter().map(|c| c.path.clone()).collect();
+    let (open_paths, complete) = collect_open_path_ancestors(&roots);
+    if !complete {
+        logger.send(ActivityEvent::Error {
+            code: "SBH-3003".to_string(),
+            message: "compress: open file scan incomplete - skipping compression for safety"
+                .to_string(),
+        });
+        return uncompressed;
+    }
+    for candidate in candidates {
+        let report = compress::compress_in_place(
+            &candidate.path,
+            config,
+            &open_paths,
+            SystemTime::now(),
+            dry_run,
+        );
+        for (path, err) in &report.errors {
+            logger.send(ActivityEvent::Error {
+                code: "SBH-COMPRESS".to_string(),
+                message: format!("compress error on {}: {err}", path.display()),
+            });
+        }
+        if report.dry_run {
+            if report.files_would_compress == 0 {
+                uncompressed.push(candidate.clone());
+            } else {
+                eprintln!(
+                    "[sbh-compress] pressure={pressure_level:?} would_compress={}B files={} \
+                     path={} dur={}ms",
+                    report.bytes_would_compress,
+                    report.files_would_compress,
+                    candidate.path.display(),
+                    report.duration.as_millis(),
+                );
+            }
+            continue;
+        }
+        if report.files_compressed == 0 {
+            uncompressed.push(candidate.clone());
+            continue;
+        }
+        eprintln!(
+            "[sbh-compress] pressure={pressure_level:?} saved={}B files={} skipped={} \
+             path={} errors={} dur={}ms",
+            report.bytes_saved(),
+            report.files_compressed,
+            report.files_skipped,
+            candidate.path.display(),
+            report.errors.len(),
+            report.duration.as_millis(),
+        );
+        logger.send(ActivityEvent::ArtifactCompressed {
+            path: candidate.path.to_string_lossy().to_string(),
+            files: report.files_compressed,
+            bytes_before: report.bytes_before,
+            bytes_after: report.bytes_after,
+            score: candidate.total_score,
+            pressure: format!("{pressure_level:?}"),
+            free_pct: 0.0,
+            duration_ms: u64::try_from(report.duration.as_millis()).unwrap_or(u64::MAX),
+        });
+    }
+    uncompressed
+}
+
+/// Reclaim idle git worktrees and agent clones that hold no unsaved work.
+///
+/// Every workspace idle long enough to be inspected gets a decision record
+/// carrying its git evidence, whether it is removed or kept.
+fn run_git_workspace_reclaim(
+    mut workspaces: Vec<GitWorkspace>,
+    scanner_config: &ScannerConfig,
+    sacred_paths: &[crate::platform::types::SacredPath],
+    policy_engine: &Arc<Mutex<PolicyEngine>>,
+    pressure_level: PressureLevel,
+    logger: &ActivityLoggerHandle,
+) {
+    workspaces.retain(|workspace| {
+        protection::find_sacred_overlaps(&workspace.path, sacred_paths)
+            .is_ok_and(|overlaps| overlaps.is_empty())
+    });
+    // Outer workspaces first, so one nested inside a removed clone is skipped.
+    workspaces.sort_by(|a, b| a.path.cmp(&b.path));
+    workspaces.dedup_by(|a, b| a.path == b.path);
+    if workspaces.is_empty() {
+        return;
+    }
+    let roots: Vec<PathBuf> = workspaces.iter().map(|ws| ws.path.clone()).collect();
+    let (open_paths, complete) = collect_open_path_ancestors(&roots);
+    if !complete {
+        logger.send(ActivityEvent::Error {
+            code: "SBH-3003".to_string(),
+            message: "git_workspaces: open file scan incomplete - skipping reclaim for safety"
+                .to_string(),
+        });
+        return;
+    }
+    let dry_run = scanner_config.dry_run || !policy_engine.lock().mode().allows_deletion();
+    let report = git_workspace::reclaim_workspaces(
+        &workspaces,
+        &scanner_config.git_workspaces,
+        &open_paths,
+        SystemTime::now(),
+        dry_run,
+    );
+    for outcome in &report.outcomes {
+        let summary = outcome.evidence.as_ref().map_or_else(
+            || {
+                format!(
+                    "{} at {}",
+                    outcome.workspace.label(),
+                    outcome.workspace.path.display()
+                )
+            },
+            |evidence| evidence.summary(&outcome.workspace),
+        );
+        let record = policy_engine
+            .lock()
+            .record_git_workspace(GitWorkspaceEvidence {
+                path: outcome.workspace.path.clone(),
+                size_bytes: outcome.size_bytes,
+                idle_secs: outcome.evidence.as_ref().map_or(0, |e| e.idle.as_secs()),
+                pattern_name: outcome.workspace.label(),
+                veto_reason: outcome.veto_reason.clone(),
+                summary,
+            });
+        logger.send(ActivityEvent::DecisionRecorded {
+            record: Box::new(record),
+        });
+    }
+    let removed = report.outcomes.iter().filter(|o| o.removed).count();
+    let (verb, bytes, count) = if report.dry_run {
+        let eligible = report
+            .outcomes
+            .iter()
+            .filter(|o| o.veto_reason.is_none())
+            .count();
+        ("would_free", report.bytes_would_free, eligible)
+    } else {
+        ("freed", report.bytes_freed, removed)
+    };
+    if count == 0 && report.errors.is_empty() {
+        return;
+    }
+    eprintln!(
+        "[sbh-git-workspaces] pressure={pressure_level:?} {verb}={bytes}B workspaces={count} \
+         inspected={} errors={} dur={}ms",
+        report.outcomes.len(),
+        report.errors.len(),
+        report.duration.as_millis(),
+    );
+    logger.send(ActivityEvent::Info {
+        message: format!(
+            "git_workspaces: {verb} {bytes} bytes across {count} idle workspace(s) at pressure={pressure_level:?}",
+        ),
+    });
+    for (path, err) in &report.errors {
+        logger.send(ActivityEvent::Error {
+            code: "SBH-GITWORKSPACE".to_string(),
+            message: format!("git_workspaces error on {}: {err}", path.display()),
+        });
+    }
+}
+
 fn scan_deadline_reached(scan_start: Instant, scan_deadline: Instant, phase: &str) -> bool {
     if Instant::now() < scan_deadline {
         return false;
@@ -1378,11 +1894,12 @@ fn behavior_should_release_ballast(mode: BehaviorMode) -> bool {
 
 fn behavior_mode_summary(mode: BehaviorMode) -> String {
     format!(
-        "scan={:?} cleanup={:?} ballast={:?} notify={:?}",
+        "scan={:?} cleanup={:?} ballast={:?} notify={:?} throttle={:?}",
         mode.scan_aggressiveness,
         mode.cleanup_action,
         mode.ballast_action,
-        mode.notification_priority
+        mode.notification_priority,
+        mode.write_throttle
     )
 }
 
@@ -1420,6 +1937,26 @@ struct StatusDumpCounters {
     dropped_log_events: u64,
 }
 
+/// Per-mount reading from the latest `check_pressure` pass: the mount's own
+/// controller response plus the inputs behind it.
+#[derive(Debug, Clone)]
+struct MountPressureSnapshot {
+    mount: PathBuf,
+    response: PressureResponse,
+    free_bytes: u64,
+    total_bytes: u64,
+    free_pct: f64,
+    /// Outstanding bytes of reservations on this mount (already subtracted
+    /// from the free space the controller saw).
+    reserved_bytes: u64,
+    rate_bytes_per_sec: f64,
+    rate_confidence: f64,
+    seconds_to_exhaustion: f64,
+    thresholds: PressureThresholds,
+    /// `None` when inode tracking is off or the filesystem has no inode table.
+    inodes: Option<InodeSnapshot>,
+}
+
 struct StatusDumpPayloadInput<'a> {
     timestamp: String,
     version: &'static str,
@@ -1431,6 +1968,10 @@ struct StatusDumpPayloadInput<'a> {
     mount_available_bytes: Option<u64>,
     ballast_available: usize,
     ballast_total: usize,
+    ballast_pools: &'a [PoolInventory],
+    mounts: &'a [MountPressureSnapshot],
+    reservations: Vec<Value>,
+    deleted_open_files: Vec<Value>,
     memory_info: Option<&'a MemoryInfo>,
     policy_mode: String,
     behavior_mode: BehaviorMode,
@@ -1500,6 +2041,128 @@ fn memory_status_json(memory: &MemoryInfo) -> Value {
     })
 }
 
+/// Paths a control-socket scan may cover: the configured roots when none are
+/// given, otherwise absolute paths inside one of them.
+///
+/// The socket can be opened to a group, so a request must not steer the
+/// daemon into scanning and cleaning anything the config does not already
+/// cover. `..` components are refused rather than resolved.
+fn control_scan_paths(
+    requested: &[PathBuf],
+    roots: &[PathBuf],
+) -> std::result::Result<Vec<PathBuf>, String> {
+    if requested.is_empty() {
+        return Ok(roots.to_vec());
+    }
+    for path in requested {
+        if !path.is_absolute() {
+            return Err(format!("scan paths must be absolute: {}", path.display()));
+        }
+        if path
+            .components()
+            .any(|component| component == std::path::Component::ParentDir)
+        {
+            return Err(format!(
+                "scan paths must not contain '..': {}",
+                path.display()
+            ));
+        }
+        if !roots.iter().any(|root| path.starts_with(root)) {
+            return Err(format!(
+                "scan path {} is outside scanner.root_paths",
+                path.display()
+            ));
+        }
+    }
+    Ok(requested.to_vec())
+}
+
+/// Responses for every non-green mount, most severe first. Falls back to the
+/// overall response if no snapshot is pressured (e.g. before the first check).
+fn pressured_mount_responses(
+    snapshots: &[MountPressureSnapshot],
+    overall: &PressureResponse,
+) -> Vec<PressureResponse> {
+    let mut pressured: Vec<PressureResponse> = snapshots
+        .iter()
+        .map(|snapshot| snapshot.response.clone())
+        .filter(|response| response.level > PressureLevel::Green)
+        .collect();
+    if pressured.is_empty() {
+        pressured.push(overall.clone());
+    }
+    pressured.sort_by(|a, b| {
+        b.level
+            .cmp(&a.level)
+            .then_with(|| b.urgency.total_cmp(&a.urgency))
+    });
+    pressured
+}
+
+/// Union of the per-volume scan roots, in order, without duplicates.
+fn merge_scan_paths(volume_paths: Vec<Vec<PathBuf>>) -> Vec<PathBuf> {
+    let mut merged: Vec<PathBuf> = Vec::new();
+    for path in volume_paths.into_iter().flatten() {
+        if !merged.contains(&path) {
+            merged.push(path);
+        }
+    }
+    merged
+}
+
+fn mount_snapshot_json(snapshot: &MountPressureSnapshot) -> Value {
+    // Exhaustion is only meaningful while the mount is filling.
+    let predicted_exhaustion_seconds = (snapshot.rate_bytes_per_sec > 0.0)
+        .then_some(snapshot.seconds_to_exhaustion)
+        .and_then(finite_f64);
+    json!({
+        "mount": snapshot.mount.to_string_lossy(),
+        "level": pressure_level_json(snapshot.response.level),
+        "urgency": finite_f64(snapshot.response.urgency),
+        "max_delete_batch": snapshot.response.max_delete_batch,
+        "release_ballast_files": snapshot.response.release_ballast_files,
+        "free_bytes": snapshot.free_bytes,
+        "total_bytes": snapshot.total_bytes,
+        "free_pct": finite_f64(snapshot.free_pct),
+        "reserved_bytes": snapshot.reserved_bytes,
+        "ewma_rate_bytes_per_sec": finite_f64(snapshot.rate_bytes_per_sec),
+        "ewma_confidence": finite_f64(snapshot.rate_confidence),
+        "predicted_exhaustion_seconds": predicted_exhaustion_seconds,
+        "thresholds": {
+            "green_min_free_pct": snapshot.thresholds.green_min_free_pct,
+            "yellow_min_free_pct": snapshot.thresholds.yellow_min_free_pct,
+            "orange_min_free_pct": snapshot.thresholds.orange_min_free_pct,
+            "red_min_free_pct": snapshot.thresholds.red_min_free_pct,
+        },
+        "pressure_dimension": snapshot.response.dimension.as_str(),
+        "inodes": snapshot.inodes.as_ref().map(inode_snapshot_json),
+    })
+}
+
+fn inode_snapshot_json(inodes: &InodeSnapshot) -> Value {
+    let predicted_exhaustion_seconds = (inodes.rate_per_sec > 0.0)
+        .then_some(inodes.seconds_to_exhaustion)
+        .and_then(finite_f64);
+    json!({
+        "level": pressure_level_json(inodes.level),
+        "free_inodes": inodes.free_inodes,
+        "total_inodes": inodes.total_inodes,
+        "free_pct": finite_f64(inodes.free_pct),
+        "ewma_rate_inodes_per_sec": finite_f64(inodes.rate_per_sec),
+        "predicted_exhaustion_seconds": predicted_exhaustion_seconds,
+    })
+}
+
+fn ballast_pool_json(pool: &PoolInventory) -> Value {
+    json!({
+        "mount": pool.mount_point.to_string_lossy(),
+        "files_available": pool.files_available,
+        "files_total": pool.files_total,
+        "releasable_bytes": pool.releasable_bytes,
+        "skipped": pool.skipped,
+    })
+}
+
 fn guard_diagnostics_json(guard: &GuardDiagnostics) -> Value {
     json!({
         "status": guard.status.to_string(),
@@ -1535,10 +2198,19 @@ fn build_status_dump_payload(input: &StatusDumpPayloadInput<'_>) -> Value {
             "max_delete_batch": response.max_delete_batch,
             "fallback_active": response.fallback_active,
         },
+        "mounts": input.mounts.iter().map(mount_snapshot_json).collect::<Vec<_>>(),
+        "reservations": &input.reservations,
+        "deleted_open_files": &input.deleted_open_files,
         "ballast": {
             "available": input.ballast_available,
             "total": input.ballast_total,
             "released": input.ballast_total.saturating_sub(input.ballast_available),
+            "releasable_bytes": input
+                .ballast_pools
+                .iter()
+                .map(|pool| pool.releasable_bytes)
+                .sum::<u64>(),
+            "pools": input.ballast_pools.iter().map(ballast_pool_json).collect::<Vec<_>>(),
         },
             "memory": input.memory_info.map(memory_status_json),
             "policy": {
@@ -1685,12 +2357,17 @@ impl MonitoringDaemon {
         let behavior_state =
             PressureBehaviorState::new(MemoryPressureLevel::Unknown, PressureLevel::Green);
 
-        let cached_primary_path = compute_primary_path(&config);
-        let prediction_config = config.pressure.prediction.clone();
+        // 15. Disk-space reservations survive restarts.
+        let reservations = ReservationLedger::load(config.paths.reservations_file());
+
+        // 16. Write throttles left behind by a crashed daemon are lifted in `run()`.
+        let write_throttler = WriteThrottler::load(config.paths.write_throttles_file());
+
+        // 17. Likewise, writers left frozen are thawed in `run()`.
+        let freeze_ledger = FreezeLedger::load(config.paths.frozen_writers_file());
 
         Ok(Self {
             config,
-            cached_primary_path,
             platform,
             logger_handle,
             logger_join: Some(logger_join),
@@ -1717,7 +2394,6 @@ impl MonitoringDaemon {
             last_predictive_warning: None,
             last_predictive_level: None,
             last_ewma_confidence: 0.0,
-            predictive_policy: PredictiveActionPolicy::from_config(prediction_config),
             last_predictive_action: PredictiveAction::Clear,
             last_tick_cleanup_ran: false,
             last_swap_thrash_warning: None,
@@ -1743,6 +2419,17 @@ impl MonitoringDaemon {
             executor_heartbeat,
             shared_guard_diagnostics,
             prediction_scorecard: PredictionScorecard::new(200),
+            mount_snapshots: Vec::new(),
+            metrics_exporter: None,
+            reservations,
+            write_throttler,
+            freeze_ledger,
+            cleanup_stall: CleanupStallTracker::default(),
+            held_deleted_files: Vec::new(),
+            last_deleted_open_sweep: None,
+            last_snapshot_thinning: HashMap::new(),
+            #[cfg(unix)]
+            control_server: None,
         })
     }
 
@@ -1926,7 +2613,7 @@ impl MonitoringDaemon {
             if remaining.is_zero() {
                 break;
             }
-            if self.signal_handler.has_pending_status_dump() {
+            if self.signal_handler.has_pending_status_dump() || self.control_request_pending() {
                 break;
             }
 
@@ -1948,6 +2635,10 @@ impl MonitoringDaemon {
     }
 
     fn emit_status_dump(&self, response: &PressureResponse) {
+        eprintln!("{}", self.status_dump_payload(response));
+    }
+
+    fn status_dump_payload(&self, response: &PressureResponse) -> Value {
         let mount_stats = self.fs_collector.collect(&response.causing_mount).ok();
         let ballast_inventory = self.ballast_coordinator.inventory();
         let ballast_available = ballast_inventory
@@ -1982,6 +2673,10 @@ impl MonitoringDaemon {
             mount_available_bytes: mount_stats.as_ref().map(|stats| stats.available_bytes),
             ballast_available,
             ballast_total,
+            ballast_pools: &ballast_inventory,
+            mounts: &self.mount_snapshots,
+            reservations: self.reservations_json(),
+            deleted_open_files: self.deleted_open_files_json(),
             memory_info: memory_info.as_ref(),
             policy_mode: self.policy_engine.lock().mode().to_string(),
             behavior_mode: self.behavior_state.mode,
@@ -2003,9 +2698,37 @@ impl MonitoringDaemon {
             },
             thread_status: &health.thread_status,
         };
-        let payload = build_status_dump_payload(&payload_input);
+        build_status_dump_payload(&payload_input)
+    }
+
+    fn start_metrics_exporter(&mut self) {
+        // Drop any previous exporter first so a reload can rebind its address.
+        self.metrics_exporter = None;
+        match MetricsExporter::start(&self.config.metrics) {
+            Ok(Some(exporter)) => {
+                eprintln!(
+                    "[SBH-DAEMON] metrics exporter enabled ({:?})",
+                    self.config.metrics.mode
+                );
+                self.metrics_exporter = Some(exporter);
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("[SBH-DAEMON] metrics exporter unavailable: {e}"),
+        }
+    }
 
-        eprintln!("{payload}");
+    fn maybe_publish_metrics(&mut self, response: &PressureResponse) {
+        if !self
+            .metrics_exporter
+            .as_ref()
+            .is_some_and(|exporter| exporter.is_due(Instant::now()))
+        {
+            return;
+        }
+        let payload = self.status_dump_payload(response);
+        if let Some(exporter) = self.metrics_exporter.as_mut() {
+            exporter.publish(&payload);
+        }
     }
 
     fn maybe_write_self_monitor_state(&mut self, response: &PressureResponse) -> SelfMonitorTick {
@@ -2111,6 +2834,27 @@ impl MonitoringDaemon {
             index_feedback_tx.clone(),
         )?);
 
+        // Control socket for typed CLI requests. Best-effort: signals keep
+        // working when the socket cannot be bound.
+        #[cfg(unix)]
+        {
+            let socket_path = self.config.paths.control_socket();
+            match ControlServer::bind(&socket_path, &self.config.control) {
+                Ok(server) => {
+                    eprintln!(
+                        "[SBH-DAEMON] control socket listening on {}",
+                        socket_path.display()
+                    );
+                    self.control_server = Some(server);
+                }
+                Err(e) => eprintln!("[SBH-DAEMON] control socket unavailable: {e}"),
+            }
+        }
+
+        self.start_metrics_exporter();
+        self.lift_all_write_throttles("left over from a previous daemon");
+        self.thaw_all_frozen_writers("left over from a previous daemon");
+
         let mut last_health_check = Instant::now();
         let mut shutdown_result = Ok(());
 
@@ -2132,6 +2876,9 @@ impl MonitoringDaemon {
             // 2b. Periodically re-check macOS FDA grants and log success when granted.
             self.maybe_log_full_disk_access_status(false);
 
+            // 2c. Expire stale reservations and grant pending ones that now fit.
+            self.maintain_reservations();
+
             // 3. Collect filesystem stats and run pressure analysis.
             let response = match self.check_pressure() {
                 Ok(r) => r,
@@ -2169,7 +2916,7 @@ impl MonitoringDaemon {
                     true
                 };
                 if should_notify {
-                    self.log_pressure_change(&response);
+                    self.notify_pressure_change(&response);
                     self.last_pressure_notify_time = Some(Instant::now());
                     if response.level > self.last_notified_pressure_level {
                         self.last_notified_pressure_level = response.level;
@@ -2193,6 +2940,11 @@ impl MonitoringDaemon {
                 self.emit_status_dump(&response);
             }
 
+            // Control socket requests are answered against the same fresh
+            // pressure response, before this tick's cleanup work.
+            #[cfg(unix)]
+            self.handle_control_requests(&scan_tx, &response);
+
             // Check daemon memory limits before scheduling cleanup work. A hard
             // RSS breach should exit promptly for the service manager restart
             // path instead of spending another tick on scans or deletions.
@@ -2201,10 +2953,23 @@ impl MonitoringDaemon {
                 shutdown_result = Err(self.rss_hard_limit_error(self_monitor_tick));
                 break;
             }
+            self.maybe_publish_metrics(&response);
 
             // 5. Handle pressure response.
             self.handle_pressure(&response, &scan_tx, &scan_rx);
 
+            // 5b. Throttle (or release) runaway writers on red volumes, and
+            //     freeze the top writer as a last resort on critical ones.
+            let write_rates = self.sample_cgroup_write_rates();
+            self.maintain_write_throttles(&write_rates);
+            self.maintain_frozen_writers(&write_rates);
+
+            // 5c. Reclaim space held by deleted-but-open files.
+            self.maintain_deleted_open_files();
+
+            // 5d. Thin tool-managed btrfs/ZFS snapshots on red volumes.
+            self.maintain_snapshot_thinning();
+
             // 6. Check special locations independently.
             self.check_special_locations(&scan_tx, &scan_rx);
 
@@ -2250,7 +3015,9 @@ impl MonitoringDaemon {
                         deleted,
                         bytes_freed,
                         failed,
+                        stalled,
                     } => {
+                        self.cleanup_stall.observe_batch(stalled, Instant::now());
                         self.summary_deleted += deleted;
                         self.summary_failed += failed;
                         self.summary_bytes_freed += bytes_freed;
@@ -2276,7 +3043,9 @@ impl MonitoringDaemon {
 
             // 9. Forced scan signal (SIGUSR1).
             if self.signal_handler.should_scan() {
-                self.trigger_forced_scan(&scan_tx, &response);
+                eprintln!("[SBH-DAEMON] forced scan triggered (SIGUSR1)");
+                let paths = self.config.scanner.root_paths.clone();
+                Self::trigger_forced_scan(&scan_tx, &response, paths);
             }
 
             // 10. Thread health check.
@@ -2447,11 +3216,6 @@ impl MonitoringDaemon {
 
     // ──────────────────── helpers ────────────────────
 
-    /// Return the first configured root path, or `/` as fallback.
-    fn primary_path(&self) -> &Path {
-        &self.cached_primary_path
-    }
-
     fn rss_hard_limit_error(&self, tick: SelfMonitorTick) -> SbhError {
         let details = format!(
             "daemon RSS hard limit exceeded: rss={} bytes hard_limit={} bytes; exiting nonzero so the service manager can restart after its throttle interval",
@@ -2478,6 +3242,11 @@ impl MonitoringDaemon {
         if !paths.iter().any(|p| p == Path::new("/")) {
             paths.push(PathBuf::from("/"));
         }
+        // Reserved volumes are watched even outside the scan roots, so their
+        // committed bytes still drive that volume's pressure response.
+        for lease in self.reservations.leases() {
+            push_unique_path(&mut paths, lease.mount.clone());
+        }
 
         // Group paths by mount point to avoid redundant updates.
         let mut stats_by_mount: HashMap<PathBuf, crate::platform::pal::FsStats> = HashMap::new();
@@ -2503,18 +3272,36 @@ impl MonitoringDaemon {
         let mut worst_guard_diag: Option<GuardDiagnostics> = None;
         // Reset per-tick predictive action so we track the worst across mounts.
         self.last_predictive_action = PredictiveAction::Clear;
+        let mut mount_snapshots = Vec::with_capacity(stats_by_mount.len());
+        let mut transitions = Vec::new();
 
         // Update monitors for each active mount.
         for (mount_path, stats) in stats_by_mount {
+            let mount_key = mount_path.to_string_lossy();
             let monitor = self
                 .mount_monitors
                 .entry(mount_path.clone())
-                .or_insert_with(|| MountMonitor::new(&self.config));
-
-            // Update EWMA rate estimator.
-            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-            let red_threshold_bytes =
-                (stats.total_bytes as f64 * self.config.pressure.red_min_free_pct / 100.0) as u64;
+                .or_insert_with(|| MountMonitor::new(&self.config, &mount_key));
+            let thresholds = self
+                .config
+                .pressure
+                .thresholds_for(&mount_key, stats.total_bytes);
+            monitor.apply_thresholds(thresholds);
+
+            // Reserved-but-unwritten bytes count as already used: the
+            // controller sees the free space left after every lease is
+            // honoured, and the forecast reaches red that much sooner.
+            let reserved_bytes = self
+                .reservations
+                .outstanding_for_mount(&mount_path, stats.available_bytes);
+            let effective_free_bytes = stats.available_bytes.saturating_sub(reserved_bytes);
+            let effective_free_pct = bytes_to_pct(effective_free_bytes, stats.total_bytes);
+
+            // Update EWMA rate estimator. It tracks real free space so the
+            // rate stays a measurement; the threshold absorbs the leases.
+            let red_threshold_bytes = thresholds
+                .red_min_free_bytes(stats.total_bytes)
+                .saturating_add(reserved_bytes);
 
             let rate_estimate =
                 monitor
@@ -2538,19 +3325,58 @@ impl MonitoringDaemon {
 
             // Run PID controller.
             let reading = PressureReading {
-                free_bytes: stats.available_bytes,
+                free_bytes: effective_free_bytes,
                 total_bytes: stats.total_bytes,
                 mount: stats.mount_point.clone(),
             };
-            let response = monitor
+            let byte_response = monitor
                 .pressure_controller
                 .update(reading, predicted_seconds, now);
+            // The volume is as pressured as its scarcer resource.
+            let inode_reading = monitor
+                .inodes
+                .observe(&self.config.pressure.inodes, &stats, now);
+            let response = match &inode_reading {
+                Some(inodes) if inodes.response.is_more_severe_than(&byte_response) => {
+                    inodes.response.clone()
+                }
+                _ => byte_response,
+            };
+            if response.level != monitor.level {
+                #[allow(clippy::cast_possible_wrap)]
+                transitions.push(ActivityEvent::PressureChanged {
+                    from: format!("{:?}", monitor.level),
+                    to: format!("{:?}", response.level),
+                    free_pct: effective_free_pct,
+                    rate_bps: finite_f64(rate_estimate.bytes_per_second),
+                    mount_point: mount_key.to_string(),
+                    total_bytes: stats.total_bytes as i64,
+                    free_bytes: effective_free_bytes as i64,
+                    ewma_rate: finite_f64(rate_estimate.bytes_per_second),
+                    pid_output: Some(response.urgency),
+                });
+                monitor.level = response.level;
+            }
+            mount_snapshots.push(MountPressureSnapshot {
+                mount: mount_path.clone(),
+                response: response.clone(),
+                free_bytes: stats.available_bytes,
+                total_bytes: stats.total_bytes,
+                free_pct: stats.free_pct(),
+                reserved_bytes,
+                rate_bytes_per_sec: rate_estimate.bytes_per_second,
+                rate_confidence: rate_estimate.confidence,
+                seconds_to_exhaustion: rate_estimate.seconds_to_exhaustion,
+                thresholds,
+                inodes: inode_reading.map(|inodes| inodes.snapshot),
+            });
 
             // Evaluate predictive policy with full confidence/trend gating.
-            let free_pct = stats.free_pct();
-            let mut pred_action =
-                self.predictive_policy
-                    .evaluate(&rate_estimate, free_pct, mount_path.clone());
+            let mut pred_action = monitor.predictive_policy.evaluate(
+                &rate_estimate,
+                effective_free_pct,
+                mount_path.clone(),
+            );
 
             // Force low-confidence predictions to Clear so they don't trigger
             // scans or other downstream actions (breaks scan saturation feedback loop).
@@ -2559,7 +3385,7 @@ impl MonitoringDaemon {
             // based on realized accuracy.
             let effective_min_conf = self
                 .prediction_scorecard
-                .dynamic_min_confidence(self.config.pressure.prediction.min_confidence);
+                .dynamic_min_confidence(monitor.prediction.min_confidence);
             if !matches!(pred_action, PredictiveAction::Clear)
                 && rate_estimate.confidence < effective_min_conf
             {
@@ -2630,6 +3456,13 @@ impl MonitoringDaemon {
             }
         }
         *self.shared_guard_diagnostics.write() = worst_guard_diag;
+        mount_snapshots.sort_by(|a, b| a.mount.cmp(&b.mount));
+        self.mount_snapshots = mount_snapshots;
+        // Each mount's transitions land in pressure_history with its own
+        // controller output; the overall level only drives notifications.
+        for event in transitions {
+            self.logger_handle.send(event);
+        }
 
         // Clean up monitors for unmounted/disappeared volumes?
         // For now we keep them; volume churn is rare in typical operation.
@@ -2640,33 +3473,22 @@ impl MonitoringDaemon {
         })
     }
 
-    fn log_pressure_change(&mut self, response: &crate::monitor::pid::PressureResponse) {
-        // Use the causing mount so the log entry reflects the mount that
+    fn notify_pressure_change(&mut self, response: &crate::monitor::pid::PressureResponse) {
+        // Use the causing mount so the notification reflects the mount that
         // actually drove the pressure level change, not the primary path.
-        let (free_pct, mount, total, free) =
-            if let Ok(stats) = self.fs_collector.collect(&response.causing_mount) {
-                #[allow(clippy::cast_possible_wrap)]
-                (
-                    stats.free_pct(),
-                    stats.mount_point.to_string_lossy().to_string(),
-                    stats.total_bytes as i64,
-                    stats.available_bytes as i64,
-                )
-            } else {
-                (0.0, "/".to_string(), 0, 0)
-            };
-
-        self.logger_handle.send(ActivityEvent::PressureChanged {
-            from: format!("{:?}", self.last_pressure_level),
-            to: format!("{:?}", response.level),
-            free_pct,
-            rate_bps: None,
-            mount_point: mount.clone(),
-            total_bytes: total,
-            free_bytes: free,
-            ewma_rate: None,
-            pid_output: Some(response.urgency),
-        });
+        // The activity log already has a per-mount record from `check_pressure`.
+        let (free_pct, mount) = self
+            .fs_collector
+            .collect(&response.causing_mount)
+            .map_or_else(
+                |_| (0.0, "/".to_string()),
+                |stats| {
+                    (
+                        stats.free_pct(),
+                        stats.mount_point.to_string_lossy().to_string(),
+                    )
+                },
+            );
 
         self.notification_manager
             .notify(&NotificationEvent::PressureChanged {
@@ -2696,10 +3518,81 @@ impl MonitoringDaemon {
         let scan_allowed = behavior_allows_scan(behavior);
         let release_ballast = behavior_should_release_ballast(behavior);
 
-        // Determine scan targets: routine maintenance (Green) scans everything;
-        // elevated pressure targets only the causing volume to maximize ROI.
-        let elevated_pressure = response.level != PressureLevel::Green;
-        let scan_paths = if elevated_pressure {
+        // Replenishment is gated per pool on its own volume's level, so a calm
+        // volume can refill while another one is under pressure.
+        self.maybe_replenish_ballast(response.level);
+
+        if response.level == PressureLevel::Green {
+            // Predictive Safety Net: use the PredictiveActionPolicy to decide
+            // whether to start scanning even when static pressure is Green.
+            // Extract values from the match before calling &mut self methods.
+            let predictive_min_score = match &self.last_predictive_action {
+                PredictiveAction::PreemptiveCleanup {
+                    recommended_min_score,
+                    ..
+                } => Some(*recommended_min_score),
+                _ => None,
+            };
+            let predictive_ballast_mount = match &self.last_predictive_action {
+                PredictiveAction::ImminentDanger { mount, .. } => Some(mount.clone()),
+                _ => None,
+            };
+            let needs_scan = !matches!(self.last_predictive_action, PredictiveAction::Clear);
+
+            if let Some(min_score) = predictive_min_score {
+                self.shared_executor_config.set_min_score(min_score);
+            }
+            if let Some(ref mount) = predictive_ballast_mount {
+                let mount_response = self
+                    .mount_response(mount)
+                    .unwrap_or_else(|| response.clone());
+                let _ = self.release_ballast(mount, &mount_response);
+                self.last_tick_cleanup_ran = true;
+            }
+            // Force periodic scans when stuck in FallbackSafe at green
+            // pressure so that guard windows can update and recovery can
+            // trigger. Without this, FallbackSafe at green is permanent.
+            let in_fallback = self.policy_engine.lock().mode() == ActiveMode::FallbackSafe;
+            if scan_allowed && (needs_scan || in_fallback) {
+                // Routine maintenance scans every root.
+                let paths = self.config.scanner.root_paths.clone();
+                self.send_scan_request(scan_tx, scan_rx, response, paths);
+                if needs_scan {
+                    self.last_tick_cleanup_ran = true;
+                }
+            }
+            return;
+        }
+
+        // Every pressured volume releases ballast from its own pool, driven by
+        // its own controller's response. Scanning is one request covering all
+        // pressured volumes' roots, sent with the worst volume's urgency and
+        // delete batch: separate per-volume requests would queue the most
+        // urgent scan behind a milder volume's full scan.
+        let pressured = pressured_mount_responses(&self.mount_snapshots, response);
+        let mut worst = None;
+        let mut volume_paths = Vec::with_capacity(pressured.len());
+        for mount_response in &pressured {
+            if let Some(paths) = self.handle_volume_pressure(mount_response, release_ballast) {
+                worst.get_or_insert(mount_response);
+                volume_paths.push(paths);
+            }
+        }
+        if scan_allowed && let Some(worst) = worst {
+            self.send_scan_request(scan_tx, scan_rx, worst, merge_scan_paths(volume_paths));
+            self.last_tick_cleanup_ran = true;
+        }
+    }
+
+    /// Ballast release for one pressured volume. Returns the roots a scan
+    /// should cover for it, or `None` when no root can free space there.
+    fn handle_volume_pressure(
+        &mut self,
+        response: &PressureResponse,
+        release_ballast: bool,
+    ) -> Option<Vec<PathBuf>> {
+        // Elevated pressure targets only the volume's own roots to maximize ROI.
+        let scan_paths: Vec<PathBuf> = {
             let collector = &self.fs_collector;
             let target = &response.causing_mount;
             self.config
@@ -2709,8 +3602,6 @@ impl MonitoringDaemon {
                 .filter(|p| collector.collect(p).is_ok_and(|s| s.mount_point == *target))
                 .cloned()
                 .collect()
-        } else {
-            self.config.scanner.root_paths.clone()
         };
 
         // ── B5: device-affinity gate ──────────────────────────────────────
@@ -2722,7 +3613,7 @@ impl MonitoringDaemon {
         // aggressive scan that does nothing (the trj `/`-pressured /tmp+/data-tmp
         // hot-loop). In that case, log once and back off instead of spinning.
         if should_skip_for_device_affinity(
-            elevated_pressure,
+            true,
             scan_paths.is_empty(),
             self.config.scanner.cross_devices,
         ) {
@@ -2742,11 +3633,10 @@ impl MonitoringDaemon {
                 self.logger_handle
                     .send(ActivityEvent::Info { message: msg });
             }
-            // Skip ballast handling + scan dispatch for this tick. Ballast on a
-            // device with no root_path is still released by Green-tick logic and
-            // the dedicated release paths; here we only suppress the futile
-            // aggressive scan loop.
-            return;
+            // Skip ballast handling + scan dispatch for this volume. Ballast on a
+            // device with no root_path is still released by the dedicated
+            // release paths; here we only suppress the futile aggressive scan loop.
+            return None;
         }
 
         // Fallback to all paths if filtering somehow yielded nothing (e.g. config
@@ -2757,133 +3647,72 @@ impl MonitoringDaemon {
             scan_paths
         };
 
-        match response.level {
-            PressureLevel::Green => {
-                // Maybe replenish ballast.
-                // We must find a pool that needs replenishment.
-                // Iterating all pools and trying to replenish one is safe because
-                // ReleaseController enforces global rate limits.
-                let collector = &self.fs_collector;
-                let inventory = self.ballast_coordinator.inventory();
-
-                for pool_info in inventory {
-                    let mount_path = pool_info.mount_point.clone();
-                    if self.release_controller.is_ready_for_replenish(
-                        &mount_path,
-                        response.level,
-                        pool_info.files_available,
-                        pool_info.files_total,
-                    ) {
-                        let free_check =
-                            || collector.collect(&mount_path).map_or(0.0, |s| s.free_pct());
-
-                        // Try to replenish this pool.
-                        if let Ok(Some(report)) = self
-                            .ballast_coordinator
-                            .replenish_for_mount(&mount_path, Some(&free_check))
-                            && report.files_created > 0
-                        {
-                            self.release_controller
-                                .on_replenished(&mount_path, report.files_created);
-                            self.notification_manager.notify(
-                                &NotificationEvent::BallastReplenished {
-                                    mount: mount_path.to_string_lossy().to_string(),
-                                    files_replenished: report.files_created,
-                                },
-                            );
-                            // One file replenished globally per tick is sufficient.
-                            break;
-                        }
-                    }
-                }
+        // Yellow: light scanning (frequency handled by the PID interval).
+        // Orange: scanning + gentle cleanup + early ballast release.
+        // Red: ballast release + aggressive scan + delete.
+        // Critical: release all ballast + delete everything safe.
+        if release_ballast {
+            let _ = self.release_ballast(&response.causing_mount, response);
+        }
 
-                // Predictive Safety Net: use the PredictiveActionPolicy to decide
-                // whether to start scanning even when static pressure is Green.
-                // Extract values from the match before calling &mut self methods.
-                let predictive_min_score = match &self.last_predictive_action {
-                    PredictiveAction::PreemptiveCleanup {
-                        recommended_min_score,
-                        ..
-                    } => Some(*recommended_min_score),
-                    _ => None,
-                };
-                let predictive_ballast_mount = match &self.last_predictive_action {
-                    PredictiveAction::ImminentDanger { mount, .. } => Some(mount.clone()),
-                    _ => None,
-                };
-                let needs_scan = !matches!(self.last_predictive_action, PredictiveAction::Clear);
+        if response.level == PressureLevel::Critical {
+            self.logger_handle.send(ActivityEvent::Emergency {
+                details: format!(
+                    "critical pressure on {}: urgency={:.2}, releasing all ballast",
+                    response.causing_mount.display(),
+                    response.urgency
+                ),
+                free_pct: response.free_pct,
+            });
+        }
+        Some(paths_to_scan)
+    }
 
-                if let Some(min_score) = predictive_min_score {
-                    self.shared_executor_config.set_min_score(min_score);
-                }
-                if let Some(ref mount) = predictive_ballast_mount {
-                    let _ = self.release_ballast(mount, response);
-                    self.last_tick_cleanup_ran = true;
-                }
-                // Force periodic scans when stuck in FallbackSafe at green
-                // pressure so that guard windows can update and recovery can
-                // trigger. Without this, FallbackSafe at green is permanent.
-                let in_fallback = self.policy_engine.lock().mode() == ActiveMode::FallbackSafe;
-                if scan_allowed && (needs_scan || in_fallback) {
-                    self.send_scan_request(scan_tx, scan_rx, response, paths_to_scan);
-                    if needs_scan {
-                        self.last_tick_cleanup_ran = true;
-                    }
-                }
-            }
-            PressureLevel::Yellow => {
-                // Increase scan frequency (handled by PID interval).
-                // Light scanning.
-                if release_ballast {
-                    let _ = self.release_ballast(&response.causing_mount, response);
-                }
-                if scan_allowed {
-                    self.send_scan_request(scan_tx, scan_rx, response, paths_to_scan);
-                    self.last_tick_cleanup_ran = true;
-                }
-            }
-            PressureLevel::Orange => {
-                // Start scanning + gentle cleanup + early ballast release.
-                if release_ballast {
-                    let _ = self.release_ballast(&response.causing_mount, response);
-                }
-                if scan_allowed {
-                    self.send_scan_request(scan_tx, scan_rx, response, paths_to_scan);
-                    self.last_tick_cleanup_ran = true;
-                }
-            }
-            PressureLevel::Red => {
-                // Release ballast + aggressive scan + delete.
-                if release_ballast {
-                    let _ = self.release_ballast(&response.causing_mount, response);
-                }
-                if scan_allowed {
-                    self.send_scan_request(scan_tx, scan_rx, response, paths_to_scan);
-                    self.last_tick_cleanup_ran = true;
-                }
-            }
-            PressureLevel::Critical => {
-                // Emergency: release all ballast + delete everything safe.
-                if release_ballast {
-                    let _ = self.release_ballast(&response.causing_mount, response);
-                }
-                if scan_allowed {
-                    self.send_scan_request(scan_tx, scan_rx, response, paths_to_scan);
-                    self.last_tick_cleanup_ran = true;
-                }
+    /// Latest controller response for the monitored mount holding `mount`.
+    fn mount_response(&self, mount: &Path) -> Option<PressureResponse> {
+        self.mount_snapshots
+            .iter()
+            .find(|snapshot| snapshot.mount == mount)
+            .map(|snapshot| snapshot.response.clone())
+    }
+
+    /// Replenish at most one ballast pool per tick, gating each pool on its own
+    /// volume's level (`fallback_level` for pools on unmonitored mounts).
+    fn maybe_replenish_ballast(&mut self, fallback_level: PressureLevel) {
+        // Iterating all pools and trying to replenish one is safe because
+        // ReleaseController enforces global rate limits.
+        let inventory = self.ballast_coordinator.inventory();
+
+        for pool_info in inventory {
+            let mount_path = pool_info.mount_point.clone();
+            let level = self
+                .mount_response(&mount_path)
+                .map_or(fallback_level, |mount_response| mount_response.level);
+            if self.release_controller.is_ready_for_replenish(
+                &mount_path,
+                level,
+                pool_info.files_available,
+                pool_info.files_total,
+            ) {
+                let collector = &self.fs_collector;
+                let free_check = || collector.collect(&mount_path).map_or(0.0, |s| s.free_pct());
 
-                let primary = self.primary_path();
-                let actual_free_pct = self
-                    .fs_collector
-                    .collect(primary)
-                    .map_or(0.0, |s| s.free_pct());
-                self.logger_handle.send(ActivityEvent::Emergency {
-                    details: format!(
-                        "critical pressure: urgency={:.2}, releasing all ballast",
-                        response.urgency
-                    ),
-                    free_pct: actual_free_pct,
-                });
+                // Try to replenish this pool.
+                if let Ok(Some(report)) = self
+                    .ballast_coordinator
+                    .replenish_for_mount(&mount_path, Some(&free_check))
+                    && report.files_created > 0
+                {
+                    self.release_controller
+                        .on_replenished(&mount_path, report.files_created);
+                    self.notification_manager
+                        .notify(&NotificationEvent::BallastReplenished {
+                            mount: mount_path.to_string_lossy().to_string(),
+                            files_replenished: report.files_created,
+                        });
+                    // One file replenished globally per tick is sufficient.
+                    break;
+                }
             }
         }
     }
@@ -2937,71 +3766,1198 @@ impl MonitoringDaemon {
             return;
         }
 
-        let request = ScanRequest {
-            paths,
-            urgency: response.urgency,
-            pressure_level: response.level,
-            free_pct: Some(response.free_pct),
-            max_delete_batch: behavior_delete_batch_limit(
-                self.behavior_state.mode,
-                response.max_delete_batch,
-            ),
-            force_full_scan: false,
-            config_update: None,
+        let request = ScanRequest {
+            paths,
+            urgency: response.urgency,
+            pressure_level: response.level,
+            free_pct: Some(response.free_pct),
+            max_delete_batch: behavior_delete_batch_limit(
+                self.behavior_state.mode,
+                response.max_delete_batch,
+            ),
+            force_full_scan: false,
+            config_update: None,
+            pressure_dimension: response.dimension,
+        };
+
+        let replace_on_full = response.level >= PressureLevel::Red || response.urgency >= 0.90;
+        match enqueue_scan_request(scan_tx, scan_rx, request, replace_on_full) {
+            ScanEnqueueStatus::Queued => {}
+            ScanEnqueueStatus::ReplacedStale | ScanEnqueueStatus::DeferredFull => {
+                // Rate-limit to once per hour. Scans routinely take 300-600s
+                // while monitor ticks every 60s, so this condition fires on
+                // nearly every tick during active scanning — expected behavior,
+                // not worth logging frequently.
+                let now = Instant::now();
+                let should_log = self
+                    .last_scan_channel_warn
+                    .is_none_or(|last| now.duration_since(last) >= Duration::from_hours(1));
+                if should_log {
+                    let suppressed = self.scan_channel_warn_suppressed;
+                    self.scan_channel_warn_suppressed = 0;
+                    self.last_scan_channel_warn = Some(now);
+                    if suppressed > 0 {
+                        eprintln!(
+                            "[SBH-DAEMON] scan channel saturated ({suppressed} deferred requests since last log)"
+                        );
+                    } else {
+                        eprintln!(
+                            "[SBH-DAEMON] scan channel saturated (request replaced or deferred)"
+                        );
+                    }
+                } else {
+                    self.scan_channel_warn_suppressed += 1;
+                }
+            }
+            ScanEnqueueStatus::Disconnected => {
+                eprintln!("[SBH-DAEMON] scan channel disconnected, dropping scan request");
+            }
+        }
+    }
+
+    /// Queue a full scan of `paths` outside the pressure schedule.
+    ///
+    /// Returns `false` when the scanner channel stayed full past the brief wait.
+    fn trigger_forced_scan(
+        scan_tx: &Sender<ScanRequest>,
+        response: &crate::monitor::pid::PressureResponse,
+        paths: Vec<PathBuf>,
+    ) -> bool {
+        let request = ScanRequest {
+            paths,
+            urgency: response.urgency.max(0.5), // at least moderate urgency for forced scans
+            pressure_level: response.level,
+            free_pct: Some(response.free_pct),
+            max_delete_batch: response.max_delete_batch,
+            force_full_scan: true,
+            config_update: None,
+            pressure_dimension: response.dimension,
+        };
+        // For forced scans, block briefly to ensure delivery.
+        scan_tx
+            .send_timeout(request, Duration::from_millis(100))
+            .is_ok()
+    }
+
+    // ──────────────────── control socket ────────────────────
+
+    #[cfg(unix)]
+    fn control_request_pending(&self) -> bool {
+        self.control_server
+            .as_ref()
+            .is_some_and(ControlServer::has_pending)
+    }
+
+    #[cfg(not(unix))]
+    #[allow(clippy::unused_self)]
+    const fn control_request_pending(&self) -> bool {
+        false
+    }
+
+    #[cfg(unix)]
+    fn handle_control_requests(
+        &mut self,
+        scan_tx: &Sender<ScanRequest>,
+        response: &crate::monitor::pid::PressureResponse,
+    ) {
+        let Some(server) = self.control_server.as_ref() else {
+            return;
+        };
+        let commands: Vec<_> = std::iter::from_fn(|| server.try_recv()).collect();
+        for command in commands {
+            let reply = self.execute_control_request(&command.request, scan_tx, response);
+            command.respond(reply);
+        }
+    }
+
+    #[cfg(unix)]
+    fn execute_control_request(
+        &mut self,
+        request: &ControlRequest,
+        scan_tx: &Sender<ScanRequest>,
+        response: &crate::monitor::pid::PressureResponse,
+    ) -> ControlResponse {
+        match request {
+            ControlRequest::Status => ControlResponse::success(self.status_dump_payload(response)),
+            ControlRequest::PolicyDiagnostics => {
+                let diagnostics = self.policy_engine.lock().diagnostics();
+                match serde_json::to_value(diagnostics) {
+                    Ok(value) => ControlResponse::success(value),
+                    Err(e) => ControlResponse::failure(format!("serialize diagnostics: {e}")),
+                }
+            }
+            ControlRequest::Scan { paths } => {
+                let paths = match control_scan_paths(paths, &self.config.scanner.root_paths) {
+                    Ok(paths) => paths,
+                    Err(reason) => return ControlResponse::failure(reason),
+                };
+                eprintln!(
+                    "[SBH-DAEMON] forced scan requested over control socket ({} paths)",
+                    paths.len()
+                );
+                if Self::trigger_forced_scan(scan_tx, response, paths.clone()) {
+                    ControlResponse::success(json!({ "queued": true, "paths": paths }))
+                } else {
+                    ControlResponse::failure("scanner busy: scan queue is full, retry shortly")
+                }
+            }
+            ControlRequest::ReleaseBallast { mount, count } => {
+                self.control_release_ballast(mount.as_deref(), *count)
+            }
+            ControlRequest::Reserve {
+                path,
+                bytes,
+                ttl_secs,
+                holder,
+            } => self.control_reserve(path, *bytes, *ttl_secs, holder.clone()),
+            ControlRequest::ReleaseReservation { id } => match self.reservations.release(id) {
+                Some(lease) => {
+                    self.persist_reservations();
+                    self.logger_handle.send(ActivityEvent::Info {
+                        message: format!(
+                            "reservation {id} released ({} bytes on {})",
+                            lease.bytes,
+                            lease.mount.display()
+                        ),
+                    });
+                    ControlResponse::success(lease.status_json(None, reservations::unix_now()))
+                }
+                None => ControlResponse::failure(format!("no reservation with id {id}")),
+            },
+            ControlRequest::Reservations => {
+                ControlResponse::success(json!({ "reservations": self.reservations_json() }))
+            }
+            ControlRequest::SetMode { mode } => {
+                let (from, applied) = {
+                    let mut policy = self.policy_engine.lock();
+                    let from = policy.mode();
+                    (from, policy.set_mode(*mode))
+                };
+                if applied {
+                    ControlResponse::success(json!({
+                        "from": from.to_string(),
+                        "mode": mode.to_string(),
+                    }))
+                } else {
+                    ControlResponse::failure(format!(
+                        "policy engine refused {from} → {mode} (kill-switch engaged?)"
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Operator-requested ballast release. Bypasses the pressure-driven
+    /// release controller but still records the release so replenishment
+    /// honours the usual cooldown.
+    #[cfg(unix)]
+    fn control_release_ballast(&mut self, target: Option<&Path>, count: usize) -> ControlResponse {
+        if count == 0 {
+            return ControlResponse::failure("release count must be > 0");
+        }
+        let target = target.unwrap_or(self.config.paths.ballast_dir.as_path());
+        let mount = self
+            .fs_collector
+            .collect(target)
+            .ok()
+            .map(|stats| stats.mount_point)
+            .filter(|mount| self.ballast_coordinator.has_pool(mount));
+        let Some(mount) = mount else {
+            return ControlResponse::failure(format!(
+                "no ballast pool on the volume holding {}",
+                target.display()
+            ));
+        };
+
+        match self.release_ballast_on_mount(&mount, count) {
+            Ok(Some(report)) => {
+                let remaining = self
+                    .ballast_coordinator
+                    .pool_for_mount(&mount)
+                    .map_or(0, crate::ballast::coordinator::BallastPool::available_count);
+                ControlResponse::success(json!({
+                    "mount": mount,
+                    "requested": count,
+                    "files_released": report.files_released,
+                    "bytes_freed": report.bytes_freed,
+                    "remaining": remaining,
+                    "warnings": report.warnings,
+                    "errors": report.errors,
+                }))
+            }
+            Ok(None) => ControlResponse::failure(format!(
+                "no ballast files available to release on {}",
+                mount.display()
+            )),
+            Err(e) => ControlResponse::failure(e.to_string()),
+        }
+    }
+
+    /// Release up to `count` ballast files on `mount` outside the pressure
+    /// pipeline, recording the release for the replenish cooldown.
+    #[cfg(unix)]
+    fn release_ballast_on_mount(
+        &mut self,
+        mount: &Path,
+        count: usize,
+    ) -> Result<Option<crate::ballast::manager::ReleaseReport>> {
+        let report = self.ballast_coordinator.release_for_mount(mount, count)?;
+        if let Some(report) = &report {
+            self.release_controller
+                .on_released(mount, report.files_released);
+            if report.files_released > 0 {
+                self.notification_manager
+                    .notify(&NotificationEvent::BallastReleased {
+                        mount: mount.to_string_lossy().to_string(),
+                        files_released: report.files_released,
+                        bytes_freed: report.bytes_freed,
+                    });
+            }
+        }
+        Ok(report)
+    }
+
+    /// `sbh reserve`: record a lease on the volume holding `path` and grant
+    /// it at once if the volume can absorb it without dropping into red,
+    /// releasing ballast on that volume first when that closes the gap.
+    /// Otherwise the lease stays pending; its committed bytes raise the
+    /// volume's pressure so the normal pipeline frees space, and
+    /// `maintain_reservations` grants it once it fits.
+    #[cfg(unix)]
+    fn control_reserve(
+        &mut self,
+        path: &Path,
+        bytes: u64,
+        ttl_secs: u64,
+        holder: Option<String>,
+    ) -> ControlResponse {
+        if bytes == 0 {
+            return ControlResponse::failure("reservation size must be > 0");
+        }
+        if ttl_secs == 0 {
+            return ControlResponse::failure("reservation ttl must be > 0");
+        }
+        if !path.is_absolute() {
+            return ControlResponse::failure(format!(
+                "reservation path must be absolute: {}",
+                path.display()
+            ));
+        }
+        let stats = match self.fs_collector.collect(path) {
+            Ok(stats) => stats,
+            Err(e) => return ControlResponse::failure(e.to_string()),
+        };
+        if bytes > stats.total_bytes {
+            return ControlResponse::failure(format!(
+                "cannot reserve {bytes} bytes on {}: volume is only {} bytes",
+                stats.mount_point.display(),
+                stats.total_bytes
+            ));
+        }
+
+        let now = reservations::unix_now();
+        let id = self.reservations.create(
+            path.to_path_buf(),
+            stats.mount_point.clone(),
+            bytes,
+            ttl_secs,
+            holder,
+            now,
+        );
+
+        // Stats are cached for fs_cache_ttl_ms, so account for ballast freed
+        // here explicitly rather than re-reading the volume.
+        let mut free = stats.available_bytes;
+        let floor = self
+            .config
+            .pressure
+            .thresholds_for(&stats.mount_point.to_string_lossy(), stats.total_bytes)
+            .orange_min_free_bytes(stats.total_bytes);
+        let committed = self
+            .reservations
+            .committed_if_granted(&id, free)
+            .unwrap_or(bytes);
+        if !reservations::fits(free, committed, floor)
+            && self.ballast_coordinator.has_pool(&stats.mount_point)
+        {
+            let deficit = floor.saturating_add(committed).saturating_sub(free);
+            let file_size = self
+                .config
+                .ballast
+                .effective_file_size_bytes(&stats.mount_point.to_string_lossy())
+                .max(1);
+            let count = usize::try_from(deficit.div_ceil(file_size)).unwrap_or(usize::MAX);
+            match self.release_ballast_on_mount(&stats.mount_point, count) {
+                Ok(Some(report)) => {
+                    self.last_tick_cleanup_ran = true;
+                    free = free.saturating_add(report.bytes_freed);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    eprintln!("[SBH-DAEMON] ballast release for reservation {id} failed: {e}");
+                }
+            }
+        }
+        if reservations::fits(free, committed, floor) {
+            self.reservations.grant(&id, free);
+        }
+        self.persist_reservations();
+
+        let Some(lease) = self.reservations.get(&id) else {
+            return ControlResponse::failure("reservation vanished before it was recorded");
+        };
+        self.logger_handle.send(ActivityEvent::Info {
+            message: format!(
+                "reservation {id} {} for {bytes} bytes on {} (holder {})",
+                if lease.state == reservations::ReservationState::Granted {
+                    "granted"
+                } else {
+                    "pending"
+                },
+                stats.mount_point.display(),
+                lease.holder.as_deref().unwrap_or("-")
+            ),
+        });
+        ControlResponse::success(lease.status_json(Some(free), now))
+    }
+
+    /// Drop expired leases and grant pending ones whose volume can now
+    /// absorb them. Runs every tick before the pressure check.
+    fn maintain_reservations(&mut self) {
+        if self.reservations.leases().is_empty() {
+            return;
+        }
+        let mut changed = false;
+        for lease in self.reservations.expire(reservations::unix_now()) {
+            changed = true;
+            self.logger_handle.send(ActivityEvent::Info {
+                message: format!(
+                    "reservation {} expired ({:?}, {} bytes on {})",
+                    lease.id,
+                    lease.state,
+                    lease.bytes,
+                    lease.mount.display()
+                ),
+            });
+        }
+        for id in self.reservations.pending_ids() {
+            let Some(mount) = self.reservations.get(&id).map(|lease| lease.mount.clone()) else {
+                continue;
+            };
+            let Ok(stats) = self.fs_collector.collect(&mount) else {
+                continue;
+            };
+            let floor = self
+                .config
+                .pressure
+                .thresholds_for(&mount.to_string_lossy(), stats.total_bytes)
+                .orange_min_free_bytes(stats.total_bytes);
+            let fits = self
+                .reservations
+                .committed_if_granted(&id, stats.available_bytes)
+                .is_some_and(|committed| {
+                    reservations::fits(stats.available_bytes, committed, floor)
+                });
+            if fits && self.reservations.grant(&id, stats.available_bytes) {
+                changed = true;
+                self.logger_handle.send(ActivityEvent::Info {
+                    message: format!("reservation {id} granted on {}", mount.display()),
+                });
+            }
+        }
+        if changed {
+            self.persist_reservations();
+        }
+    }
+
+    fn persist_reservations(&self) {
+        if let Err(e) = self.reservations.persist() {
+            eprintln!("[SBH-DAEMON] failed to persist reservations: {e}");
+        }
+    }
+
+    /// Leases with their outstanding bytes against the latest mount readings.
+    fn reservations_json(&self) -> Vec<Value> {
+        let now = reservations::unix_now();
+        self.reservations
+            .leases()
+            .iter()
+            .map(|lease| {
+                let free = self
+                    .mount_snapshots
+                    .iter()
+                    .find(|snapshot| snapshot.mount == lease.mount)
+                    .map(|snapshot| snapshot.free_bytes);
+                lease.status_json(free, now)
+            })
+            .collect()
+    }
+
+    // ──────────────────── write throttling ────────────────────
+
+    /// Per-cgroup write rates since the previous sample, while any volume is
+    /// at Red or worse and throttling or freezing is enabled. Empty between
+    /// samples.
+    fn sample_cgroup_write_rates(&mut self) -> Vec<CgroupWriteRate> {
+        let policy = &self.config.policy;
+        let wanted = (policy.write_throttle.enabled || policy.freeze.enabled)
+            && self
+                .mount_snapshots
+                .iter()
+                .any(|snapshot| snapshot.response.level >= PressureLevel::Red);
+        if !wanted {
+            self.write_throttler.reset_observations();
+            return Vec::new();
+        }
+
+        let now = Instant::now();
+        if !self.write_throttler.sample_due(now) {
+            return Vec::new();
+        }
+        let totals = self.cgroup_write_totals();
+        self.write_throttler.observe(totals, now)
+    }
+
+    /// Throttle the top writer on each red volume, and lift throttles whose
+    /// volume recovered or whose preconditions no longer hold.
+    fn maintain_write_throttles(&mut self, rates: &[CgroupWriteRate]) {
+        let settings = self.config.policy.write_throttle.clone();
+        let enforcing = self.policy_engine.lock().mode() == ActiveMode::Enforce;
+        let requested = self.behavior_state.mode.write_throttle == WriteThrottleAction::TopWriter;
+        let pressured: Vec<PathBuf> = if settings.enabled && enforcing && requested {
+            self.mount_snapshots
+                .iter()
+                .filter(|snapshot| snapshot.response.level >= PressureLevel::Red)
+                .map(|snapshot| snapshot.mount.clone())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let released = self
+            .write_throttler
+            .release_unless(|throttle| pressured.contains(&throttle.mount));
+        if !released.is_empty() {
+            let reason = if !settings.enabled {
+                "write throttling disabled"
+            } else if !enforcing {
+                "policy engine left enforce mode"
+            } else {
+                "pressure recovered"
+            };
+            for throttle in &released {
+                self.lift_write_throttle(throttle, reason);
+            }
+            self.persist_write_throttles();
+        }
+        if pressured.is_empty() || rates.is_empty() {
+            return;
+        }
+        let daemon_cgroup = match self.platform.self_cgroup() {
+            Ok(cgroup) => cgroup,
+            Err(error) => {
+                if self.write_throttler.first_notice("error:self_cgroup") {
+                    self.logger_handle.send(ActivityEvent::Error {
+                        code: daemon_activity_error_code(&error),
+                        message: format!("write throttling disabled, own cgroup unknown: {error}"),
+                    });
+                }
+                return;
+            }
+        };
+        // Throttling the daemon's own cgroup, or one above it, would slow the
+        // cleanup the throttle is meant to buy time for.
+        let rates: Vec<&CgroupWriteRate> = rates
+            .iter()
+            .filter(|rate| !cgroup_contains(&rate.cgroup, &daemon_cgroup))
+            .collect();
+
+        let mut changed = false;
+        for mount in pressured {
+            let device = match self.platform.block_device_number(&mount) {
+                Ok(device) => device,
+                Err(error) => {
+                    if self
+                        .write_throttler
+                        .first_notice(&format!("device:{}", mount.display()))
+                    {
+                        self.logger_handle.send(ActivityEvent::Error {
+                            code: daemon_activity_error_code(&error),
+                            message: format!(
+                                "cannot resolve the block device of {} for write throttling: {error}",
+                                mount.display()
+                            ),
+                        });
+                    }
+                    continue;
+                }
+            };
+            let device_rates: Vec<CgroupWriteRate> =
+                rates.iter().map(|rate| rate.on_device(&device)).collect();
+            let selection = select_throttle_target(
+                &device_rates,
+                &settings,
+                &self.write_throttler.throttled_on(&mount),
+            );
+            for vetoed in &selection.vetoed {
+                if self
+                    .write_throttler
+                    .first_notice(&format!("veto:{}", vetoed.cgroup))
+                {
+                    let summary = format!(
+                        "protected cgroup {} writing {} bytes/s on {} left unthrottled",
+                        vetoed.cgroup,
+                        vetoed.bytes_per_sec,
+                        mount.display()
+                    );
+                    self.record_throttle_decision(
+                        vetoed,
+                        ActionRecord::Throttle,
+                        Some("protected cgroup".to_string()),
+                        summary,
+                    );
+                }
+            }
+            if let Some(target) = selection.target {
+                changed |= self.apply_write_throttle(&target, &mount, settings.limit_bytes_per_sec);
+            }
+        }
+        if changed {
+            self.persist_write_throttles();
+        }
+    }
+
+    /// Cumulative block I/O per cgroup of every live process.
+    fn cgroup_write_totals(&self) -> BTreeMap<String, CgroupIo> {
+        let processes = match self.platform.process_list() {
+            Ok(processes) => processes,
+            Err(error) => {
+                self.logger_handle.send(ActivityEvent::Error {
+                    code: daemon_activity_error_code(&error),
+                    message: format!("write throttle process scan failed: {error}"),
+                });
+                return BTreeMap::new();
+            }
+        };
+        let cgroups: BTreeSet<String> = processes
+            .into_iter()
+            .filter_map(|process| process.cgroup)
+            .collect();
+        cgroups
+            .into_iter()
+            .filter_map(|cgroup| {
+                let io = self.platform.cgroup_io(&cgroup).ok()?;
+                Some((cgroup, io))
+            })
+            .collect()
+    }
+
+    fn apply_write_throttle(&mut self, target: &CgroupWriteRate, mount: &Path, limit: u64) -> bool {
+        // Remember the cgroup's own limit so lifting restores it; one already
+        // held at or below ours needs nothing.
+        let previous = match self.platform.cgroup_write_limit(&target.cgroup, mount) {
+            Ok(Some(existing)) if existing <= limit => return false,
+            Ok(previous) => previous,
+            Err(error) => {
+                self.report_throttle_failure(target, mount, &error);
+                return false;
+            }
+        };
+        if let Err(error) = self
+            .platform
+            .set_cgroup_write_limit(&target.cgroup, mount, Some(limit))
+        {
+            self.report_throttle_failure(target, mount, &error);
+            return false;
+        }
+
+        let summary = format!(
+            "throttled writes of {} on {} to {limit} bytes/s (was writing {} bytes/s)",
+            target.cgroup,
+            mount.display(),
+            target.bytes_per_sec
+        );
+        eprintln!("[SBH-DAEMON] {summary}");
+        self.record_throttle_decision(target, ActionRecord::Throttle, None, summary);
+        self.write_throttler.record(WriteThrottle {
+            cgroup: target.cgroup.clone(),
+            mount: mount.to_path_buf(),
+            limit_bytes_per_sec: limit,
+            previous_limit_bytes_per_sec: previous,
+            observed_bytes_per_sec: target.bytes_per_sec,
+            applied_at: reservations::unix_now(),
+        });
+        true
+    }
+
+    fn report_throttle_failure(
+        &mut self,
+        target: &CgroupWriteRate,
+        mount: &Path,
+        error: &SbhError,
+    ) {
+        if self
+            .write_throttler
+            .first_notice(&format!("error:{}", target.cgroup))
+        {
+            self.logger_handle.send(ActivityEvent::Error {
+                code: daemon_activity_error_code(error),
+                message: format!(
+                    "failed to throttle writes of {} on {}: {error}",
+                    target.cgroup,
+                    mount.display()
+                ),
+            });
+        }
+    }
+
+    fn lift_write_throttle(&self, throttle: &WriteThrottle, reason: &str) {
+        if let Err(error) = self.platform.set_cgroup_write_limit(
+            &throttle.cgroup,
+            &throttle.mount,
+            throttle.previous_limit_bytes_per_sec,
+        ) {
+            // The cgroup may be gone already, which lifts the limit with it.
+            self.logger_handle.send(ActivityEvent::Error {
+                code: daemon_activity_error_code(&error),
+                message: format!(
+                    "failed to lift write throttle on {} ({}): {error}",
+                    throttle.cgroup,
+                    throttle.mount.display()
+                ),
+            });
+            return;
+        }
+        let summary = format!(
+            "lifted write throttle on {} ({}): {reason}",
+            throttle.cgroup,
+            throttle.mount.display()
+        );
+        eprintln!("[SBH-DAEMON] {summary}");
+        let held_secs = reservations::unix_now().saturating_sub(throttle.applied_at);
+        let record = self.policy_engine.lock().record_throttle(
+            ThrottleEvidence {
+                cgroup: throttle.cgroup.clone(),
+                bytes_written: 0,
+                window_secs: held_secs,
+                veto_reason: None,
+                summary,
+            },
+            ActionRecord::Unthrottle,
+        );
+        self.logger_handle.send(ActivityEvent::DecisionRecorded {
+            record: Box::new(record),
+        });
+    }
+
+    fn lift_all_write_throttles(&mut self, reason: &str) {
+        let released = self.write_throttler.release_unless(|_| false);
+        if released.is_empty() {
+            return;
+        }
+        for throttle in &released {
+            self.lift_write_throttle(throttle, reason);
+        }
+        self.persist_write_throttles();
+    }
+
+    fn record_throttle_decision(
+        &self,
+        rate: &CgroupWriteRate,
+        action: ActionRecord,
+        veto_reason: Option<String>,
+        summary: String,
+    ) {
+        let record = self.policy_engine.lock().record_throttle(
+            ThrottleEvidence {
+                cgroup: rate.cgroup.clone(),
+                bytes_written: rate.bytes_written,
+                window_secs: rate.window.as_secs(),
+                veto_reason,
+                summary,
+            },
+            action,
+        );
+        self.logger_handle.send(ActivityEvent::DecisionRecorded {
+            record: Box::new(record),
+        });
+    }
+
+    fn persist_write_throttles(&self) {
+        if let Err(e) = self.write_throttler.persist() {
+            eprintln!("[SBH-DAEMON] failed to persist write throttles: {e}");
+        }
+    }
+
+    // ──────────────────── last-resort freeze ────────────────────
+
+    /// Thaw frozen writers that are no longer justified, then freeze the top
+    /// writer on each Critical volume whose ballast is spent and whose cleanup
+    /// has stalled.
+    fn maintain_frozen_writers(&mut self, rates: &[CgroupWriteRate]) {
+        let settings = self.config.policy.freeze.clone();
+        let now = Instant::now();
+        let unix_now = reservations::unix_now();
+        let critical: Vec<PathBuf> = self
+            .mount_snapshots
+            .iter()
+            .filter(|snapshot| snapshot.response.level == PressureLevel::Critical)
+            .map(|snapshot| snapshot.mount.clone())
+            .collect();
+        self.cleanup_stall
+            .observe_pressure(!critical.is_empty(), now);
+
+        self.thaw_unjustified_writers(&settings, unix_now);
+        if critical.is_empty() {
+            self.freeze_ledger.reset_notices();
+            return;
+        }
+        if !settings.enabled || rates.is_empty() {
+            return;
+        }
+
+        let stalled =
+            self.cleanup_stall.stalled_for(now) >= Duration::from_secs(settings.stall_secs);
+        let daemon_cgroup = match self.platform.self_cgroup() {
+            Ok(cgroup) => cgroup,
+            Err(error) => {
+                if self.freeze_ledger.first_notice("error:self_cgroup") {
+                    self.logger_handle.send(ActivityEvent::Error {
+                        code: daemon_activity_error_code(&error),
+                        message: format!("writer freeze disabled, own cgroup unknown: {error}"),
+                    });
+                }
+                return;
+            }
+        };
+        // Freezing the daemon's own cgroup, or one above it, would freeze the
+        // daemon with the writer and nothing would thaw it.
+        let candidates: Vec<CgroupWriteRate> = rates
+            .iter()
+            .filter(|rate| !cgroup_contains(&rate.cgroup, &daemon_cgroup))
+            .cloned()
+            .collect();
+
+        let mut changed = false;
+        for mount in critical {
+            if self.freeze_ledger.has_frozen_on(&mount)
+                || self.freeze_ledger.in_cooldown(&mount, unix_now)
+            {
+                continue;
+            }
+            let ballast_exhausted = self
+                .ballast_coordinator
+                .pool_for_mount(&mount)
+                .is_none_or(|pool| pool.available_count() == 0);
+            if !self
+                .policy_engine
+                .lock()
+                .check_freeze_escalation(true, ballast_exhausted, stalled)
+            {
+                continue;
+            }
+
+            let selection = select_top_writer(
+                &candidates,
+                settings.min_writer_bytes_per_sec,
+                &settings.protected_cgroups,
+                &self.freeze_ledger.frozen_cgroups(),
+            );
+            for vetoed in &selection.vetoed {
+                if self
+                    .freeze_ledger
+                    .first_notice(&format!("veto:{}", vetoed.cgroup))
+                {
+                    let summary = format!(
+                        "protected cgroup {} writing {} bytes/s on {} left running",
+                        vetoed.cgroup,
+                        vetoed.bytes_per_sec,
+                        mount.display()
+                    );
+                    self.record_throttle_decision(
+                        vetoed,
+                        ActionRecord::Freeze,
+                        Some("protected cgroup".to_string()),
+                        summary,
+                    );
+                }
+            }
+            if let Some(target) = selection.target {
+                changed |= self.freeze_writer(&target, &mount, settings.max_freeze_secs);
+            }
+        }
+        if changed {
+            self.persist_frozen_writers();
+        }
+    }
+
+    /// Thaw writers whose volume left Red, whose freeze timed out, or whose
+    /// freeze preconditions (opt-in, enforce mode) no longer hold.
+    fn thaw_unjustified_writers(&mut self, settings: &FreezeConfig, unix_now: u64) {
+        if self.freeze_ledger.frozen().is_empty() {
+            return;
+        }
+        let enforcing = self.policy_engine.lock().mode() == ActiveMode::Enforce;
+        let red: Vec<PathBuf> = self
+            .mount_snapshots
+            .iter()
+            .filter(|snapshot| snapshot.response.level >= PressureLevel::Red)
+            .map(|snapshot| snapshot.mount.clone())
+            .collect();
+        let released = self.freeze_ledger.release_unless(|writer| {
+            settings.enabled
+                && enforcing
+                && red.contains(&writer.mount)
+                && writer.frozen_secs(unix_now) < settings.max_freeze_secs
+        });
+        if released.is_empty() {
+            return;
+        }
+        for writer in &released {
+            let reason = if !settings.enabled {
+                "writer freezing disabled"
+            } else if !enforcing {
+                "policy engine left enforce mode"
+            } else if !red.contains(&writer.mount) {
+                "pressure recovered"
+            } else {
+                self.freeze_ledger.start_cooldown(
+                    &writer.mount,
+                    unix_now.saturating_add(settings.max_freeze_secs),
+                );
+                "maximum freeze duration reached"
+            };
+            self.thaw_writer(writer, reason);
+        }
+        self.persist_frozen_writers();
+    }
+
+    fn freeze_writer(
+        &mut self,
+        target: &CgroupWriteRate,
+        mount: &Path,
+        max_freeze_secs: u64,
+    ) -> bool {
+        if let Err(error) = self.platform.set_cgroup_frozen(&target.cgroup, true) {
+            if self
+                .freeze_ledger
+                .first_notice(&format!("error:{}", target.cgroup))
+            {
+                self.logger_handle.send(ActivityEvent::Error {
+                    code: daemon_activity_error_code(&error),
+                    message: format!(
+                        "failed to freeze {} on {}: {error}",
+                        target.cgroup,
+                        mount.display()
+                    ),
+                });
+            }
+            return false;
+        }
+
+        let summary = format!(
+            "froze {} writing {} bytes/s on {}: ballast exhausted and cleanup stalled",
+            target.cgroup,
+            target.bytes_per_sec,
+            mount.display()
+        );
+        eprintln!("[SBH-DAEMON] {summary}");
+        self.record_throttle_decision(target, ActionRecord::Freeze, None, summary);
+        self.notification_manager
+            .notify(&NotificationEvent::WriterFrozen {
+                cgroup: target.cgroup.clone(),
+                mount: mount.display().to_string(),
+                bytes_per_sec: target.bytes_per_sec,
+                max_freeze_secs,
+            });
+        self.freeze_ledger.record(FrozenWriter {
+            cgroup: target.cgroup.clone(),
+            mount: mount.to_path_buf(),
+            observed_bytes_per_sec: target.bytes_per_sec,
+            frozen_at: reservations::unix_now(),
+        });
+        true
+    }
+
+    fn thaw_writer(&mut self, writer: &FrozenWriter, reason: &str) {
+        if let Err(error) = self.platform.set_cgroup_frozen(&writer.cgroup, false) {
+            // The cgroup may be gone already, taking its frozen processes with it.
+            self.logger_handle.send(ActivityEvent::Error {
+                code: daemon_activity_error_code(&error),
+                message: format!("failed to thaw {}: {error}", writer.cgroup),
+            });
+            return;
+        }
+        let frozen_secs = writer.frozen_secs(reservations::unix_now());
+        let summary = format!(
+            "thawed {} ({}) after {frozen_secs}s: {reason}",
+            writer.cgroup,
+            writer.mount.display()
+        );
+        eprintln!("[SBH-DAEMON] {summary}");
+        let record = self.policy_engine.lock().record_throttle(
+            ThrottleEvidence {
+                cgroup: writer.cgroup.clone(),
+                bytes_written: 0,
+                window_secs: frozen_secs,
+                veto_reason: None,
+                summary,
+            },
+            ActionRecord::Thaw,
+        );
+        self.logger_handle.send(ActivityEvent::DecisionRecorded {
+            record: Box::new(record),
+        });
+        self.notification_manager
+            .notify(&NotificationEvent::WriterThawed {
+                cgroup: writer.cgroup.clone(),
+                reason: reason.to_string(),
+                frozen_secs,
+            });
+    }
+
+    fn thaw_all_frozen_writers(&mut self, reason: &str) {
+        let released = self.freeze_ledger.release_unless(|_| false);
+        if released.is_empty() {
+            return;
+        }
+        for writer in &released {
+            self.thaw_writer(writer, reason);
+        }
+        self.persist_frozen_writers();
+    }
+
+    fn persist_frozen_writers(&self) {
+        if let Err(e) = self.freeze_ledger.persist() {
+            eprintln!("[SBH-DAEMON] failed to persist frozen writers: {e}");
+        }
+    }
+
+    // ──────────────────── deleted-but-open files ────────────────────
+
+    /// Re-enumerate deleted-but-open files and, on volumes at or below the
+    /// pressure ceiling, truncate the ones that pass the safety gates
+    /// (`[scanner.deleted_open_files]`).
+    fn maintain_deleted_open_files(&mut self) {
+        let settings = self.config.scanner.deleted_open_files.clone();
+        let ceiling = f64::from(settings.pressure_free_pct_ceiling);
+        let pressured: Vec<PathBuf> = self
+            .mount_snapshots
+            .iter()
+            .filter(|snapshot| snapshot.free_pct <= ceiling)
+            .map(|snapshot| snapshot.mount.clone())
+            .collect();
+        let interval = if pressured.is_empty() {
+            DELETED_OPEN_IDLE_SWEEP_INTERVAL
+        } else {
+            DELETED_OPEN_PRESSURE_SWEEP_INTERVAL
+        };
+        if self
+            .last_deleted_open_sweep
+            .is_some_and(|last| last.elapsed() < interval)
+        {
+            return;
+        }
+        self.last_deleted_open_sweep = Some(Instant::now());
+
+        self.held_deleted_files = self.enumerate_deleted_open_files();
+        let allowed = self.policy_engine.lock().mode().allows_deletion();
+        if !settings.truncate || !allowed || pressured.is_empty() {
+            return;
+        }
+
+        let dry_run = self.config.scanner.dry_run;
+        let report = truncate_held_files(
+            self.held_deleted_files.iter().filter(|file| {
+                file.mount
+                    .as_ref()
+                    .is_some_and(|mount| pressured.contains(mount))
+            }),
+            &settings,
+            dry_run,
+        );
+        let (verb, bytes, files) = if dry_run {
+            (
+                "would_free",
+                report.bytes_would_reclaim,
+                report.files_would_truncate,
+            )
+        } else {
+            ("freed", report.bytes_reclaimed, report.files_truncated)
         };
+        if files == 0 && report.errors.is_empty() {
+            return;
+        }
+        eprintln!(
+            "[sbh-deleted-open] {verb}={bytes}B files={files} skipped={} errors={} dur={}ms",
+            report.files_skipped,
+            report.errors.len(),
+            report.duration.as_millis(),
+        );
+        self.logger_handle.send(ActivityEvent::Info {
+            message: format!(
+                "deleted_open_truncation: {verb} {bytes} bytes across {files} deleted-but-open file(s)"
+            ),
+        });
+        for (path, err) in &report.errors {
+            self.logger_handle.send(ActivityEvent::Error {
+                code: "SBH-DELOPEN".to_string(),
+                message: format!("deleted_open_truncation error on {}: {err}", path.display()),
+            });
+        }
+        if report.files_truncated > 0 {
+            // Truncated files stay open; re-read so status shows what is left.
+            self.held_deleted_files = self.enumerate_deleted_open_files();
+        }
+    }
 
-        let replace_on_full = response.level >= PressureLevel::Red || response.urgency >= 0.90;
-        match enqueue_scan_request(scan_tx, scan_rx, request, replace_on_full) {
-            ScanEnqueueStatus::Queued => {}
-            ScanEnqueueStatus::ReplacedStale | ScanEnqueueStatus::DeferredFull => {
-                // Rate-limit to once per hour. Scans routinely take 300-600s
-                // while monitor ticks every 60s, so this condition fires on
-                // nearly every tick during active scanning — expected behavior,
-                // not worth logging frequently.
-                let now = Instant::now();
-                let should_log = self
-                    .last_scan_channel_warn
-                    .is_none_or(|last| now.duration_since(last) >= Duration::from_hours(1));
-                if should_log {
-                    let suppressed = self.scan_channel_warn_suppressed;
-                    self.scan_channel_warn_suppressed = 0;
-                    self.last_scan_channel_warn = Some(now);
-                    if suppressed > 0 {
+    // ──────────────────── filesystem snapshot thinning ────────────────────
+
+    /// On Red+ volumes, in enforce mode, destroy the oldest snapper or
+    /// zfs-auto-snapshot snapshots beyond each group's newest `keep_latest`
+    /// (`[policy.snapshot_thinning]`).
+    fn maintain_snapshot_thinning(&mut self) {
+        let settings = self.config.policy.snapshot_thinning.clone();
+        if !settings.enabled || self.policy_engine.lock().mode() != ActiveMode::Enforce {
+            return;
+        }
+        let interval = Duration::from_secs(settings.min_interval_secs);
+        let pressured: Vec<(PathBuf, PressureLevel)> = self
+            .mount_snapshots
+            .iter()
+            .filter(|snapshot| snapshot.response.level >= PressureLevel::Red)
+            .map(|snapshot| (snapshot.mount.clone(), snapshot.response.level))
+            .filter(|(mount, _)| {
+                self.last_snapshot_thinning
+                    .get(mount)
+                    .is_none_or(|last| last.elapsed() >= interval)
+            })
+            .collect();
+
+        let dry_run = self.config.scanner.dry_run;
+        for (mount, level) in pressured {
+            self.last_snapshot_thinning
+                .insert(mount.clone(), Instant::now());
+            let snapshots = match self.platform.filesystem_snapshots(&mount) {
+                Ok(snapshots) => snapshots,
+                Err(e) => {
+                    self.logger_handle.send(ActivityEvent::Error {
+                        code: "SBH-SNAPSHOT".to_string(),
+                        message: format!(
+                            "snapshot_thinning: listing snapshots on {} failed: {e}",
+                            mount.display()
+                        ),
+                    });
+                    continue;
+                }
+            };
+            for snapshot in settings.select(&snapshots) {
+                let size = snapshot
+                    .exclusive_bytes
+                    .map_or_else(|| "unknown".to_string(), |bytes| format!("{bytes}B"));
+                if dry_run {
+                    eprintln!(
+                        "[sbh-snapshot-thin] would_destroy {} {} exclusive={size} mount={}",
+                        snapshot.kind,
+                        snapshot.name,
+                        mount.display()
+                    );
+                    self.record_snapshot_decision(snapshot, level, "would destroy", None);
+                    continue;
+                }
+                match self.platform.destroy_filesystem_snapshot(snapshot) {
+                    Ok(()) => {
                         eprintln!(
-                            "[SBH-DAEMON] scan channel saturated ({suppressed} deferred requests since last log)"
+                            "[sbh-snapshot-thin] destroyed {} {} exclusive={size} mount={}",
+                            snapshot.kind,
+                            snapshot.name,
+                            mount.display()
                         );
-                    } else {
-                        eprintln!(
-                            "[SBH-DAEMON] scan channel saturated (request replaced or deferred)"
+                        self.logger_handle.send(ActivityEvent::Info {
+                            message: format!(
+                                "snapshot_thinning: destroyed {} snapshot {} on {} (exclusive {size})",
+                                snapshot.kind,
+                                snapshot.name,
+                                mount.display()
+                            ),
+                        });
+                        self.record_snapshot_decision(snapshot, level, "destroyed", None);
+                    }
+                    Err(e) => {
+                        self.logger_handle.send(ActivityEvent::Error {
+                            code: "SBH-SNAPSHOT".to_string(),
+                            message: format!(
+                                "snapshot_thinning: destroying {} failed: {e}",
+                                snapshot.name
+                            ),
+                        });
+                        self.record_snapshot_decision(
+                            snapshot,
+                            level,
+                            "kept",
+                            Some(format!("destroy failed: {e}")),
                         );
                     }
-                } else {
-                    self.scan_channel_warn_suppressed += 1;
                 }
             }
-            ScanEnqueueStatus::Disconnected => {
-                eprintln!("[SBH-DAEMON] scan channel disconnected, dropping scan request");
-            }
         }
     }
 
-    fn trigger_forced_scan(
+    /// Log one thinned snapshot as a decision record, so `sbh explain` shows
+    /// what was destroyed (or would be, in a dry run) and why.
+    fn record_snapshot_decision(
         &self,
-        scan_tx: &Sender<ScanRequest>,
-        response: &crate::monitor::pid::PressureResponse,
+        snapshot: &FsSnapshotInfo,
+        level: PressureLevel,
+        outcome: &str,
+        veto_reason: Option<String>,
     ) {
-        eprintln!("[SBH-DAEMON] forced scan triggered (SIGUSR1)");
-        let request = ScanRequest {
-            paths: self.config.scanner.root_paths.clone(),
-            urgency: response.urgency.max(0.5), // at least moderate urgency for forced scans
-            pressure_level: response.level,
-            free_pct: Some(response.free_pct),
-            max_delete_batch: response.max_delete_batch,
-            force_full_scan: true,
-            config_update: None,
+        let group = snapshot
+            .retention_group()
+            .unwrap_or_else(|| snapshot.manager.tool().to_string());
+        let record = self.policy_engine.lock().record_snapshot(SnapshotEvidence {
+            name: snapshot.name.clone(),
+            manager: snapshot.manager.tool(),
+            exclusive_bytes: snapshot.exclusive_bytes,
+            veto_reason,
+            summary: format!(
+                "{outcome} {} snapshot on {} at {level:?} pressure: older than the newest {} \
+                 in {group}",
+                snapshot.kind,
+                snapshot.mount_path.display(),
+                self.config.policy.snapshot_thinning.keep_latest
+            ),
+        });
+        self.logger_handle.send(ActivityEvent::DecisionRecorded {
+            record: Box::new(record),
+        });
+    }
+
+    /// Deleted-but-open files attributed to mount points. Empty on platforms
+    /// without fd enumeration.
+    fn enumerate_deleted_open_files(&self) -> Vec<HeldDeletedFile> {
+        let Ok(files) = self.platform.deleted_open_files() else {
+            return Vec::new();
         };
-        // For forced scans, block briefly to ensure delivery.
-        let _ = scan_tx.send_timeout(request, Duration::from_millis(100));
+        let mounts: Vec<PathBuf> = self.platform.mount_points().map_or_else(
+            |_| {
+                self.mount_snapshots
+                    .iter()
+                    .map(|snapshot| snapshot.mount.clone())
+                    .collect()
+            },
+            |mounts| mounts.into_iter().map(|mount| mount.path).collect(),
+        );
+        group_deleted_open_files(files, &mounts)
+    }
+
+    fn deleted_open_files_json(&self) -> Vec<Value> {
+        let settings = &self.config.scanner.deleted_open_files;
+        self.held_deleted_files
+            .iter()
+            .take(DELETED_OPEN_STATUS_LIMIT)
+            .map(|file| file.status_json(settings))
+            .collect()
     }
 
     fn check_predictive_warning(&mut self, response: &crate::monitor::pid::PressureResponse) {
@@ -3041,11 +4997,15 @@ impl MonitoringDaemon {
         // minimum (default 70%).  Without this gate the daemon spams
         // "disk full in N minutes" warnings on healthy disks whenever the
         // EWMA estimator is in fallback mode or has insufficient data.
-        if self.last_ewma_confidence < self.config.pressure.prediction.min_confidence {
+        let prediction = self
+            .config
+            .pressure
+            .prediction_for(&response.causing_mount.to_string_lossy());
+        if self.last_ewma_confidence < prediction.min_confidence {
             return;
         }
 
-        let warning_horizon_secs = self.config.pressure.prediction.warning_horizon_minutes * 60.0;
+        let warning_horizon_secs = prediction.warning_horizon_minutes * 60.0;
 
         if seconds > warning_horizon_secs {
             return;
@@ -3057,11 +5017,11 @@ impl MonitoringDaemon {
         //   Red       < imminent_danger_minutes  (default  5 min)
         //   Orange    < action_horizon_minutes   (default 30 min)
         //   Warning   everything else within the warning horizon
-        let current_level = if minutes < self.config.pressure.prediction.critical_danger_minutes {
+        let current_level = if minutes < prediction.critical_danger_minutes {
             NotificationLevel::Critical
-        } else if minutes < self.config.pressure.prediction.imminent_danger_minutes {
+        } else if minutes < prediction.imminent_danger_minutes {
             NotificationLevel::Red
-        } else if minutes < self.config.pressure.prediction.action_horizon_minutes {
+        } else if minutes < prediction.action_horizon_minutes {
             NotificationLevel::Orange
         } else {
             NotificationLevel::Warning
@@ -3242,6 +5202,7 @@ impl MonitoringDaemon {
                         causing_mount: mount.clone(),
                         free_pct: stats.free_pct(),
                         predicted_seconds: None,
+                        dimension: PressureDimension::Bytes,
                     };
                     let _ = self.release_ballast(&mount, &release_response);
                 }
@@ -3260,6 +5221,7 @@ impl MonitoringDaemon {
                     max_delete_batch,
                     force_full_scan: false,
                     config_update: None,
+                    pressure_dimension: PressureDimension::Bytes,
                 };
 
                 match enqueue_scan_request(scan_tx, scan_rx, request, true) {
@@ -3359,9 +5321,10 @@ impl MonitoringDaemon {
                         }
                     }
 
-                    // Propagate pressure thresholds and EWMA params to all active monitors.
-                    for monitor in self.mount_monitors.values_mut() {
-                        monitor.update_config(&new_config);
+                    // Propagate pressure thresholds, prediction settings, and EWMA
+                    // params to all active monitors.
+                    for (mount, monitor) in &mut self.mount_monitors {
+                        monitor.update_config(&new_config, &mount.to_string_lossy());
                     }
 
                     // Propagate executor-critical settings via shared atomics.
@@ -3402,10 +5365,6 @@ impl MonitoringDaemon {
                         .lock()
                         .update_config(new_config.policy.clone());
 
-                    // Rebuild predictive policy with new thresholds.
-                    self.predictive_policy =
-                        PredictiveActionPolicy::from_config(new_config.pressure.prediction.clone());
-
                     // Propagate notification config (channels, webhook URLs, cooldowns).
                     self.notification_manager
                         .update_config(&new_config.notifications);
@@ -3413,8 +5372,11 @@ impl MonitoringDaemon {
                     self.logger_handle.send(ActivityEvent::ConfigReloaded {
                         details: format!("config hash: {old_hash} -> {new_hash}"),
                     });
+                    let metrics_changed = new_config.metrics != self.config.metrics;
                     self.config = new_config;
-                    self.cached_primary_path = compute_primary_path(&self.config);
+                    if metrics_changed {
+                        self.start_metrics_exporter();
+                    }
                     eprintln!("[SBH-DAEMON] config reloaded successfully");
                 }
             }
@@ -3444,6 +5406,8 @@ impl MonitoringDaemon {
         let platform = Arc::clone(&self.platform);
         let shutdown = self.signal_handler.shutdown_token();
         let scanner_index_path = self.config.paths.scanner_index_file();
+        let provenance_path = self.config.paths.artifact_provenance_file();
+        let policy_engine = Arc::clone(&self.policy_engine);
         thread::Builder::new()
             .name("sbh-scanner".to_string())
             .spawn(move || {
@@ -3458,7 +5422,9 @@ impl MonitoringDaemon {
                     &report_tx,
                     &shutdown,
                     &scanner_index_path,
+                    &provenance_path,
                     &index_feedback_rx,
+                    &policy_engine,
                 );
             })
             .map_err(|source| SbhError::Runtime {
@@ -3512,6 +5478,16 @@ impl MonitoringDaemon {
     ) {
         let uptime_secs = self.start_time.elapsed().as_secs();
 
+        // 0. Stop accepting control requests and unlink the socket.
+        #[cfg(unix)]
+        {
+            self.control_server = None;
+        }
+
+        // 0b. Never leave a writer throttled or frozen behind a stopped daemon.
+        self.lift_all_write_throttles("daemon shutdown");
+        self.thaw_all_frozen_writers("daemon shutdown");
+
         // 1. Broadcast cancellation, then drop channel senders to signal worker threads to exit.
         self.signal_handler.request_shutdown();
         drop(scan_tx);
@@ -3653,11 +5629,15 @@ fn persist_scanner_index_records(
     index: &mut ScannerCandidateIndex,
     records: &mut Vec<CandidateIndexRecord>,
     scanner_index_path: &Path,
+    provenance_path: &Path,
     logger: &ActivityLoggerHandle,
 ) {
     if records.is_empty() {
         return;
     }
+    // Re-read per batch: `sbh run` updates the ledger from other processes.
+    let provenance = ArtifactProvenance::load(provenance_path);
+    ScannerCandidateIndex::tag_owners(records, &provenance);
     for record in records.drain(..) {
         index.upsert(record);
     }
@@ -3866,12 +5846,17 @@ fn scanner_thread_main(
     report_tx: &Sender<WorkerReport>,
     shutdown: &Arc<AtomicBool>,
     scanner_index_path: &Path,
+    provenance_path: &Path,
     index_feedback_rx: &Receiver<ScannerIndexFeedback>,
+    policy_engine: &Arc<Mutex<PolicyEngine>>,
 ) {
     const DIR_SIZE_FLOOR: u64 = 100 * 1_048_576; // 100 MiB
 
-    // Initialize pattern registry (default built-ins).
-    let pattern_registry = ArtifactPatternRegistry::default();
+    // Pattern registry: built-ins plus configured custom patterns, rebuilt
+    // when a config reload changes `scanner.custom_patterns`.
+    let mut custom_patterns = shared_scanner_config.read().custom_patterns.clone();
+    let mut pattern_registry =
+        ArtifactPatternRegistry::default().with_custom(custom_patterns.clone());
 
     // Incremental scan cursor — persists across scan iterations to skip
     // barren directory subtrees that yielded no candidates on a prior pass.
@@ -3912,6 +5897,16 @@ fn scanner_thread_main(
         // Read latest config at the start of each scan.
         let current_scoring_config = shared_scoring_config.read().clone();
         let current_scanner_config = shared_scanner_config.read().clone();
+        if current_scanner_config.custom_patterns != custom_patterns {
+            custom_patterns.clone_from(&current_scanner_config.custom_patterns);
+            pattern_registry = ArtifactPatternRegistry::from_config(&current_scanner_config);
+            logger.send(ActivityEvent::Info {
+                message: format!(
+                    "scanner: reloaded {} custom artifact pattern(s)",
+                    custom_patterns.len()
+                ),
+            });
+        }
 
         // B6: skip this pressure-driven pass if recent passes dispatched
         // nothing reclaimable and the (backed-off) cooldown has not elapsed.
@@ -4056,10 +6051,12 @@ fn scanner_thread_main(
             last_scanner_engine_mode = Some(scanner_engine_mode);
         }
 
+        let inode_pressure = request.pressure_dimension == PressureDimension::Inodes;
         let engine = ScoringEngine::from_config(
             &current_scoring_config,
             current_scanner_config.min_file_age_minutes,
-        );
+        )
+        .with_inode_pressure(inode_pressure);
 
         // If no paths to scan, skip.
         if request.paths.is_empty() {
@@ -4180,6 +6177,7 @@ fn scanner_thread_main(
             None
         };
         let mut v2_candidate_bytes_seen = 0u64;
+        let mut candidate_apparent_bytes = 0u64;
 
         if scanner_index_enabled
             && request.pressure_level >= PressureLevel::Orange
@@ -4281,7 +6279,8 @@ fn scanner_thread_main(
             let prescan_engine = ScoringEngine::from_config(
                 &current_scoring_config,
                 current_scanner_config.min_file_age_minutes,
-            );
+            )
+            .with_inode_pressure(inode_pressure);
             'priority_roots: for root in &active_scan_paths {
                 if shutdown.load(Ordering::Relaxed) {
                     scanner_should_exit = true;
@@ -4480,6 +6479,8 @@ fn scanner_thread_main(
                                 active_references: ActiveReferenceSummary::default(),
                                 is_open: false,
                                 excluded: false,
+                                entry_count: 0,
+                                shared_bytes: 0,
                             };
                             let mut score = prescan_engine.score_candidate(&input, request.urgency);
                             if score.decision.action
@@ -4694,6 +6695,7 @@ fn scanner_thread_main(
                     index,
                     &mut scanner_index_records,
                     scanner_index_path,
+                    provenance_path,
                     logger,
                 );
             }
@@ -4719,6 +6721,7 @@ fn scanner_thread_main(
                     index,
                     &mut scanner_index_records,
                     scanner_index_path,
+                    provenance_path,
                     logger,
                 );
             }
@@ -4812,6 +6815,12 @@ fn scanner_thread_main(
         let mut paths_scanned = 0;
         let mut opaque_pruned_dirs = 0usize;
         let mut scored: Vec<CandidacyScore> = Vec::with_capacity(1024);
+        // Rust targets kept whole only because they are recent or held open.
+        let mut sweep_targets: Vec<PathBuf> = Vec::new();
+        // Repository caches next to the Bazel output bases seen this pass.
+        let mut bazel_caches: Vec<PathBuf> = Vec::new();
+        // Linked worktrees and agent clones, checked against git after the walk.
+        let mut git_workspaces: Vec<GitWorkspace> = Vec::new();
 
         // Track directories for the incremental scan cursor.
         let mut visited_dirs: HashSet<PathBuf> = HashSet::new();
@@ -4900,6 +6909,19 @@ fn scanner_thread_main(
                 .elapsed()
                 .unwrap_or(Duration::ZERO);
 
+            if current_scanner_config.git_workspaces.enabled
+                && entry.metadata.is_dir
+                && entry.structural_signals.has_git
+                && let Some(workspace) = git_workspace::detect(&entry.path)
+                && (workspace.is_worktree()
+                    || pattern_registry
+                        .classify(&entry.path, entry.structural_signals)
+                        .category
+                        == ArtifactCategory::AgentWorkspace)
+            {
+                git_workspaces.push(workspace);
+            }
+
             // Skip directories already known to contain .git (project roots).
             if entry.metadata.is_dir && known_git_dirs.contains(&entry.path) {
                 continue;
@@ -4946,9 +6968,15 @@ fn scanner_thread_main(
                 dirs_with_candidates.insert(parent.to_path_buf());
             }
 
+            let (size_bytes, shared_bytes) = crate::scanner::cow_extents::discount_shared(
+                &entry.path,
+                entry.metadata.is_dir,
+                entry.metadata.content_size_bytes,
+            );
+
             let mut input = crate::scanner::scoring::CandidateInput {
                 path: entry.path.clone(), // Clone needed for input
-                size_bytes: entry.metadata.content_size_bytes,
+                size_bytes,
                 age: adjusted_candidate_age(
                     age,
                     current_scanner_config.min_file_age_minutes,
@@ -4960,7 +6988,9 @@ fn scanner_thread_main(
                 signals: entry.structural_signals,
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
-                excluded: false, // Walker already filters excluded paths.
+                excluded: false, // Walker already filters excluded paths.,
+                entry_count: entry.metadata.content_entry_count,
+                shared_bytes,
             };
 
             let mut score = engine.score_candidate(&input, request.urgency);
@@ -5019,6 +7049,28 @@ fn scanner_thread_main(
                     &sacred_overlaps,
                 );
             }
+            if current_scanner_config.target_sweep.enabled
+                && input.classification.category == ArtifactCategory::RustTarget
+                && let Ok(sacred_overlaps) =
+                    protection::find_sacred_overlaps(&entry.path, &sacred_paths)
+                && target_sweep::eligible_for_sweep(
+                    &engine,
+                    &current_scanner_config.target_sweep,
+                    &input,
+                    &score,
+                    request.urgency,
+                    &sacred_overlaps,
+                )
+            {
+                sweep_targets.push(entry.path.clone());
+            }
+            if current_scanner_config.bazel.prune_by_access
+                && input.classification.category == ArtifactCategory::BazelOutputBase
+                && let Some(cache) = bazel::BazelOutputBase::repository_cache(&entry.path)
+                && !bazel_caches.contains(&cache)
+            {
+                bazel_caches.push(cache);
+            }
 
             score.identity = Some(entry.metadata.identity());
             let mut scanner_index_backoff_active = false;
@@ -5058,6 +7110,8 @@ fn scanner_thread_main(
             {
                 candidates_found += 1;
                 v2_candidate_bytes_seen = v2_candidate_bytes_seen.saturating_add(score.size_bytes);
+                candidate_apparent_bytes =
+                    candidate_apparent_bytes.saturating_add(entry.metadata.apparent_size_bytes);
                 scored.push(score);
                 if let Some(root) = root_path
                     && let Some(stat) = root_stats_map.get_mut(root)
@@ -5122,11 +7176,47 @@ fn scanner_thread_main(
 
         eprintln!(
             "[SBH-SCANNER] scan complete: {paths_scanned} entries, \
-             {candidates_found} candidates, {:.1}s{}",
+             {candidates_found} candidates (reclaimable={v2_candidate_bytes_seen}B \
+             apparent={candidate_apparent_bytes}B), {:.1}s{}",
             total_scan_duration.as_secs_f64(),
             if scan_timed_out { " (timed out)" } else { "" },
         );
 
+        if !sweep_targets.is_empty() {
+            run_target_sweep(
+                &target_sweep::collapse_nested(std::mem::take(&mut sweep_targets)),
+                &current_scanner_config,
+                request.pressure_level,
+                logger,
+            );
+        }
+        if current_scanner_config.bazel.prune_by_access {
+            bazel_caches.extend(
+                current_scanner_config
+                    .bazel
+                    .disk_cache_paths
+                    .iter()
+                    .cloned(),
+            );
+            run_bazel_prune(
+                &bazel_caches,
+                &current_scanner_config,
+                &sacred_paths,
+                request.pressure_level,
+                logger,
+            );
+        }
+        if !git_workspaces.is_empty() {
+            run_git_workspace_reclaim(
+                std::mem::take(&mut git_workspaces),
+                &current_scanner_config,
+                &sacred_paths,
+                policy_engine,
+                request.pressure_level,
+                logger,
+            );
+        }
+
         // Update the incremental scan cursor. On timeout, barren dirs are
         // cached so the next pass skips them. On full completion, cache is
         // cleared for a fresh scan.
@@ -5138,6 +7228,7 @@ fn scanner_thread_main(
                 index,
                 &mut scanner_index_records,
                 scanner_index_path,
+                provenance_path,
                 logger,
             );
         }
@@ -5358,6 +7449,101 @@ impl RepeatDeletionTracker {
     }
 }
 
+// ──────────────────── decision persistence ────────────────────
+
+/// How long a path may go unevaluated before its last persisted decision is
+/// forgotten and the next one is written again.
+const DECISION_MEMORY: Duration = Duration::from_hours(24);
+
+/// How long an unchanged decision stands before it is written again. Kept well
+/// inside [`crate::logger::dual::ACTIVITY_RETENTION_DAYS`] so pruning never leaves a path that is
+/// still being evaluated without a row for `sbh explain --path`.
+const DECISION_REWRITE_INTERVAL: Duration = Duration::from_hours(7 * 24);
+
+/// The parts of a decision that make it worth a new ledger row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DecisionOutcomeKey {
+    policy_mode: PolicyMode,
+    action: ActionRecord,
+    effective_action: Option<ActionRecord>,
+    vetoed: bool,
+}
+
+impl DecisionOutcomeKey {
+    const fn of(record: &DecisionRecord) -> Self {
+        Self {
+            policy_mode: record.policy_mode,
+            action: record.action,
+            effective_action: record.effective_action,
+            vetoed: record.vetoed,
+        }
+    }
+}
+
+/// Last persisted outcome for a path.
+#[derive(Debug, Clone, Copy)]
+struct PersistedDecision {
+    key: DecisionOutcomeKey,
+    written: Instant,
+    seen: Instant,
+}
+
+/// Persists a path's decision only when it differs from the last one written,
+/// or when the last write is [`DECISION_REWRITE_INTERVAL`] old.
+///
+/// Every batch re-evaluates the same kept candidates, so writing each record
+/// would grow `activity_log` by an identical keep decision per path per scan.
+/// Deleted paths are forgotten so a rebuilt artifact gets a fresh record.
+struct DecisionChangeFilter {
+    last: HashMap<PathBuf, PersistedDecision>,
+}
+
+impl DecisionChangeFilter {
+    fn new() -> Self {
+        Self {
+            last: HashMap::new(),
+        }
+    }
+
+    /// Whether `record` should be persisted: its path is new, its outcome
+    /// changed, or the last row written for it is due to be refreshed.
+    fn should_persist(&mut self, record: &DecisionRecord, now: Instant) -> bool {
+        let key = DecisionOutcomeKey::of(record);
+        if let Some(last) = self.last.get_mut(&record.path) {
+            last.seen = now;
+            if last.key == key
+                && now.saturating_duration_since(last.written) < DECISION_REWRITE_INTERVAL
+            {
+                return false;
+            }
+            last.key = key;
+            last.written = now;
+            return true;
+        }
+        self.last.insert(
+            record.path.clone(),
+            PersistedDecision {
+                key,
+                written: now,
+                seen: now,
+            },
+        );
+        true
+    }
+
+    fn forget(&mut self, paths: &[PathBuf]) {
+        for path in paths {
+            self.last.remove(path);
+        }
+    }
+
+    /// Drop paths not evaluated within [`DECISION_MEMORY`].
+    fn prune_stale(&mut self, now: Instant) {
+        self.last
+            .retain(|_, last| now.saturating_duration_since(last.seen) < DECISION_MEMORY);
+    }
+}
+
 // ──────────────────── executor thread ────────────────────
 
 /// Executor thread: receives deletion batches and safely removes artifacts.
@@ -5385,6 +7571,7 @@ fn executor_thread_main(
         Duration::from_secs(shared_config.repeat_base_cooldown_secs()),
         Duration::from_secs(shared_config.repeat_max_cooldown_secs()),
     );
+    let mut decisions = DecisionChangeFilter::new();
     let mut batch_count: u64 = 0;
     let mut last_circuit_breaker_trip: Option<Instant> = None;
     let base_circuit_breaker_cooldown = DeletionConfig::default().circuit_breaker_cooldown;
@@ -5442,18 +7629,60 @@ fn executor_thread_main(
         );
 
         // Gate candidates through the policy engine. The lock is held only for
-        // the duration of evaluate() (pure computation, no I/O).
-        let (approved_candidates, policy_mode) = {
+        // the duration of evaluate() (no deletion I/O; compress-in-place only
+        // stats the candidate's files).
+        let (mut approved_candidates, approved_for_compression, compress_config, policy_mode) = {
             let guard_snapshot = shared_guard_diagnostics.read().clone();
             let guard_for_policy = guard_snapshot
                 .as_ref()
                 .filter(|diag| diag.status != GuardStatus::Unknown);
-            let decision = policy_engine
-                .lock()
-                .evaluate(&batch.candidates, guard_for_policy);
-            (decision.approved_for_deletion, decision.mode)
+            let (decision, compress_config) = {
+                let mut engine = policy_engine.lock();
+                let decision = engine.evaluate(&batch.candidates, guard_for_policy);
+                (decision, engine.compress_in_place_config().clone())
+            };
+            // Persist the evidence ledger so `sbh explain` can answer for
+            // every evaluated candidate, not just the ones deleted. Unchanged
+            // decisions are already on record.
+            let now = Instant::now();
+            for record in decision.records {
+                if decisions.should_persist(&record, now) {
+                    logger.send(ActivityEvent::DecisionRecorded {
+                        record: Box::new(record),
+                    });
+                }
+            }
+            (
+                decision.approved_for_deletion,
+                decision.approved_for_compression,
+                compress_config,
+                decision.mode,
+            )
         };
 
+        if !approved_for_compression.is_empty() {
+            eprintln!(
+                "[SBH-EXECUTOR] policy engine chose compression for {}/{} candidates (mode={})",
+                approved_for_compression.len(),
+                batch.candidates.len(),
+                policy_mode,
+            );
+            // Candidates whose pass compressed nothing free nothing; they
+            // go on to the ordinary delete path instead.
+            let uncompressed = run_compression(
+                &approved_for_compression,
+                &compress_config,
+                &shared_scanner_config.read().clone(),
+                batch.pressure_level,
+                shared_config.dry_run.load(Ordering::Relaxed),
+                logger,
+            );
+            approved_candidates.extend(uncompressed);
+            if approved_candidates.is_empty() {
+                continue;
+            }
+        }
+
         if !approved_candidates.is_empty() {
             eprintln!(
                 "[SBH-EXECUTOR] policy engine approved {}/{} candidates (mode={})",
@@ -5628,6 +7857,7 @@ fn executor_thread_main(
 
         // Record deletions for repeat-deletion dampening.
         tracker.record_deletions(&report.deleted_paths);
+        decisions.forget(&report.deleted_paths);
 
         if report.dry_run {
             if report.items_would_delete > 0 || report.items_failed > 0 {
@@ -5656,6 +7886,7 @@ fn executor_thread_main(
             deleted: report.items_deleted as u64,
             bytes_freed: report.bytes_freed,
             failed: report.items_failed as u64,
+            stalled: report.stalled(),
         });
 
         if report.circuit_breaker_tripped {
@@ -5680,6 +7911,7 @@ fn executor_thread_main(
         // Periodic pruning of expired dampening entries.
         if batch_count.is_multiple_of(10) {
             tracker.prune_expired();
+            decisions.prune_stale(Instant::now());
         }
     }
 }
@@ -5690,13 +7922,14 @@ fn executor_thread_main(
 mod tests {
     use super::*;
     use crate::core::config::Config;
-    use crate::daemon::policy::NotificationPriority;
+    use crate::daemon::policy::{NotificationPriority, PolicyConfig};
     use crate::monitor::pid::PressureLevel;
     use crate::monitor::special_locations::{
         SpecialKind, SpecialLocation, SpecialLocationRegistry,
     };
     use crate::platform::pal::{MemoryInfo, MockPlatform};
     use crate::platform::types::PalError;
+    use crate::scanner::decision_record::DecisionRecordBuilder;
     use crate::scanner::patterns::{ArtifactCategory, ArtifactClassification};
     use crate::scanner::scoring::{DecisionAction, DecisionOutcome, EvidenceLedger, ScoreFactors};
     use std::path::Path;
@@ -5938,6 +8171,7 @@ mod tests {
                 max_delete_batch: 10,
                 force_full_scan: false,
                 config_update: None,
+                pressure_dimension: PressureDimension::Bytes,
             })
             .unwrap();
         drop(scan_tx);
@@ -5953,7 +8187,9 @@ mod tests {
             &report_tx,
             &shutdown,
             &scanner_index_path,
+            &temp.path().join("artifact-provenance.json"),
             &index_feedback_rx,
+            &Arc::new(Mutex::new(PolicyEngine::new(PolicyConfig::default()))),
         );
 
         assert!(
@@ -6036,6 +8272,7 @@ mod tests {
                 max_delete_batch: 10,
                 force_full_scan: true,
                 config_update: None,
+                pressure_dimension: PressureDimension::Bytes,
             })
             .unwrap();
         drop(scan_tx);
@@ -6051,7 +8288,9 @@ mod tests {
             &report_tx,
             &shutdown,
             &scanner_index_path,
+            &temp.path().join("artifact-provenance.json"),
             &index_feedback_rx,
+            &Arc::new(Mutex::new(PolicyEngine::new(PolicyConfig::default()))),
         );
 
         let report = report_rx
@@ -6326,6 +8565,7 @@ mod tests {
             cleanup_action,
             ballast_action,
             notification_priority,
+            write_throttle: WriteThrottleAction::None,
         }
     }
 
@@ -6349,7 +8589,18 @@ mod tests {
         assert_eq!(transition.to_memory, case.memory);
         assert_eq!(transition.to_disk, case.disk);
         assert_eq!(transition.to_mode, state.mode);
-        assert_eq!(state.mode, case.mode);
+        let write_throttle = if case.disk >= PressureLevel::Red {
+            WriteThrottleAction::TopWriter
+        } else {
+            WriteThrottleAction::None
+        };
+        assert_eq!(
+            state.mode,
+            BehaviorMode {
+                write_throttle,
+                ..case.mode
+            }
+        );
         assert_eq!(behavior_allows_scan(state.mode), case.allows_scan);
         assert_eq!(
             behavior_delete_batch_limit(state.mode, configured_limit),
@@ -6461,6 +8712,7 @@ mod tests {
             max_delete_batch: 0,
             force_full_scan: false,
             config_update: None,
+            pressure_dimension: PressureDimension::Bytes,
         };
         let mut scored = vec![test_candidate("/tmp/a", 0.4), test_candidate("/tmp/b", 0.6)];
 
@@ -6596,6 +8848,7 @@ mod tests {
             max_delete_batch: 10,
             force_full_scan: false,
             config_update: None,
+            pressure_dimension: PressureDimension::Bytes,
         };
         assert_eq!(request.paths.len(), 2);
         assert_eq!(request.urgency.to_bits(), 0.7_f64.to_bits());
@@ -6632,6 +8885,7 @@ mod tests {
             max_delete_batch: 0,
             force_full_scan: false,
             config_update: None,
+            pressure_dimension: PressureDimension::Bytes,
         };
         assert_eq!(
             log_truncation_free_pct_for_request(&request).to_bits(),
@@ -6657,6 +8911,141 @@ mod tests {
     }
 
     #[test]
+    fn mount_monitors_run_independent_controllers() {
+        let config = Config::default();
+        let mut busy = MountMonitor::new(&config, "/data");
+        let mut quiet = MountMonitor::new(&config, "/");
+        let start = Instant::now();
+        let mut busy_response = None;
+        let mut quiet_response = None;
+        for tick in 0..20u64 {
+            let now = start + Duration::from_secs(tick * 5);
+            busy_response = Some(busy.pressure_controller.update(
+                PressureReading {
+                    free_bytes: 3,
+                    total_bytes: 100,
+                    mount: PathBuf::from("/data"),
+                },
+                None,
+                now,
+            ));
+            quiet_response = Some(quiet.pressure_controller.update(
+                PressureReading {
+                    free_bytes: 60,
+                    total_bytes: 100,
+                    mount: PathBuf::from("/"),
+                },
+                None,
+                now,
+            ));
+        }
+        let busy_response = busy_response.expect("busy response");
+        let quiet_response = quiet_response.expect("quiet response");
+
+        // Windup on /data must not leak into the root volume's response.
+        assert!(busy_response.level >= PressureLevel::Red);
+        assert!(busy_response.urgency > 0.9);
+        assert!(busy_response.max_delete_batch > quiet_response.max_delete_batch);
+        assert_eq!(quiet_response.level, PressureLevel::Green);
+        assert!(quiet_response.urgency < f64::EPSILON);
+        assert_eq!(quiet_response.release_ballast_files, 0);
+    }
+
+    #[test]
+    fn control_scan_paths_stay_inside_configured_roots() {
+        let roots = vec![PathBuf::from("/data/projects"), PathBuf::from("/tmp")];
+
+        assert_eq!(control_scan_paths(&[], &roots), Ok(roots.clone()));
+        assert_eq!(
+            control_scan_paths(&[PathBuf::from("/data/projects/app/target")], &roots),
+            Ok(vec![PathBuf::from("/data/projects/app/target")])
+        );
+        for rejected in [
+            "relative/target",
+            "/",
+            "/home/other",
+            "/data/projects-other",
+            "/data/projects/../../etc",
+        ] {
+            assert!(
+                control_scan_paths(&[PathBuf::from(rejected)], &roots).is_err(),
+                "{rejected} should be refused"
+            );
+        }
+    }
+
+    #[test]
+    fn pressured_mount_responses_orders_worst_first_and_skips_green() {
+        let response = |mount: &str, level: PressureLevel, urgency: f64| PressureResponse {
+            level,
+            urgency,
+            scan_interval: Duration::from_secs(1),
+            release_ballast_files: 0,
+            max_delete_batch: 1,
+            fallback_active: false,
+            causing_mount: PathBuf::from(mount),
+            free_pct: 10.0,
+            predicted_seconds: None,
+            dimension: PressureDimension::Bytes,
+        };
+        let snapshot = |response: PressureResponse| MountPressureSnapshot {
+            mount: response.causing_mount.clone(),
+            response,
+            free_bytes: 1,
+            total_bytes: 10,
+            free_pct: 10.0,
+            reserved_bytes: 0,
+            rate_bytes_per_sec: 0.0,
+            rate_confidence: 0.0,
+            seconds_to_exhaustion: f64::INFINITY,
+            thresholds: Config::default().pressure.thresholds_for("/", 10),
+            inodes: None,
+        };
+        let snapshots = vec![
+            snapshot(response("/", PressureLevel::Green, 0.0)),
+            snapshot(response("/a", PressureLevel::Yellow, 0.9)),
+            snapshot(response("/b", PressureLevel::Red, 0.5)),
+            snapshot(response("/c", PressureLevel::Yellow, 0.95)),
+        ];
+        let overall = response("/b", PressureLevel::Red, 0.5);
+
+        let order: Vec<PathBuf> = pressured_mount_responses(&snapshots, &overall)
+            .into_iter()
+            .map(|r| r.causing_mount)
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                PathBuf::from("/b"),
+                PathBuf::from("/c"),
+                PathBuf::from("/a")
+            ]
+        );
+
+        // One merged scan covers every pressured volume, worst volume's roots
+        // first and shared roots only once.
+        let merged = merge_scan_paths(vec![
+            vec![PathBuf::from("/b"), PathBuf::from("/shared")],
+            vec![PathBuf::from("/c")],
+            vec![PathBuf::from("/shared"), PathBuf::from("/a")],
+        ]);
+        assert_eq!(
+            merged,
+            vec![
+                PathBuf::from("/b"),
+                PathBuf::from("/shared"),
+                PathBuf::from("/c"),
+                PathBuf::from("/a")
+            ]
+        );
+
+        let fallback = pressured_mount_responses(&[], &overall);
+        assert_eq!(fallback.len(), 1);
+        assert_eq!(fallback[0].causing_mount, PathBuf::from("/b"));
+    }
+
+    #[test]
+    #[allow(clippy::too_many_lines)]
     fn siginfo_status_dump_payload_serializes_as_single_json_object() {
         let response = PressureResponse {
             level: PressureLevel::Yellow,
@@ -6668,6 +9057,7 @@ mod tests {
             causing_mount: PathBuf::from("/"),
             free_pct: 12.5,
             predicted_seconds: Some(120.0),
+            dimension: PressureDimension::Bytes,
         };
         let memory = MemoryInfo {
             total_bytes: 16,
@@ -6681,6 +9071,30 @@ mod tests {
         }];
         let behavior_mode = BehaviorDispatchTable::default()
             .mode_for(MemoryPressureLevel::Normal, PressureLevel::Yellow);
+        let mounts = vec![MountPressureSnapshot {
+            mount: PathBuf::from("/"),
+            response: response.clone(),
+            free_bytes: 8,
+            total_bytes: 16,
+            free_pct: 50.0,
+            reserved_bytes: 3,
+            rate_bytes_per_sec: 2.0,
+            rate_confidence: 0.75,
+            seconds_to_exhaustion: 4.0,
+            thresholds: Config::default().pressure.thresholds_for("/", 16),
+            inodes: None,
+        }];
+        let ballast_pools = vec![PoolInventory {
+            mount_point: PathBuf::from("/"),
+            ballast_dir: PathBuf::from("/var/lib/sbh/ballast"),
+            fs_type: "ext4".to_string(),
+            strategy: crate::ballast::coordinator::ProvisionStrategy::Fallocate,
+            files_available: 2,
+            files_total: 5,
+            releasable_bytes: 2048,
+            skipped: false,
+            skip_reason: None,
+        }];
 
         let payload_input = StatusDumpPayloadInput {
             timestamp: "2026-05-07T21:22:00.000Z".to_string(),
@@ -6693,6 +9107,10 @@ mod tests {
             mount_available_bytes: Some(8),
             ballast_available: 2,
             ballast_total: 5,
+            ballast_pools: &ballast_pools,
+            mounts: &mounts,
+            reservations: vec![json!({"id": "rsv-1", "mount": "/", "bytes": 4})],
+            deleted_open_files: vec![json!({"path": "/data/app.log", "allocated_bytes": 8})],
             memory_info: Some(&memory),
             policy_mode: "enforce".to_string(),
             behavior_mode,
@@ -6727,6 +9145,18 @@ mod tests {
             "identify_only"
         );
         assert_eq!(parsed["threads"][0]["status"], "running");
+        assert_eq!(parsed["mounts"][0]["predicted_exhaustion_seconds"], 4.0);
+        assert_eq!(parsed["mounts"][0]["thresholds"]["red_min_free_pct"], 6.0);
+        assert_eq!(parsed["ballast"]["releasable_bytes"], 2048);
+        assert_eq!(parsed["mounts"][0]["reserved_bytes"], 3);
+        assert_eq!(parsed["reservations"][0]["id"], "rsv-1");
+
+        // The metrics exporter renders this exact payload.
+        let metrics = crate::daemon::metrics::render_prometheus(&payload);
+        assert!(metrics.contains("sbh_mount_free_bytes{mount=\"/\"} 8\n"));
+        assert!(metrics.contains("sbh_ballast_releasable_bytes{mount=\"/\"} 2048\n"));
+        assert!(metrics.contains("sbh_pressure_level 1\n"));
+        assert!(metrics.contains("sbh_mount_reserved_bytes{mount=\"/\"} 3\n"));
     }
 
     #[test]
@@ -6744,6 +9174,7 @@ mod tests {
             max_delete_batch: 10,
             force_full_scan: false,
             config_update: None,
+            pressure_dimension: PressureDimension::Bytes,
         };
         // With capacity 0, send blocks until recv is called.
         // We use thread to unblock.
@@ -6865,6 +9296,7 @@ mod tests {
             max_delete_batch: 4,
             force_full_scan: false,
             config_update: None,
+            pressure_dimension: PressureDimension::Bytes,
         };
 
         assert_eq!(v2_pressure_candidate_byte_target(&request), None);
@@ -6889,6 +9321,7 @@ mod tests {
             max_delete_batch: 10,
             force_full_scan: false,
             config_update: None,
+            pressure_dimension: PressureDimension::Bytes,
         };
         let mut dirty = BTreeSet::new();
 
@@ -6915,6 +9348,7 @@ mod tests {
             max_delete_batch: 10,
             force_full_scan: true,
             config_update: None,
+            pressure_dimension: PressureDimension::Bytes,
         };
         let dirty = BTreeSet::new();
 
@@ -6957,6 +9391,7 @@ mod tests {
             max_delete_batch: 10,
             force_full_scan: false,
             config_update: None,
+            pressure_dimension: PressureDimension::Bytes,
         }
     }
 
@@ -7339,6 +9774,7 @@ mod tests {
             max_delete_batch: 40,
             force_full_scan: false,
             config_update: None,
+            pressure_dimension: PressureDimension::Bytes,
         };
 
         // Fill the channel to capacity.
@@ -7365,6 +9801,7 @@ mod tests {
             max_delete_batch: 40,
             force_full_scan: false,
             config_update: None,
+            pressure_dimension: PressureDimension::Bytes,
         };
 
         // Fill queue with stale requests.
@@ -7433,6 +9870,7 @@ mod tests {
             max_delete_batch: 40,
             force_full_scan: false,
             config_update: None,
+            pressure_dimension: PressureDimension::Bytes,
         };
         for _ in 0..SCANNER_CHANNEL_CAP {
             tx.try_send(make_request())
@@ -7453,6 +9891,7 @@ mod tests {
             max_delete_batch: 1,
             force_full_scan: false,
             config_update: None,
+            pressure_dimension: PressureDimension::Bytes,
         };
         let (del_tx, del_rx) = bounded::<DeletionBatch>(4);
         let mut scored = vec![
@@ -7485,6 +9924,7 @@ mod tests {
             max_delete_batch: 1,
             force_full_scan: false,
             config_update: None,
+            pressure_dimension: PressureDimension::Bytes,
         };
         let (del_tx, del_rx) = bounded::<DeletionBatch>(1);
         del_tx
@@ -7882,6 +10322,49 @@ mod tests {
         assert_eq!(dampened.len(), 1);
     }
 
+    #[test]
+    fn decision_records_persist_only_when_the_outcome_changes() {
+        let mut decisions = DecisionChangeFilter::new();
+        let mut builder = DecisionRecordBuilder::new();
+        let candidate = test_candidate("/tmp/target/debug", 0.9);
+        let path = candidate.path.clone();
+        let record = |builder: &mut DecisionRecordBuilder, action| {
+            let mut record = builder.build(&candidate, PolicyMode::Live, None, None, None);
+            record.action = action;
+            record
+        };
+        let start = Instant::now();
+
+        let keep = record(&mut builder, ActionRecord::Keep);
+        assert!(decisions.should_persist(&keep, start));
+        let keep_again = record(&mut builder, ActionRecord::Keep);
+        assert!(!decisions.should_persist(&keep_again, start));
+        let delete = record(&mut builder, ActionRecord::Delete);
+        assert!(decisions.should_persist(&delete, start));
+
+        // A deleted path that comes back is a new artifact.
+        decisions.forget(std::slice::from_ref(&path));
+        assert!(decisions.should_persist(&delete, start));
+
+        // Paths not evaluated for a day are forgotten.
+        decisions.prune_stale(start + DECISION_MEMORY);
+        assert!(decisions.should_persist(&delete, start + DECISION_MEMORY));
+
+        // A decision evaluated every scan is still rewritten before the
+        // activity log prunes its last row, counted from the last write.
+        let retention =
+            Duration::from_hours(24 * u64::from(crate::logger::dual::ACTIVITY_RETENTION_DAYS));
+        assert!(DECISION_REWRITE_INTERVAL < retention);
+        let written = start + DECISION_MEMORY;
+        let mut now = written;
+        while now < written + DECISION_REWRITE_INTERVAL {
+            assert!(!decisions.should_persist(&delete, now));
+            now += Duration::from_hours(1);
+        }
+        assert!(decisions.should_persist(&delete, now));
+        assert!(!decisions.should_persist(&delete, now + Duration::from_hours(1)));
+    }
+
     #[test]
     fn repeat_dampening_mixed_paths() {
         let mut tracker =
@@ -0,0 +1,591 @@
+//! Prometheus exporter for daemon state.
+//!
+//! Metrics are rendered from the status-dump payload (`build_status_dump_payload`
+//! in `loop_main.rs`) rather than from daemon internals, so a SIGINFO dump, a
+//! control-socket `status` reply and a scrape taken on the same tick always
+//! agree. Two transports are supported:
+//!
+//! - `http`: a loopback-only listener serving `GET /metrics`.
+//! - `textfile`: an atomically replaced `*.prom` file for node_exporter's
+//!   textfile collector.
+
+#![allow(missing_docs)]
+
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde_json::Value;
+
+use crate::core::config::{MetricsConfig, MetricsMode};
+use crate::core::errors::{Result, SbhError};
+
+const HTTP_IO_TIMEOUT: Duration = Duration::from_secs(2);
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_REQUEST_HEADERS: usize = 64;
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+// ──────────────────── rendering ────────────────────
+
+/// Render a status-dump payload in the Prometheus text exposition format.
+#[must_use]
+#[allow(clippy::too_many_lines)]
+pub fn render_prometheus(status: &Value) -> String {
+    let mut out = MetricWriter::default();
+    let mounts = status["mounts"].as_array().map_or(&[][..], Vec::as_slice);
+    let pools = status["ballast"]["pools"]
+        .as_array()
+        .map_or(&[][..], Vec::as_slice);
+
+    out.family(
+        "sbh_up",
+        "gauge",
+        "Whether the sbh daemon produced this snapshot.",
+    );
+    out.sample("sbh_up", &[], 1.0);
+    out.family("sbh_build_info", "gauge", "sbh daemon version.");
+    out.sample(
+        "sbh_build_info",
+        &[("version", status["version"].as_str().unwrap_or("unknown"))],
+        1.0,
+    );
+    out.gauge(
+        "sbh_uptime_seconds",
+        "Seconds since the daemon started.",
+        &status["uptime_seconds"],
+    );
+
+    out.gauge(
+        "sbh_pressure_level",
+        "Worst pressure level across mounts (0=green .. 4=critical).",
+        &level_value(&status["pressure"]["overall"]),
+    );
+    out.gauge(
+        "sbh_pressure_urgency",
+        "PID controller output for the worst mount (0..1).",
+        &status["pressure"]["urgency"],
+    );
+
+    out.per_mount(
+        mounts,
+        "sbh_mount_pressure_level",
+        "Pressure level per mount (0=green .. 4=critical).",
+        |m| level_value(&m["level"]),
+    );
+    out.per_mount(
+        mounts,
+        "sbh_mount_pid_output",
+        "PID controller output per mount (0..1).",
+        |m| m["urgency"].clone(),
+    );
+    out.per_mount(
+        mounts,
+        "sbh_mount_free_bytes",
+        "Bytes available to unprivileged users per mount.",
+        |m| m["free_bytes"].clone(),
+    );
+    out.per_mount(
+        mounts,
+        "sbh_mount_total_bytes",
+        "Filesystem size per mount.",
+        |m| m["total_bytes"].clone(),
+    );
+    out.per_mount(
+        mounts,
+        "sbh_mount_reserved_bytes",
+        "Outstanding bytes of disk-space reservations per mount.",
+        |m| m["reserved_bytes"].clone(),
+    );
+    out.per_mount(
+        mounts,
+        "sbh_mount_ewma_rate_bytes_per_second",
+        "EWMA consumption rate per mount (positive = filling).",
+        |m| m["ewma_rate_bytes_per_sec"].clone(),
+    );
+    out.per_mount(
+        mounts,
+        "sbh_mount_ewma_confidence",
+        "Confidence of the EWMA rate estimate (0..1).",
+        |m| m["ewma_confidence"].clone(),
+    );
+    out.per_mount(
+        mounts,
+        "sbh_mount_predicted_exhaustion_seconds",
+        "Predicted seconds until the mount is full (absent when not filling).",
+        |m| m["predicted_exhaustion_seconds"].clone(),
+    );
+
+    out.per_mount(
+        pools,
+        "sbh_ballast_releasable_bytes",
+        "Ballast bytes that can be released right now, per pool.",
+        |p| p["releasable_bytes"].clone(),
+    );
+    out.per_mount(
+        pools,
+        "sbh_ballast_files_available",
+        "Ballast files present per pool.",
+        |p| p["files_available"].clone(),
+    );
+    out.per_mount(
+        pools,
+        "sbh_ballast_files_total",
+        "Configured ballast files per pool.",
+        |p| p["files_total"].clone(),
+    );
+
+    let totals = &status["counters"]["total"];
+    for (name, help, key) in [
+        ("sbh_scans_total", "Scans completed.", "scans"),
+        ("sbh_deletions_total", "Artifacts deleted.", "deletions"),
+        (
+            "sbh_freed_bytes_total",
+            "Bytes freed by deletions.",
+            "bytes_freed",
+        ),
+        ("sbh_errors_total", "Daemon errors recorded.", "errors"),
+        (
+            "sbh_dropped_log_events_total",
+            "Activity log events dropped under backpressure.",
+            "dropped_log_events",
+        ),
+    ] {
+        out.family(name, "counter", help);
+        if let Some(value) = number(&totals[key]) {
+            out.sample(name, &[], value);
+        }
+    }
+
+    let policy = &status["policy"];
+    out.family(
+        "sbh_policy_mode",
+        "gauge",
+        "Active policy mode (1 for the current mode).",
+    );
+    let mode = policy["mode"].as_str().unwrap_or_default();
+    for candidate in ["observe", "canary", "enforce", "fallback_safe"] {
+        out.sample(
+            "sbh_policy_mode",
+            &[("mode", candidate)],
+            f64::from(u8::from(candidate == mode)),
+        );
+    }
+
+    let guard = &policy["guard"];
+    let guard_status = guard["status"]
+        .as_str()
+        .map_or_else(|| "unknown".to_string(), str::to_ascii_lowercase);
+    out.family(
+        "sbh_guard_status",
+        "gauge",
+        "Adaptive guard calibration status (1 for the current status).",
+    );
+    for candidate in ["pass", "unknown", "fail"] {
+        out.sample(
+            "sbh_guard_status",
+            &[("status", candidate)],
+            f64::from(u8::from(candidate == guard_status)),
+        );
+    }
+    out.gauge(
+        "sbh_guard_e_process_alarm",
+        "Whether the guard e-process drift alarm is raised.",
+        &Value::from(u8::from(guard["e_process_alarm"].as_bool() == Some(true))),
+    );
+
+    let threads = status["threads"].as_array().map_or(&[][..], Vec::as_slice);
+    out.family(
+        "sbh_thread_up",
+        "gauge",
+        "Worker thread heartbeat health (1 = running, 0 = stalled or dead).",
+    );
+    for thread in threads {
+        let name = thread["name"].as_str().unwrap_or("unknown");
+        let running = thread["status"].as_str() == Some("running");
+        out.sample(
+            "sbh_thread_up",
+            &[("thread", name)],
+            f64::from(u8::from(running)),
+        );
+    }
+    out.family(
+        "sbh_thread_heartbeat_age_seconds",
+        "gauge",
+        "Seconds since a running worker thread last reported a heartbeat.",
+    );
+    for thread in threads {
+        if let Some(age_ms) = number(&thread["last_heartbeat_age_ms"]) {
+            let name = thread["name"].as_str().unwrap_or("unknown");
+            out.sample(
+                "sbh_thread_heartbeat_age_seconds",
+                &[("thread", name)],
+                age_ms / 1000.0,
+            );
+        }
+    }
+
+    out.finish()
+}
+
+fn level_value(level: &Value) -> Value {
+    match level.as_str() {
+        Some("green") => Value::from(0),
+        Some("yellow") => Value::from(1),
+        Some("orange") => Value::from(2),
+        Some("red") => Value::from(3),
+        Some("critical") => Value::from(4),
+        _ => Value::Null,
+    }
+}
+
+fn number(value: &Value) -> Option<f64> {
+    value.as_f64().filter(|v| v.is_finite())
+}
+
+#[derive(Default)]
+struct MetricWriter {
+    out: String,
+}
+
+impl MetricWriter {
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# HELP {name} {help}");
+        let _ = writeln!(self.out, "# TYPE {name} {kind}");
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        self.out.push_str(name);
+        if !labels.is_empty() {
+            self.out.push('{');
+            for (i, (key, val)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.out.push(',');
+                }
+                let _ = write!(self.out, "{key}=\"{}\"", escape_label(val));
+            }
+            self.out.push('}');
+        }
+        let _ = writeln!(self.out, " {value}");
+    }
+
+    fn gauge(&mut self, name: &str, help: &str, value: &Value) {
+        self.family(name, "gauge", help);
+        if let Some(value) = number(value) {
+            self.sample(name, &[], value);
+        }
+    }
+
+    fn per_mount(
+        &mut self,
+        rows: &[Value],
+        name: &str,
+        help: &str,
+        value: impl Fn(&Value) -> Value,
+    ) {
+        self.family(name, "gauge", help);
+        for row in rows {
+            let Some(mount) = row["mount"].as_str() else {
+                continue;
+            };
+            if let Some(v) = number(&value(row)) {
+                self.sample(name, &[("mount", mount)], v);
+            }
+        }
+    }
+
+    fn finish(self) -> String {
+        self.out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+// ──────────────────── exporter ────────────────────
+
+/// Running exporter: holds the latest rendering and owns the HTTP thread.
+pub struct MetricsExporter {
+    config: MetricsConfig,
+    latest: Arc<Mutex<String>>,
+    last_refresh: Option<Instant>,
+    last_error: Option<String>,
+    stop: Arc<AtomicBool>,
+    join: Option<thread::JoinHandle<()>>,
+}
+
+impl MetricsExporter {
+    /// Start the exporter described by `config`; `Ok(None)` when disabled.
+    pub fn start(config: &MetricsConfig) -> Result<Option<Self>> {
+        let latest = Arc::new(Mutex::new(String::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let join = match config.mode {
+            MetricsMode::Off => return Ok(None),
+            MetricsMode::Textfile => None,
+            MetricsMode::Http => {
+                let listener =
+                    TcpListener::bind(&config.listen_addr).map_err(|e| SbhError::Runtime {
+                        details: format!(
+                            "failed to bind metrics listener {}: {e}",
+                            config.listen_addr
+                        ),
+                    })?;
+                listener
+                    .set_nonblocking(true)
+                    .map_err(|e| SbhError::Runtime {
+                        details: format!("metrics listener setup failed: {e}"),
+                    })?;
+                let thread_latest = Arc::clone(&latest);
+                let thread_stop = Arc::clone(&stop);
+                let handle = thread::Builder::new()
+                    .name("sbh-metrics".to_string())
+                    .spawn(move || http_thread_main(&listener, &thread_latest, &thread_stop))
+                    .map_err(|source| SbhError::Runtime {
+                        details: format!("failed to spawn metrics thread: {source}"),
+                    })?;
+                Some(handle)
+            }
+        };
+
+        Ok(Some(Self {
+            config: config.clone(),
+            latest,
+            last_refresh: None,
+            last_error: None,
+            stop,
+            join,
+        }))
+    }
+
+    /// Configuration this exporter was started with.
+    #[must_use]
+    pub const fn config(&self) -> &MetricsConfig {
+        &self.config
+    }
+
+    /// Whether `refresh_interval_secs` has elapsed since the last snapshot.
+    #[must_use]
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.last_refresh.is_none_or(|last| {
+            now.duration_since(last) >= Duration::from_secs(self.config.refresh_interval_secs)
+        })
+    }
+
+    /// Render `status` and publish it. Textfile write failures are logged
+    /// once per distinct error so a missing collector dir does not spam.
+    pub fn publish(&mut self, status: &Value) {
+        self.last_refresh = Some(Instant::now());
+        let rendered = render_prometheus(status);
+        if self.config.mode == MetricsMode::Textfile {
+            match write_textfile(&self.config.textfile_path, &rendered) {
+                Ok(()) => self.last_error = None,
+                Err(e) => {
+                    let message = e.to_string();
+                    if self.last_error.as_deref() != Some(message.as_str()) {
+                        eprintln!("[SBH-DAEMON] metrics textfile write failed: {message}");
+                        self.last_error = Some(message);
+                    }
+                }
+            }
+        }
+        *self.latest.lock() = rendered;
+    }
+}
+
+impl Drop for MetricsExporter {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(join) = self.join.take() {
+            let _ = join.join();
+        }
+    }
+}
+
+/// Replace `path` atomically so the collector never reads a partial file.
+fn write_textfile(path: &Path, contents: &str) -> Result<()> {
+    let tmp_path = path.with_extension("prom.tmp");
+    std::fs::write(&tmp_path, contents).map_err(|e| SbhError::io(&tmp_path, e))?;
+    std::fs::rename(&tmp_path, path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp_path);
+        SbhError::io(path, e)
+    })
+}
+
+fn http_thread_main(listener: &TcpListener, latest: &Mutex<String>, stop: &AtomicBool) {
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                if let Err(e) = serve_scrape(&stream, latest) {
+                    eprintln!("[SBH-DAEMON] metrics scrape failed: {e}");
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(ACCEPT_POLL_INTERVAL);
+            }
+            Err(e) => {
+                eprintln!("[SBH-DAEMON] metrics accept failed: {e}");
+                thread::sleep(ACCEPT_POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+fn serve_scrape(stream: &TcpStream, latest: &Mutex<String>) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(HTTP_IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(HTTP_IO_TIMEOUT))?;
+
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain headers so closing the socket does not reset the connection
+    // before the client has read the response.
+    for _ in 0..MAX_REQUEST_HEADERS {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or_default();
+
+    let (status, content_type, body) = if method != "GET" {
+        (
+            "405 Method Not Allowed",
+            "text/plain",
+            "GET only\n".to_string(),
+        )
+    } else if target == "/metrics" || target.starts_with("/metrics?") {
+        ("200 OK", CONTENT_TYPE, latest.lock().clone())
+    } else {
+        ("404 Not Found", "text/plain", "see /metrics\n".to_string())
+    };
+
+    let mut writer = stream;
+    write!(
+        writer,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    writer.write_all(body.as_bytes())?;
+    writer.flush()
+}
+
+// ──────────────────── tests ────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::Read;
+
+    fn sample_status() -> Value {
+        json!({
+            "version": "1.2.3",
+            "uptime_seconds": 90,
+            "pressure": {"overall": "orange", "urgency": 0.6},
+            "mounts": [
+                {"mount": "/", "level": "green", "urgency": 0.0, "free_bytes": 500, "total_bytes": 1000,
+                 "ewma_rate_bytes_per_sec": -2.5, "ewma_confidence": 0.9, "predicted_exhaustion_seconds": null},
+                {"mount": "/data \"x\"", "level": "orange", "urgency": 0.6, "free_bytes": 80, "total_bytes": 1000,
+                 "ewma_rate_bytes_per_sec": 10.0, "ewma_confidence": 0.8, "predicted_exhaustion_seconds": 8.0}
+            ],
+            "ballast": {"pools": [
+                {"mount": "/data \"x\"", "releasable_bytes": 2048, "files_available": 2, "files_total": 5}
+            ]},
+            "policy": {"mode": "canary", "guard": {"status": "PASS", "e_process_alarm": false}},
+            "counters": {"total": {"scans": 4, "deletions": 3, "bytes_freed": 4096, "errors": 1, "dropped_log_events": 0}},
+            "threads": [
+                {"name": "sbh-scanner", "status": "running", "last_heartbeat_age_ms": 1500},
+                {"name": "sbh-executor", "status": "stalled", "stalled_for_ms": 70000}
+            ]
+        })
+    }
+
+    #[test]
+    fn renders_status_payload_as_prometheus_text() {
+        let text = render_prometheus(&sample_status());
+        assert!(text.contains("# TYPE sbh_scans_total counter\nsbh_scans_total 4\n"));
+        assert!(text.contains("sbh_pressure_level 2\n"));
+        assert!(text.contains("sbh_mount_free_bytes{mount=\"/\"} 500\n"));
+        assert!(text.contains("sbh_mount_pressure_level{mount=\"/data \\\"x\\\"\"} 2\n"));
+        assert!(text.contains("sbh_mount_ewma_rate_bytes_per_second{mount=\"/\"} -2.5\n"));
+        assert!(text.contains("sbh_ballast_releasable_bytes{mount=\"/data \\\"x\\\"\"} 2048\n"));
+        assert!(text.contains("sbh_policy_mode{mode=\"canary\"} 1\n"));
+        assert!(text.contains("sbh_policy_mode{mode=\"enforce\"} 0\n"));
+        assert!(text.contains("sbh_guard_status{status=\"pass\"} 1\n"));
+        assert!(text.contains("sbh_thread_up{thread=\"sbh-executor\"} 0\n"));
+        assert!(text.contains("sbh_thread_heartbeat_age_seconds{thread=\"sbh-scanner\"} 1.5\n"));
+        // Non-filling mounts have no prediction sample at all.
+        assert!(!text.contains("sbh_mount_predicted_exhaustion_seconds{mount=\"/\"}"));
+        assert!(
+            text.contains("sbh_mount_predicted_exhaustion_seconds{mount=\"/data \\\"x\\\"\"} 8\n")
+        );
+    }
+
+    #[test]
+    fn every_sample_belongs_to_a_declared_family() {
+        let text = render_prometheus(&sample_status());
+        let mut declared = Vec::new();
+        for line in text.lines() {
+            if let Some(rest) = line.strip_prefix("# TYPE ") {
+                declared.push(rest.split_whitespace().next().unwrap().to_string());
+            } else if !line.starts_with('#') {
+                let name = line.split(['{', ' ']).next().unwrap();
+                assert_eq!(declared.last().map(String::as_str), Some(name), "{line}");
+            }
+        }
+    }
+
+    #[test]
+    fn textfile_mode_writes_prom_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = MetricsConfig {
+            mode: MetricsMode::Textfile,
+            textfile_path: dir.path().join("sbh.prom"),
+            ..MetricsConfig::default()
+        };
+        let mut exporter = MetricsExporter::start(&config).unwrap().unwrap();
+        assert!(exporter.is_due(Instant::now()));
+        exporter.publish(&sample_status());
+        assert!(!exporter.is_due(Instant::now()));
+
+        let written = std::fs::read_to_string(dir.path().join("sbh.prom")).unwrap();
+        assert!(written.contains("sbh_up 1"));
+        assert!(!dir.path().join("sbh.prom.tmp").exists());
+    }
+
+    #[test]
+    fn http_scrape_serves_latest_snapshot() {
+        let latest = Mutex::new(render_prometheus(&sample_status()));
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            stream
+                .write_all(b"GET /metrics HTTP/1.1\r\nHost: x\r\n\r\n")
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+        let (stream, _) = listener.accept().unwrap();
+        serve_scrape(&stream, &latest).unwrap();
+        drop(stream);
+
+        let response = client.join().unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains(CONTENT_TYPE));
+        assert!(response.contains("sbh_scans_total 4"));
+    }
+}
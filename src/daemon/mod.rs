@@ -1,13 +1,24 @@
 //! Daemon subsystem: main monitoring loop, service integration, signal handling,
-//! self-monitoring, and multi-channel notifications.
+//! local control socket, metrics export, disk-space reservations, self-monitoring,
+//! and multi-channel notifications.
 
+#[cfg(all(unix, feature = "daemon"))]
+pub mod control;
+#[cfg(feature = "daemon")]
+pub mod freeze;
 #[cfg(feature = "daemon")]
 pub mod loop_main;
+#[cfg(feature = "daemon")]
+pub mod metrics;
 pub mod notifications;
 pub mod policy;
 #[cfg(feature = "daemon")]
 pub mod process_io_history;
+#[cfg(feature = "daemon")]
+pub mod reservations;
 pub mod self_monitor;
 pub mod service;
 #[cfg(feature = "daemon")]
 pub mod signals;
+#[cfg(feature = "daemon")]
+pub mod write_throttle;
@@ -99,6 +99,17 @@ pub enum NotificationEvent {
         disk_level: String,
         action: String,
     },
+    WriterFrozen {
+        cgroup: String,
+        mount: String,
+        bytes_per_sec: u64,
+        max_freeze_secs: u64,
+    },
+    WriterThawed {
+        cgroup: String,
+        reason: String,
+        frozen_secs: u64,
+    },
     DaemonStarted {
         version: String,
         volumes_monitored: usize,
@@ -185,7 +196,11 @@ impl NotificationEvent {
 
             Self::BallastReleased { .. } => NotificationLevel::Orange,
 
-            Self::BehaviorEmergency { .. } => NotificationLevel::Critical,
+            Self::BehaviorEmergency { .. } | Self::WriterFrozen { .. } => {
+                NotificationLevel::Critical
+            }
+
+            Self::WriterThawed { .. } => NotificationLevel::Warning,
 
             Self::Error { .. } => NotificationLevel::Red,
         }
@@ -201,6 +216,8 @@ impl NotificationEvent {
             Self::BallastReleased { .. } => "ballast_released",
             Self::BallastReplenished { .. } => "ballast_replenished",
             Self::BehaviorEmergency { .. } => "behavior_emergency",
+            Self::WriterFrozen { .. } => "writer_frozen",
+            Self::WriterThawed { .. } => "writer_thawed",
             Self::DaemonStarted { .. } => "daemon_started",
             Self::DaemonStopped { .. } => "daemon_stopped",
             Self::Error { .. } => "error",
@@ -257,6 +274,22 @@ impl NotificationEvent {
                     "Emergency pressure response from {source}: memory={memory_level} disk={disk_level} ({action})"
                 )
             }
+            Self::WriterFrozen {
+                cgroup,
+                mount,
+                bytes_per_sec,
+                max_freeze_secs,
+            } => {
+                let mb = *bytes_per_sec as f64 / 1_048_576.0;
+                format!(
+                    "FROZE {cgroup} writing {mb:.0} MB/s to {mount}: ballast exhausted and cleanup stalled (thaws within {max_freeze_secs}s)"
+                )
+            }
+            Self::WriterThawed {
+                cgroup,
+                reason,
+                frozen_secs,
+            } => format!("Thawed {cgroup} after {frozen_secs}s ({reason})"),
             Self::DaemonStarted {
                 version,
                 volumes_monitored,
@@ -1003,6 +1036,35 @@ mod tests {
         assert_eq!(event.type_key(), "behavior_emergency");
     }
 
+    #[test]
+    fn writer_freeze_events_are_loud_and_distinct() {
+        let frozen = NotificationEvent::WriterFrozen {
+            cgroup: "/user.slice/build.scope".to_string(),
+            mount: "/data".to_string(),
+            bytes_per_sec: 512 * 1_048_576,
+            max_freeze_secs: 300,
+        };
+        assert_eq!(frozen.level(), NotificationLevel::Critical);
+        assert_eq!(frozen.type_key(), "writer_frozen");
+        assert!(
+            frozen
+                .summary()
+                .contains("FROZE /user.slice/build.scope writing 512 MB/s")
+        );
+
+        let thawed = NotificationEvent::WriterThawed {
+            cgroup: "/user.slice/build.scope".to_string(),
+            reason: "pressure recovered".to_string(),
+            frozen_secs: 42,
+        };
+        assert_eq!(thawed.level(), NotificationLevel::Warning);
+        assert_eq!(thawed.type_key(), "writer_thawed");
+        assert_eq!(
+            thawed.summary(),
+            "Thawed /user.slice/build.scope after 42s (pressure recovered)"
+        );
+    }
+
     #[test]
     fn event_summary_pressure_changed() {
         let event = NotificationEvent::PressureChanged {
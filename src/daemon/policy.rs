@@ -9,15 +9,22 @@
 
 #![allow(clippy::cast_precision_loss)]
 
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
 use std::fmt;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 use serde::{Deserialize, Serialize};
 
 use crate::monitor::guardrails::{GuardDiagnostics, GuardStatus};
 use crate::monitor::pid::PressureLevel;
-use crate::platform::types::MemoryPressureLevel;
-use crate::scanner::decision_record::{DecisionRecord, DecisionRecordBuilder, PolicyMode};
+use crate::platform::types::{FsSnapshotInfo, MemoryPressureLevel};
+use crate::scanner::compress;
+use crate::scanner::decision_record::{
+    ActionRecord, DecisionRecord, DecisionRecordBuilder, GitWorkspaceEvidence, PolicyMode,
+    SnapshotEvidence, ThrottleEvidence,
+};
+use crate::scanner::patterns::ArtifactCategory;
 use crate::scanner::scoring::{CandidacyScore, DecisionAction};
 
 // ──────────────────── policy mode ────────────────────
@@ -89,6 +96,8 @@ pub enum FallbackReason {
     SerializationFailure,
     /// External kill-switch (env var or config).
     KillSwitch,
+    /// Operator asked for fallback over the daemon control socket.
+    OperatorRequest,
 }
 
 impl fmt::Display for FallbackReason {
@@ -102,6 +111,7 @@ impl fmt::Display for FallbackReason {
             Self::PolicyError { details } => write!(f, "policy error: {details}"),
             Self::SerializationFailure => write!(f, "evidence serialization failure"),
             Self::KillSwitch => write!(f, "kill-switch engaged"),
+            Self::OperatorRequest => write!(f, "operator request"),
         }
     }
 }
@@ -141,6 +151,14 @@ pub struct PolicyConfig {
     /// main loop ticks at 100-250ms, which would flood the guard's statistical
     /// machinery. Set to 0 in tests to allow back-to-back calls.
     pub observe_min_interval_secs: u64,
+    /// Cgroup write throttling of runaway writers under Red/Critical pressure.
+    pub write_throttle: WriteThrottleConfig,
+    /// Last-resort freeze of the top writer at Critical pressure (opt-in).
+    pub freeze: FreezeConfig,
+    /// Thinning of tool-managed btrfs/ZFS snapshots at Red pressure (opt-in).
+    pub snapshot_thinning: SnapshotThinningConfig,
+    /// Compression of cold files in place of deletion below Red (opt-in).
+    pub compress_in_place: CompressInPlaceConfig,
 }
 
 impl Default for PolicyConfig {
@@ -159,10 +177,222 @@ impl Default for PolicyConfig {
             loss_review: 5.0,
             kill_switch: false,
             observe_min_interval_secs: MIN_OBSERVE_INTERVAL_SECS,
+            write_throttle: WriteThrottleConfig::default(),
+            freeze: FreezeConfig::default(),
+            snapshot_thinning: SnapshotThinningConfig::default(),
+            compress_in_place: CompressInPlaceConfig::default(),
         }
     }
 }
 
+/// Cgroup write throttling (`[policy.write_throttle]`).
+///
+/// Under Red/Critical disk pressure and only in enforce mode, the daemon caps
+/// the write bandwidth of the top-writing cgroup with a cgroup v2 `io.max`
+/// limit on the pressured volume's device, and lifts it once the volume
+/// recovers. Off unless explicitly enabled.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct WriteThrottleConfig {
+    /// Allow the daemon to apply `io.max` limits at all.
+    pub enabled: bool,
+    /// Write bandwidth (bytes/sec) imposed on a throttled cgroup.
+    pub limit_bytes_per_sec: u64,
+    /// Only cgroups writing faster than this (bytes/sec) count as runaway writers.
+    pub min_writer_bytes_per_sec: u64,
+    /// Cgroups never throttled, together with everything below them
+    /// (e.g. `/system.slice/postgresql.service`).
+    pub protected_cgroups: Vec<String>,
+}
+
+impl Default for WriteThrottleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            limit_bytes_per_sec: 32 * 1024 * 1024,
+            min_writer_bytes_per_sec: 64 * 1024 * 1024,
+            protected_cgroups: vec!["/init.scope".to_string()],
+        }
+    }
+}
+
+impl WriteThrottleConfig {
+    /// Whether `cgroup` is, or lives below, a protected cgroup.
+    #[must_use]
+    pub fn is_protected(&self, cgroup: &str) -> bool {
+        cgroup_is_protected(&self.protected_cgroups, cgroup)
+    }
+}
+
+/// Last-resort freeze of the top writer (`[policy.freeze]`).
+///
+/// Once a volume is Critical, its ballast is exhausted and cleanup has freed
+/// nothing for `stall_secs`, the daemon freezes the top-writing cgroup
+/// (`cgroup.freeze`, or SIGSTOP of its processes where the kernel lacks it).
+/// The writer is thawed when the volume drops below Red or after
+/// `max_freeze_secs`, whichever comes first. Off unless explicitly enabled.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct FreezeConfig {
+    /// Allow the daemon to freeze writers at all.
+    pub enabled: bool,
+    /// Longest a writer stays frozen; the same volume is not frozen again for
+    /// as long after a timed-out freeze.
+    pub max_freeze_secs: u64,
+    /// How long cleanup must free nothing at Critical before freezing.
+    pub stall_secs: u64,
+    /// Only cgroups writing faster than this (bytes/sec) are frozen.
+    pub min_writer_bytes_per_sec: u64,
+    /// Cgroups never frozen, together with everything below them.
+    pub protected_cgroups: Vec<String>,
+}
+
+impl Default for FreezeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_freeze_secs: 300,
+            stall_secs: 60,
+            min_writer_bytes_per_sec: 16 * 1024 * 1024,
+            protected_cgroups: vec!["/init.scope".to_string()],
+        }
+    }
+}
+
+impl FreezeConfig {
+    /// Whether `cgroup` is, or lives below, a protected cgroup.
+    #[must_use]
+    pub fn is_protected(&self, cgroup: &str) -> bool {
+        cgroup_is_protected(&self.protected_cgroups, cgroup)
+    }
+}
+
+/// Thinning of filesystem snapshots (`[policy.snapshot_thinning]`).
+///
+/// Snapshots keep the blocks of deleted files alive, so on a snapshotted
+/// btrfs or ZFS volume artifact cleanup can free nothing. At Red pressure or
+/// worse, in enforce mode, the daemon destroys the oldest snapshots created by
+/// snapper or zfs-auto-snapshot, always keeping the newest `keep_latest` of
+/// each snapper config or zfs-auto-snapshot schedule. Snapshots taken by hand
+/// or by other tools are never touched. Off unless explicitly enabled.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct SnapshotThinningConfig {
+    /// Allow the daemon to destroy snapshots at all.
+    pub enabled: bool,
+    /// Newest snapshots kept per snapper config or zfs-auto-snapshot label.
+    pub keep_latest: usize,
+    /// Snapshots destroyed per volume in one pass.
+    pub max_per_pass: usize,
+    /// Minimum seconds between passes on the same volume.
+    pub min_interval_secs: u64,
+}
+
+impl Default for SnapshotThinningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            keep_latest: 3,
+            max_per_pass: 2,
+            min_interval_secs: 300,
+        }
+    }
+}
+
+impl SnapshotThinningConfig {
+    /// Snapshots to destroy in one pass, oldest first: managed snapshots
+    /// beyond the newest `keep_latest` of their retention group, capped at
+    /// `max_per_pass`.
+    #[must_use]
+    pub fn select<'a>(&self, snapshots: &'a [FsSnapshotInfo]) -> Vec<&'a FsSnapshotInfo> {
+        let mut groups: BTreeMap<String, Vec<&FsSnapshotInfo>> = BTreeMap::new();
+        for snapshot in snapshots {
+            if let Some(group) = snapshot.retention_group() {
+                groups.entry(group).or_default().push(snapshot);
+            }
+        }
+
+        let mut expendable: Vec<&FsSnapshotInfo> = groups
+            .into_values()
+            .flat_map(|mut group| {
+                group.sort_by_key(|snapshot| Reverse(snapshot.sequence));
+                group.into_iter().skip(self.keep_latest)
+            })
+            .collect();
+        expendable.sort_by_key(|snapshot| snapshot.sequence);
+        expendable.truncate(self.max_per_pass);
+        expendable
+    }
+}
+
+/// Compress-in-place as a graduated step before deletion
+/// (`[policy.compress_in_place]`).
+///
+/// At Yellow and Orange pressure, delete-worthy candidates of the listed
+/// categories are compressed instead: their cold files with a listed
+/// extension are replaced by verified `<name>.zst` copies, and the candidate
+/// stays. From Red up they are deleted as usual. `sbh decompress` restores
+/// compressed files. Off unless explicitly enabled.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct CompressInPlaceConfig {
+    /// Prefer compression over deletion below Red at all.
+    pub enabled: bool,
+    /// Candidate categories compressed rather than deleted below Red.
+    pub categories: Vec<ArtifactCategory>,
+    /// File extensions (without the dot) eligible for compression.
+    pub extensions: Vec<String>,
+    /// Files smaller than this are not worth compressing.
+    pub min_file_bytes: u64,
+    /// Files modified more recently than this are left alone.
+    pub min_idle_secs: u64,
+    /// zstd compression level (1-19).
+    pub level: i32,
+    /// Keep the original unless the `.zst` copy is at least this much smaller.
+    pub min_savings_pct: u8,
+}
+
+impl Default for CompressInPlaceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            categories: vec![
+                ArtifactCategory::CacheDir,
+                ArtifactCategory::TempDir,
+                ArtifactCategory::AgentWorkspace,
+            ],
+            extensions: ["log", "jsonl", "ndjson", "txt", "out", "csv"]
+                .map(String::from)
+                .to_vec(),
+            min_file_bytes: 1024 * 1024,
+            min_idle_secs: 24 * 60 * 60,
+            level: 3,
+            min_savings_pct: 20,
+        }
+    }
+}
+
+/// Whether `cgroup` equals `ancestor` or lives below it.
+#[must_use]
+pub fn cgroup_contains(ancestor: &str, cgroup: &str) -> bool {
+    let ancestor = ancestor.trim_end_matches('/');
+    let cgroup = cgroup.trim_end_matches('/');
+    cgroup == ancestor
+        || cgroup
+            .strip_prefix(ancestor)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Whether `cgroup` is covered by `protected`. The root cgroup always is: it
+/// has no `io.max` or `cgroup.freeze` of its own.
+#[must_use]
+pub fn cgroup_is_protected(protected: &[String], cgroup: &str) -> bool {
+    cgroup.trim_end_matches('/').is_empty()
+        || protected
+            .iter()
+            .any(|ancestor| cgroup_contains(ancestor, cgroup))
+}
+
 // ──────────────────── policy decision ────────────────────
 
 /// The result of evaluating a batch of candidates through the policy engine.
@@ -172,6 +402,8 @@ pub struct PolicyDecision {
     pub records: Vec<DecisionRecord>,
     /// Candidates approved for actual deletion (empty in observe/fallback modes).
     pub approved_for_deletion: Vec<CandidacyScore>,
+    /// Candidates approved for compress-in-place instead of deletion.
+    pub approved_for_compression: Vec<CandidacyScore>,
     /// Count of candidates that would be deleted in enforce mode.
     pub hypothetical_deletes: usize,
     /// Count of candidates that would be kept.
@@ -289,6 +521,17 @@ pub enum BallastAction {
     ReleaseFirst,
 }
 
+/// Write-bandwidth response selected by the behavior dispatch table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WriteThrottleAction {
+    /// Leave writers alone.
+    #[default]
+    None,
+    /// Cap the write bandwidth of the top-writing cgroup (enforce mode only).
+    TopWriter,
+}
+
 /// Operator notification severity selected by the behavior dispatch table.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -316,6 +559,9 @@ pub struct BehaviorMode {
     pub ballast_action: BallastAction,
     /// Notification severity.
     pub notification_priority: NotificationPriority,
+    /// Write-bandwidth response.
+    #[serde(default)]
+    pub write_throttle: WriteThrottleAction,
 }
 
 const DEFAULT_BEHAVIOR_CELLS: [[BehaviorMode; 3]; 3] = [
@@ -325,18 +571,21 @@ const DEFAULT_BEHAVIOR_CELLS: [[BehaviorMode; 3]; 3] = [
             cleanup_action: CleanupAction::None,
             ballast_action: BallastAction::None,
             notification_priority: NotificationPriority::None,
+            write_throttle: WriteThrottleAction::None,
         },
         BehaviorMode {
             scan_aggressiveness: ScanAggressiveness::Aggressive,
             cleanup_action: CleanupAction::IdentifyOnly,
             ballast_action: BallastAction::None,
             notification_priority: NotificationPriority::Low,
+            write_throttle: WriteThrottleAction::None,
         },
         BehaviorMode {
             scan_aggressiveness: ScanAggressiveness::Aggressive,
             cleanup_action: CleanupAction::DefiniteCandidates,
             ballast_action: BallastAction::Release,
             notification_priority: NotificationPriority::High,
+            write_throttle: WriteThrottleAction::TopWriter,
         },
     ],
     [
@@ -345,18 +594,21 @@ const DEFAULT_BEHAVIOR_CELLS: [[BehaviorMode; 3]; 3] = [
             cleanup_action: CleanupAction::None,
             ballast_action: BallastAction::None,
             notification_priority: NotificationPriority::Low,
+            write_throttle: WriteThrottleAction::None,
         },
         BehaviorMode {
             scan_aggressiveness: ScanAggressiveness::Light,
             cleanup_action: CleanupAction::HighConfidenceCandidates,
             ballast_action: BallastAction::Release,
             notification_priority: NotificationPriority::Normal,
+            write_throttle: WriteThrottleAction::None,
         },
         BehaviorMode {
             scan_aggressiveness: ScanAggressiveness::DefiniteOnly,
             cleanup_action: CleanupAction::DefiniteCandidates,
             ballast_action: BallastAction::ReleaseFirst,
             notification_priority: NotificationPriority::High,
+            write_throttle: WriteThrottleAction::TopWriter,
         },
     ],
     [
@@ -365,18 +617,21 @@ const DEFAULT_BEHAVIOR_CELLS: [[BehaviorMode; 3]; 3] = [
             cleanup_action: CleanupAction::None,
             ballast_action: BallastAction::None,
             notification_priority: NotificationPriority::Normal,
+            write_throttle: WriteThrottleAction::None,
         },
         BehaviorMode {
             scan_aggressiveness: ScanAggressiveness::DefiniteOnly,
             cleanup_action: CleanupAction::MostPromisingCandidates,
             ballast_action: BallastAction::Release,
             notification_priority: NotificationPriority::High,
+            write_throttle: WriteThrottleAction::None,
         },
         BehaviorMode {
             scan_aggressiveness: ScanAggressiveness::DefiniteOnly,
             cleanup_action: CleanupAction::AnyDefiniteCandidate,
             ballast_action: BallastAction::ReleaseFirst,
             notification_priority: NotificationPriority::Emergency,
+            write_throttle: WriteThrottleAction::TopWriter,
         },
     ],
 ];
@@ -583,6 +838,50 @@ impl PolicyEngine {
         self.total_decisions
     }
 
+    /// The rung after [`Self::check_emergency_escalation`]: whether the top
+    /// writer may be frozen. Requires the `[policy.freeze]` opt-in, enforce
+    /// mode (which a fallback deadlock reaches through emergency escalation),
+    /// Critical pressure, exhausted ballast and stalled cleanup.
+    #[must_use]
+    pub fn check_freeze_escalation(
+        &self,
+        pressure_is_critical: bool,
+        ballast_exhausted: bool,
+        cleanup_stalled: bool,
+    ) -> bool {
+        self.config.freeze.enabled
+            && self.mode == ActiveMode::Enforce
+            && pressure_is_critical
+            && ballast_exhausted
+            && cleanup_stalled
+    }
+
+    /// Record a write-throttle decision in the same evidence ledger (and id
+    /// sequence) as candidate decisions.
+    pub fn record_throttle(
+        &mut self,
+        evidence: ThrottleEvidence,
+        action: ActionRecord,
+    ) -> DecisionRecord {
+        self.total_decisions += 1;
+        self.builder
+            .build_throttle(evidence, action, self.mode.to_policy_mode())
+    }
+
+    /// Record an idle git workspace decision in the same evidence ledger.
+    pub fn record_git_workspace(&mut self, evidence: GitWorkspaceEvidence) -> DecisionRecord {
+        self.total_decisions += 1;
+        self.builder
+            .build_git_workspace(evidence, self.mode.to_policy_mode())
+    }
+
+    /// Record a snapshot-thinning decision in the same evidence ledger.
+    pub fn record_snapshot(&mut self, evidence: SnapshotEvidence) -> DecisionRecord {
+        self.total_decisions += 1;
+        self.builder
+            .build_snapshot(evidence, self.mode.to_policy_mode())
+    }
+
     /// Total times fallback_safe was entered.
     #[must_use]
     pub fn total_fallback_entries(&self) -> u64 {
@@ -621,6 +920,7 @@ impl PolicyEngine {
 
         let mut records = Vec::with_capacity(budget);
         let mut approved = Vec::new();
+        let mut approved_for_compression = Vec::new();
         let mut hypothetical_deletes = 0usize;
         let mut hypothetical_keeps = 0usize;
         let mut hypothetical_reviews = 0usize;
@@ -654,14 +954,16 @@ impl PolicyEngine {
 
             // Count hypothetical outcomes based on the candidate's inherent score action.
             match candidate.decision.action {
-                DecisionAction::Delete => hypothetical_deletes += 1,
+                DecisionAction::Delete | DecisionAction::Compress => hypothetical_deletes += 1,
                 DecisionAction::Keep => hypothetical_keeps += 1,
                 DecisionAction::Review => hypothetical_reviews += 1,
             }
 
             // Approve for actual deletion if effective action is Delete.
-            if effective_action == DecisionAction::Delete {
-                approved.push(candidate.clone());
+            match effective_action {
+                DecisionAction::Delete => approved.push(candidate.clone()),
+                DecisionAction::Compress => approved_for_compression.push(candidate.clone()),
+                DecisionAction::Keep | DecisionAction::Review => {}
             }
 
             records.push(record);
@@ -678,6 +980,7 @@ impl PolicyEngine {
         PolicyDecision {
             records,
             approved_for_deletion: approved,
+            approved_for_compression,
             hypothetical_deletes,
             hypothetical_keeps,
             hypothetical_reviews,
@@ -858,6 +1161,34 @@ impl PolicyEngine {
         }
     }
 
+    /// Operator override: switch directly to `to` (control socket `set_mode`).
+    ///
+    /// Entering `fallback_safe` records an operator fallback; leaving it clears
+    /// the fallback state without waiting for clean windows. Deletion-capable
+    /// modes are refused while the kill-switch is engaged.
+    ///
+    /// Returns `true` if the engine is in `to` afterwards.
+    pub fn set_mode(&mut self, to: ActiveMode) -> bool {
+        if self.mode == to {
+            return true;
+        }
+        if self.config.kill_switch && to.allows_deletion() {
+            return false;
+        }
+        if to == ActiveMode::FallbackSafe {
+            self.enter_fallback(FallbackReason::OperatorRequest);
+            return self.mode == ActiveMode::FallbackSafe;
+        }
+        let from = self.mode;
+        if from == ActiveMode::FallbackSafe {
+            self.fallback_reason = None;
+            self.fallback_entered_at = None;
+        }
+        self.apply_transition(to, "operator");
+        eprintln!("[SBH-POLICY] {from} → {to} (operator override)");
+        true
+    }
+
     /// Force fallback_safe mode with the given reason.
     ///
     /// Respects the emergency escalation grace period: if the engine was recently
@@ -867,8 +1198,12 @@ impl PolicyEngine {
     /// overrides the grace period.
     pub fn enter_fallback(&mut self, reason: FallbackReason) {
         if self.mode != ActiveMode::FallbackSafe {
-            // Respect emergency escalation grace period (kill-switch always overrides).
-            if reason != FallbackReason::KillSwitch {
+            // Respect emergency escalation grace period (kill-switch and
+            // explicit operator requests always override).
+            if !matches!(
+                reason,
+                FallbackReason::KillSwitch | FallbackReason::OperatorRequest
+            ) {
                 if let Some(escalated_at) = self.emergency_escalated_at {
                     let grace = Duration::from_secs(EMERGENCY_GRACE_PERIOD_SECS);
                     if escalated_at.elapsed() < grace {
@@ -1050,9 +1385,35 @@ impl PolicyEngine {
             self.canary_deletes_this_hour += 1;
         }
 
+        // Below Red, a reversible compression is preferred over deletion when
+        // the candidate holds anything to compress.
+        if self.prefers_compression(candidate) {
+            return DecisionAction::Compress;
+        }
+
         DecisionAction::Delete
     }
 
+    fn prefers_compression(&self, candidate: &CandidacyScore) -> bool {
+        let config = &self.config.compress_in_place;
+        config.enabled
+            && matches!(
+                self.pressure_level,
+                PressureLevel::Yellow | PressureLevel::Orange
+            )
+            && config
+                .categories
+                .contains(&candidate.classification.category)
+            && compress::has_eligible_files(&candidate.path, config, SystemTime::now())
+    }
+
+    /// Compress-in-place settings, for the executor that acts on
+    /// [`PolicyDecision::approved_for_compression`].
+    #[must_use]
+    pub fn compress_in_place_config(&self) -> &CompressInPlaceConfig {
+        &self.config.compress_in_place
+    }
+
     fn check_guard_triggers(&mut self, diag: &GuardDiagnostics) {
         // Only enter FallbackSafe from guard drift alarm at Orange+ pressure.
         //
@@ -1273,10 +1634,12 @@ mod tests {
             cleanup_action,
             ballast_action,
             notification_priority,
+            write_throttle: WriteThrottleAction::None,
         }
     }
 
     #[test]
+    #[allow(clippy::too_many_lines)]
     fn behavior_dispatch_table_encodes_nine_pressure_cells() {
         let table = BehaviorDispatchTable::default();
 
@@ -1373,7 +1736,11 @@ mod tests {
             ),
         ];
 
-        for (memory_level, disk_level, expected) in cases {
+        for (memory_level, disk_level, mut expected) in cases {
+            // Only the disk Red/Critical column throttles writers.
+            if disk_level == BehaviorPressureLevel::Critical {
+                expected.write_throttle = WriteThrottleAction::TopWriter;
+            }
             assert_eq!(
                 table.mode_for_levels(memory_level, disk_level),
                 expected,
@@ -1562,6 +1929,35 @@ mod tests {
         assert_eq!(engine.mode(), ActiveMode::Observe);
     }
 
+    #[test]
+    fn set_mode_jumps_directly_and_clears_fallback() {
+        let mut engine = PolicyEngine::new(default_config());
+        assert!(engine.set_mode(ActiveMode::Enforce));
+        assert_eq!(engine.mode(), ActiveMode::Enforce);
+
+        assert!(engine.set_mode(ActiveMode::FallbackSafe));
+        assert_eq!(
+            engine.fallback_reason(),
+            Some(&FallbackReason::OperatorRequest)
+        );
+
+        assert!(engine.set_mode(ActiveMode::Observe));
+        assert_eq!(engine.mode(), ActiveMode::Observe);
+        assert!(engine.fallback_reason().is_none());
+        let last = engine.transition_log().last().unwrap();
+        assert_eq!(last.transition, "operator");
+    }
+
+    #[test]
+    fn set_mode_refuses_deletion_modes_under_kill_switch() {
+        let mut config = default_config();
+        config.kill_switch = true;
+        let mut engine = PolicyEngine::new(config);
+        assert!(!engine.set_mode(ActiveMode::Enforce));
+        assert_eq!(engine.mode(), ActiveMode::FallbackSafe);
+        assert!(engine.set_mode(ActiveMode::Observe));
+    }
+
     #[test]
     fn demote_observe_fails() {
         let mut engine = PolicyEngine::new(default_config());
@@ -1728,6 +2124,101 @@ mod tests {
         assert_eq!(engine.mode(), ActiveMode::FallbackSafe);
     }
 
+    #[test]
+    fn freeze_escalation_is_the_rung_after_emergency_escalation() {
+        let mut config = default_config();
+        config.freeze.enabled = true;
+        let mut engine = PolicyEngine::new(config);
+        engine.promote(); // canary
+        engine.set_pressure_level(PressureLevel::Orange);
+        engine.evaluate(&[], Some(&failing_guard()));
+        assert_eq!(engine.mode(), ActiveMode::FallbackSafe);
+
+        // Fallback never freezes, even with every other precondition met.
+        assert!(!engine.check_freeze_escalation(true, true, true));
+
+        engine.fallback_entered_at = engine.fallback_entered_at.and_then(|entered| {
+            entered.checked_sub(Duration::from_secs(FALLBACK_EMERGENCY_ESCALATION_SECS + 1))
+        });
+        assert!(engine.check_emergency_escalation(true));
+        assert!(engine.check_freeze_escalation(true, true, true));
+        assert!(!engine.check_freeze_escalation(false, true, true));
+        assert!(!engine.check_freeze_escalation(true, false, true));
+        assert!(!engine.check_freeze_escalation(true, true, false));
+
+        // Without the explicit opt-in nothing is ever frozen.
+        engine.config.freeze.enabled = false;
+        assert!(!engine.check_freeze_escalation(true, true, true));
+    }
+
+    #[test]
+    fn cgroup_protection_matches_on_path_components() {
+        let protected = vec!["/system.slice/".to_string()];
+        assert!(cgroup_is_protected(
+            &protected,
+            "/system.slice/sshd.service"
+        ));
+        assert!(!cgroup_is_protected(&protected, "/system.slice-extra"));
+        assert!(cgroup_is_protected(&[], "/"));
+        assert!(cgroup_contains("/user.slice", "/user.slice/build.scope"));
+        assert!(cgroup_contains("/", "/user.slice"));
+        assert!(!cgroup_contains("/user.slice/build.scope", "/user.slice"));
+    }
+
+    #[test]
+    fn snapshot_thinning_keeps_the_newest_of_each_group_and_skips_unmanaged() {
+        use crate::platform::types::{FsSnapshotKind, SnapshotManager};
+
+        let snapper = |number: u64| FsSnapshotInfo {
+            kind: FsSnapshotKind::Btrfs,
+            name: format!("@/.snapshots/{number}/snapshot"),
+            created: None,
+            sequence: 300 + number,
+            exclusive_bytes: Some(1 << 20),
+            manager: SnapshotManager::Snapper {
+                config: "root".to_string(),
+                number,
+            },
+            mount_path: PathBuf::from("/"),
+        };
+        let manual = FsSnapshotInfo {
+            name: "@/manual".to_string(),
+            sequence: 1,
+            manager: SnapshotManager::Unmanaged,
+            ..snapper(0)
+        };
+        let snapshots = vec![
+            snapper(4),
+            manual,
+            snapper(1),
+            snapper(3),
+            snapper(2),
+            snapper(5),
+        ];
+
+        let config = SnapshotThinningConfig {
+            enabled: true,
+            keep_latest: 2,
+            max_per_pass: 2,
+            min_interval_secs: 0,
+        };
+        let picked: Vec<&str> = config
+            .select(&snapshots)
+            .iter()
+            .map(|snapshot| snapshot.name.as_str())
+            .collect();
+        assert_eq!(
+            picked,
+            vec!["@/.snapshots/1/snapshot", "@/.snapshots/2/snapshot"]
+        );
+
+        let keep_all = SnapshotThinningConfig {
+            keep_latest: 5,
+            ..config
+        };
+        assert!(keep_all.select(&snapshots).is_empty());
+    }
+
     // ──── evaluation tests ────
 
     #[test]
@@ -1763,6 +2254,69 @@ mod tests {
         assert_eq!(decision.hypothetical_keeps, 1);
     }
 
+    fn compressing_engine() -> PolicyEngine {
+        let mut config = default_config();
+        config.compress_in_place.enabled = true;
+        config.compress_in_place.min_file_bytes = 1;
+        let mut engine = PolicyEngine::new(config);
+        engine.promote();
+        engine.promote(); // enforce
+        engine
+    }
+
+    fn cache_candidate(dir: &std::path::Path, files: &[(&str, u64)]) -> CandidacyScore {
+        for (name, idle_hours) in files {
+            let path = dir.join(name);
+            std::fs::write(&path, b"cold output\n").unwrap();
+            let mtime = std::time::SystemTime::now() - Duration::from_hours(*idle_hours);
+            filetime::set_file_mtime(&path, filetime::FileTime::from_system_time(mtime)).unwrap();
+        }
+        let mut cache = sample_candidate(DecisionAction::Delete, 2.5);
+        cache.path = dir.to_path_buf();
+        cache.classification.category = ArtifactCategory::CacheDir;
+        cache
+    }
+
+    #[test]
+    fn compress_in_place_is_preferred_below_red_only() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut engine = compressing_engine();
+        let cache = cache_candidate(tmp.path(), &[("build.log", 48)]);
+        let candidates = vec![cache, sample_candidate(DecisionAction::Delete, 2.5)];
+        let guard = passing_guard();
+
+        engine.set_pressure_level(PressureLevel::Yellow);
+        let decision = engine.evaluate(&candidates, Some(&guard));
+        assert_eq!(decision.approved_for_compression.len(), 1);
+        assert_eq!(
+            decision.approved_for_compression[0].classification.category,
+            ArtifactCategory::CacheDir
+        );
+        assert_eq!(decision.approved_for_deletion.len(), 1);
+
+        engine.set_pressure_level(PressureLevel::Red);
+        let decision = engine.evaluate(&candidates, Some(&guard));
+        assert!(decision.approved_for_compression.is_empty());
+        assert_eq!(decision.approved_for_deletion.len(), 2);
+    }
+
+    #[test]
+    fn cache_without_compressible_files_is_still_deleted_below_red() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut engine = compressing_engine();
+        // Wrong extension, already compressed, and too recently written.
+        let cache = cache_candidate(
+            tmp.path(),
+            &[("blob.bin", 48), ("old.log.zst", 48), ("fresh.log", 0)],
+        );
+        let guard = passing_guard();
+
+        engine.set_pressure_level(PressureLevel::Yellow);
+        let decision = engine.evaluate(&[cache], Some(&guard));
+        assert!(decision.approved_for_compression.is_empty());
+        assert_eq!(decision.approved_for_deletion.len(), 1);
+    }
+
     #[test]
     fn canary_mode_respects_hourly_budget() {
         let mut config = default_config();
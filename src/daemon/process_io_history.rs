@@ -365,6 +365,114 @@ impl ProcessIoHistory {
     }
 }
 
+/// Bytes written by one process tree (a root PID and its descendants).
+///
+/// Used by `sbh run`. Each sample walks the live process list from the root;
+/// processes that have exited keep their last sampled total, so writes made
+/// between the final sample and exit are not counted.
+#[derive(Debug)]
+pub struct ProcessTreeWrites {
+    root_pid: i32,
+    started_at_unix_ms: i64,
+    history: ProcessIoHistory,
+    written_by_process: HashMap<ProcessIoHistoryKey, u64>,
+}
+
+impl ProcessTreeWrites {
+    /// Track the tree rooted at `root_pid`, counting writes from now on.
+    #[must_use]
+    pub fn new(root_pid: i32) -> Self {
+        let mut history = Self::in_memory_history();
+        // A build can outlive the daemon's attribution window.
+        history.history_window = Duration::from_hours(24);
+        Self {
+            root_pid,
+            started_at_unix_ms: unix_time_ms(),
+            history,
+            written_by_process: HashMap::new(),
+        }
+    }
+
+    fn in_memory_history() -> ProcessIoHistory {
+        // Never persisted: `maybe_sample` is not used for tree tracking.
+        ProcessIoHistory::new(PathBuf::new())
+    }
+
+    /// Sample every live process in the tree. Returns how many were sampled.
+    pub fn sample(&mut self, platform: &dyn Platform) -> usize {
+        self.sample_at(platform, unix_time_ms())
+    }
+
+    fn sample_at(&mut self, platform: &dyn Platform, collected_at_unix_ms: i64) -> usize {
+        let Ok(processes) = platform.process_list() else {
+            return 0;
+        };
+        let mut children: HashMap<i32, Vec<&crate::platform::types::ProcessInfo>> = HashMap::new();
+        let mut root = None;
+        for process in &processes {
+            if process.pid == self.root_pid {
+                root = Some(process);
+            }
+            if let Some(parent) = process.parent_pid {
+                children.entry(parent).or_default().push(process);
+            }
+        }
+        let Some(root) = root else {
+            return 0;
+        };
+
+        let run_window = Duration::from_millis(
+            u64::try_from(collected_at_unix_ms.saturating_sub(self.started_at_unix_ms))
+                .unwrap_or(0),
+        );
+        let mut sampled = 0;
+        let mut stack = vec![root];
+        let mut seen = std::collections::HashSet::new();
+        while let Some(process) = stack.pop() {
+            if !seen.insert(process.pid) {
+                continue;
+            }
+            if let Some(kids) = children.get(&process.pid) {
+                stack.extend(kids.iter().copied());
+            }
+            let Ok(io) = platform.process_io(process.pid) else {
+                continue;
+            };
+            let key = ProcessIoHistoryKey {
+                pid: process.pid,
+                start_time_unix_ms: process.start_time_unix_ms,
+            };
+            let io = self.history.record_process_sample_at(
+                io,
+                process.start_time_unix_ms,
+                collected_at_unix_ms,
+            );
+            // Processes started during the run count in full; anything else
+            // (unknown start time) counts from its first sample.
+            let written = self
+                .history
+                .recent_totals_for_process(
+                    &io,
+                    process.start_time_unix_ms,
+                    collected_at_unix_ms,
+                    run_window,
+                )
+                .map_or(0, |recent| recent.bytes_written);
+            self.written_by_process.insert(key, written);
+            sampled += 1;
+        }
+        sampled
+    }
+
+    /// Total bytes written by the tree so far.
+    #[must_use]
+    pub fn bytes_written(&self) -> u64 {
+        self.written_by_process
+            .values()
+            .fold(0, |total, written| total.saturating_add(*written))
+    }
+}
+
 fn current_delta_since(
     samples: &VecDeque<ProcessIoSample>,
     io: &ProcessIo,
@@ -423,6 +531,8 @@ mod tests {
             resident_memory_bytes: None,
             cpu_user_micros: None,
             cpu_system_micros: None,
+            uid: None,
+            cgroup: None,
         }
     }
 
@@ -448,6 +558,45 @@ mod tests {
         assert_eq!(current.bytes_written_recent_15m, Some(250));
     }
 
+    #[test]
+    fn process_tree_writes_follow_descendants_only() {
+        let started = unix_time_ms();
+        let child = |pid: i32, parent: i32| ProcessInfo {
+            parent_pid: Some(parent),
+            start_time_unix_ms: Some(started + 10),
+            ..process(pid)
+        };
+        let platform = MockPlatform::healthy()
+            .with_process(ProcessInfo {
+                start_time_unix_ms: Some(started + 5),
+                ..process(100)
+            })
+            .with_process(child(101, 100))
+            .with_process(child(102, 101))
+            .with_process(child(200, 1))
+            .with_process_io(io(100, 0, 1_000))
+            .with_process_io(io(101, 0, 20_000))
+            .with_process_io(io(102, 0, 300))
+            .with_process_io(io(200, 0, 9_999_999));
+
+        let mut tree = ProcessTreeWrites::new(100);
+        tree.started_at_unix_ms = started;
+        assert_eq!(tree.sample_at(&platform, started + 1_000), 3);
+        assert_eq!(tree.bytes_written(), 21_300);
+
+        // Exited processes keep their last total.
+        let after_exit = MockPlatform::healthy()
+            .with_process(ProcessInfo {
+                start_time_unix_ms: Some(started + 5),
+                ..process(100)
+            })
+            .with_process_io(io(100, 0, 5_000));
+        assert_eq!(tree.sample_at(&after_exit, started + 2_000), 1);
+        assert_eq!(tree.bytes_written(), 25_300);
+
+        assert_eq!(ProcessTreeWrites::new(999).sample(&platform), 0);
+    }
+
     #[test]
     fn record_sample_prunes_samples_outside_one_hour_window() {
         let dir = tempfile::TempDir::new().expect("temp dir should be created");
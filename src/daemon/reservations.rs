@@ -0,0 +1,361 @@
+//! Disk-space reservations: time-limited leases on a volume's free space.
+//!
+//! A lease says "something on this volume is about to write N bytes". The
+//! daemon counts each lease's outstanding bytes as committed usage when it
+//! computes pressure and forecasts for the volume, and only grants a lease
+//! once honouring it keeps the volume out of red; until then the lease is
+//! pending and the extra committed usage drives the normal ballast/cleanup
+//! pipeline for that volume.
+//!
+//! A granted lease is consumed as the volume fills: its outstanding bytes
+//! shrink by however much free space has dropped since the grant, so the
+//! reserving build's own writes are not counted twice.
+//!
+//! Leases live in `reservations.json` next to the state file so they survive
+//! a daemon restart, and expire on their own wall-clock deadline.
+
+#![allow(missing_docs)]
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::core::state_file::write_json_atomic;
+
+/// Lease lifecycle. Pending leases already count as committed usage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReservationState {
+    Pending,
+    Granted,
+}
+
+/// One disk-space lease.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reservation {
+    pub id: String,
+    /// Path the caller asked to reserve space for.
+    pub path: PathBuf,
+    /// Mount point holding `path`.
+    pub mount: PathBuf,
+    pub bytes: u64,
+    #[serde(default)]
+    pub holder: Option<String>,
+    /// Unix seconds.
+    pub created_at: u64,
+    /// Unix seconds.
+    pub expires_at: u64,
+    pub state: ReservationState,
+    /// Free bytes on the mount when the lease was granted.
+    #[serde(default)]
+    pub baseline_free_bytes: u64,
+}
+
+impl Reservation {
+    /// Bytes of this lease not yet written, given the mount's current free space.
+    #[must_use]
+    pub const fn outstanding_bytes(&self, current_free: u64) -> u64 {
+        match self.state {
+            ReservationState::Pending => self.bytes,
+            ReservationState::Granted => {
+                let consumed = self.baseline_free_bytes.saturating_sub(current_free);
+                self.bytes.saturating_sub(consumed)
+            }
+        }
+    }
+
+    #[must_use]
+    pub const fn is_expired(&self, now: u64) -> bool {
+        now >= self.expires_at
+    }
+
+    /// JSON view used by the control socket and `sbh status`.
+    #[must_use]
+    pub fn status_json(&self, current_free: Option<u64>, now: u64) -> Value {
+        json!({
+            "id": self.id,
+            "path": self.path,
+            "mount": self.mount,
+            "bytes": self.bytes,
+            "outstanding_bytes": current_free.map(|free| self.outstanding_bytes(free)),
+            "holder": self.holder,
+            "state": self.state,
+            "created_at": self.created_at,
+            "expires_at": self.expires_at,
+            "expires_in_secs": self.expires_at.saturating_sub(now),
+        })
+    }
+}
+
+/// True when `current_free` still covers `committed` bytes without dropping
+/// below `floor_bytes`.
+#[must_use]
+pub const fn fits(current_free: u64, committed: u64, floor_bytes: u64) -> bool {
+    current_free.saturating_sub(committed) >= floor_bytes
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ReservationFile {
+    leases: Vec<Reservation>,
+}
+
+/// In-memory lease table, persisted on every change.
+#[derive(Debug)]
+pub struct ReservationLedger {
+    path: PathBuf,
+    leases: Vec<Reservation>,
+    seq: u64,
+}
+
+impl ReservationLedger {
+    /// Load leases from `path`. A missing or unreadable file starts empty.
+    #[must_use]
+    pub fn load(path: PathBuf) -> Self {
+        let leases = read_reservations(&path);
+        Self {
+            path,
+            leases,
+            seq: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn leases(&self) -> &[Reservation] {
+        &self.leases
+    }
+
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&Reservation> {
+        self.leases.iter().find(|lease| lease.id == id)
+    }
+
+    /// Committed-but-unwritten bytes on `mount` across all leases.
+    #[must_use]
+    pub fn outstanding_for_mount(&self, mount: &Path, current_free: u64) -> u64 {
+        self.leases
+            .iter()
+            .filter(|lease| lease.mount == mount)
+            .map(|lease| lease.outstanding_bytes(current_free))
+            .fold(0, u64::saturating_add)
+    }
+
+    /// Bytes committed on a lease's mount if `id` were granted now: every
+    /// granted lease's outstanding bytes plus this lease in full. Other pending
+    /// leases wait their turn rather than blocking each other.
+    #[must_use]
+    pub fn committed_if_granted(&self, id: &str, current_free: u64) -> Option<u64> {
+        let candidate = self.get(id)?;
+        let granted = self
+            .leases
+            .iter()
+            .filter(|lease| {
+                lease.mount == candidate.mount
+                    && lease.id != id
+                    && lease.state == ReservationState::Granted
+            })
+            .map(|lease| lease.outstanding_bytes(current_free))
+            .fold(0, u64::saturating_add);
+        Some(granted.saturating_add(candidate.bytes))
+    }
+
+    /// Record a new pending lease and return its id.
+    pub fn create(
+        &mut self,
+        path: PathBuf,
+        mount: PathBuf,
+        bytes: u64,
+        ttl_secs: u64,
+        holder: Option<String>,
+        now: u64,
+    ) -> String {
+        self.seq += 1;
+        let id = format!("rsv-{now:x}-{:x}-{}", std::process::id(), self.seq);
+        self.leases.push(Reservation {
+            id: id.clone(),
+            path,
+            mount,
+            bytes,
+            holder,
+            created_at: now,
+            expires_at: now.saturating_add(ttl_secs),
+            state: ReservationState::Pending,
+            baseline_free_bytes: 0,
+        });
+        id
+    }
+
+    /// Mark a pending lease granted; consumption is measured from `current_free`.
+    pub fn grant(&mut self, id: &str, current_free: u64) -> bool {
+        let Some(lease) = self.leases.iter_mut().find(|lease| lease.id == id) else {
+            return false;
+        };
+        if lease.state == ReservationState::Granted {
+            return false;
+        }
+        lease.state = ReservationState::Granted;
+        lease.baseline_free_bytes = current_free;
+        true
+    }
+
+    /// Drop a lease early.
+    pub fn release(&mut self, id: &str) -> Option<Reservation> {
+        let index = self.leases.iter().position(|lease| lease.id == id)?;
+        Some(self.leases.remove(index))
+    }
+
+    /// Drop and return every lease whose deadline has passed.
+    pub fn expire(&mut self, now: u64) -> Vec<Reservation> {
+        let (expired, live): (Vec<_>, Vec<_>) = std::mem::take(&mut self.leases)
+            .into_iter()
+            .partition(|lease| lease.is_expired(now));
+        self.leases = live;
+        expired
+    }
+
+    /// Ids of pending leases, oldest first.
+    #[must_use]
+    pub fn pending_ids(&self) -> Vec<String> {
+        self.leases
+            .iter()
+            .filter(|lease| lease.state == ReservationState::Pending)
+            .map(|lease| lease.id.clone())
+            .collect()
+    }
+
+    /// Write the lease table atomically (`.tmp` + rename, mode 0644 like the state file).
+    pub fn persist(&self) -> io::Result<()> {
+        write_json_atomic(
+            &self.path,
+            &ReservationFile {
+                leases: self.leases.clone(),
+            },
+        )
+    }
+}
+
+/// Read persisted leases (used by the CLI when no daemon answers).
+#[must_use]
+pub fn read_reservations(path: &Path) -> Vec<Reservation> {
+    fs::read(path)
+        .ok()
+        .and_then(|raw| serde_json::from_slice::<ReservationFile>(&raw).ok())
+        .map(|file| file.leases)
+        .unwrap_or_default()
+}
+
+/// Current wall-clock time in Unix seconds.
+#[must_use]
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger(dir: &Path) -> ReservationLedger {
+        ReservationLedger::load(dir.join("reservations.json"))
+    }
+
+    #[test]
+    fn granted_lease_is_consumed_as_the_mount_fills() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ledger = ledger(dir.path());
+        let id = ledger.create(
+            PathBuf::from("/data/build"),
+            PathBuf::from("/data"),
+            20,
+            60,
+            None,
+            1_000,
+        );
+
+        // Pending leases count in full.
+        assert_eq!(ledger.outstanding_for_mount(Path::new("/data"), 100), 20);
+        assert_eq!(ledger.outstanding_for_mount(Path::new("/"), 100), 0);
+
+        let second = ledger.create(
+            PathBuf::from("/data/other"),
+            PathBuf::from("/data"),
+            7,
+            60,
+            None,
+            1_000,
+        );
+        // Another pending lease does not count against this one's grant.
+        assert_eq!(ledger.committed_if_granted(&id, 100), Some(20));
+        assert_eq!(ledger.committed_if_granted("missing", 100), None);
+        assert!(ledger.release(&second).is_some());
+
+        assert!(ledger.grant(&id, 100));
+        assert!(!ledger.grant(&id, 100));
+        assert_eq!(ledger.outstanding_for_mount(Path::new("/data"), 100), 20);
+        // 15 bytes written since the grant: 5 still outstanding.
+        assert_eq!(ledger.outstanding_for_mount(Path::new("/data"), 85), 5);
+        // Fully written (or more) leaves nothing committed.
+        assert_eq!(ledger.outstanding_for_mount(Path::new("/data"), 50), 0);
+        // Space freed elsewhere never inflates the lease.
+        assert_eq!(ledger.outstanding_for_mount(Path::new("/data"), 150), 20);
+    }
+
+    #[test]
+    fn leases_expire_release_and_persist() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ledger = ledger(dir.path());
+        let short = ledger.create(
+            PathBuf::from("/data"),
+            PathBuf::from("/data"),
+            1,
+            10,
+            Some("cargo".to_string()),
+            1_000,
+        );
+        let long = ledger.create(
+            PathBuf::from("/data"),
+            PathBuf::from("/data"),
+            2,
+            100,
+            None,
+            1_000,
+        );
+        let early = ledger.create(PathBuf::from("/"), PathBuf::from("/"), 3, 100, None, 1_000);
+        assert_ne!(short, long);
+        assert_eq!(
+            ledger.pending_ids(),
+            vec![short.clone(), long.clone(), early.clone()]
+        );
+
+        assert_eq!(ledger.release(&early).map(|lease| lease.bytes), Some(3));
+        assert!(ledger.release(&early).is_none());
+
+        let expired = ledger.expire(1_010);
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].id, short);
+        assert_eq!(expired[0].holder.as_deref(), Some("cargo"));
+
+        ledger.persist().unwrap();
+        assert!(!dir.path().join("reservations.json.tmp").exists());
+        let reloaded = ReservationLedger::load(dir.path().join("reservations.json"));
+        assert_eq!(reloaded.leases(), ledger.leases());
+        assert!(reloaded.get(&long).is_some());
+
+        let status = reloaded.get(&long).unwrap().status_json(Some(10), 1_040);
+        assert_eq!(status["state"], "pending");
+        assert_eq!(status["outstanding_bytes"], 2);
+        assert_eq!(status["expires_in_secs"], 60);
+    }
+
+    #[test]
+    fn fits_keeps_committed_bytes_above_the_floor() {
+        assert!(fits(100, 30, 70));
+        assert!(!fits(100, 31, 70));
+        assert!(fits(10, 50, 0));
+        assert!(!fits(10, 50, 1));
+    }
+}
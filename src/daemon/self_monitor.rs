@@ -9,7 +9,7 @@
 #![allow(missing_docs)]
 #![allow(clippy::cast_precision_loss)]
 
-use std::fs::{self, OpenOptions};
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -18,6 +18,7 @@ use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
 
 use crate::core::config::TelemetryConfig;
+use crate::core::state_file::write_json_atomic;
 use crate::monitor::pid::PressureLevel;
 use crate::platform::pal::Platform;
 use crate::platform::types::SelfStats;
@@ -402,7 +403,7 @@ impl SelfMonitor {
             policy_mode: policy_mode.to_string(),
         };
 
-        let result = write_state_atomic(&self.state_file_path, &state);
+        let result = write_json_atomic(&self.state_file_path, &state);
         if let Err(e) = &result {
             eprintln!("[SBH-SELFMON] failed to write state file: {e}");
         }
@@ -548,48 +549,6 @@ fn parse_last_scan_instant(timestamp: &str) -> Option<Instant> {
     )
 }
 
-// ──────────────────── atomic state file write ────────────────────
-
-/// Write state.json atomically: write to .tmp, then rename.
-///
-/// Sets 0o644 permissions on the temp file (Unix only) so the state file is
-/// world-readable. The state file contains only operational telemetry (pressure
-/// levels, uptime, counters) and must be readable by the CLI running as a
-/// non-root user (e.g. `sbh status` run by ubuntu while daemon runs as root).
-fn write_state_atomic(path: &Path, state: &DaemonState) -> std::io::Result<()> {
-    let tmp_path = path.with_extension("json.tmp");
-
-    // Ensure parent directory exists.
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
-    }
-
-    let json = serde_json::to_string_pretty(state).map_err(std::io::Error::other)?;
-
-    let result = (|| {
-        {
-            use std::io::Write;
-            let mut opts = OpenOptions::new();
-            opts.write(true).create(true).truncate(true);
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::OpenOptionsExt as _;
-                opts.mode(0o644);
-            }
-            let mut file = opts.open(&tmp_path)?;
-            file.write_all(json.as_bytes())?;
-            file.sync_all()?;
-        }
-        fs::rename(&tmp_path, path)?;
-        Ok(())
-    })();
-
-    if result.is_err() {
-        let _ = fs::remove_file(&tmp_path);
-    }
-    result
-}
-
 fn empty_self_stats() -> SelfStats {
     SelfStats {
         rss_bytes: 0,
@@ -716,7 +675,7 @@ mod tests {
             policy_mode: String::new(),
         };
 
-        write_state_atomic(&path, &state).unwrap();
+        write_json_atomic(&path, &state).unwrap();
         assert!(path.exists());
 
         // No temp file left behind.
@@ -767,7 +726,7 @@ mod tests {
             policy_mode: String::new(),
         };
 
-        write_state_atomic(&path, &state).unwrap();
+        write_json_atomic(&path, &state).unwrap();
 
         let mode = fs::metadata(&path).unwrap().permissions().mode() & 0o777;
         assert_eq!(
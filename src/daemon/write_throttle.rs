@@ -0,0 +1,471 @@
+//! Cgroup write throttling of runaway writers.
+//!
+//! When a volume sits at Red/Critical pressure, the behavior matrix asks for
+//! it, and the policy engine is in enforce mode, the daemon caps the write
+//! bandwidth of the cgroup writing fastest with a cgroup v2 `io.max` limit on
+//! that volume's device. Deleting artifacts cannot outrun a build writing
+//! hundreds of MB/s; slowing the writer buys the cleanup pipeline time.
+//!
+//! Write rates come from the same per-cgroup `io.stat` counters `sbh blame
+//! --group-by cgroup` reports, differenced between samples. Writers are
+//! ranked by what they wrote to the pressured volume's device only, since
+//! that is the device the limit applies to. Cgroups listed in
+//! `policy.write_throttle.protected_cgroups` are never throttled; vetoes are
+//! logged once per pressure episode.
+//!
+//! Limits are lifted when the volume drops below Red, when throttling stops
+//! being allowed, and on shutdown. Lifting restores whatever `wbps` limit the
+//! cgroup had before, so an operator's own `io.max` setting survives. A cgroup
+//! already limited at or below the throttle is left alone. Active limits and
+//! the limits they replaced live in `write-throttles.json` next to the state
+//! file so a daemon restarted after a crash can restore them.
+
+#![allow(missing_docs)]
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::state_file::write_json_atomic;
+use crate::daemon::policy::{WriteThrottleConfig, cgroup_is_protected};
+use crate::platform::types::CgroupIo;
+
+/// Minimum spacing between cgroup write-rate samples.
+pub const WRITE_THROTTLE_SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// One applied `io.max` write limit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WriteThrottle {
+    pub cgroup: String,
+    /// Mount whose backing device carries the limit.
+    pub mount: PathBuf,
+    pub limit_bytes_per_sec: u64,
+    /// The cgroup's own `wbps` limit on the device before sbh throttled it;
+    /// restored when the throttle is lifted. `None` means unlimited.
+    #[serde(default)]
+    pub previous_limit_bytes_per_sec: Option<u64>,
+    /// Write rate that triggered the throttle.
+    pub observed_bytes_per_sec: u64,
+    /// Unix seconds.
+    pub applied_at: u64,
+}
+
+/// Bytes a cgroup wrote between two samples.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CgroupWriteRate {
+    pub cgroup: String,
+    pub bytes_written: u64,
+    pub window: Duration,
+    pub bytes_per_sec: u64,
+    /// `bytes_written` split by block device (`MAJ:MIN`).
+    pub bytes_written_by_device: BTreeMap<String, u64>,
+}
+
+impl CgroupWriteRate {
+    fn new(cgroup: String, bytes_written: u64, window: Duration) -> Self {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_precision_loss,
+            clippy::cast_sign_loss
+        )]
+        let bytes_per_sec = (bytes_written as f64 / window.as_secs_f64()) as u64;
+        Self {
+            cgroup,
+            bytes_written,
+            window,
+            bytes_per_sec,
+            bytes_written_by_device: BTreeMap::new(),
+        }
+    }
+
+    /// The rate counting only writes to `device` (`MAJ:MIN`).
+    #[must_use]
+    pub fn on_device(&self, device: &str) -> Self {
+        let bytes_written = self
+            .bytes_written_by_device
+            .get(device)
+            .copied()
+            .unwrap_or(0);
+        let mut rate = Self::new(self.cgroup.clone(), bytes_written, self.window);
+        rate.bytes_written_by_device = BTreeMap::from([(device.to_string(), bytes_written)]);
+        rate
+    }
+}
+
+/// The writer to act on (if any) plus the faster writers the protected list
+/// vetoed on the way to it.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct WriterSelection {
+    pub target: Option<CgroupWriteRate>,
+    pub vetoed: Vec<CgroupWriteRate>,
+}
+
+/// Pick the fastest writer above `min_writer_bytes_per_sec` that is neither
+/// protected nor already in `throttled`.
+#[must_use]
+pub fn select_throttle_target(
+    rates: &[CgroupWriteRate],
+    config: &WriteThrottleConfig,
+    throttled: &[&str],
+) -> WriterSelection {
+    select_top_writer(
+        rates,
+        config.min_writer_bytes_per_sec,
+        &config.protected_cgroups,
+        throttled,
+    )
+}
+
+/// Pick the fastest writer at or above `min_bytes_per_sec` that is not in
+/// `skip`; faster writers covered by `protected` are reported as vetoed.
+#[must_use]
+pub fn select_top_writer(
+    rates: &[CgroupWriteRate],
+    min_bytes_per_sec: u64,
+    protected: &[String],
+    skip: &[&str],
+) -> WriterSelection {
+    let mut ranked: Vec<&CgroupWriteRate> = rates
+        .iter()
+        .filter(|rate| rate.bytes_per_sec >= min_bytes_per_sec)
+        .filter(|rate| !skip.contains(&rate.cgroup.as_str()))
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.bytes_per_sec
+            .cmp(&a.bytes_per_sec)
+            .then_with(|| a.cgroup.cmp(&b.cgroup))
+    });
+
+    let mut selection = WriterSelection::default();
+    for rate in ranked {
+        if cgroup_is_protected(protected, &rate.cgroup) {
+            selection.vetoed.push(rate.clone());
+        } else {
+            selection.target = Some(rate.clone());
+            break;
+        }
+    }
+    selection
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ThrottleFile {
+    throttles: Vec<WriteThrottle>,
+}
+
+/// Active throttles plus the previous write-counter sample.
+#[derive(Debug)]
+pub struct WriteThrottler {
+    path: PathBuf,
+    active: Vec<WriteThrottle>,
+    last_totals: HashMap<String, CgroupIo>,
+    last_sampled_at: Option<Instant>,
+    notices: HashSet<String>,
+}
+
+impl WriteThrottler {
+    /// Load throttles persisted by a previous daemon. A missing or unreadable
+    /// file starts empty.
+    #[must_use]
+    pub fn load(path: PathBuf) -> Self {
+        let active = fs::read(&path)
+            .ok()
+            .and_then(|raw| serde_json::from_slice::<ThrottleFile>(&raw).ok())
+            .map(|file| file.throttles)
+            .unwrap_or_default();
+        Self {
+            path,
+            active,
+            last_totals: HashMap::new(),
+            last_sampled_at: None,
+            notices: HashSet::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn active(&self) -> &[WriteThrottle] {
+        &self.active
+    }
+
+    /// Cgroups already throttled on `mount`.
+    #[must_use]
+    pub fn throttled_on(&self, mount: &Path) -> Vec<&str> {
+        self.active
+            .iter()
+            .filter(|throttle| throttle.mount == mount)
+            .map(|throttle| throttle.cgroup.as_str())
+            .collect()
+    }
+
+    #[must_use]
+    pub fn sample_due(&self, now: Instant) -> bool {
+        self.last_sampled_at
+            .is_none_or(|at| now.saturating_duration_since(at) >= WRITE_THROTTLE_SAMPLE_INTERVAL)
+    }
+
+    /// Record cumulative per-cgroup write counters and return each cgroup's
+    /// rate since the previous sample. The first sample yields no rates.
+    pub fn observe(
+        &mut self,
+        totals: BTreeMap<String, CgroupIo>,
+        now: Instant,
+    ) -> Vec<CgroupWriteRate> {
+        let window = self
+            .last_sampled_at
+            .map(|at| now.saturating_duration_since(at));
+        self.last_sampled_at = Some(now);
+        let previous = std::mem::take(&mut self.last_totals);
+
+        let mut rates = Vec::new();
+        if let Some(window) = window.filter(|window| !window.is_zero()) {
+            for (cgroup, total) in &totals {
+                let Some(before) = previous.get(cgroup) else {
+                    continue;
+                };
+                let mut rate = CgroupWriteRate::new(
+                    cgroup.clone(),
+                    total.bytes_written.saturating_sub(before.bytes_written),
+                    window,
+                );
+                rate.bytes_written_by_device = total
+                    .bytes_written_by_device
+                    .iter()
+                    .map(|(device, written)| {
+                        let earlier = before
+                            .bytes_written_by_device
+                            .get(device)
+                            .copied()
+                            .unwrap_or(0);
+                        (device.clone(), written.saturating_sub(earlier))
+                    })
+                    .collect();
+                rates.push(rate);
+            }
+        }
+        self.last_totals = totals.into_iter().collect();
+        rates
+    }
+
+    /// Forget samples and logged notices once no volume needs throttling, so
+    /// the next pressure episode starts fresh.
+    pub fn reset_observations(&mut self) {
+        self.last_totals.clear();
+        self.last_sampled_at = None;
+        self.notices.clear();
+    }
+
+    /// True the first time `key` comes up in this pressure episode; keeps
+    /// veto records and apply failures from repeating every sample.
+    pub fn first_notice(&mut self, key: &str) -> bool {
+        self.notices.insert(key.to_string())
+    }
+
+    pub fn record(&mut self, throttle: WriteThrottle) {
+        self.active.push(throttle);
+    }
+
+    /// Remove and return every throttle for which `keep` is false.
+    pub fn release_unless(&mut self, keep: impl Fn(&WriteThrottle) -> bool) -> Vec<WriteThrottle> {
+        let (kept, released): (Vec<_>, Vec<_>) = std::mem::take(&mut self.active)
+            .into_iter()
+            .partition(|throttle| keep(throttle));
+        self.active = kept;
+        released
+    }
+
+    /// Write the active throttles atomically (`.tmp` + rename, mode 0644).
+    pub fn persist(&self) -> io::Result<()> {
+        write_json_atomic(
+            &self.path,
+            &ThrottleFile {
+                throttles: self.active.clone(),
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate(cgroup: &str, bytes_per_sec: u64) -> CgroupWriteRate {
+        CgroupWriteRate::new(
+            cgroup.to_string(),
+            bytes_per_sec * 5,
+            Duration::from_secs(5),
+        )
+    }
+
+    fn io(by_device: &[(&str, u64)]) -> CgroupIo {
+        let bytes_written_by_device: BTreeMap<String, u64> = by_device
+            .iter()
+            .map(|(device, written)| ((*device).to_string(), *written))
+            .collect();
+        CgroupIo {
+            bytes_read: 0,
+            bytes_written: bytes_written_by_device.values().sum(),
+            bytes_written_by_device,
+        }
+    }
+
+    fn config() -> WriteThrottleConfig {
+        WriteThrottleConfig {
+            min_writer_bytes_per_sec: 100,
+            protected_cgroups: vec!["/system.slice/postgresql.service".to_string()],
+            ..WriteThrottleConfig::default()
+        }
+    }
+
+    #[test]
+    fn protected_cgroups_cover_descendants_but_not_siblings() {
+        let config = config();
+        assert!(config.is_protected("/system.slice/postgresql.service"));
+        assert!(config.is_protected("/system.slice/postgresql.service/worker"));
+        assert!(!config.is_protected("/system.slice/postgresql.service-backup"));
+        assert!(!config.is_protected("/user.slice/build.scope"));
+        assert!(config.is_protected("/"));
+    }
+
+    #[test]
+    fn selection_skips_slow_protected_and_already_throttled_writers() {
+        let rates = [
+            rate("/system.slice/postgresql.service", 900),
+            rate("/user.slice/agent-a.scope", 500),
+            rate("/user.slice/agent-b.scope", 400),
+            rate("/user.slice/idle.scope", 10),
+        ];
+        let selection = select_throttle_target(&rates, &config(), &[]);
+        assert_eq!(
+            selection.target.map(|rate| rate.cgroup),
+            Some("/user.slice/agent-a.scope".to_string())
+        );
+        assert_eq!(selection.vetoed.len(), 1);
+        assert_eq!(
+            selection.vetoed[0].cgroup,
+            "/system.slice/postgresql.service"
+        );
+
+        let selection = select_throttle_target(&rates, &config(), &["/user.slice/agent-a.scope"]);
+        assert_eq!(
+            selection.target.map(|rate| rate.cgroup),
+            Some("/user.slice/agent-b.scope".to_string())
+        );
+
+        let slow = [rate("/user.slice/idle.scope", 10)];
+        assert_eq!(
+            select_throttle_target(&slow, &config(), &[]),
+            WriterSelection::default()
+        );
+    }
+
+    #[test]
+    fn observe_differences_counters_between_samples() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut throttler = WriteThrottler::load(dir.path().join("write-throttles.json"));
+        let start = Instant::now();
+        assert!(throttler.sample_due(start));
+
+        let first = throttler.observe(
+            BTreeMap::from([("/a".to_string(), io(&[("8:0", 1_000)]))]),
+            start,
+        );
+        assert!(first.is_empty());
+        assert!(!throttler.sample_due(start + Duration::from_secs(1)));
+
+        let later = start + Duration::from_secs(5);
+        let rates = throttler.observe(
+            BTreeMap::from([
+                ("/a".to_string(), io(&[("8:0", 6_000), ("259:0", 500)])),
+                ("/new".to_string(), io(&[("8:0", 50)])),
+            ]),
+            later,
+        );
+        assert_eq!(rates.len(), 1);
+        assert_eq!(rates[0].cgroup, "/a");
+        assert_eq!(rates[0].bytes_written, 5_500);
+        assert_eq!(rates[0].window, Duration::from_secs(5));
+        assert_eq!(rates[0].bytes_per_sec, 1_100);
+        assert_eq!(
+            rates[0].bytes_written_by_device,
+            BTreeMap::from([("8:0".to_string(), 5_000), ("259:0".to_string(), 500)])
+        );
+
+        assert!(throttler.first_notice("veto:/a"));
+        assert!(!throttler.first_notice("veto:/a"));
+        throttler.reset_observations();
+        assert!(throttler.first_notice("veto:/a"));
+        assert!(throttler.sample_due(later));
+    }
+
+    #[test]
+    fn writers_are_ranked_by_writes_to_the_pressured_device() {
+        let window = Duration::from_secs(5);
+        let mut busy_elsewhere =
+            CgroupWriteRate::new("/user.slice/a.scope".to_string(), 5_000, window);
+        busy_elsewhere.bytes_written_by_device =
+            BTreeMap::from([("259:0".to_string(), 4_900), ("8:0".to_string(), 100)]);
+        let mut busy_here = CgroupWriteRate::new("/user.slice/b.scope".to_string(), 1_000, window);
+        busy_here.bytes_written_by_device = BTreeMap::from([("8:0".to_string(), 1_000)]);
+        let rates = [busy_elsewhere, busy_here];
+
+        let on_sda: Vec<_> = rates.iter().map(|rate| rate.on_device("8:0")).collect();
+        assert_eq!(on_sda[0].bytes_per_sec, 20);
+        assert_eq!(on_sda[1].bytes_per_sec, 200);
+        let selection = select_throttle_target(&on_sda, &config(), &[]);
+        assert_eq!(
+            selection.target.map(|rate| rate.cgroup),
+            Some("/user.slice/b.scope".to_string())
+        );
+
+        let on_nvme: Vec<_> = rates.iter().map(|rate| rate.on_device("259:0")).collect();
+        let selection = select_throttle_target(&on_nvme, &config(), &[]);
+        assert_eq!(
+            selection.target.map(|rate| rate.cgroup),
+            Some("/user.slice/a.scope".to_string())
+        );
+    }
+
+    #[test]
+    fn throttles_persist_and_release_by_mount() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("write-throttles.json");
+        let mut throttler = WriteThrottler::load(path.clone());
+        for (cgroup, mount, previous) in [("/a", "/data", None), ("/b", "/", Some(4_096))] {
+            throttler.record(WriteThrottle {
+                cgroup: cgroup.to_string(),
+                mount: PathBuf::from(mount),
+                limit_bytes_per_sec: 10,
+                previous_limit_bytes_per_sec: previous,
+                observed_bytes_per_sec: 1_000,
+                applied_at: 1_000,
+            });
+        }
+        throttler.persist().unwrap();
+        assert!(!dir.path().join("write-throttles.json.tmp").exists());
+
+        let mut reloaded = WriteThrottler::load(path);
+        assert_eq!(reloaded.active(), throttler.active());
+        assert_eq!(reloaded.throttled_on(Path::new("/data")), vec!["/a"]);
+
+        let released = reloaded.release_unless(|throttle| throttle.mount == Path::new("/"));
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].cgroup, "/a");
+        assert_eq!(reloaded.active().len(), 1);
+        assert_eq!(
+            reloaded.active()[0].previous_limit_bytes_per_sec,
+            Some(4_096)
+        );
+
+        // Files written before the previous limit was recorded still load.
+        let legacy = r#"{"throttles":[{"cgroup":"/c","mount":"/","limit_bytes_per_sec":10,
+            "observed_bytes_per_sec":1000,"applied_at":1}]}"#;
+        let legacy_path = dir.path().join("legacy.json");
+        fs::write(&legacy_path, legacy).unwrap();
+        let legacy = WriteThrottler::load(legacy_path);
+        assert_eq!(legacy.active()[0].previous_limit_bytes_per_sec, None);
+    }
+}
@@ -20,7 +20,9 @@ use crate::monitor::guardrails::{
 use crate::scanner::decision_record::{
     ActionRecord, DecisionRecordBuilder, ExplainLevel, PolicyMode, format_explain,
 };
-use crate::scanner::patterns::{ArtifactCategory, ArtifactClassification, StructuralSignals};
+use crate::scanner::patterns::{
+    ArtifactCategory, ArtifactClassification, SiblingManifests, StructuralSignals,
+};
 use crate::scanner::scoring::{
     ActiveReferenceSummary, CandidacyScore, CandidateInput, DecisionAction, DecisionOutcome,
     EvidenceLedger, EvidenceTerm, ScoreFactors, ScoringEngine,
@@ -103,10 +105,14 @@ fn make_candidate(
             has_git: false,
             has_cargo_toml: false,
             mostly_object_files: rng.next_f64() > 0.4,
+            has_jvm_outputs: false,
+            sibling_manifests: SiblingManifests::default(),
         },
         active_references: ActiveReferenceSummary::default(),
         is_open: false,
         excluded: false,
+        entry_count: 0,
+        shared_bytes: 0,
     }
 }
 
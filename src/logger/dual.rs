@@ -19,12 +19,16 @@ use crate::logger::jsonl::{
 };
 #[cfg(feature = "sqlite")]
 use crate::logger::sqlite::{ActivityRow, PressureRow, SqliteLogger};
+use crate::scanner::decision_record::{DecisionRecord, decision_summary_line};
 
 // ──────────────────── channel capacity ────────────────────
 
 /// Default bounded channel capacity for log events.
 const CHANNEL_CAPACITY: usize = 1024;
 
+/// Days of `activity_log` and `pressure_history` rows kept in SQLite.
+pub const ACTIVITY_RETENTION_DAYS: u32 = 30;
+
 // ──────────────────── public event type ────────────────────
 
 /// Structured scan-completion fields used for scanner rollout validation.
@@ -86,12 +90,26 @@ pub enum ActivityEvent {
         pressure: String,
         free_pct: f64,
         duration_ms: u64,
+        /// Growth in the filesystem's available bytes across the deletion, when
+        /// it could be measured. `size_bytes` is the pre-deletion estimate.
+        measured_freed_bytes: Option<u64>,
     },
     ArtifactDeletionFailed {
         path: String,
         error_code: String,
         error_message: String,
     },
+    /// Cold files below `path` replaced by verified `.zst` copies.
+    ArtifactCompressed {
+        path: String,
+        files: usize,
+        bytes_before: u64,
+        bytes_after: u64,
+        score: f64,
+        pressure: String,
+        free_pct: f64,
+        duration_ms: u64,
+    },
     ScanCompleted {
         paths_scanned: usize,
         candidates_found: usize,
@@ -112,6 +130,10 @@ pub enum ActivityEvent {
         details: String,
         free_pct: f64,
     },
+    /// Policy evidence record for a single evaluated candidate (`sbh explain`).
+    DecisionRecorded {
+        record: Box<DecisionRecord>,
+    },
     /// Sentinel to request graceful shutdown of the logger thread.
     Shutdown,
 }
@@ -229,8 +251,6 @@ fn logger_thread_main(
     // Retention: prune old rows every PRUNE_INTERVAL events (~1 hour at typical rates).
     #[cfg(feature = "sqlite")]
     const PRUNE_INTERVAL: u64 = 3600;
-    #[cfg(feature = "sqlite")]
-    const RETENTION_DAYS: u32 = 30;
 
     // Open backends.
     #[cfg(feature = "sqlite")]
@@ -316,8 +336,8 @@ fn logger_thread_main(
                 if events_since_prune >= PRUNE_INTERVAL {
                     events_since_prune = 0;
                     if let Some(db) = &sqlite {
-                        let _ = db.prune_pressure_history(RETENTION_DAYS);
-                        let _ = db.prune_activity_log(RETENTION_DAYS);
+                        let _ = db.prune_pressure_history(ACTIVITY_RETENTION_DAYS);
+                        let _ = db.prune_activity_log(ACTIVITY_RETENTION_DAYS);
                     }
                 }
             } else {
@@ -368,6 +388,15 @@ fn scan_completion_details(
     )
 }
 
+/// Reclaim estimate beside the measured free-space delta.
+fn reclaim_details(estimated_bytes: u64, measured_freed_bytes: u64) -> String {
+    format!("estimated={estimated_bytes} statvfs_delta={measured_freed_bytes}")
+}
+
+fn compression_details(files: usize, bytes_before: u64, bytes_after: u64) -> String {
+    format!("files={files} original={bytes_before} compressed={bytes_after}")
+}
+
 #[allow(clippy::too_many_lines)]
 fn event_to_log_entry(event: &ActivityEvent) -> LogEntry {
     match event {
@@ -440,6 +469,7 @@ fn event_to_log_entry(event: &ActivityEvent) -> LogEntry {
             pressure,
             free_pct,
             duration_ms,
+            measured_freed_bytes,
         } => {
             let mut e = LogEntry::new(EventType::ArtifactDelete, Severity::Info);
             e.path = Some(path.clone());
@@ -450,6 +480,28 @@ fn event_to_log_entry(event: &ActivityEvent) -> LogEntry {
             e.free_pct = Some(*free_pct);
             e.duration_ms = Some(*duration_ms);
             e.ok = Some(true);
+            e.details = measured_freed_bytes.map(|measured| reclaim_details(*size_bytes, measured));
+            e
+        }
+        ActivityEvent::ArtifactCompressed {
+            path,
+            files,
+            bytes_before,
+            bytes_after,
+            score,
+            pressure,
+            free_pct,
+            duration_ms,
+        } => {
+            let mut e = LogEntry::new(EventType::ArtifactCompress, Severity::Info);
+            e.path = Some(path.clone());
+            e.size = Some(bytes_before.saturating_sub(*bytes_after));
+            e.score = Some(*score);
+            e.pressure = Some(pressure.clone());
+            e.free_pct = Some(*free_pct);
+            e.duration_ms = Some(*duration_ms);
+            e.ok = Some(true);
+            e.details = Some(compression_details(*files, *bytes_before, *bytes_after));
             e
         }
         ActivityEvent::ArtifactDeletionFailed {
@@ -505,6 +557,15 @@ fn event_to_log_entry(event: &ActivityEvent) -> LogEntry {
             e.free_pct = Some(*free_pct);
             e
         }
+        ActivityEvent::DecisionRecorded { record } => {
+            let mut e = LogEntry::new(EventType::Decision, Severity::Info);
+            e.path = Some(record.path.to_string_lossy().into_owned());
+            e.size = Some(record.size_bytes);
+            e.score = Some(record.total_score);
+            e.details = Some(decision_summary_line(record));
+            e.ok = Some(true);
+            e
+        }
         ActivityEvent::Shutdown => {
             // Should not reach here; handled above.
             LogEntry::new(EventType::DaemonStop, Severity::Info)
@@ -545,6 +606,7 @@ fn event_to_activity_row(event: &ActivityEvent) -> Option<ActivityRow> {
             pressure,
             free_pct,
             duration_ms,
+            measured_freed_bytes,
         } => Some(ActivityRow {
             timestamp: ts,
             event_type: "artifact_delete".to_string(),
@@ -559,7 +621,34 @@ fn event_to_activity_row(event: &ActivityEvent) -> Option<ActivityRow> {
             success: 1,
             error_code: None,
             error_message: None,
-            details: None,
+            details: measured_freed_bytes.map(|measured| reclaim_details(*size_bytes, measured)),
+        }),
+        ActivityEvent::ArtifactCompressed {
+            path,
+            files,
+            bytes_before,
+            bytes_after,
+            score,
+            pressure,
+            free_pct,
+            duration_ms,
+        } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "artifact_compress".to_string(),
+            severity: "info".to_string(),
+            path: Some(path.clone()),
+            size_bytes: Some(
+                i64::try_from(bytes_before.saturating_sub(*bytes_after)).unwrap_or(i64::MAX),
+            ),
+            score: Some(*score),
+            score_factors: None,
+            pressure_level: Some(pressure.clone()),
+            free_pct: Some(*free_pct),
+            duration_ms: Some(i64::try_from(*duration_ms).unwrap_or(i64::MAX)),
+            success: 1,
+            error_code: None,
+            error_message: None,
+            details: Some(compression_details(*files, *bytes_before, *bytes_after)),
         }),
         ActivityEvent::ArtifactDeletionFailed {
             path,
@@ -707,6 +796,24 @@ fn event_to_activity_row(event: &ActivityEvent) -> Option<ActivityRow> {
             error_message: None,
             details: Some(details.clone()),
         }),
+        // The full record goes into `details` so `sbh explain` can rebuild it
+        // with `parse_decision_from_details`.
+        ActivityEvent::DecisionRecorded { record } => Some(ActivityRow {
+            timestamp: ts,
+            event_type: "decision".to_string(),
+            severity: "info".to_string(),
+            path: Some(record.path.to_string_lossy().into_owned()),
+            size_bytes: Some(i64::try_from(record.size_bytes).unwrap_or(i64::MAX)),
+            score: Some(record.total_score),
+            score_factors: serde_json::to_string(&record.factors).ok(),
+            pressure_level: None,
+            free_pct: None,
+            duration_ms: None,
+            success: 1,
+            error_code: None,
+            error_message: None,
+            details: Some(record.to_json_compact()),
+        }),
         // Events that only need JSONL logging (pressure goes to pressure_history table).
         _ => None,
     }
@@ -778,6 +885,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn artifact_deleted_records_estimate_beside_measured_delta() {
+        let deleted = |measured_freed_bytes| ActivityEvent::ArtifactDeleted {
+            path: "/data/projects/foo/target".to_string(),
+            size_bytes: 1_000,
+            score: 0.9,
+            factors: ScoreFactorsRecord {
+                location: 0.8,
+                name: 0.9,
+                age: 1.0,
+                size: 0.5,
+                structure: 0.9,
+            },
+            pressure: "red".to_string(),
+            free_pct: 4.0,
+            duration_ms: 12,
+            measured_freed_bytes,
+        };
+
+        let measured = deleted(Some(4_096));
+        let expected = Some("estimated=1000 statvfs_delta=4096".to_string());
+        assert_eq!(event_to_log_entry(&measured).details, expected);
+        assert_eq!(event_to_activity_row(&measured).unwrap().details, expected);
+
+        let unmeasured = deleted(None);
+        assert_eq!(event_to_log_entry(&unmeasured).details, None);
+        assert_eq!(event_to_activity_row(&unmeasured).unwrap().details, None);
+    }
+
+    #[test]
+    fn artifact_compressed_records_saved_bytes() {
+        let event = ActivityEvent::ArtifactCompressed {
+            path: "/tmp/agent-run/logs".to_string(),
+            files: 3,
+            bytes_before: 10_000,
+            bytes_after: 2_500,
+            score: 0.7,
+            pressure: "yellow".to_string(),
+            free_pct: 18.0,
+            duration_ms: 40,
+        };
+        let expected = Some("files=3 original=10000 compressed=2500".to_string());
+
+        let entry = event_to_log_entry(&event);
+        assert_eq!(entry.size, Some(7_500));
+        assert_eq!(entry.ok, Some(true));
+        assert_eq!(entry.details, expected);
+
+        let row = event_to_activity_row(&event).unwrap();
+        assert_eq!(row.event_type, "artifact_compress");
+        assert_eq!(row.size_bytes, Some(7_500));
+        assert_eq!(row.details, expected);
+    }
+
     #[test]
     fn spawn_and_shutdown() {
         let dir = tempfile::tempdir().unwrap();
@@ -824,6 +985,7 @@ mod tests {
             pressure: "orange".to_string(),
             free_pct: 8.3,
             duration_ms: 145,
+            measured_freed_bytes: Some(2_950_000_000),
         });
         handle.shutdown();
         join.join().unwrap();
@@ -845,6 +1007,80 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn decision_record_roundtrips_through_sqlite_details() {
+        use crate::logger::sqlite::DecisionQuery;
+        use crate::scanner::decision_record::{
+            ActionRecord, ClassificationRecord, FactorsRecord, PolicyMode,
+            parse_decision_from_details,
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let (handle, join) = spawn_logger(test_config(dir.path())).unwrap();
+        let record = DecisionRecord {
+            decision_id: 7,
+            trace_id: "sbh-00000007".to_string(),
+            timestamp: "2026-02-14T16:30:00.000Z".to_string(),
+            policy_mode: PolicyMode::Shadow,
+            path: std::path::PathBuf::from("/data/projects/foo/target"),
+            size_bytes: 2_000_000_000,
+            age_secs: 7200,
+            classification: ClassificationRecord {
+                pattern_name: "target".to_string(),
+                category: "RustTarget".to_string(),
+                combined_confidence: 0.9,
+            },
+            factors: FactorsRecord {
+                location: 0.8,
+                name: 0.9,
+                age: 1.0,
+                size: 0.7,
+                structure: 0.9,
+                pressure_multiplier: 1.0,
+            },
+            factor_contributions: Vec::new(),
+            total_score: 1.9,
+            posterior_abandoned: 0.8,
+            expected_loss_keep: 8.0,
+            expected_loss_delete: 2.0,
+            calibration_score: 0.85,
+            fallback_active: false,
+            fallback_reason: None,
+            vetoed: false,
+            veto_reason: None,
+            action: ActionRecord::Delete,
+            effective_action: Some(ActionRecord::Keep),
+            guard_status: None,
+            comparator_action: Some(ActionRecord::Delete),
+            summary: "action=Delete".to_string(),
+        };
+        handle.send(ActivityEvent::DecisionRecorded {
+            record: Box::new(record),
+        });
+        handle.shutdown();
+        join.join().unwrap();
+
+        let contents = std::fs::read_to_string(dir.path().join("test.jsonl")).unwrap();
+        assert!(contents.contains("\"event\":\"decision\""));
+
+        let db = SqliteLogger::open(&dir.path().join("test.db")).unwrap();
+        let rows = db
+            .decision_rows(&DecisionQuery {
+                decision_id: Some(7),
+                limit: 10,
+                ..DecisionQuery::default()
+            })
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        let parsed = parse_decision_from_details(&rows[0].details).unwrap();
+        assert_eq!(
+            parsed.path,
+            std::path::PathBuf::from("/data/projects/foo/target")
+        );
+        assert_eq!(parsed.effective_action, Some(ActionRecord::Keep));
+    }
+
     #[test]
     fn handles_cloneable_and_send() {
         let dir = tempfile::tempdir().unwrap();
@@ -1113,6 +1349,7 @@ mod tests {
             pressure: "red".to_string(),
             free_pct: 3.2,
             duration_ms: 200,
+            measured_freed_bytes: None,
         });
         handle.send(ActivityEvent::ArtifactDeletionFailed {
             path: "/data/protected/.target".to_string(),
@@ -35,6 +35,7 @@ pub enum Severity {
 #[serde(rename_all = "snake_case")]
 pub enum EventType {
     ArtifactDelete,
+    ArtifactCompress,
     BallastRelease,
     BallastReplenish,
     BallastProvision,
@@ -46,6 +47,7 @@ pub enum EventType {
     Info,
     Error,
     Emergency,
+    Decision,
 }
 
 /// A single JSONL log entry — all fields optional except `ts`, `event`, `severity`.
@@ -959,6 +961,7 @@ mod tests {
 
         let event_types = [
             EventType::ArtifactDelete,
+            EventType::ArtifactCompress,
             EventType::BallastRelease,
             EventType::BallastReplenish,
             EventType::BallastProvision,
@@ -970,6 +973,7 @@ mod tests {
             EventType::Info,
             EventType::Error,
             EventType::Emergency,
+            EventType::Decision,
         ];
 
         for et in &event_types {
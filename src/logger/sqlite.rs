@@ -205,6 +205,44 @@ impl SqliteLogger {
         Ok(deleted)
     }
 
+    // ──────────────────── decisions ────────────────────
+
+    /// Query persisted policy decision records, newest first.
+    ///
+    /// Unset filters in `query` match everything. A path filter matches the
+    /// path itself and anything beneath it.
+    pub fn decision_rows(&self, query: &DecisionQuery) -> Result<Vec<DecisionRow>> {
+        let decision_id = query
+            .decision_id
+            .map(|id| i64::try_from(id).unwrap_or(i64::MAX));
+        let path = query
+            .path
+            .as_deref()
+            .map(|p| p.trim_end_matches('/'))
+            .map(|p| if p.is_empty() { "/" } else { p });
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT timestamp, details FROM activity_log
+             WHERE event_type = 'decision' AND details IS NOT NULL
+               AND (?1 IS NULL OR json_extract(details, '$.decision_id') = ?1)
+               AND (?2 IS NULL OR ?2 = '/' OR path = ?2
+                    OR substr(path, 1, length(?2) + 1) = ?2 || '/')
+               AND (?3 IS NULL OR timestamp >= ?3)
+             ORDER BY id DESC LIMIT ?4",
+        )?;
+        let rows = stmt
+            .query_map(
+                params![decision_id, path, query.since, query.limit],
+                |row| {
+                    Ok(DecisionRow {
+                        timestamp: row.get(0)?,
+                        details: row.get(1)?,
+                    })
+                },
+            )?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
     // ──────────────────── ballast_inventory ────────────────────
 
     /// Upsert a ballast file record.
@@ -322,6 +360,27 @@ pub struct PressureRow {
     pub pid_output: Option<f64>,
 }
 
+/// Filter for [`SqliteLogger::decision_rows`].
+#[derive(Debug, Clone, Default)]
+pub struct DecisionQuery {
+    /// Match `DecisionRecord::decision_id`.
+    pub decision_id: Option<u64>,
+    /// Match the candidate path or any path beneath it.
+    pub path: Option<String>,
+    /// Only rows with `timestamp >= since` (RFC 3339).
+    pub since: Option<String>,
+    /// Maximum number of rows returned.
+    pub limit: u32,
+}
+
+/// A persisted decision record as stored in `activity_log`.
+#[derive(Debug, Clone)]
+pub struct DecisionRow {
+    pub timestamp: String,
+    /// Serialized `DecisionRecord` JSON.
+    pub details: String,
+}
+
 /// Row for the `ballast_inventory` table.
 #[derive(Debug, Clone)]
 pub struct BallastRow {
@@ -562,6 +621,76 @@ mod tests {
         assert_eq!(freed, 15_000_000); // 1+2+3+4+5 million
     }
 
+    #[test]
+    fn decision_rows_filter_by_id_path_and_time() {
+        let (_dir, logger) = temp_db();
+        let entries = [
+            (1_u64, "2026-02-14T10:00:00Z", "/data/projects/a/target"),
+            (2, "2026-02-14T11:00:00Z", "/data/projects/a/target/debug"),
+            (3, "2026-02-14T12:00:00Z", "/data/projects/ab/target"),
+        ];
+        for (id, ts, path) in entries {
+            logger
+                .log_activity(&ActivityRow {
+                    timestamp: ts.to_string(),
+                    event_type: "decision".to_string(),
+                    severity: "info".to_string(),
+                    path: Some(path.to_string()),
+                    size_bytes: None,
+                    score: None,
+                    score_factors: None,
+                    pressure_level: None,
+                    free_pct: None,
+                    duration_ms: None,
+                    success: 1,
+                    error_code: None,
+                    error_message: None,
+                    details: Some(format!(
+                        r#"{{"decision_id":{id},"trace_id":"sbh-{id:08x}"}}"#
+                    )),
+                })
+                .unwrap();
+        }
+
+        let all = logger
+            .decision_rows(&DecisionQuery {
+                limit: 10,
+                ..DecisionQuery::default()
+            })
+            .unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].timestamp, "2026-02-14T12:00:00Z");
+
+        let by_id = logger
+            .decision_rows(&DecisionQuery {
+                decision_id: Some(2),
+                limit: 10,
+                ..DecisionQuery::default()
+            })
+            .unwrap();
+        assert_eq!(by_id.len(), 1);
+        assert!(by_id[0].details.contains("\"decision_id\":2"));
+
+        // Path filter matches the subtree but not a sibling sharing a prefix.
+        let by_path = logger
+            .decision_rows(&DecisionQuery {
+                path: Some("/data/projects/a/".to_string()),
+                limit: 10,
+                ..DecisionQuery::default()
+            })
+            .unwrap();
+        assert_eq!(by_path.len(), 2);
+
+        let since = logger
+            .decision_rows(&DecisionQuery {
+                since: Some("2026-02-14T10:30:00Z".to_string()),
+                limit: 10,
+                ..DecisionQuery::default()
+            })
+            .unwrap();
+        assert_eq!(since.len(), 2);
+    }
+
     #[test]
     fn rapid_inserts_no_data_loss() {
         let (_dir, logger) = temp_db();
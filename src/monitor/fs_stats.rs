@@ -269,6 +269,8 @@ mod tests {
             fs_type: "tmpfs".to_string(),
             mount_point: PathBuf::from("/tmp"),
             is_readonly: false,
+            total_inodes: 0,
+            free_inodes: 0,
         };
         let platform = Arc::new(CountingPlatform::new(
             mounts,
@@ -300,6 +302,8 @@ mod tests {
             fs_type: "tmpfs".to_string(),
             mount_point: PathBuf::from("/tmp"),
             is_readonly: false,
+            total_inodes: 0,
+            free_inodes: 0,
         };
         let platform = Arc::new(CountingPlatform::new(
             mounts,
@@ -367,6 +371,8 @@ mod tests {
             fs_type: "tmpfs".to_string(),
             mount_point: PathBuf::from("/tmp"),
             is_readonly: false,
+            total_inodes: 0,
+            free_inodes: 0,
         };
         let platform = Arc::new(CountingPlatform::new(
             mounts,
@@ -402,6 +408,8 @@ mod tests {
             fs_type: "tmpfs".to_string(),
             mount_point: PathBuf::from("/tmp"),
             is_readonly: false,
+            total_inodes: 0,
+            free_inodes: 0,
         };
         let platform = Arc::new(CountingPlatform::new(
             mounts,
@@ -436,6 +444,8 @@ mod tests {
             fs_type: "tmpfs".to_string(),
             mount_point: PathBuf::from("/tmp"),
             is_readonly: false,
+            total_inodes: 0,
+            free_inodes: 0,
         };
         let platform = Arc::new(CountingPlatform::new(
             mounts,
@@ -480,6 +490,8 @@ mod tests {
             fs_type: "ext4".to_string(),
             mount_point: mount_real.clone(),
             is_readonly: false,
+            total_inodes: 0,
+            free_inodes: 0,
         };
         let platform = Arc::new(CountingPlatform::new(
             mounts,
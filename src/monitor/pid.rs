@@ -16,7 +16,28 @@ pub enum PressureLevel {
     Critical,
 }
 
-/// Current filesystem pressure reading.
+/// Resource a pressure response was computed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PressureDimension {
+    /// Free bytes.
+    #[default]
+    Bytes,
+    /// Free inodes.
+    Inodes,
+}
+
+impl PressureDimension {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Bytes => "bytes",
+            Self::Inodes => "inodes",
+        }
+    }
+}
+
+/// Current filesystem pressure reading. An inode controller feeds free and
+/// total inode counts through the same fields.
 #[derive(Debug, Clone)]
 pub struct PressureReading {
     pub free_bytes: u64,
@@ -46,6 +67,16 @@ pub struct PressureResponse {
     pub causing_mount: PathBuf,
     pub free_pct: f64,
     pub predicted_seconds: Option<f64>,
+    /// Whether free bytes or free inodes drove this response.
+    pub dimension: PressureDimension,
+}
+
+impl PressureResponse {
+    /// Higher level wins; at equal levels, higher urgency does.
+    #[must_use]
+    pub fn is_more_severe_than(&self, other: &Self) -> bool {
+        self.level > other.level || (self.level == other.level && self.urgency > other.urgency)
+    }
 }
 
 /// PID controller with hysteresis and anti-windup.
@@ -71,6 +102,7 @@ pub struct PidPressureController {
     last_derivative: f64,
     last_update: Option<Instant>,
     level: PressureLevel,
+    dimension: PressureDimension,
 }
 
 impl PidPressureController {
@@ -108,9 +140,17 @@ impl PidPressureController {
             last_derivative: 0.0,
             last_update: None,
             level: PressureLevel::Green,
+            dimension: PressureDimension::Bytes,
         }
     }
 
+    /// Tag responses with the resource this controller tracks.
+    #[must_use]
+    pub const fn with_dimension(mut self, dimension: PressureDimension) -> Self {
+        self.dimension = dimension;
+        self
+    }
+
     /// Derive urgency boost thresholds from the predictive action horizon.
     ///
     /// The thresholds scale linearly: critical = horizon/30, high = horizon/6, moderate = horizon/2.
@@ -233,8 +273,13 @@ impl PidPressureController {
         }
         self.level = new_level;
 
-        let (scan_interval, release_ballast_files, max_delete_batch) =
+        let (scan_interval, mut release_ballast_files, max_delete_batch) =
             response_policy(self.base_poll_interval, self.level, urgency);
+        // Ballast files hold bytes, not inodes; releasing them cannot relieve
+        // inode pressure.
+        if self.dimension == PressureDimension::Inodes {
+            release_ballast_files = 0;
+        }
 
         PressureResponse {
             level: self.level,
@@ -246,6 +291,7 @@ impl PidPressureController {
             causing_mount: reading.mount,
             free_pct,
             predicted_seconds: predicted_seconds_to_red,
+            dimension: self.dimension,
         }
     }
 }
@@ -379,7 +425,7 @@ fn response_policy(
 
 #[cfg(test)]
 mod tests {
-    use super::{PidPressureController, PressureLevel, PressureReading};
+    use super::{PidPressureController, PressureDimension, PressureLevel, PressureReading};
     use std::path::PathBuf;
     use std::time::{Duration, Instant};
 
@@ -784,4 +830,52 @@ mod tests {
         // 40 + (0.5 * 120) = 100
         assert_eq!(high_crit, 100);
     }
+
+    #[test]
+    fn inode_controller_tags_responses_and_never_releases_ballast() {
+        let controller = || {
+            PidPressureController::new(
+                0.25,
+                0.08,
+                0.02,
+                100.0,
+                20.0,
+                1.0,
+                20.0,
+                14.0,
+                10.0,
+                6.0,
+                Duration::from_secs(1),
+            )
+        };
+        let reading = || PressureReading {
+            free_bytes: 2,
+            total_bytes: 100,
+            mount: PathBuf::from("/data"),
+        };
+        let now = Instant::now();
+
+        let bytes = controller().update(reading(), None, now);
+        assert_eq!(bytes.dimension, PressureDimension::Bytes);
+        assert!(bytes.release_ballast_files > 0);
+
+        let inodes = controller()
+            .with_dimension(PressureDimension::Inodes)
+            .update(reading(), None, now);
+        assert_eq!(inodes.level, PressureLevel::Critical);
+        assert_eq!(inodes.dimension, PressureDimension::Inodes);
+        assert_eq!(inodes.release_ballast_files, 0);
+        assert_eq!(inodes.max_delete_batch, bytes.max_delete_batch);
+
+        let calmer = controller().update(
+            PressureReading {
+                free_bytes: 40,
+                ..reading()
+            },
+            None,
+            now,
+        );
+        assert!(inodes.is_more_severe_than(&calmer));
+        assert!(!calmer.is_more_severe_than(&inodes));
+    }
 }
@@ -273,6 +273,8 @@ mod tests {
             fs_type: "tmpfs".to_string(),
             mount_point: PathBuf::from("/tmp"),
             is_readonly: false,
+            total_inodes: 0,
+            free_inodes: 0,
         };
         assert!(loc.needs_attention(&stats_low));
 
@@ -283,6 +285,8 @@ mod tests {
             fs_type: "tmpfs".to_string(),
             mount_point: PathBuf::from("/tmp"),
             is_readonly: false,
+            total_inodes: 0,
+            free_inodes: 0,
         };
         assert!(!loc.needs_attention(&stats_ok));
     }
@@ -2,10 +2,17 @@
 
 #![allow(missing_docs)]
 
+use std::borrow::Cow;
+use std::fmt;
 use std::path::Path;
 use std::time::Duration;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+use crate::scanner::patterns::ArtifactCategory;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CleanupConfidence {
     Definite,
     Likely,
@@ -14,13 +21,16 @@ pub enum CleanupConfidence {
     Sacred,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CheckRequirement {
+    #[default]
     Required,
     NotRequired,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ReclaimCommand {
     RemoveTree,
     RemoveMatchingFiles,
@@ -28,6 +38,83 @@ pub enum ReclaimCommand {
     PromptBeforeRemove,
     ReportOnly,
     Refuse,
+    /// Trim the cache with its owning tool instead of removing files, whose
+    /// direct deletion can corrupt the tool's own bookkeeping. Built-in rules
+    /// only; configured rules cannot name a program to run.
+    #[serde(skip_deserializing)]
+    Tool(ToolReclaim),
+}
+
+impl fmt::Display for ReclaimCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RemoveTree => f.write_str("remove_tree"),
+            Self::RemoveMatchingFiles => f.write_str("remove_matching_files"),
+            Self::ThinLocalSnapshots => f.write_str("thin_local_snapshots"),
+            Self::PromptBeforeRemove => f.write_str("prompt_before_remove"),
+            Self::ReportOnly => f.write_str("report_only"),
+            Self::Refuse => f.write_str("refuse"),
+            Self::Tool(tool) => write!(f, "tool: {}", tool.argv.join(" ")),
+        }
+    }
+}
+
+/// Placeholder in [`ToolReclaim`] arguments for the matched path.
+pub const TOOL_PATH_PLACEHOLDER: &str = "{path}";
+/// Placeholder in [`ToolReclaim`] arguments for the matched path's parent.
+pub const TOOL_PARENT_PLACEHOLDER: &str = "{parent}";
+
+/// A tool invocation that trims a cache in place.
+///
+/// `argv` and `env` values may contain `{path}` and `{parent}`, expanded to
+/// the matched path and its parent before the tool runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ToolReclaim {
+    pub argv: &'static [&'static str],
+    pub env: &'static [(&'static str, &'static str)],
+    /// The tool is killed once it runs this long.
+    pub timeout_secs: u64,
+    /// Share of the matched size the tool is expected to free, in percent.
+    /// Tools rarely empty a cache, so this is what dry runs report.
+    pub expected_reclaim_percent: u8,
+}
+
+impl ToolReclaim {
+    /// Bytes a run is expected to free from a cache of `size_bytes`.
+    #[must_use]
+    pub const fn expected_bytes(&self, size_bytes: u64) -> u64 {
+        let percent = if self.expected_reclaim_percent > 100 {
+            100
+        } else {
+            self.expected_reclaim_percent as u64
+        };
+        size_bytes / 100 * percent + size_bytes % 100 * percent / 100
+    }
+
+    /// `argv` with placeholders expanded for `path`.
+    #[must_use]
+    pub fn resolved_argv(&self, path: &Path) -> Vec<String> {
+        self.argv
+            .iter()
+            .map(|arg| expand_tool_placeholders(arg, path))
+            .collect()
+    }
+
+    /// `env` with placeholders expanded for `path`.
+    #[must_use]
+    pub fn resolved_env(&self, path: &Path) -> Vec<(String, String)> {
+        self.env
+            .iter()
+            .map(|(key, value)| ((*key).to_string(), expand_tool_placeholders(value, path)))
+            .collect()
+    }
+}
+
+fn expand_tool_placeholders(template: &str, path: &Path) -> String {
+    let parent = path.parent().unwrap_or(path);
+    template
+        .replace(TOOL_PATH_PLACEHOLDER, &path.to_string_lossy())
+        .replace(TOOL_PARENT_PLACEHOLDER, &parent.to_string_lossy())
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -51,10 +138,12 @@ impl AgeThreshold {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A cleanup rule. Built-ins borrow their strings; rules declared in config
+/// own theirs.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CleanupRule {
-    pub name: &'static str,
-    pub path_glob: &'static str,
+    pub name: Cow<'static, str>,
+    pub path_glob: Cow<'static, str>,
     pub age_threshold: AgeThreshold,
     pub fd_check: CheckRequirement,
     pub parent_check: CheckRequirement,
@@ -72,6 +161,7 @@ impl CleanupRule {
                 | ReclaimCommand::RemoveMatchingFiles
                 | ReclaimCommand::ThinLocalSnapshots
                 | ReclaimCommand::PromptBeforeRemove
+                | ReclaimCommand::Tool(_)
         )
     }
 
@@ -82,9 +172,19 @@ impl CleanupRule {
             ReclaimCommand::RemoveTree
                 | ReclaimCommand::RemoveMatchingFiles
                 | ReclaimCommand::PromptBeforeRemove
+                | ReclaimCommand::Tool(_)
         )
     }
 
+    /// The owning-tool invocation, for rules reclaimed by a tool.
+    #[must_use]
+    pub const fn tool(&self) -> Option<&ToolReclaim> {
+        match &self.reclaim_command {
+            ReclaimCommand::Tool(tool) => Some(tool),
+            _ => None,
+        }
+    }
+
     #[must_use]
     pub const fn is_report_only(&self) -> bool {
         matches!(self.reclaim_command, ReclaimCommand::ReportOnly)
@@ -96,23 +196,103 @@ impl CleanupRule {
     }
 
     #[must_use]
-    pub const fn scanner_label(&self) -> &'static str {
-        if str_starts_with(self.name, "user-named-trash") {
-            "user-named-trash"
-        } else if str_starts_with(self.name, "electron-cache-root") {
-            "electron-cache"
-        } else if str_starts_with(self.name, "electron-code-cache-root") {
-            "electron-code-cache"
-        } else if str_starts_with(self.name, "electron-gpu-cache-root") {
-            "electron-gpu-cache"
-        } else if str_starts_with(self.name, "electron-indexed-db-root") {
-            "electron-indexed-db"
-        } else if str_starts_with(self.name, "electron-vm-bundles-root") {
-            "electron-vm-bundles"
-        } else if str_starts_with(self.name, "electron-service-worker-cache-root") {
-            "electron-service-worker-cache"
-        } else {
-            self.name
+    pub fn scanner_label(&self) -> Cow<'static, str> {
+        const LABELS: &[(&str, &str)] = &[
+            ("user-named-trash", "user-named-trash"),
+            ("electron-cache-root", "electron-cache"),
+            ("electron-code-cache-root", "electron-code-cache"),
+            ("electron-gpu-cache-root", "electron-gpu-cache"),
+            ("electron-indexed-db-root", "electron-indexed-db"),
+            ("electron-vm-bundles-root", "electron-vm-bundles"),
+            (
+                "electron-service-worker-cache-root",
+                "electron-service-worker-cache",
+            ),
+        ];
+        LABELS
+            .iter()
+            .find(|(prefix, _)| self.name.starts_with(prefix))
+            .map_or_else(|| self.name.clone(), |(_, label)| Cow::Borrowed(*label))
+    }
+}
+
+/// Where an active cleanup rule was defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CleanupRuleSource {
+    /// Compiled into the platform catalog.
+    Builtin,
+    /// Declared under `[[scanner.cleanup_rules]]`.
+    UserConfig,
+}
+
+/// Owned-string cleanup rule as declared in `[[scanner.cleanup_rules]]`.
+///
+/// Configured rules are converted with [`OwnedCleanupRule::to_rule`] once per
+/// distinct config and merged after the built-ins.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwnedCleanupRule {
+    pub name: String,
+    pub path_glob: String,
+    #[serde(default = "default_owned_rule_age_hours")]
+    pub age_threshold_hours: u64,
+    #[serde(default)]
+    pub fd_check: CheckRequirement,
+    #[serde(default)]
+    pub parent_check: CheckRequirement,
+    #[serde(default)]
+    pub sacred_overlaps_check: CheckRequirement,
+    pub reclaim_command: ReclaimCommand,
+    pub confidence: CleanupConfidence,
+    /// Category reported for matches; rule names carry no category hint the
+    /// way built-in names do.
+    pub category: ArtifactCategory,
+}
+
+const fn default_owned_rule_age_hours() -> u64 {
+    24
+}
+
+impl OwnedCleanupRule {
+    /// Problems that make the rule unusable, or `None` when it is valid.
+    #[must_use]
+    pub fn validation_error(&self) -> Option<String> {
+        if self.name.trim().is_empty() {
+            return Some("name must not be empty".to_string());
+        }
+        let xdg_relative = XDG_GLOB_PREFIXES
+            .iter()
+            .any(|(prefix, _)| self.path_glob.starts_with(prefix));
+        if !(self.path_glob.starts_with('/') || self.path_glob.starts_with("~/") || xdg_relative) {
+            return Some(format!(
+                "path_glob must be absolute or start with ~/, $XDG_CACHE_HOME/ or $XDG_DATA_HOME/, got {:?}",
+                self.path_glob
+            ));
+        }
+        if matches!(self.path_glob.trim_end_matches('/'), "" | "~") {
+            return Some("path_glob must not match a filesystem or home root".to_string());
+        }
+        if self.reclaim_command == ReclaimCommand::ThinLocalSnapshots {
+            return Some("reclaim_command thin_local_snapshots is macOS-only".to_string());
+        }
+        if self.category == ArtifactCategory::Unknown {
+            return Some("category must not be \"unknown\"".to_string());
+        }
+        None
+    }
+
+    /// The catalog rule this declaration describes.
+    #[must_use]
+    pub fn to_rule(&self) -> CleanupRule {
+        CleanupRule {
+            name: Cow::Owned(self.name.clone()),
+            path_glob: Cow::Owned(self.path_glob.clone()),
+            age_threshold: AgeThreshold::from_hours(self.age_threshold_hours),
+            fd_check: self.fd_check,
+            parent_check: self.parent_check,
+            sacred_overlaps_check: self.sacred_overlaps_check,
+            reclaim_command: self.reclaim_command,
+            confidence: self.confidence,
         }
     }
 }
@@ -125,62 +305,62 @@ pub fn find_rule<'a>(rules: &'a [CleanupRule], name: &str) -> Option<&'a Cleanup
 }
 
 #[must_use]
-pub fn match_rule(path: &Path, rules: &'static [CleanupRule]) -> Option<&'static CleanupRule> {
+pub fn match_rule<'a>(path: &Path, rules: &'a [CleanupRule]) -> Option<&'a CleanupRule> {
     rules
         .iter()
-        .find(|rule| path_matches_glob(path, rule.path_glob))
+        .find(|rule| path_matches_glob(path, &rule.path_glob))
 }
 
 #[must_use]
-pub fn match_rule_with_home(
+pub fn match_rule_with_home<'a>(
     path: &Path,
-    rules: &'static [CleanupRule],
+    rules: &'a [CleanupRule],
     home: &Path,
-) -> Option<&'static CleanupRule> {
+) -> Option<&'a CleanupRule> {
     rules
         .iter()
-        .find(|rule| path_matches_glob_with_home(path, rule.path_glob, home))
+        .find(|rule| path_matches_glob_with_home(path, &rule.path_glob, home))
 }
 
 #[must_use]
-pub fn match_path_scanner_rule(
+pub fn match_path_scanner_rule<'a>(
     path: &Path,
-    rules: &'static [CleanupRule],
-) -> Option<&'static CleanupRule> {
+    rules: &'a [CleanupRule],
+) -> Option<&'a CleanupRule> {
     rules
         .iter()
-        .find(|rule| rule.is_path_scanner_candidate() && path_matches_glob(path, rule.path_glob))
+        .find(|rule| rule.is_path_scanner_candidate() && path_matches_glob(path, &rule.path_glob))
 }
 
 #[must_use]
-pub fn match_path_scanner_rule_with_home(
+pub fn match_path_scanner_rule_with_home<'a>(
     path: &Path,
-    rules: &'static [CleanupRule],
+    rules: &'a [CleanupRule],
     home: &Path,
-) -> Option<&'static CleanupRule> {
+) -> Option<&'a CleanupRule> {
     rules.iter().find(|rule| {
-        rule.is_path_scanner_candidate() && path_matches_glob_with_home(path, rule.path_glob, home)
+        rule.is_path_scanner_candidate() && path_matches_glob_with_home(path, &rule.path_glob, home)
     })
 }
 
 #[must_use]
-pub fn match_scan_visible_rule(
+pub fn match_scan_visible_rule<'a>(
     path: &Path,
-    rules: &'static [CleanupRule],
-) -> Option<&'static CleanupRule> {
+    rules: &'a [CleanupRule],
+) -> Option<&'a CleanupRule> {
     rules
         .iter()
-        .find(|rule| rule.is_scan_visible_candidate() && path_matches_glob(path, rule.path_glob))
+        .find(|rule| rule.is_scan_visible_candidate() && path_matches_glob(path, &rule.path_glob))
 }
 
 #[must_use]
-pub fn match_scan_visible_rule_with_home(
+pub fn match_scan_visible_rule_with_home<'a>(
     path: &Path,
-    rules: &'static [CleanupRule],
+    rules: &'a [CleanupRule],
     home: &Path,
-) -> Option<&'static CleanupRule> {
+) -> Option<&'a CleanupRule> {
     rules.iter().find(|rule| {
-        rule.is_scan_visible_candidate() && path_matches_glob_with_home(path, rule.path_glob, home)
+        rule.is_scan_visible_candidate() && path_matches_glob_with_home(path, &rule.path_glob, home)
     })
 }
 
@@ -194,7 +374,31 @@ pub fn path_matches_glob_with_home(path: &Path, path_glob: &str, home: &Path) ->
     path_matches_glob_inner(path, path_glob, Some(home))
 }
 
+/// XDG base-directory prefixes a glob may start with, and the home-relative
+/// directory each falls back to when the variable is unset.
+const XDG_GLOB_PREFIXES: &[(&str, &str)] = &[
+    ("$XDG_CACHE_HOME/", ".cache"),
+    ("$XDG_DATA_HOME/", ".local/share"),
+];
+
 fn path_matches_glob_inner(path: &Path, path_glob: &str, home: Option<&Path>) -> bool {
+    for (prefix, default_dir) in XDG_GLOB_PREFIXES {
+        let Some(rest) = path_glob.strip_prefix(prefix) else {
+            continue;
+        };
+        // An explicit home (fixture or per-user scan) always uses the XDG
+        // defaults under it; otherwise an absolute override in this
+        // process's environment is honored as well.
+        if home.is_none()
+            && let Some(dir) = std::env::var_os(&prefix[1..prefix.len() - 1])
+            && Path::new(&dir).is_absolute()
+            && path_matches_glob_inner(path, &format!("{}/{rest}", Path::new(&dir).display()), None)
+        {
+            return true;
+        }
+        return path_matches_glob_inner(path, &format!("~/{default_dir}/{rest}"), home);
+    }
+
     let path_text = normalize_path_text(path);
     let path_candidates = path_aliases(&path_text);
     let explicit_home = home.map(normalize_path_text);
@@ -339,25 +543,11 @@ fn glob_match_bytes(pattern: &[u8], text: &[u8]) -> bool {
     text_index == text.len()
 }
 
-const fn str_starts_with(text: &str, prefix: &str) -> bool {
-    let text = text.as_bytes();
-    let prefix = prefix.as_bytes();
-    if prefix.len() > text.len() {
-        return false;
-    }
-    let mut index = 0;
-    while index < prefix.len() {
-        if text[index] != prefix[index] {
-            return false;
-        }
-        index += 1;
-    }
-    true
-}
-
 #[cfg(test)]
 mod tests {
-    use super::{CleanupRule, ReclaimCommand, path_matches_glob, path_matches_glob_with_home};
+    use super::{
+        CleanupRule, ReclaimCommand, ToolReclaim, path_matches_glob, path_matches_glob_with_home,
+    };
     use std::path::Path;
 
     #[test]
@@ -429,8 +619,8 @@ mod tests {
     #[test]
     fn cleanup_rules_distinguish_path_scanner_commands() {
         let remove = CleanupRule {
-            name: "remove",
-            path_glob: "/tmp/*",
+            name: "remove".into(),
+            path_glob: "/tmp/*".into(),
             age_threshold: super::AgeThreshold::NONE,
             fd_check: super::CheckRequirement::Required,
             parent_check: super::CheckRequirement::Required,
@@ -440,7 +630,7 @@ mod tests {
         };
         let report = CleanupRule {
             reclaim_command: ReclaimCommand::ReportOnly,
-            ..remove
+            ..remove.clone()
         };
 
         assert!(remove.is_path_scanner_candidate());
@@ -448,5 +638,64 @@ mod tests {
         assert!(!report.is_path_scanner_candidate());
         assert!(report.is_report_only());
         assert!(report.is_scan_visible_candidate());
+
+        let tool = CleanupRule {
+            reclaim_command: ReclaimCommand::Tool(ToolReclaim {
+                argv: &["go", "clean", "-cache"],
+                env: &[],
+                timeout_secs: 60,
+                expected_reclaim_percent: 100,
+            }),
+            ..remove.clone()
+        };
+        assert!(tool.is_path_scanner_candidate());
+        assert!(tool.is_destructive());
+        assert!(tool.tool().is_some());
+        assert!(remove.tool().is_none());
+    }
+
+    #[test]
+    #[allow(clippy::literal_string_with_formatting_args)]
+    fn tool_reclaim_expands_placeholders_and_estimates_bytes() {
+        let tool = ToolReclaim {
+            argv: &["npm", "cache", "verify", "--cache", "{parent}"],
+            env: &[("CACHE_DIR", "{path}")],
+            timeout_secs: 60,
+            expected_reclaim_percent: 40,
+        };
+        let path = Path::new("/home/dev/.npm/_cacache");
+        assert_eq!(
+            tool.resolved_argv(path),
+            ["npm", "cache", "verify", "--cache", "/home/dev/.npm"]
+        );
+        assert_eq!(
+            tool.resolved_env(path),
+            [(
+                "CACHE_DIR".to_string(),
+                "/home/dev/.npm/_cacache".to_string()
+            )]
+        );
+        assert_eq!(tool.expected_bytes(1_000), 400);
+        assert_eq!(tool.expected_bytes(u64::MAX), u64::MAX / 100 * 40 + 6);
+        assert_eq!(
+            ReclaimCommand::Tool(tool).to_string(),
+            "tool: npm cache verify --cache {parent}"
+        );
+        assert_eq!(ReclaimCommand::RemoveTree.to_string(), "remove_tree");
+    }
+
+    #[test]
+    fn configured_rules_cannot_declare_tool_commands() {
+        let err = toml::from_str::<super::OwnedCleanupRule>(
+            r#"
+                name = "sneaky"
+                path_glob = "~/.cache/sneaky"
+                reclaim_command = "tool"
+                confidence = "likely"
+                category = "cache_dir"
+            "#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown variant"), "{err}");
     }
 }
@@ -1,11 +1,19 @@
-//! Static Linux cleanup heuristic catalog.
+//! Linux cleanup heuristic catalog: static built-ins plus rules declared in
+//! `[[scanner.cleanup_rules]]`.
 
 #![allow(missing_docs)]
 
+use std::borrow::Cow;
+use std::sync::{Arc, LazyLock};
+
+use parking_lot::RwLock;
+
 use crate::platform::cleanup_catalog;
 pub use crate::platform::cleanup_catalog::{
-    AgeThreshold, CheckRequirement, CleanupConfidence, CleanupRule, ReclaimCommand,
+    AgeThreshold, CheckRequirement, CleanupConfidence, CleanupRule, CleanupRuleSource,
+    OwnedCleanupRule, ReclaimCommand, ToolReclaim,
 };
+use crate::scanner::patterns::ArtifactCategory;
 
 const fn cleanup_rule(
     name: &'static str,
@@ -16,8 +24,8 @@ const fn cleanup_rule(
     confidence: CleanupConfidence,
 ) -> CleanupRule {
     CleanupRule {
-        name,
-        path_glob,
+        name: Cow::Borrowed(name),
+        path_glob: Cow::Borrowed(path_glob),
         age_threshold,
         fd_check,
         parent_check: CheckRequirement::Required,
@@ -81,6 +89,146 @@ pub const VAR_TMP_UNDERSCORE_TARGET: CleanupRule = cleanup_rule(
     CleanupConfidence::Likely,
 );
 
+// Developer caches. Each is rebuilt on demand by its tool, so the whole cache
+// root is one candidate. `$XDG_CACHE_HOME/` and `$XDG_DATA_HOME/` globs fall
+// back to `~/.cache/` and `~/.local/share/` under every home directory.
+
+pub const PIP_CACHE: CleanupRule = cleanup_rule(
+    "pip-cache",
+    "$XDG_CACHE_HOME/pip",
+    AgeThreshold::from_days(7),
+    CheckRequirement::Required,
+    ReclaimCommand::RemoveTree,
+    CleanupConfidence::Definite,
+);
+
+/// `npm cache verify` garbage-collects unreferenced content and rebuilds the
+/// index; removing `_cacache` under a running npm breaks its integrity checks.
+pub const NPM_CACHE_VERIFY: ToolReclaim = ToolReclaim {
+    argv: &["npm", "cache", "verify", "--cache", "{parent}"],
+    env: &[],
+    timeout_secs: 300,
+    expected_reclaim_percent: 10,
+};
+
+pub const NPM_CACACHE: CleanupRule = cleanup_rule(
+    "npm-cacache",
+    "~/.npm/_cacache",
+    AgeThreshold::from_days(7),
+    CheckRequirement::Required,
+    ReclaimCommand::Tool(NPM_CACHE_VERIFY),
+    CleanupConfidence::Definite,
+);
+
+pub const YARN_CACHE: CleanupRule = cleanup_rule(
+    "yarn-cache",
+    "$XDG_CACHE_HOME/yarn",
+    AgeThreshold::from_days(7),
+    CheckRequirement::Required,
+    ReclaimCommand::RemoveTree,
+    CleanupConfidence::Definite,
+);
+
+/// Projects hard-link into the store, so only `pnpm store prune` knows which
+/// packages are unreferenced.
+pub const PNPM_STORE_PRUNE: ToolReclaim = ToolReclaim {
+    argv: &["pnpm", "store", "prune", "--store-dir", "{path}"],
+    env: &[],
+    timeout_secs: 600,
+    expected_reclaim_percent: 40,
+};
+
+/// pnpm hardlinks store files into each `node_modules`, so installed projects
+/// keep working; only the next install re-downloads.
+pub const PNPM_STORE: CleanupRule = cleanup_rule(
+    "pnpm-store",
+    "$XDG_DATA_HOME/pnpm/store",
+    AgeThreshold::from_days(14),
+    CheckRequirement::Required,
+    ReclaimCommand::Tool(PNPM_STORE_PRUNE),
+    CleanupConfidence::Likely,
+);
+
+pub const CARGO_REGISTRY_CACHE: CleanupRule = cleanup_rule(
+    "cargo-registry-cache",
+    "~/.cargo/registry/cache",
+    AgeThreshold::from_days(14),
+    CheckRequirement::Required,
+    ReclaimCommand::RemoveTree,
+    CleanupConfidence::Definite,
+);
+
+/// GOCACHE entries are read-only and indexed by action ID; `go clean -cache`
+/// removes them without tripping over the permission bits.
+pub const GO_CLEAN_CACHE: ToolReclaim = ToolReclaim {
+    argv: &["go", "clean", "-cache"],
+    env: &[("GOCACHE", "{path}")],
+    timeout_secs: 300,
+    expected_reclaim_percent: 100,
+};
+
+pub const GO_BUILD_CACHE: CleanupRule = cleanup_rule(
+    "go-build-cache",
+    "$XDG_CACHE_HOME/go-build",
+    AgeThreshold::from_days(7),
+    CheckRequirement::Required,
+    ReclaimCommand::Tool(GO_CLEAN_CACHE),
+    CleanupConfidence::Definite,
+);
+
+/// Holds lock files while a Gradle daemon is running; the fd check keeps
+/// an active build's cache in place.
+pub const GRADLE_CACHES: CleanupRule = cleanup_rule(
+    "gradle-caches",
+    "~/.gradle/caches",
+    AgeThreshold::from_days(14),
+    CheckRequirement::Required,
+    ReclaimCommand::RemoveTree,
+    CleanupConfidence::Likely,
+);
+
+/// `mvn install` puts locally built artifacts here that no remote
+/// repository can restore, so the rule stays below the auto-delete bar.
+pub const MAVEN_REPOSITORY: CleanupRule = cleanup_rule(
+    "maven-repository",
+    "~/.m2/repository",
+    AgeThreshold::from_days(30),
+    CheckRequirement::Required,
+    ReclaimCommand::RemoveTree,
+    CleanupConfidence::Unclear,
+);
+
+/// Downloaded models: re-downloadable, but slow to restore. Only `hub` is
+/// covered; `huggingface/token` and `stored_tokens` beside it are the
+/// user's credentials.
+pub const HUGGINGFACE_HUB_CACHE: CleanupRule = cleanup_rule(
+    "huggingface-hub-cache",
+    "$XDG_CACHE_HOME/huggingface/hub",
+    AgeThreshold::from_days(30),
+    CheckRequirement::Required,
+    ReclaimCommand::RemoveTree,
+    CleanupConfidence::Likely,
+);
+
+/// Downloaded and processed datasets, rebuilt by `datasets` on next load.
+pub const HUGGINGFACE_DATASETS_CACHE: CleanupRule = cleanup_rule(
+    "huggingface-datasets-cache",
+    "$XDG_CACHE_HOME/huggingface/datasets",
+    AgeThreshold::from_days(30),
+    CheckRequirement::Required,
+    ReclaimCommand::RemoveTree,
+    CleanupConfidence::Likely,
+);
+
+pub const PLAYWRIGHT_BROWSERS_CACHE: CleanupRule = cleanup_rule(
+    "playwright-browsers-cache",
+    "$XDG_CACHE_HOME/ms-playwright",
+    AgeThreshold::from_days(14),
+    CheckRequirement::Required,
+    ReclaimCommand::RemoveTree,
+    CleanupConfidence::Likely,
+);
+
 pub const LINUX_CLEANUP_RULES: &[CleanupRule] = &[
     TMP_DASH_TARGET,
     TMP_UNDERSCORE_TARGET,
@@ -88,32 +236,118 @@ pub const LINUX_CLEANUP_RULES: &[CleanupRule] = &[
     DATA_TMP_UNDERSCORE_TARGET,
     VAR_TMP_DASH_TARGET,
     VAR_TMP_UNDERSCORE_TARGET,
+    PIP_CACHE,
+    NPM_CACACHE,
+    YARN_CACHE,
+    PNPM_STORE,
+    CARGO_REGISTRY_CACHE,
+    GO_BUILD_CACHE,
+    GRADLE_CACHES,
+    MAVEN_REPOSITORY,
+    HUGGINGFACE_HUB_CACHE,
+    HUGGINGFACE_DATASETS_CACHE,
+    PLAYWRIGHT_BROWSERS_CACHE,
 ];
 
-#[must_use]
-pub fn cleanup_rules() -> &'static [CleanupRule] {
+/// Built-ins merged with the configured rules last installed.
+struct ActiveCatalog {
+    rules: Arc<[CleanupRule]>,
+    configured: Vec<OwnedCleanupRule>,
+}
+
+static ACTIVE_CATALOG: LazyLock<RwLock<ActiveCatalog>> = LazyLock::new(|| {
+    RwLock::new(ActiveCatalog {
+        rules: LINUX_CLEANUP_RULES.into(),
+        configured: Vec::new(),
+    })
+});
+
+/// Merge `configured` after the built-ins and make the result the active
+/// catalog.
+///
+/// Built-ins come first so they win when globs overlap. Snapshots handed out
+/// by [`cleanup_rules`] before the swap keep the previous rule set alive until
+/// their holders drop them; installing the rule set that is already active is
+/// a no-op.
+pub fn install_configured_rules(configured: &[OwnedCleanupRule]) {
+    let mut active = ACTIVE_CATALOG.write();
+    if active.configured == configured {
+        return;
+    }
+    active.rules = merge_rules(configured).into();
+    active.configured = configured.to_vec();
+}
+
+fn merge_rules(configured: &[OwnedCleanupRule]) -> Vec<CleanupRule> {
     LINUX_CLEANUP_RULES
+        .iter()
+        .cloned()
+        .chain(configured.iter().map(OwnedCleanupRule::to_rule))
+        .collect()
+}
+
+/// Snapshot of the active catalog.
+#[must_use]
+pub fn cleanup_rules() -> Arc<[CleanupRule]> {
+    Arc::clone(&ACTIVE_CATALOG.read().rules)
+}
+
+/// Whether `rule` came from the installed config or a built-in catalog.
+#[must_use]
+pub fn rule_source(rule: &CleanupRule) -> CleanupRuleSource {
+    if configured_category(&rule.name).is_some() {
+        CleanupRuleSource::UserConfig
+    } else {
+        CleanupRuleSource::Builtin
+    }
+}
+
+/// Category declared by the configured rule named `name`.
+#[must_use]
+pub fn configured_category(name: &str) -> Option<ArtifactCategory> {
+    ACTIVE_CATALOG
+        .read()
+        .configured
+        .iter()
+        .find(|rule| rule.name == name)
+        .map(|rule| rule.category)
+}
+
+#[must_use]
+pub fn find_rule(name: &str) -> Option<CleanupRule> {
+    cleanup_catalog::find_rule(&cleanup_rules(), name).cloned()
 }
 
 #[must_use]
-pub fn find_rule(name: &str) -> Option<&'static CleanupRule> {
-    cleanup_catalog::find_rule(LINUX_CLEANUP_RULES, name)
+pub fn match_rule(path: &std::path::Path) -> Option<CleanupRule> {
+    cleanup_catalog::match_rule(path, &cleanup_rules()).cloned()
 }
 
 #[must_use]
-pub fn match_rule(path: &std::path::Path) -> Option<&'static CleanupRule> {
-    cleanup_catalog::match_rule(path, LINUX_CLEANUP_RULES)
+pub fn match_path_scanner_rule(path: &std::path::Path) -> Option<CleanupRule> {
+    cleanup_catalog::match_path_scanner_rule(path, &cleanup_rules()).cloned()
 }
 
+/// Match against an explicit home directory instead of `$HOME`, for
+/// per-user scans and fixtures.
 #[must_use]
-pub fn match_path_scanner_rule(path: &std::path::Path) -> Option<&'static CleanupRule> {
-    cleanup_catalog::match_path_scanner_rule(path, LINUX_CLEANUP_RULES)
+pub fn match_rule_with_home(path: &std::path::Path, home: &std::path::Path) -> Option<CleanupRule> {
+    cleanup_catalog::match_rule_with_home(path, &cleanup_rules(), home).cloned()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{DATA_TMP_UNDERSCORE_TARGET, cleanup_rules, match_path_scanner_rule};
-    use crate::platform::cleanup_catalog::{AgeThreshold, CheckRequirement, ReclaimCommand};
+    use super::{
+        DATA_TMP_UNDERSCORE_TARGET, LINUX_CLEANUP_RULES, MAVEN_REPOSITORY, cleanup_rules,
+        configured_category, install_configured_rules, match_path_scanner_rule, match_rule,
+        match_rule_with_home, merge_rules, rule_source,
+    };
+    use crate::platform::cleanup_catalog::{
+        self, AgeThreshold, CheckRequirement, CleanupConfidence, CleanupRuleSource,
+        OwnedCleanupRule, ReclaimCommand,
+    };
+    use crate::scanner::patterns::ArtifactCategory;
+    use std::collections::HashSet;
     use std::path::Path;
 
     #[test]
@@ -121,6 +355,7 @@ mod tests {
         assert_eq!(
             cleanup_rules()
                 .iter()
+                .filter(|rule| rule.path_glob.ends_with("target"))
                 .filter(|rule| rule.fd_check == CheckRequirement::Required)
                 .count(),
             6
@@ -152,4 +387,153 @@ mod tests {
             .is_none()
         );
     }
+
+    #[test]
+    fn configured_rules_merge_after_builtins() {
+        let configured = OwnedCleanupRule {
+            name: "ci-scratch".to_string(),
+            path_glob: "/scratch/ci-*".to_string(),
+            age_threshold_hours: 6,
+            fd_check: CheckRequirement::Required,
+            parent_check: CheckRequirement::NotRequired,
+            sacred_overlaps_check: CheckRequirement::Required,
+            reclaim_command: ReclaimCommand::RemoveTree,
+            confidence: CleanupConfidence::Likely,
+            category: ArtifactCategory::BuildOutput,
+        };
+        assert!(configured.validation_error().is_none());
+
+        let merged = merge_rules(std::slice::from_ref(&configured));
+        assert_eq!(merged.len(), LINUX_CLEANUP_RULES.len() + 1);
+        assert_eq!(&merged[..LINUX_CLEANUP_RULES.len()], LINUX_CLEANUP_RULES);
+        let rule = cleanup_catalog::match_path_scanner_rule(Path::new("/scratch/ci-1234"), &merged)
+            .expect("configured glob should match");
+        assert_eq!(rule.name, "ci-scratch");
+        assert_eq!(rule.age_threshold, AgeThreshold::from_hours(6));
+        assert_eq!(rule.parent_check, CheckRequirement::NotRequired);
+        assert_eq!(
+            rule_source(&DATA_TMP_UNDERSCORE_TARGET),
+            CleanupRuleSource::Builtin
+        );
+
+        // The glob does not end in "target", so concurrent tests counting the
+        // temp-target rules are unaffected while this rule is installed.
+        let configured = OwnedCleanupRule {
+            name: "catalog-install-test".to_string(),
+            path_glob: "/sbh-catalog-install-test/*".to_string(),
+            ..configured
+        };
+        install_configured_rules(std::slice::from_ref(&configured));
+        let installed = match_rule(Path::new("/sbh-catalog-install-test/a"))
+            .expect("installed rule should be active");
+        assert_eq!(rule_source(&installed), CleanupRuleSource::UserConfig);
+        assert_eq!(
+            configured_category("catalog-install-test"),
+            Some(ArtifactCategory::BuildOutput)
+        );
+        install_configured_rules(&[]);
+        assert!(match_rule(Path::new("/sbh-catalog-install-test/a")).is_none());
+    }
+
+    #[test]
+    fn configured_rules_reject_roots_and_macos_commands() {
+        let mut rule = OwnedCleanupRule {
+            name: "bad".to_string(),
+            path_glob: "/".to_string(),
+            age_threshold_hours: 24,
+            fd_check: CheckRequirement::Required,
+            parent_check: CheckRequirement::Required,
+            sacred_overlaps_check: CheckRequirement::Required,
+            reclaim_command: ReclaimCommand::RemoveTree,
+            confidence: CleanupConfidence::Likely,
+            category: ArtifactCategory::TempDir,
+        };
+        assert!(rule.validation_error().is_some());
+        rule.path_glob = "relative/*".to_string();
+        assert!(rule.validation_error().is_some());
+        rule.path_glob = "~/scratch/*".to_string();
+        assert!(rule.validation_error().is_none());
+        rule.reclaim_command = ReclaimCommand::ThinLocalSnapshots;
+        assert!(
+            rule.validation_error()
+                .is_some_and(|err| err.contains("macOS-only"))
+        );
+    }
+
+    #[test]
+    fn catalog_names_are_unique_and_destructive_rules_check_safety() {
+        let mut names = HashSet::new();
+        for rule in LINUX_CLEANUP_RULES {
+            assert!(
+                names.insert(&rule.name),
+                "duplicate rule name: {}",
+                rule.name
+            );
+            if rule.is_destructive() {
+                assert_eq!(rule.fd_check, CheckRequirement::Required, "{}", rule.name);
+                assert_eq!(
+                    rule.parent_check,
+                    CheckRequirement::Required,
+                    "{}",
+                    rule.name
+                );
+                assert_eq!(
+                    rule.sacred_overlaps_check,
+                    CheckRequirement::Required,
+                    "{}",
+                    rule.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn developer_caches_resolve_under_xdg_defaults() {
+        let home = Path::new("/tmp/sbh-fixture/home/dev");
+        for (path, expected) in [
+            (".cache/pip", "pip-cache"),
+            (".npm/_cacache", "npm-cacache"),
+            (".cache/yarn", "yarn-cache"),
+            (".local/share/pnpm/store", "pnpm-store"),
+            (".cargo/registry/cache", "cargo-registry-cache"),
+            (".cache/go-build", "go-build-cache"),
+            (".gradle/caches", "gradle-caches"),
+            (".m2/repository", "maven-repository"),
+            (".cache/huggingface/hub", "huggingface-hub-cache"),
+            (".cache/huggingface/datasets", "huggingface-datasets-cache"),
+            (".cache/ms-playwright", "playwright-browsers-cache"),
+        ] {
+            let rule = match_rule_with_home(&home.join(path), home)
+                .unwrap_or_else(|| panic!("{path} should match"));
+            assert_eq!(rule.name, expected);
+        }
+
+        // Without an explicit home, any /home/<user> qualifies.
+        assert_eq!(
+            match_rule(Path::new("/home/ci/.cache/pip")).map(|rule| rule.name),
+            Some("pip-cache".into())
+        );
+        // The cache root is the candidate, not its contents or siblings.
+        assert!(match_rule_with_home(&home.join(".cache/pip/wheels"), home).is_none());
+        assert!(match_rule_with_home(&home.join(".cache/fontconfig"), home).is_none());
+        // Bazel data is reclaimed only through the scanner's output-base
+        // scoring and access-time pruning, never as one tree.
+        assert!(match_rule_with_home(&home.join(".cache/bazel"), home).is_none());
+        // Hugging Face keeps the user's tokens beside its caches.
+        for path in [
+            ".cache/huggingface",
+            ".cache/huggingface/token",
+            ".cache/huggingface/stored_tokens",
+        ] {
+            assert!(
+                match_rule_with_home(&home.join(path), home).is_none(),
+                "{path}"
+            );
+        }
+        assert_eq!(
+            MAVEN_REPOSITORY.confidence,
+            CleanupConfidence::Unclear,
+            "locally installed Maven artifacts cannot be re-downloaded"
+        );
+    }
 }
@@ -25,7 +25,7 @@ use crate::platform::pal::{
 use crate::platform::sacred_catalog::cross_platform_sacred_paths;
 #[cfg(target_os = "linux")]
 use crate::platform::types::{
-    MemoryPressure, MemoryPressureCallback, PalError, ProcessInfo, ProcessIo, SacredPath,
+    CgroupIo, MemoryPressure, MemoryPressureCallback, PalError, ProcessInfo, ProcessIo, SacredPath,
     SelfStats, ServiceKind, SubscriptionHandle,
 };
 
@@ -38,6 +38,8 @@ pub mod process;
 #[cfg(target_os = "linux")]
 pub mod service;
 #[cfg(target_os = "linux")]
+pub mod snapshots;
+#[cfg(target_os = "linux")]
 pub mod writeback;
 
 /// Linux platform implementation using `/proc` + `statvfs`.
@@ -105,6 +107,8 @@ impl Platform for LinuxPal {
             fs_type: mount.fs_type.clone(),
             mount_point: mount.path.clone(),
             is_readonly: stat.flags().contains(nix::sys::statvfs::FsFlags::ST_RDONLY),
+            total_inodes: stat.files(),
+            free_inodes: stat.files_available(),
         })
     }
 
@@ -147,10 +151,50 @@ impl Platform for LinuxPal {
         process::read_process_io(pid)
     }
 
+    fn cgroup_io(&self, cgroup: &str) -> Result<CgroupIo> {
+        process::read_cgroup_io(cgroup)
+    }
+
+    fn block_device_number(&self, path: &Path) -> Result<String> {
+        let device = self.block_device_for(path)?;
+        writeback::device_number(&device.device)
+    }
+
+    fn cgroup_write_limit(&self, cgroup: &str, path: &Path) -> Result<Option<u64>> {
+        let number = self.block_device_number(path)?;
+        process::read_cgroup_write_limit(cgroup, &number)
+    }
+
+    fn set_cgroup_write_limit(
+        &self,
+        cgroup: &str,
+        path: &Path,
+        bytes_per_sec: Option<u64>,
+    ) -> Result<()> {
+        let number = self.block_device_number(path)?;
+        process::write_cgroup_write_limit(cgroup, &number, bytes_per_sec)
+    }
+
+    fn set_cgroup_frozen(&self, cgroup: &str, frozen: bool) -> Result<()> {
+        process::write_cgroup_frozen(cgroup, frozen)
+    }
+
+    fn leaf_cgroups(&self) -> Result<Vec<String>> {
+        process::read_leaf_cgroups()
+    }
+
+    fn self_cgroup(&self) -> Result<String> {
+        process::read_self_cgroup()
+    }
+
     fn open_files_under(&self, path: &Path) -> Result<Vec<crate::platform::types::OpenFile>> {
         process::read_open_files_under(path)
     }
 
+    fn deleted_open_files(&self) -> Result<Vec<crate::platform::types::DeletedOpenFile>> {
+        process::read_deleted_open_files()
+    }
+
     fn executables_under(&self, path: &Path) -> Result<Vec<ProcessInfo>> {
         process::read_executables_under(path)
     }
@@ -163,6 +207,21 @@ impl Platform for LinuxPal {
         process::read_self_stats()
     }
 
+    fn filesystem_snapshots(
+        &self,
+        mount: &Path,
+    ) -> Result<Vec<crate::platform::types::FsSnapshotInfo>> {
+        let mounts = self.mount_points()?;
+        disk::find_mount(mount, &mounts).map_or_else(|| Ok(Vec::new()), snapshots::list_snapshots)
+    }
+
+    fn destroy_filesystem_snapshot(
+        &self,
+        snapshot: &crate::platform::types::FsSnapshotInfo,
+    ) -> Result<()> {
+        snapshots::destroy_snapshot(snapshot)
+    }
+
     fn service_manager(&self) -> Box<dyn ServiceManager> {
         service::service_manager()
     }
@@ -2,20 +2,27 @@
 
 #![allow(missing_docs)]
 
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use nix::sys::signal::{Signal, kill};
+use nix::unistd::Pid;
+
 use crate::core::errors::{Result, SbhError};
 use crate::core::paths::resolve_absolute_path;
 use crate::platform::types::{
-    MappedRegion, OpenFile, OpenFileKind, OpenFileMode, PalError, ProcessInfo, ProcessIo, SelfStats,
+    CgroupIo, DeletedOpenFile, MappedRegion, OpenFile, OpenFileKind, OpenFileMode, PalError,
+    ProcessInfo, ProcessIo, SelfStats,
 };
 
 const PROC_SELF_STATUS: &str = "/proc/self/status";
 const PROC_SELF_STAT: &str = "/proc/self/stat";
 const PROC_SELF_IO: &str = "/proc/self/io";
+const PROC_SELF_CGROUP: &str = "/proc/self/cgroup";
 const PROC_ROOT: &str = "/proc";
 const PROC_STAT: &str = "/proc/stat";
+const CGROUP_V2_ROOT: &str = "/sys/fs/cgroup";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct StatusMemory {
@@ -76,6 +83,26 @@ pub(super) fn read_open_files_under(root: &Path) -> Result<Vec<OpenFile>> {
     Ok(open_files)
 }
 
+pub(super) fn read_deleted_open_files() -> Result<Vec<DeletedOpenFile>> {
+    let mut files = Vec::new();
+    let mut mapped_inodes = HashSet::new();
+    for pid in proc_pids()? {
+        if pid > 0 {
+            files.extend(deleted_open_files_for_pid(pid));
+            mapped_inodes.extend(deleted_mapped_inodes_for_pid(pid));
+        }
+    }
+    for file in &mut files {
+        file.mapped = mapped_inodes.contains(&file.inode);
+    }
+    files.sort_by(|left, right| {
+        left.pid
+            .cmp(&right.pid)
+            .then_with(|| left.fd.cmp(&right.fd))
+    });
+    Ok(files)
+}
+
 pub(super) fn read_executables_under(root: &Path) -> Result<Vec<ProcessInfo>> {
     let root = resolve_absolute_path(root);
     let boot_time_unix_ms = read_proc_file(PROC_STAT)
@@ -133,6 +160,143 @@ pub(super) fn read_process_io(pid: i32) -> Result<ProcessIo> {
     })
 }
 
+pub(super) fn read_cgroup_io(cgroup: &str) -> Result<CgroupIo> {
+    let path = cgroup_dir(cgroup).join("io.stat");
+    let raw = fs::read_to_string(&path).map_err(|source| SbhError::Io {
+        path: path.clone(),
+        source,
+    })?;
+    Ok(parse_cgroup_io_stat(&raw))
+}
+
+pub(super) fn read_leaf_cgroups() -> Result<Vec<String>> {
+    collect_leaf_cgroups(Path::new(CGROUP_V2_ROOT))
+}
+
+/// cgroup paths (relative to `root`, `/`-prefixed) of every directory below
+/// `root` that has no subdirectory.
+fn collect_leaf_cgroups(root: &Path) -> Result<Vec<String>> {
+    let mut leaves = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), String::new())];
+    while let Some((dir, cgroup)) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(source) if cgroup.is_empty() => return Err(SbhError::Io { path: dir, source }),
+            // A cgroup removed while we walk.
+            Err(_) => continue,
+        };
+        let mut has_children = false;
+        for entry in entries.flatten() {
+            if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                has_children = true;
+                let name = entry.file_name();
+                pending.push((entry.path(), format!("{cgroup}/{}", name.to_string_lossy())));
+            }
+        }
+        if !has_children && !cgroup.is_empty() {
+            leaves.push(cgroup);
+        }
+    }
+    leaves.sort();
+    Ok(leaves)
+}
+
+/// Set (or with `None`, lift) the `wbps` limit of `cgroup` on block device
+/// `device` (`MAJ:MIN`) through cgroup v2 `io.max`.
+pub(super) fn write_cgroup_write_limit(
+    cgroup: &str,
+    device: &str,
+    bytes_per_sec: Option<u64>,
+) -> Result<()> {
+    let path = cgroup_dir(cgroup).join("io.max");
+    fs::write(&path, io_max_write_limit(device, bytes_per_sec))
+        .map_err(|source| SbhError::Io { path, source })
+}
+
+/// The `wbps` limit `cgroup` currently has on `device` (`MAJ:MIN`); `None`
+/// when it has none.
+pub(super) fn read_cgroup_write_limit(cgroup: &str, device: &str) -> Result<Option<u64>> {
+    let path = cgroup_dir(cgroup).join("io.max");
+    let raw = fs::read_to_string(&path).map_err(|source| SbhError::Io { path, source })?;
+    Ok(parse_io_max_write_limit(&raw, device))
+}
+
+/// The `wbps` value of `device`'s line in `io.max` contents. Devices without
+/// a line, and `wbps=max`, have no limit.
+fn parse_io_max_write_limit(raw: &str, device: &str) -> Option<u64> {
+    let line = raw
+        .lines()
+        .find(|line| line.split_whitespace().next() == Some(device))?;
+    line.split_whitespace()
+        .find_map(|field| field.strip_prefix("wbps="))
+        .and_then(|value| value.parse().ok())
+}
+
+/// `io.max` line that changes only the write-bandwidth key for one device.
+fn io_max_write_limit(device: &str, bytes_per_sec: Option<u64>) -> String {
+    bytes_per_sec.map_or_else(
+        || format!("{device} wbps=max"),
+        |limit| format!("{device} wbps={limit}"),
+    )
+}
+
+/// Freeze or thaw every process of a cgroup. Uses the cgroup v2 freezer
+/// where the kernel has one (5.2+) and otherwise stops or continues each
+/// member process, never the daemon itself.
+pub(super) fn write_cgroup_frozen(cgroup: &str, frozen: bool) -> Result<()> {
+    let dir = cgroup_dir(cgroup);
+    let freeze = dir.join("cgroup.freeze");
+    if freeze.exists() {
+        return fs::write(&freeze, if frozen { "1" } else { "0" }).map_err(|source| SbhError::Io {
+            path: freeze,
+            source,
+        });
+    }
+
+    let procs = dir.join("cgroup.procs");
+    let raw = fs::read_to_string(&procs).map_err(|source| SbhError::Io {
+        path: procs,
+        source,
+    })?;
+    let signal = if frozen {
+        Signal::SIGSTOP
+    } else {
+        Signal::SIGCONT
+    };
+    let own_pid = i32::try_from(std::process::id()).unwrap_or(i32::MAX);
+    for pid in parse_cgroup_procs(&raw) {
+        if pid != own_pid {
+            // Members may exit between listing and signalling.
+            let _ = kill(Pid::from_raw(pid), signal);
+        }
+    }
+    Ok(())
+}
+
+/// The daemon's own cgroup v2 path.
+pub(super) fn read_self_cgroup() -> Result<String> {
+    let raw = read_proc_file(PROC_SELF_CGROUP)?;
+    parse_proc_cgroup_v2(&raw)
+        .ok_or_else(|| process_parse_error("self_cgroup", "no cgroup v2 entry"))
+}
+
+fn parse_cgroup_procs(raw: &str) -> Vec<i32> {
+    raw.lines()
+        .filter_map(|line| line.trim().parse::<i32>().ok())
+        .filter(|pid| *pid > 0)
+        .collect()
+}
+
+fn cgroup_dir(cgroup: &str) -> PathBuf {
+    let mut dir = PathBuf::from(CGROUP_V2_ROOT);
+    dir.extend(
+        cgroup
+            .split('/')
+            .filter(|part| !part.is_empty() && *part != ".."),
+    );
+    dir
+}
+
 pub(super) fn read_self_stats() -> Result<SelfStats> {
     let status = read_proc_file(PROC_SELF_STATUS)?;
     let stat = read_proc_file(PROC_SELF_STAT)?;
@@ -224,6 +388,10 @@ fn process_info_for_pid(pid: i32, boot_time_unix_ms: Option<i64>) -> Option<Proc
             .and_then(|raw| parse_status_kib_field_from_raw(raw, "VmRSS")),
         cpu_user_micros: cpu.map(|times| times.user_micros),
         cpu_system_micros: cpu.map(|times| times.system_micros),
+        uid: status.as_deref().and_then(parse_status_real_uid),
+        cgroup: fs::read_to_string(proc_path.join("cgroup"))
+            .ok()
+            .and_then(|raw| parse_proc_cgroup_v2(&raw)),
     })
 }
 
@@ -277,6 +445,92 @@ fn open_file_for_fd_under(
     })
 }
 
+fn deleted_open_files_for_pid(pid: i32) -> Vec<DeletedOpenFile> {
+    use std::os::unix::fs::MetadataExt;
+
+    let proc_path = pid_proc_path(pid);
+    let Ok(entries) = fs::read_dir(proc_path.join("fd")) else {
+        return Vec::new();
+    };
+    let mut process_name: Option<Option<String>> = None;
+    let mut files = Vec::new();
+    for entry in entries.flatten() {
+        let fd_path = entry.path();
+        let Some(fd) = fd_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.parse::<i32>().ok())
+        else {
+            continue;
+        };
+        let Some(path) = fs::read_link(&fd_path)
+            .ok()
+            .and_then(|target| deleted_link_target(&target))
+        else {
+            continue;
+        };
+        // `metadata` follows the fd link to the still-open inode.
+        let Ok(meta) = fs::metadata(&fd_path) else {
+            continue;
+        };
+        if !meta.is_file() || meta.nlink() != 0 {
+            continue;
+        }
+        let flags = fs::read_to_string(proc_path.join("fdinfo").join(fd.to_string()))
+            .ok()
+            .and_then(|raw| parse_fdinfo_flags(&raw))
+            .unwrap_or(0);
+        let name = process_name.get_or_insert_with(|| {
+            fs::read_to_string(proc_path.join("comm"))
+                .ok()
+                .map(|raw| raw.trim().to_string())
+        });
+        files.push(DeletedOpenFile {
+            pid,
+            process_name: name.clone(),
+            fd,
+            path,
+            device: meta.dev(),
+            inode: meta.ino(),
+            size_bytes: meta.len(),
+            allocated_bytes: meta.blocks().saturating_mul(512),
+            mode: open_file_mode_for_fd(&proc_path, fd),
+            append: flags & u64::try_from(libc::O_APPEND).unwrap_or(0) != 0,
+            mapped: false,
+        });
+    }
+    files
+}
+
+/// Original path of an unlinked file from its `/proc/<pid>/fd` link target.
+/// Anonymous memory files (`memfd_create`, SysV shared memory) never held
+/// disk blocks and are skipped.
+fn deleted_link_target(target: &Path) -> Option<PathBuf> {
+    let path = target.to_str()?.strip_suffix(" (deleted)")?;
+    if !path.starts_with('/') || path.starts_with("/memfd:") || path.starts_with("/SYSV") {
+        return None;
+    }
+    Some(PathBuf::from(path))
+}
+
+fn deleted_mapped_inodes_for_pid(pid: i32) -> Vec<u64> {
+    let Ok(raw) = fs::read_to_string(pid_proc_path(pid).join("maps")) else {
+        return Vec::new();
+    };
+    raw.lines().filter_map(deleted_mapping_inode).collect()
+}
+
+/// Inode of a `/proc/<pid>/maps` line that maps an unlinked file.
+fn deleted_mapping_inode(line: &str) -> Option<u64> {
+    let (_, rest) = take_whitespace_field(line)?;
+    let (_, rest) = take_whitespace_field(rest)?;
+    let (_, rest) = take_whitespace_field(rest)?;
+    let (_, rest) = take_whitespace_field(rest)?;
+    let (inode, rest) = take_whitespace_field(rest)?;
+    rest.trim().ends_with(" (deleted)").then_some(())?;
+    inode.parse::<u64>().ok().filter(|inode| *inode != 0)
+}
+
 fn open_file_kind_for_fd(fd_path: &Path) -> OpenFileKind {
     use std::os::unix::fs::FileTypeExt;
 
@@ -398,6 +652,59 @@ fn parse_status_i32_field(raw: &str, key: &'static str) -> Option<i32> {
     })
 }
 
+/// Real uid: the first of the four ids on the `Uid:` line.
+fn parse_status_real_uid(raw: &str) -> Option<u32> {
+    raw.lines().find_map(|line| {
+        line.strip_prefix("Uid:")?
+            .split_whitespace()
+            .next()?
+            .parse::<u32>()
+            .ok()
+    })
+}
+
+/// The unified-hierarchy entry (`0::<path>`) of `/proc/<pid>/cgroup`. Hybrid
+/// v1 hosts also list controller hierarchies, which are ignored.
+fn parse_proc_cgroup_v2(raw: &str) -> Option<String> {
+    raw.lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .map(str::trim)
+        .filter(|path| path.starts_with('/'))
+        .map(str::to_string)
+}
+
+/// Sum `rbytes`/`wbytes` over every device line of a cgroup `io.stat`.
+fn parse_cgroup_io_stat(raw: &str) -> CgroupIo {
+    let mut io = CgroupIo::default();
+    for line in raw.lines() {
+        let mut fields = line.split_whitespace();
+        let Some(device) = fields.next() else {
+            continue;
+        };
+        for field in fields {
+            let Some((key, value)) = field.split_once('=') else {
+                continue;
+            };
+            let Ok(value) = value.parse::<u64>() else {
+                continue;
+            };
+            match key {
+                "rbytes" => io.bytes_read = io.bytes_read.saturating_add(value),
+                "wbytes" => {
+                    io.bytes_written = io.bytes_written.saturating_add(value);
+                    let written = io
+                        .bytes_written_by_device
+                        .entry(device.to_string())
+                        .or_default();
+                    *written = written.saturating_add(value);
+                }
+                _ => {}
+            }
+        }
+    }
+    io
+}
+
 fn parse_status_kib_field_from_raw(raw: &str, key: &'static str) -> Option<u64> {
     raw.lines()
         .find_map(|line| parse_status_kib_field(line, key).ok().flatten())
@@ -583,13 +890,17 @@ fn process_parse_error(method: &'static str, details: impl Into<String>) -> SbhE
 #[cfg(test)]
 mod tests {
     use super::{
-        mapped_region_from_maps_line, parse_fdinfo_flags, parse_proc_boot_time_unix_ms,
-        parse_proc_io, parse_proc_self_stat, parse_proc_self_status, parse_proc_start_time_unix_ms,
+        cgroup_dir, collect_leaf_cgroups, deleted_link_target, deleted_mapping_inode,
+        io_max_write_limit, mapped_region_from_maps_line, parse_cgroup_io_stat, parse_cgroup_procs,
+        parse_fdinfo_flags, parse_io_max_write_limit, parse_proc_boot_time_unix_ms,
+        parse_proc_cgroup_v2, parse_proc_io, parse_proc_self_stat, parse_proc_self_status,
+        parse_proc_start_time_unix_ms, parse_status_real_uid, read_deleted_open_files,
         read_executables_under, read_mmap_regions_under, read_open_files_under, read_process_io,
         read_process_list, read_self_stats, ticks_to_micros,
     };
     use crate::core::paths::resolve_absolute_path;
     use crate::platform::types::{OpenFileKind, OpenFileMode};
+    use std::path::{Path, PathBuf};
 
     #[test]
     fn parses_proc_status_memory_fields() {
@@ -629,6 +940,85 @@ mod tests {
         assert_eq!(start_time, 1_700_000_123_450);
     }
 
+    #[test]
+    fn parses_cgroup_membership_uid_and_io_stat() {
+        assert_eq!(
+            parse_proc_cgroup_v2(
+                "12:pids:/user.slice\n0::/user.slice/user-1000.slice/session-3.scope\n"
+            )
+            .as_deref(),
+            Some("/user.slice/user-1000.slice/session-3.scope")
+        );
+        assert_eq!(parse_proc_cgroup_v2("4:memory:/docker/abc\n"), None);
+        assert_eq!(
+            parse_status_real_uid("Name:\tcargo\nUid:\t1000\t1000\t1000\t1000\n"),
+            Some(1000)
+        );
+
+        let io = parse_cgroup_io_stat(
+            "8:0 rbytes=4096 wbytes=1048576 rios=1 wios=256 dbytes=0 dios=0\n\
+             259:0 rbytes=10 wbytes=20 rios=1 wios=1 dbytes=0 dios=0\n",
+        );
+        assert_eq!(io.bytes_read, 4_106);
+        assert_eq!(io.bytes_written, 1_048_596);
+        assert_eq!(io.bytes_written_by_device.get("8:0"), Some(&1_048_576));
+        assert_eq!(io.bytes_written_by_device.get("259:0"), Some(&20));
+        assert_eq!(
+            cgroup_dir("/system.slice/../../etc"),
+            std::path::PathBuf::from("/sys/fs/cgroup/system.slice/etc")
+        );
+    }
+
+    #[test]
+    fn leaf_cgroups_include_empty_cgroups_but_not_their_parents() {
+        let root = tempfile::tempdir().unwrap();
+        for dir in [
+            "system.slice/docker-abc.scope",
+            "user.slice/user-1000.slice/session-3.scope",
+            "init.scope",
+        ] {
+            std::fs::create_dir_all(root.path().join(dir)).unwrap();
+        }
+        std::fs::write(root.path().join("init.scope/io.stat"), "").unwrap();
+        assert_eq!(
+            collect_leaf_cgroups(root.path()).unwrap(),
+            vec![
+                "/init.scope",
+                "/system.slice/docker-abc.scope",
+                "/user.slice/user-1000.slice/session-3.scope",
+            ]
+        );
+        assert!(collect_leaf_cgroups(&root.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn io_max_write_limit_sets_and_lifts_only_wbps() {
+        assert_eq!(
+            io_max_write_limit("259:0", Some(33_554_432)),
+            "259:0 wbps=33554432"
+        );
+        assert_eq!(io_max_write_limit("8:0", None), "8:0 wbps=max");
+    }
+
+    #[test]
+    fn io_max_write_limit_is_read_per_device() {
+        let raw = "8:0 rbps=max wbps=1048576 riops=max wiops=max\n\
+                   259:0 rbps=2097152 wbps=max riops=max wiops=max\n";
+        assert_eq!(parse_io_max_write_limit(raw, "8:0"), Some(1_048_576));
+        assert_eq!(parse_io_max_write_limit(raw, "259:0"), None);
+        assert_eq!(parse_io_max_write_limit(raw, "8:16"), None);
+        assert_eq!(parse_io_max_write_limit("", "8:0"), None);
+    }
+
+    #[test]
+    fn parses_cgroup_procs_members() {
+        assert_eq!(
+            parse_cgroup_procs("412\n9001\n\n0\nbogus\n"),
+            vec![412, 9001]
+        );
+        assert!(parse_cgroup_procs("").is_empty());
+    }
+
     #[test]
     fn parses_proc_boot_time() {
         let boot_time =
@@ -749,6 +1139,69 @@ mod tests {
         assert!(actual.fd.is_some());
     }
 
+    #[test]
+    fn deleted_fd_targets_and_mappings_are_recognized() {
+        assert_eq!(
+            deleted_link_target(Path::new("/var/log/app.log (deleted)")),
+            Some(PathBuf::from("/var/log/app.log"))
+        );
+        assert_eq!(deleted_link_target(Path::new("/var/log/app.log")), None);
+        assert_eq!(
+            deleted_link_target(Path::new("/memfd:wayland (deleted)")),
+            None
+        );
+        assert_eq!(
+            deleted_link_target(Path::new("socket:[123] (deleted)")),
+            None
+        );
+
+        assert_eq!(
+            deleted_mapping_inode(
+                "7f00-7f10 r--s 00000000 fd:01 1234567    /tmp/cache.bin (deleted)"
+            ),
+            Some(1_234_567)
+        );
+        assert_eq!(
+            deleted_mapping_inode("7f00-7f10 r-xp 00000000 fd:01 42    /usr/lib/libc.so.6"),
+            None
+        );
+        assert_eq!(
+            deleted_mapping_inode("7f00-7f10 rw-p 00000000 00:00 0    [heap]"),
+            None
+        );
+    }
+
+    #[test]
+    fn linux_deleted_open_files_reports_unlinked_append_fd() {
+        use std::io::Write as _;
+
+        let dir = tempfile::TempDir::new().expect("temp dir should be created");
+        let path = dir
+            .path()
+            .canonicalize()
+            .expect("temp dir should resolve")
+            .join("held.log");
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .expect("temp file should open");
+        file.write_all(&[b'x'; 8192])
+            .expect("temp file should be written");
+        std::fs::remove_file(&path).expect("temp file should be unlinked");
+        let current_pid = i32::try_from(std::process::id()).expect("pid should fit i32");
+
+        let deleted = read_deleted_open_files().expect("/proc should be readable");
+        let held = deleted
+            .iter()
+            .find(|held| held.pid == current_pid && held.path == path)
+            .unwrap_or_else(|| panic!("unlinked fd was not reported; deleted={deleted:?}"));
+        assert_eq!(held.size_bytes, 8192);
+        assert_eq!(held.mode, OpenFileMode::Write);
+        assert!(held.append);
+        assert!(!held.mapped);
+    }
+
     #[test]
     fn linux_executables_under_reports_current_process_executable() {
         let exe = std::env::current_exe().expect("current executable should be known");
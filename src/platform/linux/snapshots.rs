@@ -0,0 +1,472 @@
+//! btrfs and ZFS snapshot inventory via `btrfs`, `snapper` and `zfs`.
+//!
+//! Snapshots pin the blocks of every file they captured, so deleting a build
+//! artifact that a snapshot still references frees nothing. This module lists
+//! the snapshots of a mounted filesystem together with the bytes only each
+//! snapshot holds (btrfs qgroup `excl`, ZFS `used`) and destroys a managed
+//! snapshot through the tool that created it.
+//!
+//! A btrfs snapshot counts as snapper's when its path ends in
+//! `.snapshots/<n>/snapshot` and a snapper config owns the mount; a ZFS
+//! snapshot counts as zfs-auto-snapshot's when its name starts with
+//! `zfs-auto-snap_`. Everything else is reported but never destroyed.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::core::errors::Result;
+use crate::platform::pal::MountPoint;
+use crate::platform::types::{FsSnapshotInfo, FsSnapshotKind, PalError, SnapshotManager};
+
+const LIST_TIMEOUT: Duration = Duration::from_secs(30);
+const DESTROY_TIMEOUT: Duration = Duration::from_mins(5);
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+const ZFS_AUTO_SNAPSHOT_PREFIX: &str = "zfs-auto-snap_";
+
+/// Snapshots of the filesystem mounted at `mount`; empty unless btrfs or ZFS.
+pub(super) fn list_snapshots(mount: &MountPoint) -> Result<Vec<FsSnapshotInfo>> {
+    match mount.fs_type.as_str() {
+        "btrfs" => btrfs_snapshots(&mount.path),
+        "zfs" => zfs_snapshots(mount),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Destroy a managed snapshot with `snapper delete` or `zfs destroy`.
+pub(super) fn destroy_snapshot(snapshot: &FsSnapshotInfo) -> Result<()> {
+    match &snapshot.manager {
+        SnapshotManager::Snapper { config, number } => {
+            let number = number.to_string();
+            run_tool(
+                "destroy_filesystem_snapshot",
+                "snapper",
+                &[
+                    OsStr::new("--no-dbus"),
+                    OsStr::new("-c"),
+                    OsStr::new(config),
+                    OsStr::new("delete"),
+                    OsStr::new(&number),
+                ],
+                DESTROY_TIMEOUT,
+            )
+            .map(drop)
+        }
+        SnapshotManager::ZfsAutoSnapshot { .. } => {
+            // `zfs destroy` on a bare dataset name would destroy the dataset.
+            if !is_zfs_snapshot_name(&snapshot.name) {
+                return Err(failed(
+                    "destroy_filesystem_snapshot",
+                    format!("refusing to destroy non-snapshot {}", snapshot.name),
+                ));
+            }
+            run_tool(
+                "destroy_filesystem_snapshot",
+                "zfs",
+                &[OsStr::new("destroy"), OsStr::new(&snapshot.name)],
+                DESTROY_TIMEOUT,
+            )
+            .map(drop)
+        }
+        SnapshotManager::Unmanaged => Err(failed(
+            "destroy_filesystem_snapshot",
+            format!("refusing to destroy unmanaged snapshot {}", snapshot.name),
+        )),
+    }
+}
+
+fn btrfs_snapshots(mount: &Path) -> Result<Vec<FsSnapshotInfo>> {
+    let listing = run_tool(
+        "filesystem_snapshots",
+        "btrfs",
+        &[
+            OsStr::new("subvolume"),
+            OsStr::new("list"),
+            OsStr::new("-s"),
+            OsStr::new("-o"),
+            mount.as_os_str(),
+        ],
+        LIST_TIMEOUT,
+    )?;
+    // Fails when quotas are off; exclusive sizes are then unknown.
+    let exclusive = run_tool(
+        "filesystem_snapshots",
+        "btrfs",
+        &[
+            OsStr::new("qgroup"),
+            OsStr::new("show"),
+            OsStr::new("--raw"),
+            mount.as_os_str(),
+        ],
+        LIST_TIMEOUT,
+    )
+    .map(|out| parse_qgroup_exclusive(&out))
+    .unwrap_or_default();
+    let snapper_config = run_tool(
+        "filesystem_snapshots",
+        "snapper",
+        &[
+            OsStr::new("--no-dbus"),
+            OsStr::new("--csvout"),
+            OsStr::new("list-configs"),
+        ],
+        LIST_TIMEOUT,
+    )
+    .ok()
+    .and_then(|out| {
+        parse_snapper_configs(&out)
+            .into_iter()
+            .find(|(_, subvolume)| subvolume == mount)
+            .map(|(config, _)| config)
+    });
+
+    Ok(parse_btrfs_snapshot_list(&listing)
+        .into_iter()
+        .map(|(id, created, path)| {
+            let manager = match (&snapper_config, snapper_number(&path)) {
+                (Some(config), Some(number)) => SnapshotManager::Snapper {
+                    config: config.clone(),
+                    number,
+                },
+                _ => SnapshotManager::Unmanaged,
+            };
+            FsSnapshotInfo {
+                kind: FsSnapshotKind::Btrfs,
+                name: path,
+                created,
+                sequence: id,
+                exclusive_bytes: exclusive.get(&id).copied(),
+                manager,
+                mount_path: mount.to_path_buf(),
+            }
+        })
+        .collect())
+}
+
+fn zfs_snapshots(mount: &MountPoint) -> Result<Vec<FsSnapshotInfo>> {
+    let dataset = mount.device.as_str();
+    if dataset.is_empty() || dataset.starts_with('-') || dataset.contains('@') {
+        return Ok(Vec::new());
+    }
+    let listing = run_tool(
+        "filesystem_snapshots",
+        "zfs",
+        &[
+            OsStr::new("list"),
+            OsStr::new("-H"),
+            OsStr::new("-p"),
+            OsStr::new("-t"),
+            OsStr::new("snapshot"),
+            OsStr::new("-d"),
+            OsStr::new("1"),
+            OsStr::new("-o"),
+            OsStr::new("name,used,creation,createtxg"),
+            OsStr::new(dataset),
+        ],
+        LIST_TIMEOUT,
+    )?;
+    Ok(parse_zfs_snapshots(&listing, &mount.path))
+}
+
+// ──────────────────── parsing ────────────────────
+
+/// `(subvolume id, otime, path)` rows of `btrfs subvolume list -s`, e.g.
+/// `ID 259 gen 14 cgen 14 top level 256 otime 2026-05-01 10:00:00 path @/.snapshots/1/snapshot`.
+fn parse_btrfs_snapshot_list(out: &str) -> Vec<(u64, Option<String>, String)> {
+    out.lines()
+        .filter_map(|line| {
+            let (fields, path) = line.split_once(" path ")?;
+            let tokens: Vec<&str> = fields.split_whitespace().collect();
+            let id = tokens
+                .windows(2)
+                .find(|pair| pair[0] == "ID")?
+                .get(1)?
+                .parse()
+                .ok()?;
+            let created = tokens
+                .iter()
+                .position(|token| *token == "otime")
+                .and_then(|at| Some(format!("{} {}", tokens.get(at + 1)?, tokens.get(at + 2)?)));
+            let path = path.trim().trim_start_matches("<FS_TREE>/").to_string();
+            Some((id, created, path))
+        })
+        .collect()
+}
+
+/// Exclusive bytes per level-0 qgroup (= subvolume id) from
+/// `btrfs qgroup show --raw`.
+fn parse_qgroup_exclusive(out: &str) -> HashMap<u64, u64> {
+    out.lines()
+        .filter_map(|line| {
+            let mut tokens = line.split_whitespace();
+            let id = tokens.next()?.strip_prefix("0/")?.parse().ok()?;
+            let _referenced: u64 = tokens.next()?.parse().ok()?;
+            let exclusive = tokens.next()?.parse().ok()?;
+            Some((id, exclusive))
+        })
+        .collect()
+}
+
+/// `(config, subvolume)` rows of `snapper --csvout list-configs`.
+fn parse_snapper_configs(out: &str) -> Vec<(String, PathBuf)> {
+    out.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let (config, subvolume) = line.split_once(',')?;
+            let config = config.trim();
+            if config.is_empty() {
+                return None;
+            }
+            Some((config.to_string(), PathBuf::from(subvolume.trim())))
+        })
+        .collect()
+}
+
+/// Snapshot number of a snapper snapshot path (`…/.snapshots/<n>/snapshot`).
+fn snapper_number(path: &str) -> Option<u64> {
+    let rest = path.strip_suffix("/snapshot")?;
+    let (parent, number) = rest.rsplit_once('/')?;
+    if parent != ".snapshots" && !parent.ends_with("/.snapshots") {
+        return None;
+    }
+    number.parse().ok()
+}
+
+/// Rows of `zfs list -H -p -o name,used,creation,createtxg`.
+fn parse_zfs_snapshots(out: &str, mount: &Path) -> Vec<FsSnapshotInfo> {
+    out.lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let name = fields.next()?.trim();
+            if !is_zfs_snapshot_name(name) {
+                return None;
+            }
+            let used = fields.next()?.trim().parse().ok();
+            let created = fields
+                .next()?
+                .trim()
+                .parse()
+                .ok()
+                .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+                .map(|at| at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+            let sequence = fields.next()?.trim().parse().ok()?;
+            let manager = zfs_auto_snapshot_label(name)
+                .map_or(SnapshotManager::Unmanaged, |label| {
+                    SnapshotManager::ZfsAutoSnapshot { label }
+                });
+            Some(FsSnapshotInfo {
+                kind: FsSnapshotKind::Zfs,
+                name: name.to_string(),
+                created,
+                sequence,
+                exclusive_bytes: used,
+                manager,
+                mount_path: mount.to_path_buf(),
+            })
+        })
+        .collect()
+}
+
+fn is_zfs_snapshot_name(name: &str) -> bool {
+    !name.starts_with('-')
+        && name
+            .split_once('@')
+            .is_some_and(|(dataset, snapshot)| !dataset.is_empty() && !snapshot.is_empty())
+}
+
+/// Schedule label of a zfs-auto-snapshot name
+/// (`pool/ds@zfs-auto-snap_hourly-2026-05-01-1000` → `hourly`).
+fn zfs_auto_snapshot_label(name: &str) -> Option<String> {
+    let (_, snapshot) = name.split_once('@')?;
+    let rest = snapshot.strip_prefix(ZFS_AUTO_SNAPSHOT_PREFIX)?;
+    let label = rest.split_once('-').map_or(rest, |(label, _)| label);
+    (!label.is_empty()).then(|| label.to_string())
+}
+
+// ──────────────────── command runner ────────────────────
+
+fn failed(method: &'static str, details: impl Into<String>) -> crate::core::errors::SbhError {
+    PalError::method_failed("linux", method, details).into()
+}
+
+/// Run a snapshot tool with a deadline and return its stdout.
+fn run_tool(
+    method: &'static str,
+    program: &str,
+    args: &[&OsStr],
+    timeout: Duration,
+) -> Result<String> {
+    let rendered = || {
+        let mut command_line = vec![program.to_string()];
+        command_line.extend(args.iter().map(|arg| arg.to_string_lossy().into_owned()));
+        command_line.join(" ")
+    };
+    let mut child = Command::new(program)
+        .args(args)
+        .env("LC_ALL", "C")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| failed(method, format!("could not start {program}: {error}")))?;
+    // Drain both pipes on their own threads so a chatty tool cannot block
+    // on a full pipe while we wait for it.
+    let stdout = child.stdout.take().map(drain);
+    let stderr = child.stderr.take().map(drain);
+
+    let start = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if start.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(failed(
+                    method,
+                    format!("{} killed after {}s", rendered(), timeout.as_secs()),
+                ));
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(error) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(failed(method, format!("{}: {error}", rendered())));
+            }
+        }
+    };
+    let stdout = stdout
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default();
+    let stderr = stderr
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default();
+    if !status.success() {
+        return Err(failed(
+            method,
+            format!("{} exited with {status}: {}", rendered(), stderr.trim()),
+        ));
+    }
+    Ok(stdout)
+}
+
+fn drain(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        String::from_utf8_lossy(&buf).into_owned()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn btrfs_listing_maps_snapper_snapshots_and_qgroup_sizes() {
+        let listing = "\
+ID 259 gen 14 cgen 14 top level 256 otime 2026-05-01 10:00:00 path @/.snapshots/1/snapshot
+ID 262 gen 31 cgen 31 top level 256 otime 2026-05-02 10:00:00 path <FS_TREE>/@/.snapshots/2/snapshot
+ID 270 gen 40 cgen 40 top level 256 otime 2026-05-03 09:30:12 path @/manual backup
+";
+        let rows = parse_btrfs_snapshot_list(listing);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0],
+            (
+                259,
+                Some("2026-05-01 10:00:00".to_string()),
+                "@/.snapshots/1/snapshot".to_string()
+            )
+        );
+        assert_eq!(rows[1].2, "@/.snapshots/2/snapshot");
+        assert_eq!(rows[2].2, "@/manual backup");
+
+        assert_eq!(snapper_number(&rows[0].2), Some(1));
+        assert_eq!(snapper_number(".snapshots/17/snapshot"), Some(17));
+        assert_eq!(snapper_number(&rows[2].2), None);
+        assert_eq!(snapper_number("@/not.snapshots/3/snapshot"), None);
+
+        // Older btrfs-progs print no path column; newer ones do.
+        let qgroups = "\
+qgroupid         rfer         excl
+--------         ----         ----
+0/5          16384        16384
+0/259    734003200    524288000
+Qgroupid    Referenced    Exclusive   Path
+0/262    10000    4096   @/.snapshots/2/snapshot
+1/100    1    1
+";
+        let exclusive = parse_qgroup_exclusive(qgroups);
+        assert_eq!(exclusive.get(&259), Some(&524_288_000));
+        assert_eq!(exclusive.get(&262), Some(&4_096));
+        assert!(!exclusive.contains_key(&100));
+    }
+
+    #[test]
+    fn snapper_configs_parse_from_csv() {
+        let configs = parse_snapper_configs("config,subvolume\nroot,/\nhome,/home\n");
+        assert_eq!(
+            configs,
+            vec![
+                ("root".to_string(), PathBuf::from("/")),
+                ("home".to_string(), PathBuf::from("/home")),
+            ]
+        );
+    }
+
+    #[test]
+    fn zfs_listing_recognises_auto_snapshots_only() {
+        let listing = "\
+tank/home@zfs-auto-snap_hourly-2026-05-01-1000\t1048576\t1777629600\t8812
+tank/home@zfs-auto-snap_daily-2026-05-01-0000\t0\t1777593600\t8790
+tank/home@before-upgrade\t52428800\t1777500000\t8700
+tank/home\t1\t1\t1
+";
+        let snapshots = parse_zfs_snapshots(listing, Path::new("/home"));
+        assert_eq!(snapshots.len(), 3);
+        assert_eq!(
+            snapshots[0].manager,
+            SnapshotManager::ZfsAutoSnapshot {
+                label: "hourly".to_string()
+            }
+        );
+        assert_eq!(snapshots[0].exclusive_bytes, Some(1_048_576));
+        assert_eq!(snapshots[0].sequence, 8812);
+        assert_eq!(
+            snapshots[0].created.as_deref(),
+            Some("2026-05-01T10:00:00Z")
+        );
+        assert_eq!(
+            snapshots[0].retention_group().as_deref(),
+            Some("zfs-auto-snapshot:tank/home:hourly")
+        );
+        assert_eq!(snapshots[2].manager, SnapshotManager::Unmanaged);
+        assert!(!is_zfs_snapshot_name("tank/home"));
+        assert!(!is_zfs_snapshot_name("-r@x"));
+    }
+
+    #[test]
+    fn unmanaged_and_dataset_names_are_never_destroyed() {
+        let mut snapshot = FsSnapshotInfo {
+            kind: FsSnapshotKind::Zfs,
+            name: "tank/home@before-upgrade".to_string(),
+            created: None,
+            sequence: 1,
+            exclusive_bytes: Some(1),
+            manager: SnapshotManager::Unmanaged,
+            mount_path: PathBuf::from("/home"),
+        };
+        assert!(destroy_snapshot(&snapshot).is_err());
+
+        snapshot.name = "tank/home".to_string();
+        snapshot.manager = SnapshotManager::ZfsAutoSnapshot {
+            label: "hourly".to_string(),
+        };
+        let error = destroy_snapshot(&snapshot).unwrap_err().to_string();
+        assert!(error.contains("refusing"), "{error}");
+    }
+}
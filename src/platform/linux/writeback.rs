@@ -66,6 +66,16 @@ pub(super) fn block_device_for(path: &Path, mounts: &[MountPoint]) -> Result<Blo
     })
 }
 
+/// `MAJ:MIN` of a base block device, as cgroup `io.max` expects it.
+pub(super) fn device_number(device: &str) -> Result<String> {
+    let path = Path::new("/sys/block").join(device).join("dev");
+    let raw = fs::read_to_string(&path).map_err(|source| SbhError::Io {
+        path: path.clone(),
+        source,
+    })?;
+    Ok(raw.trim().to_string())
+}
+
 fn read_u64_opt(name: &str) -> Option<u64> {
     read_u64_at(&PathBuf::from(PROC_VM).join(name))
 }
@@ -2,6 +2,9 @@
 
 #![allow(missing_docs)]
 
+use std::borrow::Cow;
+use std::sync::{Arc, LazyLock};
+
 use crate::platform::cleanup_catalog;
 pub use crate::platform::cleanup_catalog::{
     AgeThreshold, CheckRequirement, CleanupConfidence, CleanupRule, ReclaimCommand,
@@ -297,24 +300,27 @@ pub const MAC_CLEANUP_RULES: &[CleanupRule] = &[
     FINAL_CUT_LIBRARY_SACRED,
 ];
 
+static CATALOG: LazyLock<Arc<[CleanupRule]>> = LazyLock::new(|| MAC_CLEANUP_RULES.into());
+
+/// Snapshot of the catalog, shaped like the Linux one.
 #[must_use]
-pub fn cleanup_rules() -> &'static [CleanupRule] {
-    MAC_CLEANUP_RULES
+pub fn cleanup_rules() -> Arc<[CleanupRule]> {
+    Arc::clone(&CATALOG)
 }
 
 #[must_use]
-pub fn find_rule(name: &str) -> Option<&'static CleanupRule> {
-    cleanup_catalog::find_rule(MAC_CLEANUP_RULES, name)
+pub fn find_rule(name: &str) -> Option<CleanupRule> {
+    cleanup_catalog::find_rule(MAC_CLEANUP_RULES, name).cloned()
 }
 
 #[must_use]
-pub fn match_rule(path: &std::path::Path) -> Option<&'static CleanupRule> {
-    cleanup_catalog::match_rule(path, MAC_CLEANUP_RULES)
+pub fn match_rule(path: &std::path::Path) -> Option<CleanupRule> {
+    cleanup_catalog::match_rule(path, MAC_CLEANUP_RULES).cloned()
 }
 
 #[must_use]
-pub fn match_path_scanner_rule(path: &std::path::Path) -> Option<&'static CleanupRule> {
-    cleanup_catalog::match_path_scanner_rule(path, MAC_CLEANUP_RULES)
+pub fn match_path_scanner_rule(path: &std::path::Path) -> Option<CleanupRule> {
+    cleanup_catalog::match_path_scanner_rule(path, MAC_CLEANUP_RULES).cloned()
 }
 
 const fn cleanup_rule(
@@ -326,8 +332,8 @@ const fn cleanup_rule(
     confidence: CleanupConfidence,
 ) -> CleanupRule {
     CleanupRule {
-        name,
-        path_glob,
+        name: Cow::Borrowed(name),
+        path_glob: Cow::Borrowed(path_glob),
         age_threshold,
         fd_check,
         parent_check: CheckRequirement::Required,
@@ -339,8 +345,8 @@ const fn cleanup_rule(
 
 const fn sacred_rule(name: &'static str, path_glob: &'static str) -> CleanupRule {
     CleanupRule {
-        name,
-        path_glob,
+        name: Cow::Borrowed(name),
+        path_glob: Cow::Borrowed(path_glob),
         age_threshold: AgeThreshold::NONE,
         fd_check: CheckRequirement::NotRequired,
         parent_check: CheckRequirement::NotRequired,
@@ -374,7 +380,7 @@ mod tests {
         let mut names = HashSet::new();
         for rule in MAC_CLEANUP_RULES {
             assert!(
-                names.insert(rule.name),
+                names.insert(&rule.name),
                 "duplicate rule name: {}",
                 rule.name
             );
@@ -383,8 +389,8 @@ mod tests {
 
     #[test]
     fn exported_catalog_is_the_static_catalog() {
-        assert_eq!(cleanup_rules(), MAC_CLEANUP_RULES);
-        assert_eq!(find_rule("xcode-derived-data"), Some(&XCODE_DERIVED_DATA));
+        assert_eq!(&*cleanup_rules(), MAC_CLEANUP_RULES);
+        assert_eq!(find_rule("xcode-derived-data"), Some(XCODE_DERIVED_DATA));
         assert!(find_rule("missing").is_none());
     }
 
@@ -440,7 +446,7 @@ mod tests {
     #[test]
     fn xcode_derived_data_rule_is_definite() {
         assert_rule(
-            XCODE_DERIVED_DATA,
+            &XCODE_DERIVED_DATA,
             "xcode-derived-data",
             "~/Library/Developer/Xcode/DerivedData/*",
             CleanupConfidence::Definite,
@@ -452,7 +458,7 @@ mod tests {
     #[test]
     fn core_simulator_cache_rule_is_definite() {
         assert_rule(
-            CORE_SIMULATOR_CACHES,
+            &CORE_SIMULATOR_CACHES,
             "core-simulator-caches",
             "~/Library/Developer/CoreSimulator/Caches/*",
             CleanupConfidence::Definite,
@@ -519,7 +525,7 @@ mod tests {
 
         for (rule, path_glob, name) in rules {
             assert_rule(
-                rule,
+                &rule,
                 name,
                 path_glob,
                 CleanupConfidence::Likely,
@@ -571,7 +577,7 @@ mod tests {
             ),
         ] {
             assert_rule(
-                rule,
+                &rule,
                 name,
                 path_glob,
                 CleanupConfidence::Unclear,
@@ -585,7 +591,7 @@ mod tests {
     #[test]
     fn release_work_buildroot_rule_is_likely_after_seven_days() {
         assert_rule(
-            RELEASE_WORK_BUILDROOT,
+            &RELEASE_WORK_BUILDROOT,
             "release-work-buildroot",
             "~/release-work/*[-_]buildroot",
             CleanupConfidence::Likely,
@@ -597,7 +603,7 @@ mod tests {
     #[test]
     fn user_logs_rule_preserves_recent_logs() {
         assert_rule(
-            USER_LOGS,
+            &USER_LOGS,
             "user-logs",
             "~/Library/Logs/*",
             CleanupConfidence::Likely,
@@ -609,7 +615,7 @@ mod tests {
     #[test]
     fn ipsw_rule_keeps_recent_firmware_files() {
         assert_rule(
-            IPSW_SOFTWARE_UPDATES,
+            &IPSW_SOFTWARE_UPDATES,
             "ipsw-software-updates",
             "~/Library/iTunes/iPhone Software Updates/*.ipsw",
             CleanupConfidence::Definite,
@@ -622,10 +628,10 @@ mod tests {
     fn ipsw_rule_only_matches_software_update_firmware_files() {
         let firmware =
             Path::new("/Users/operator/Library/iTunes/iPhone Software Updates/iPhone_17.ipsw");
-        assert_eq!(match_rule(firmware), Some(&IPSW_SOFTWARE_UPDATES));
+        assert_eq!(match_rule(firmware), Some(IPSW_SOFTWARE_UPDATES));
         assert_eq!(
             match_path_scanner_rule(firmware),
-            Some(&IPSW_SOFTWARE_UPDATES)
+            Some(IPSW_SOFTWARE_UPDATES)
         );
 
         for path in [
@@ -648,7 +654,7 @@ mod tests {
     #[test]
     fn time_machine_rule_uses_tmutil_not_path_deletion() {
         assert_rule(
-            TIME_MACHINE_LOCAL_SNAPSHOTS,
+            &TIME_MACHINE_LOCAL_SNAPSHOTS,
             "time-machine-local-snapshots",
             "/",
             CleanupConfidence::Likely,
@@ -675,7 +681,7 @@ mod tests {
     }
 
     fn assert_rule(
-        rule: CleanupRule,
+        rule: &CleanupRule,
         name: &str,
         path_glob: &str,
         confidence: CleanupConfidence,
@@ -695,6 +695,8 @@ fn capacity_to_fs_stats(capacity: Capacity) -> FsStats {
         fs_type: capacity.fs_type,
         mount_point: capacity.mount_point,
         is_readonly: capacity.is_readonly,
+        total_inodes: capacity.total_inodes,
+        free_inodes: capacity.free_inodes,
     }
 }
 
@@ -741,6 +743,9 @@ fn statfs_to_capacity(stats: StatfsSnapshot, inventory: Option<&ApfsInventory>)
         is_primary,
         purgeable_bytes: None,
         local_snapshot_bytes: None,
+        // APFS allocates inodes on demand; there is no table to exhaust.
+        total_inodes: 0,
+        free_inodes: 0,
     }
 }
 
@@ -897,6 +902,8 @@ fn process_info_from_task_all(
         resident_memory_bytes: Some(raw.ptinfo.pti_resident_size),
         cpu_user_micros: Some(nanos_to_micros(raw.ptinfo.pti_total_user)),
         cpu_system_micros: Some(nanos_to_micros(raw.ptinfo.pti_total_system)),
+        uid: None,
+        cgroup: None,
     }
 }
 
@@ -1500,6 +1507,8 @@ mod tests {
             is_primary: true,
             purgeable_bytes: Some(50),
             local_snapshot_bytes: Some(64),
+            total_inodes: 0,
+            free_inodes: 0,
         };
 
         let stats = super::capacity_to_fs_stats(capacity);
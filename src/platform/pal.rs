@@ -12,9 +12,9 @@ use serde::{Deserialize, Serialize};
 use crate::core::config::PathsConfig;
 use crate::core::errors::{Result, SbhError};
 use crate::platform::types::{
-    Capacity, FullDiskAccessStatus, LocalSnapshotInfo, MappedRegion, MemoryPressure,
-    MemoryPressureCallback, MemoryPressureLevel, MountInfo, OpenFile, PalError, ProcessInfo,
-    ProcessIo, SacredPath, SelfStats, ServiceKind, SubscriptionHandle,
+    Capacity, CgroupIo, DeletedOpenFile, FsSnapshotInfo, FullDiskAccessStatus, LocalSnapshotInfo,
+    MappedRegion, MemoryPressure, MemoryPressureCallback, MemoryPressureLevel, MountInfo, OpenFile,
+    PalError, ProcessInfo, ProcessIo, SacredPath, SelfStats, ServiceKind, SubscriptionHandle,
 };
 
 /// Filesystem statistics for a path/mount.
@@ -26,6 +26,13 @@ pub struct FsStats {
     pub fs_type: String,
     pub mount_point: PathBuf,
     pub is_readonly: bool,
+    /// Inode table size; 0 on filesystems that allocate inodes dynamically
+    /// (btrfs, ZFS, APFS), where inode exhaustion cannot happen.
+    #[serde(default)]
+    pub total_inodes: u64,
+    /// Inodes available to unprivileged users (`f_favail`).
+    #[serde(default)]
+    pub free_inodes: u64,
 }
 
 impl FsStats {
@@ -39,6 +46,17 @@ impl FsStats {
             (self.available_bytes as f64 * 100.0) / self.total_bytes as f64
         }
     }
+
+    /// Percentage of inodes still available, or `None` when the filesystem
+    /// has no fixed inode table.
+    #[must_use]
+    pub fn inode_free_pct(&self) -> Option<f64> {
+        if self.total_inodes == 0 {
+            return None;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        Some((self.free_inodes as f64 * 100.0) / self.total_inodes as f64)
+    }
 }
 
 /// Mount-point metadata.
@@ -104,6 +122,8 @@ impl From<FsStats> for Capacity {
             is_primary: false,
             purgeable_bytes: None,
             local_snapshot_bytes: None,
+            total_inodes: value.total_inodes,
+            free_inodes: value.free_inodes,
         }
     }
 }
@@ -229,6 +249,18 @@ pub trait Platform: Send + Sync {
         Ok(Vec::new())
     }
 
+    /// btrfs subvolume snapshots and ZFS snapshots of the filesystem mounted
+    /// at `mount`. Empty on other filesystems.
+    fn filesystem_snapshots(&self, _mount: &Path) -> Result<Vec<FsSnapshotInfo>> {
+        Ok(Vec::new())
+    }
+
+    /// Destroy one managed snapshot through the tool that owns it. Requires
+    /// privilege; unmanaged snapshots are refused.
+    fn destroy_filesystem_snapshot(&self, _snapshot: &FsSnapshotInfo) -> Result<()> {
+        pal_not_implemented(self.name(), "destroy_filesystem_snapshot")
+    }
+
     fn memory_pressure(&self) -> Result<MemoryPressure> {
         pal_not_implemented(self.name(), "memory_pressure")
     }
@@ -252,10 +284,61 @@ pub trait Platform: Send + Sync {
         pal_not_implemented(self.name(), "process_io")
     }
 
+    /// Cumulative I/O of a cgroup v2 path as reported by [`ProcessInfo::cgroup`].
+    fn cgroup_io(&self, _cgroup: &str) -> Result<CgroupIo> {
+        pal_not_implemented(self.name(), "cgroup_io")
+    }
+
+    /// The `MAJ:MIN` number of the block device backing `path`, as used by
+    /// the cgroup `io.*` files.
+    fn block_device_number(&self, _path: &Path) -> Result<String> {
+        pal_not_implemented(self.name(), "block_device_number")
+    }
+
+    /// The write-bandwidth limit a cgroup has on the block device backing
+    /// `path`, or `None` when it is unlimited.
+    fn cgroup_write_limit(&self, _cgroup: &str, _path: &Path) -> Result<Option<u64>> {
+        pal_not_implemented(self.name(), "cgroup_write_limit")
+    }
+
+    /// Cap (or with `None`, lift) the write bandwidth of a cgroup on the block
+    /// device backing `path`. Requires privilege and a delegated `io` controller.
+    fn set_cgroup_write_limit(
+        &self,
+        _cgroup: &str,
+        _path: &Path,
+        _bytes_per_sec: Option<u64>,
+    ) -> Result<()> {
+        pal_not_implemented(self.name(), "set_cgroup_write_limit")
+    }
+
+    /// Freeze (or thaw) every process in a cgroup. Requires privilege over
+    /// the cgroup's members.
+    fn set_cgroup_frozen(&self, _cgroup: &str, _frozen: bool) -> Result<()> {
+        pal_not_implemented(self.name(), "set_cgroup_frozen")
+    }
+
+    /// Every cgroup v2 path without child cgroups, i.e. every cgroup that can
+    /// hold processes, including ones whose processes have all exited.
+    fn leaf_cgroups(&self) -> Result<Vec<String>> {
+        pal_not_implemented(self.name(), "leaf_cgroups")
+    }
+
+    /// The cgroup v2 path of the calling process.
+    fn self_cgroup(&self) -> Result<String> {
+        pal_not_implemented(self.name(), "self_cgroup")
+    }
+
     fn open_files_under(&self, _path: &Path) -> Result<Vec<OpenFile>> {
         pal_not_implemented(self.name(), "open_files_under")
     }
 
+    /// Every open fd, in any process visible to the caller, whose file has
+    /// been deleted.
+    fn deleted_open_files(&self) -> Result<Vec<DeletedOpenFile>> {
+        pal_not_implemented(self.name(), "deleted_open_files")
+    }
+
     fn executables_under(&self, _path: &Path) -> Result<Vec<ProcessInfo>> {
         pal_not_implemented(self.name(), "executables_under")
     }
@@ -369,7 +452,9 @@ pub struct MockPlatform {
     subscription: SubscriptionHandle,
     processes: Vec<ProcessInfo>,
     process_io: HashMap<i32, ProcessIo>,
+    cgroup_io: HashMap<String, CgroupIo>,
     open_files: Vec<OpenFile>,
+    deleted_open_files: Vec<DeletedOpenFile>,
     executables: Vec<ProcessInfo>,
     mmap_regions: Vec<MappedRegion>,
     self_stats: SelfStats,
@@ -377,6 +462,7 @@ pub struct MockPlatform {
     preallocate_failures: HashMap<PathBuf, PalError>,
     block_counts: HashMap<PathBuf, u64>,
     local_snapshots: HashMap<PathBuf, Vec<LocalSnapshotInfo>>,
+    filesystem_snapshots: HashMap<PathBuf, Vec<FsSnapshotInfo>>,
     home: PathBuf,
     temp_dirs: Vec<PathBuf>,
     cache_roots: Vec<PathBuf>,
@@ -403,7 +489,9 @@ impl MockPlatform {
             subscription: SubscriptionHandle::active("mock"),
             processes: Vec::new(),
             process_io: HashMap::new(),
+            cgroup_io: HashMap::new(),
             open_files: Vec::new(),
+            deleted_open_files: Vec::new(),
             executables: Vec::new(),
             mmap_regions: Vec::new(),
             self_stats: default_mock_self_stats(),
@@ -411,6 +499,7 @@ impl MockPlatform {
             preallocate_failures: HashMap::new(),
             block_counts: HashMap::new(),
             local_snapshots: HashMap::new(),
+            filesystem_snapshots: HashMap::new(),
             home: PathBuf::from("/home/mock"),
             temp_dirs: vec![PathBuf::from("/tmp")],
             cache_roots: vec![PathBuf::from("/home/mock/.cache")],
@@ -429,6 +518,8 @@ impl MockPlatform {
             fs_type: "mockfs".to_string(),
             mount_point: mount.clone(),
             is_readonly: false,
+            total_inodes: 0,
+            free_inodes: 0,
         };
         let mut stats_by_mount = HashMap::new();
         stats_by_mount.insert(mount.clone(), stats);
@@ -486,12 +577,24 @@ impl MockPlatform {
         self
     }
 
+    #[must_use]
+    pub fn with_cgroup_io(mut self, cgroup: impl Into<String>, io: CgroupIo) -> Self {
+        self.cgroup_io.insert(cgroup.into(), io);
+        self
+    }
+
     #[must_use]
     pub fn with_open_file(mut self, open_file: OpenFile) -> Self {
         self.open_files.push(open_file);
         self
     }
 
+    #[must_use]
+    pub fn with_deleted_open_file(mut self, deleted: DeletedOpenFile) -> Self {
+        self.deleted_open_files.push(deleted);
+        self
+    }
+
     #[must_use]
     pub fn with_executable(mut self, process: ProcessInfo) -> Self {
         self.executables.push(process);
@@ -538,6 +641,16 @@ impl MockPlatform {
         self
     }
 
+    #[must_use]
+    pub fn with_filesystem_snapshots(
+        mut self,
+        mount: impl Into<PathBuf>,
+        snapshots: Vec<FsSnapshotInfo>,
+    ) -> Self {
+        self.filesystem_snapshots.insert(mount.into(), snapshots);
+        self
+    }
+
     #[must_use]
     pub fn with_home(mut self, home: impl Into<PathBuf>) -> Self {
         self.home = home.into();
@@ -596,6 +709,14 @@ impl Platform for MockPlatform {
         Ok(self.local_snapshots.get(mount).cloned().unwrap_or_default())
     }
 
+    fn filesystem_snapshots(&self, mount: &Path) -> Result<Vec<FsSnapshotInfo>> {
+        Ok(self
+            .filesystem_snapshots
+            .get(mount)
+            .cloned()
+            .unwrap_or_default())
+    }
+
     fn is_ram_backed(&self, path: &Path) -> Result<bool> {
         Ok(find_mount(path, &self.mounts).is_some_and(|mount| mount.is_ram_backed))
     }
@@ -638,6 +759,19 @@ impl Platform for MockPlatform {
             .ok_or_else(|| PalError::not_implemented(self.name(), "process_io").into())
     }
 
+    fn cgroup_io(&self, cgroup: &str) -> Result<CgroupIo> {
+        self.cgroup_io
+            .get(cgroup)
+            .cloned()
+            .ok_or_else(|| PalError::not_implemented(self.name(), "cgroup_io").into())
+    }
+
+    fn leaf_cgroups(&self) -> Result<Vec<String>> {
+        let mut cgroups: Vec<String> = self.cgroup_io.keys().cloned().collect();
+        cgroups.sort();
+        Ok(cgroups)
+    }
+
     fn open_files_under(&self, path: &Path) -> Result<Vec<OpenFile>> {
         let root = crate::core::paths::resolve_absolute_path(path);
         Ok(self
@@ -650,6 +784,10 @@ impl Platform for MockPlatform {
             .collect())
     }
 
+    fn deleted_open_files(&self) -> Result<Vec<DeletedOpenFile>> {
+        Ok(self.deleted_open_files.clone())
+    }
+
     fn executables_under(&self, path: &Path) -> Result<Vec<ProcessInfo>> {
         let root = crate::core::paths::resolve_absolute_path(path);
         Ok(self
@@ -826,6 +964,8 @@ mod tests {
             resident_memory_bytes: None,
             cpu_user_micros: None,
             cpu_system_micros: None,
+            uid: None,
+            cgroup: None,
         }
     }
 
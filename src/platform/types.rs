@@ -3,6 +3,7 @@
 #![allow(missing_docs)]
 
 use std::any::Any;
+use std::collections::BTreeMap;
 use std::fmt;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -131,6 +132,12 @@ pub struct Capacity {
     pub is_primary: bool,
     pub purgeable_bytes: Option<u64>,
     pub local_snapshot_bytes: Option<u64>,
+    /// Inode table size; 0 where inodes are allocated dynamically.
+    #[serde(default)]
+    pub total_inodes: u64,
+    /// Inodes available to unprivileged users.
+    #[serde(default)]
+    pub free_inodes: u64,
 }
 
 #[allow(clippy::struct_excessive_bools)]
@@ -159,6 +166,85 @@ pub struct LocalSnapshotInfo {
     pub mount_path: PathBuf,
 }
 
+/// Copy-on-write filesystem a [`FsSnapshotInfo`] belongs to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FsSnapshotKind {
+    Btrfs,
+    Zfs,
+}
+
+impl fmt::Display for FsSnapshotKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Btrfs => f.write_str("btrfs"),
+            Self::Zfs => f.write_str("zfs"),
+        }
+    }
+}
+
+/// Tool that created a snapshot. Only managed snapshots are ever thinned.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "tool")]
+pub enum SnapshotManager {
+    /// snapper, with the config that owns the mount and the snapshot number.
+    Snapper { config: String, number: u64 },
+    /// zfs-auto-snapshot, with its schedule label (`hourly`, `daily`, ...).
+    ZfsAutoSnapshot { label: String },
+    /// Taken by hand or by a tool sbh does not recognise.
+    Unmanaged,
+}
+
+impl SnapshotManager {
+    /// Tool name: `snapper`, `zfs-auto-snapshot` or `unmanaged`.
+    #[must_use]
+    pub const fn tool(&self) -> &'static str {
+        match self {
+            Self::Snapper { .. } => "snapper",
+            Self::ZfsAutoSnapshot { .. } => "zfs-auto-snapshot",
+            Self::Unmanaged => "unmanaged",
+        }
+    }
+}
+
+/// A btrfs subvolume snapshot or ZFS snapshot on a mount.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FsSnapshotInfo {
+    pub kind: FsSnapshotKind,
+    /// btrfs: subvolume path from the filesystem top; ZFS: `dataset@snapshot`.
+    pub name: String,
+    /// Creation time as the filesystem reports it.
+    pub created: Option<String>,
+    /// Creation order within the filesystem (btrfs subvolume ID, ZFS `createtxg`).
+    pub sequence: u64,
+    /// Bytes referenced by this snapshot alone, freed when it is destroyed.
+    /// `None` when the filesystem cannot say (btrfs without quotas).
+    pub exclusive_bytes: Option<u64>,
+    pub manager: SnapshotManager,
+    pub mount_path: PathBuf,
+}
+
+impl FsSnapshotInfo {
+    #[must_use]
+    pub fn is_managed(&self) -> bool {
+        !matches!(self.manager, SnapshotManager::Unmanaged)
+    }
+
+    /// Snapshots sharing a retention schedule: one snapper config, or one
+    /// zfs-auto-snapshot label on one dataset. `None` for unmanaged snapshots.
+    #[must_use]
+    pub fn retention_group(&self) -> Option<String> {
+        match &self.manager {
+            SnapshotManager::Snapper { config, .. } => Some(format!("snapper:{config}")),
+            SnapshotManager::ZfsAutoSnapshot { label } => {
+                let dataset = self.name.split_once('@').map_or("", |(dataset, _)| dataset);
+                Some(format!("zfs-auto-snapshot:{dataset}:{label}"))
+            }
+            SnapshotManager::Unmanaged => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MemoryPressureLevel {
     Normal,
@@ -317,6 +403,24 @@ pub struct ProcessInfo {
     pub resident_memory_bytes: Option<u64>,
     pub cpu_user_micros: Option<u64>,
     pub cpu_system_micros: Option<u64>,
+    /// Real user id.
+    #[serde(default)]
+    pub uid: Option<u32>,
+    /// cgroup v2 path relative to the cgroup mount (Linux), e.g.
+    /// `/system.slice/docker-1234.scope`.
+    #[serde(default)]
+    pub cgroup: Option<String>,
+}
+
+/// Cumulative block I/O charged to a cgroup (`io.stat`), including processes
+/// that have already exited.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CgroupIo {
+    pub bytes_read: u64,
+    pub bytes_written: u64,
+    /// `bytes_written` split by block device, keyed by `MAJ:MIN`.
+    #[serde(default)]
+    pub bytes_written_by_device: BTreeMap<String, u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -356,6 +460,27 @@ pub struct OpenFile {
     pub mode: OpenFileMode,
 }
 
+/// An open fd whose file has been unlinked: its blocks stay allocated until
+/// every fd (and mapping) is closed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeletedOpenFile {
+    pub pid: i32,
+    pub process_name: Option<String>,
+    pub fd: i32,
+    /// Path the file had before it was unlinked.
+    pub path: PathBuf,
+    pub device: u64,
+    pub inode: u64,
+    pub size_bytes: u64,
+    /// Blocks actually allocated (sparse files hold less than their size).
+    pub allocated_bytes: u64,
+    pub mode: OpenFileMode,
+    /// The fd was opened with `O_APPEND`.
+    pub append: bool,
+    /// Some process also has the file memory-mapped.
+    pub mapped: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct MappedRegion {
     pub pid: i32,
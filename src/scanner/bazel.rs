@@ -0,0 +1,339 @@
+//! Bazel output bases and cache pruning.
+//!
+//! Bazel keeps every workspace's outputs in an output base,
+//! `<output_user_root>/<md5 of the workspace path>`, where the output user
+//! root defaults to `~/.cache/bazel/_bazel_$USER`. The workspace only keeps
+//! `bazel-*` symlinks into it. An output base names its workspace in
+//! `DO_NOT_BUILD_HERE`, and older releases name it in `README` as well. The
+//! scanner uses that marker to tell a live output base from an abandoned one
+//! whose workspace is gone.
+//!
+//! The repository cache (`<output_user_root>/cache/repos`) and any
+//! `--disk_cache` directory are content-addressed and shared by every
+//! workspace, so they are never removed whole. With `[scanner.bazel]`
+//! `prune_by_access` enabled, files nobody has read since the cutoff are
+//! removed from them instead.
+
+#![allow(missing_docs)]
+
+use std::collections::HashSet;
+use std::fs;
+use std::hash::BuildHasher;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::core::config::BazelCacheConfig;
+
+/// Marker file Bazel writes into every output base; holds the workspace path.
+const DO_NOT_BUILD_HERE: &str = "DO_NOT_BUILD_HERE";
+
+/// Files whose presence makes a directory a Bazel workspace root.
+const WORKSPACE_MARKERS: &[&str] = &["MODULE.bazel", "WORKSPACE", "WORKSPACE.bazel", "REPO.bazel"];
+
+/// Length of the md5 hex digest Bazel names output bases with.
+const OUTPUT_BASE_HASH_LEN: usize = 32;
+
+/// A directory identified as a Bazel output base.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BazelOutputBase {
+    /// Workspace named by the output base's markers, when readable.
+    pub workspace: Option<PathBuf>,
+    /// The named workspace no longer exists or is no longer a Bazel workspace.
+    pub abandoned: bool,
+}
+
+impl BazelOutputBase {
+    /// Repository cache shared by every output base under the same root.
+    #[must_use]
+    pub fn repository_cache(path: &Path) -> Option<PathBuf> {
+        path.parent().map(|root| root.join("cache").join("repos"))
+    }
+}
+
+/// Identify `path` as a Bazel output base.
+///
+/// The name checks (an md5-hex name under a `_bazel_*` parent) run first so
+/// only plausible directories cost a `stat` and a small read.
+#[must_use]
+pub fn detect_output_base(path: &Path) -> Option<BazelOutputBase> {
+    let name = path.file_name()?.to_str()?;
+    let parent = path.parent()?.file_name()?.to_str()?;
+    if !parent.starts_with("_bazel_") || !is_output_base_hash(name) {
+        return None;
+    }
+    let marker = path.join(DO_NOT_BUILD_HERE);
+    let readme = path.join("README");
+    if !marker.is_file() && !readme.is_file() {
+        return None;
+    }
+    let workspace = fs::read_to_string(&marker)
+        .ok()
+        .and_then(|contents| absolute_path(contents.trim()))
+        .or_else(|| {
+            fs::read_to_string(&readme)
+                .ok()
+                .and_then(|contents| contents.split_whitespace().find_map(absolute_path))
+        });
+    let abandoned = workspace
+        .as_deref()
+        .is_some_and(|workspace| !is_bazel_workspace(workspace));
+    Some(BazelOutputBase {
+        workspace,
+        abandoned,
+    })
+}
+
+fn is_output_base_hash(name: &str) -> bool {
+    name.len() == OUTPUT_BASE_HASH_LEN
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+/// `token` as an absolute path, without sentence punctuation around it.
+fn absolute_path(token: &str) -> Option<PathBuf> {
+    let token = token.trim_end_matches(['.', ',', ';', ':', ')']);
+    (token.len() > 1 && token.starts_with('/')).then(|| PathBuf::from(token))
+}
+
+fn is_bazel_workspace(dir: &Path) -> bool {
+    WORKSPACE_MARKERS
+        .iter()
+        .any(|marker| dir.join(marker).is_file())
+}
+
+/// Outcome of one access-time prune over the Bazel caches.
+#[derive(Debug, Clone, Default)]
+pub struct BazelPruneReport {
+    pub caches_pruned: usize,
+    pub files_removed: usize,
+    pub files_would_remove: usize,
+    /// Stale files kept because a process holds them open.
+    pub files_open: usize,
+    pub bytes_freed: u64,
+    pub bytes_would_free: u64,
+    pub errors: Vec<(PathBuf, String)>,
+    pub duration: Duration,
+    pub dry_run: bool,
+}
+
+/// Remove files not accessed within `max_access_age_days` from each cache.
+///
+/// A file's last use is the later of its access and modification times, so
+/// a `noatime` mount degrades to pruning by write time. Directories left
+/// empty are removed; the cache roots themselves always stay.
+/// `open_paths` is an open-path ancestor index covering `caches`.
+#[must_use]
+pub fn prune_caches<S: BuildHasher>(
+    caches: &[PathBuf],
+    config: &BazelCacheConfig,
+    open_paths: &HashSet<PathBuf, S>,
+    now: SystemTime,
+    dry_run: bool,
+) -> BazelPruneReport {
+    let start = Instant::now();
+    let mut report = BazelPruneReport {
+        dry_run,
+        ..BazelPruneReport::default()
+    };
+    let cutoff = now
+        .checked_sub(Duration::from_hours(
+            config.max_access_age_days.saturating_mul(24),
+        ))
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    for cache in caches {
+        if !fs::symlink_metadata(cache).is_ok_and(|meta| meta.is_dir()) {
+            continue;
+        }
+        report.caches_pruned += 1;
+        prune_dir(cache, cutoff, open_paths, dry_run, &mut report);
+    }
+    report.duration = start.elapsed();
+    report
+}
+
+/// Prune below `dir`; returns whether `dir` was left empty.
+fn prune_dir<S: BuildHasher>(
+    dir: &Path,
+    cutoff: SystemTime,
+    open_paths: &HashSet<PathBuf, S>,
+    dry_run: bool,
+    report: &mut BazelPruneReport,
+) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    let mut empty = true;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(meta) = fs::symlink_metadata(&path) else {
+            empty = false;
+            continue;
+        };
+        if meta.is_dir() {
+            if prune_dir(&path, cutoff, open_paths, dry_run, report) && !dry_run {
+                if let Err(e) = fs::remove_dir(&path) {
+                    report.errors.push((path, e.to_string()));
+                    empty = false;
+                }
+            } else {
+                empty = false;
+            }
+            continue;
+        }
+        let last_used = meta
+            .accessed()
+            .into_iter()
+            .chain(meta.modified())
+            .max()
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        if last_used >= cutoff {
+            empty = false;
+            continue;
+        }
+        if open_paths.contains(&path) {
+            report.files_open += 1;
+            empty = false;
+            continue;
+        }
+        if dry_run {
+            report.files_would_remove += 1;
+            report.bytes_would_free += meta.len();
+            continue;
+        }
+        match fs::remove_file(&path) {
+            Ok(()) => {
+                report.files_removed += 1;
+                report.bytes_freed += meta.len();
+            }
+            Err(e) => {
+                report.errors.push((path, e.to_string()));
+                empty = false;
+            }
+        }
+    }
+    empty
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filetime::{FileTime, set_file_times};
+
+    const HASH: &str = "0123456789abcdef0123456789abcdef";
+
+    fn output_base(root: &Path, workspace: &Path) -> PathBuf {
+        let base = root.join("_bazel_dev").join(HASH);
+        fs::create_dir_all(base.join("execroot")).unwrap();
+        fs::write(
+            base.join(DO_NOT_BUILD_HERE),
+            workspace.to_string_lossy().as_bytes(),
+        )
+        .unwrap();
+        base
+    }
+
+    fn write_aged(path: &Path, len: usize, days: u64) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![0u8; len]).unwrap();
+        let when = FileTime::from_system_time(SystemTime::now() - Duration::from_hours(days * 24));
+        set_file_times(path, when, when).unwrap();
+    }
+
+    #[test]
+    fn output_base_maps_to_its_workspace_and_detects_abandonment() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path().join("proj");
+        fs::create_dir(&workspace).unwrap();
+        fs::write(workspace.join("MODULE.bazel"), "").unwrap();
+        let base = output_base(&dir.path().join("cache"), &workspace);
+
+        let live = detect_output_base(&base).unwrap();
+        assert_eq!(live.workspace.as_deref(), Some(workspace.as_path()));
+        assert!(!live.abandoned);
+
+        fs::remove_dir_all(&workspace).unwrap();
+        assert!(detect_output_base(&base).unwrap().abandoned);
+        assert_eq!(
+            BazelOutputBase::repository_cache(&base),
+            Some(dir.path().join("cache/_bazel_dev/cache/repos"))
+        );
+    }
+
+    #[test]
+    fn readme_fallback_and_name_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("_bazel_dev").join(HASH);
+        fs::create_dir_all(&base).unwrap();
+        fs::write(
+            base.join("README"),
+            "This directory was generated by Bazel for the workspace /nonexistent/sbh-proj.\n",
+        )
+        .unwrap();
+        let detected = detect_output_base(&base).unwrap();
+        assert_eq!(
+            detected.workspace.as_deref(),
+            Some(Path::new("/nonexistent/sbh-proj"))
+        );
+        assert!(detected.abandoned);
+
+        // Wrong parent, wrong name, or no marker: not an output base.
+        let elsewhere = dir.path().join("other").join(HASH);
+        fs::create_dir_all(&elsewhere).unwrap();
+        fs::write(elsewhere.join("README"), "/nonexistent").unwrap();
+        assert!(detect_output_base(&elsewhere).is_none());
+        let install = dir.path().join("_bazel_dev").join("install");
+        fs::create_dir_all(&install).unwrap();
+        assert!(detect_output_base(&install).is_none());
+        let unmarked = dir
+            .path()
+            .join("_bazel_dev")
+            .join("fedcba9876543210fedcba9876543210");
+        fs::create_dir_all(&unmarked).unwrap();
+        assert!(detect_output_base(&unmarked).is_none());
+    }
+
+    #[test]
+    fn prune_removes_unused_files_and_empty_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("disk-cache");
+        let stale = cache.join("cas/ab/ab12");
+        let fresh = cache.join("cas/cd/cd34");
+        let held = cache.join("ac/ef/ef56");
+        write_aged(&stale, 1_000, 60);
+        write_aged(&fresh, 500, 1);
+        write_aged(&held, 200, 60);
+        let config = BazelCacheConfig {
+            prune_by_access: true,
+            ..BazelCacheConfig::default()
+        };
+        let open: HashSet<PathBuf> = held.ancestors().map(Path::to_path_buf).collect();
+
+        let dry = prune_caches(
+            std::slice::from_ref(&cache),
+            &config,
+            &open,
+            SystemTime::now(),
+            true,
+        );
+        assert_eq!(dry.files_would_remove, 1);
+        assert_eq!(dry.bytes_would_free, 1_000);
+        assert!(stale.exists());
+
+        let report = prune_caches(
+            std::slice::from_ref(&cache),
+            &config,
+            &open,
+            SystemTime::now(),
+            false,
+        );
+        assert_eq!(report.files_removed, 1, "{report:?}");
+        assert_eq!(report.files_open, 1);
+        assert_eq!(report.bytes_freed, 1_000);
+        assert!(report.errors.is_empty());
+        assert!(!cache.join("cas/ab").exists());
+        assert!(fresh.exists());
+        assert!(held.exists());
+        assert!(cache.exists());
+    }
+}
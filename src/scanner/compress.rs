@@ -0,0 +1,702 @@
+//! Compress-in-place of cold files with zstd.
+//!
+//! Between keeping a candidate and deleting it, cold logs, `.jsonl`
+//! transcripts and rarely read cache files can be compressed where they lie
+//! and stay readable with `zstdcat` or `sbh decompress`. Each file goes
+//! through the same sequence:
+//!
+//! 1. Compress into a hidden temp file next to it.
+//! 2. Decompress the temp file and compare it byte for byte with the original.
+//! 3. Check the original did not change while it was being read.
+//! 4. Hard-link the temp file to `<name>.zst`, which fails rather than
+//!    replaces when that name already exists, then unlink the original.
+//!
+//! At no point is neither copy present, and a failed step leaves the original
+//! untouched. Files that do not shrink by `min_savings_pct` are left alone.
+//!
+//! Each `.zst` file sbh writes carries a `user.sbh.compressed` extended
+//! attribute holding the original size. `sbh decompress` restores only
+//! files with that marker, so archives from elsewhere are never expanded,
+//! and it checks that the original size fits in free space before writing.
+//! On a filesystem without user extended attributes nothing is compressed.
+
+#![allow(missing_docs)]
+
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::hash::BuildHasher;
+use std::io::{self, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::daemon::policy::CompressInPlaceConfig;
+
+/// Extension appended to compressed files.
+pub const COMPRESSED_EXTENSION: &str = "zst";
+
+/// Suffix of the temp files written beside the file being swapped.
+const TEMP_SUFFIX: &str = ".sbh-tmp";
+
+/// Upper bound on files examined below one candidate.
+const MAX_FILES_PER_TARGET: usize = 100_000;
+
+/// Extended attribute marking a `.zst` file sbh wrote; holds the original size.
+pub const MARKER_XATTR: &str = "user.sbh.compressed";
+
+/// Outcome of compressing the eligible files below one target.
+#[derive(Debug, Clone, Default)]
+pub struct CompressionReport {
+    pub files_compressed: usize,
+    pub files_would_compress: usize,
+    /// Eligible files left alone: held open, changed while being read, or
+    /// not compressible enough.
+    pub files_skipped: usize,
+    /// Size of the compressed files before compression.
+    pub bytes_before: u64,
+    /// Size of the `.zst` files that replaced them.
+    pub bytes_after: u64,
+    /// Size of the files that would be compressed in dry-run mode.
+    pub bytes_would_compress: u64,
+    pub errors: Vec<(PathBuf, String)>,
+    pub duration: Duration,
+    pub dry_run: bool,
+}
+
+impl CompressionReport {
+    /// Bytes freed by replacing originals with their compressed copies.
+    #[must_use]
+    pub const fn bytes_saved(&self) -> u64 {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+}
+
+/// Outcome of restoring `.zst` files below one target.
+#[derive(Debug, Clone, Default)]
+pub struct DecompressionReport {
+    pub files_restored: usize,
+    pub files_would_restore: usize,
+    /// `.zst` files without sbh's marker, left as they are.
+    pub files_not_ours: usize,
+    /// Size of the `.zst` files that were restored.
+    pub bytes_before: u64,
+    /// Size of the restored files.
+    pub bytes_after: u64,
+    /// Size the files would have once restored, in dry-run mode.
+    pub bytes_would_restore: u64,
+    pub errors: Vec<(PathBuf, String)>,
+    pub duration: Duration,
+    pub dry_run: bool,
+}
+
+/// What happened to one file handed to [`compress_file`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileCompression {
+    /// Replaced by `<name>.zst`.
+    Compressed { bytes_before: u64, bytes_after: u64 },
+    /// Would not shrink by the configured minimum; left as is.
+    NotWorthwhile,
+    /// Modified while being compressed; left as is.
+    Changed,
+    /// The filesystem cannot hold the marker `sbh decompress` relies on.
+    Unmarkable,
+}
+
+/// Compress the eligible files at or below `target`.
+///
+/// `open_paths` is an open-path ancestor index covering `target`; files in it
+/// are skipped, since a writer would keep appending to the unlinked original.
+#[must_use]
+pub fn compress_in_place<S: BuildHasher>(
+    target: &Path,
+    config: &CompressInPlaceConfig,
+    open_paths: &HashSet<PathBuf, S>,
+    now: SystemTime,
+    dry_run: bool,
+) -> CompressionReport {
+    let start = Instant::now();
+    let mut report = CompressionReport {
+        dry_run,
+        ..CompressionReport::default()
+    };
+    let idle_cutoff = idle_cutoff(config, now);
+
+    for path in regular_files(target) {
+        let Ok(meta) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if !is_eligible(&path, &meta, config, idle_cutoff) {
+            continue;
+        }
+        if open_paths.contains(&path) {
+            report.files_skipped += 1;
+            continue;
+        }
+        if dry_run {
+            report.files_would_compress += 1;
+            report.bytes_would_compress += meta.len();
+            continue;
+        }
+        match compress_file(&path, config.level, config.min_savings_pct) {
+            Ok(FileCompression::Compressed {
+                bytes_before,
+                bytes_after,
+            }) => {
+                report.files_compressed += 1;
+                report.bytes_before += bytes_before;
+                report.bytes_after += bytes_after;
+            }
+            Ok(
+                FileCompression::NotWorthwhile
+                | FileCompression::Changed
+                | FileCompression::Unmarkable,
+            ) => {
+                report.files_skipped += 1;
+            }
+            Err(e) => report.errors.push((path, e.to_string())),
+        }
+    }
+    report.duration = start.elapsed();
+    report
+}
+
+/// Restore every `.zst` file sbh wrote at or below `target` to its
+/// original name. Other `.zst` files are counted and left alone.
+#[must_use]
+pub fn decompress_in_place(target: &Path, dry_run: bool) -> DecompressionReport {
+    let start = Instant::now();
+    let mut report = DecompressionReport {
+        dry_run,
+        ..DecompressionReport::default()
+    };
+    for path in regular_files(target) {
+        if compressed_original(&path).is_none() {
+            continue;
+        }
+        let Ok(meta) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+        let Some(original_bytes) = open_no_follow(&path).ok().and_then(|f| marked_size(&f)) else {
+            report.files_not_ours += 1;
+            continue;
+        };
+        if dry_run {
+            report.files_would_restore += 1;
+            report.bytes_before += meta.len();
+            report.bytes_would_restore += original_bytes;
+            continue;
+        }
+        match decompress_file(&path) {
+            Ok((_, restored_bytes)) => {
+                report.files_restored += 1;
+                report.bytes_before += meta.len();
+                report.bytes_after += restored_bytes;
+            }
+            Err(e) => report.errors.push((path, e.to_string())),
+        }
+    }
+    report.duration = start.elapsed();
+    report
+}
+
+/// Compress one regular file to `<name>.zst`, verify it, and swap it in.
+pub fn compress_file(path: &Path, level: i32, min_savings_pct: u8) -> io::Result<FileCompression> {
+    let compressed = with_suffix(path, &format!(".{COMPRESSED_EXTENSION}"))?;
+    let temp = hidden_temp(&compressed)?;
+
+    let original = open_no_follow(path)?;
+    let before = original.metadata()?;
+    let mut output = create_like(&temp, &before)?;
+    let result = (|| {
+        if let Err(e) = mark_compressed(&output, before.len()) {
+            return if e.kind() == io::ErrorKind::Unsupported {
+                Ok(FileCompression::Unmarkable)
+            } else {
+                Err(e)
+            };
+        }
+        let mut encoder = zstd::stream::Encoder::new(&mut output, level)?;
+        encoder.include_checksum(true)?;
+        io::copy(&mut BufReader::new(&original), &mut encoder)?;
+        encoder.finish()?;
+        output.sync_all()?;
+        let bytes_after = output.metadata()?.len();
+
+        let worthwhile = u128::from(bytes_after) * 100
+            <= u128::from(before.len()) * u128::from(100 - min_savings_pct.min(100));
+        if !worthwhile {
+            return Ok(FileCompression::NotWorthwhile);
+        }
+        let decoded = zstd::stream::Decoder::new(File::open(&temp)?)?;
+        if !streams_equal(decoded, BufReader::new(open_no_follow(path)?))? {
+            return Err(io::Error::other(
+                "decompressed copy does not match original",
+            ));
+        }
+        if !unchanged(&before, &fs::symlink_metadata(path)?) {
+            return Ok(FileCompression::Changed);
+        }
+        copy_times(&output, &before)?;
+        Ok(FileCompression::Compressed {
+            bytes_before: before.len(),
+            bytes_after,
+        })
+    })();
+
+    match result {
+        Ok(outcome @ FileCompression::Compressed { .. }) => {
+            swap_in(&temp, &compressed, path)?;
+            Ok(outcome)
+        }
+        other => {
+            let _ = fs::remove_file(&temp);
+            other
+        }
+    }
+}
+
+/// Restore one `<name>.zst` file sbh wrote to `<name>`; returns the
+/// restored path and size.
+///
+/// Refuses files without sbh's marker and files whose original size exceeds
+/// the free space of their filesystem.
+pub fn decompress_file(path: &Path) -> io::Result<(PathBuf, u64)> {
+    let restored = compressed_original(path).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("not a .{COMPRESSED_EXTENSION} file"),
+        )
+    })?;
+    let temp = hidden_temp(&restored)?;
+
+    let compressed = open_no_follow(path)?;
+    let original_bytes = marked_size(&compressed).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("not compressed by sbh (no {MARKER_XATTR} attribute)"),
+        )
+    })?;
+    let free = free_bytes(&compressed)?;
+    if original_bytes > free {
+        return Err(io::Error::new(
+            io::ErrorKind::StorageFull,
+            format!("restoring needs {original_bytes} bytes but only {free} are free"),
+        ));
+    }
+    let before = compressed.metadata()?;
+    let mut output = create_like(&temp, &before)?;
+    let result = (|| {
+        let mut decoder = zstd::stream::Decoder::new(&compressed)?;
+        let restored_bytes = io::copy(&mut decoder, &mut output)?;
+        output.sync_all()?;
+        copy_times(&output, &before)?;
+        Ok(restored_bytes)
+    })();
+
+    match result {
+        Ok(restored_bytes) => {
+            swap_in(&temp, &restored, path)?;
+            Ok((restored, restored_bytes))
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&temp);
+            Err(e)
+        }
+    }
+}
+
+/// The name a `.zst` file restores to, or `None` for other files.
+/// Whether any file at or below `target` is eligible for compression.
+///
+/// Stops at the first eligible file, so a candidate with something to
+/// compress costs little; one with nothing is walked in full.
+#[must_use]
+pub fn has_eligible_files(target: &Path, config: &CompressInPlaceConfig, now: SystemTime) -> bool {
+    let idle_cutoff = idle_cutoff(config, now);
+    regular_files(target).any(|path| {
+        fs::symlink_metadata(&path).is_ok_and(|meta| is_eligible(&path, &meta, config, idle_cutoff))
+    })
+}
+
+#[must_use]
+pub fn compressed_original(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+    let stem = name.strip_suffix(&format!(".{COMPRESSED_EXTENSION}"))?;
+    (!stem.is_empty()).then(|| path.with_file_name(stem))
+}
+
+fn idle_cutoff(config: &CompressInPlaceConfig, now: SystemTime) -> SystemTime {
+    now.checked_sub(Duration::from_secs(config.min_idle_secs))
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+fn is_eligible(
+    path: &Path,
+    meta: &fs::Metadata,
+    config: &CompressInPlaceConfig,
+    idle_cutoff: SystemTime,
+) -> bool {
+    if !meta.is_file() || meta.len() < config.min_file_bytes || has_other_links(meta) {
+        return false;
+    }
+    if meta
+        .modified()
+        .map_or(true, |modified| modified > idle_cutoff)
+    {
+        return false;
+    }
+    let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+        return false;
+    };
+    config
+        .extensions
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(extension))
+}
+
+/// Regular files at or below `target`, without following symlinks.
+fn regular_files(target: &Path) -> impl Iterator<Item = PathBuf> {
+    let mut pending = vec![target.to_path_buf()];
+    std::iter::from_fn(move || {
+        while let Some(path) = pending.pop() {
+            let Ok(meta) = fs::symlink_metadata(&path) else {
+                continue;
+            };
+            if meta.is_file() {
+                return Some(path);
+            }
+            if meta.is_dir()
+                && let Ok(entries) = fs::read_dir(&path)
+            {
+                pending.extend(entries.flatten().map(|entry| entry.path()));
+            }
+        }
+        None
+    })
+    .take(MAX_FILES_PER_TARGET)
+}
+
+#[cfg(unix)]
+fn has_other_links(meta: &fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    meta.nlink() > 1
+}
+
+#[cfg(not(unix))]
+const fn has_other_links(_meta: &fs::Metadata) -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn mark_compressed(file: &File, original_bytes: u64) -> io::Result<()> {
+    rustix::fs::fsetxattr(
+        file,
+        MARKER_XATTR,
+        original_bytes.to_string().as_bytes(),
+        rustix::fs::XattrFlags::empty(),
+    )
+    .map_err(|e| {
+        if e == rustix::io::Errno::NOTSUP {
+            io::Error::new(io::ErrorKind::Unsupported, e)
+        } else {
+            io::Error::from(e)
+        }
+    })
+}
+
+#[cfg(not(unix))]
+fn mark_compressed(_file: &File, _original_bytes: u64) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "extended attributes are not supported",
+    ))
+}
+
+/// Original size recorded by [`mark_compressed`], or `None` when the file
+/// was not written by sbh.
+#[cfg(unix)]
+fn marked_size(file: &File) -> Option<u64> {
+    let mut value = [0u8; 20];
+    let len = rustix::fs::fgetxattr(file, MARKER_XATTR, &mut value).ok()?;
+    std::str::from_utf8(&value[..len]).ok()?.parse().ok()
+}
+
+#[cfg(not(unix))]
+const fn marked_size(_file: &File) -> Option<u64> {
+    None
+}
+
+#[cfg(unix)]
+fn free_bytes(file: &File) -> io::Result<u64> {
+    let stats = rustix::fs::fstatvfs(file)?;
+    Ok(stats.f_bavail.saturating_mul(stats.f_frsize))
+}
+
+#[cfg(not(unix))]
+fn free_bytes(_file: &File) -> io::Result<u64> {
+    Ok(u64::MAX)
+}
+
+fn unchanged(before: &fs::Metadata, after: &fs::Metadata) -> bool {
+    before.len() == after.len() && before.modified().ok() == after.modified().ok()
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> io::Result<PathBuf> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+    let mut name = name.to_os_string();
+    name.push(suffix);
+    Ok(path.with_file_name(name))
+}
+
+/// `.<name>.sbh-tmp` beside `path`.
+fn hidden_temp(path: &Path) -> io::Result<PathBuf> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+    let mut hidden = std::ffi::OsString::from(".");
+    hidden.push(name);
+    hidden.push(TEMP_SUFFIX);
+    Ok(path.with_file_name(hidden))
+}
+
+fn open_no_follow(path: &Path) -> io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.read(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.custom_flags(libc::O_NOFOLLOW);
+    }
+    let file = options.open(path)?;
+    if !file.metadata()?.is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "not a regular file",
+        ));
+    }
+    Ok(file)
+}
+
+/// Create `path` exclusively with the permissions and owner of `like`.
+fn create_like(path: &Path, like: &fs::Metadata) -> io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(like.permissions().mode() & 0o7777);
+    }
+    let file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let created = file.metadata()?;
+        if (created.uid(), created.gid()) != (like.uid(), like.gid())
+            && let Err(e) = std::os::unix::fs::fchown(&file, Some(like.uid()), Some(like.gid()))
+        {
+            drop(file);
+            let _ = fs::remove_file(path);
+            return Err(e);
+        }
+    }
+    Ok(file)
+}
+
+fn copy_times(file: &File, like: &fs::Metadata) -> io::Result<()> {
+    let mut times = fs::FileTimes::new();
+    if let Ok(modified) = like.modified() {
+        times = times.set_modified(modified);
+    }
+    if let Ok(accessed) = like.accessed() {
+        times = times.set_accessed(accessed);
+    }
+    file.set_times(times)
+}
+
+/// Publish `temp` as `target` without replacing an existing file, then
+/// remove `replaced`.
+fn swap_in(temp: &Path, target: &Path, replaced: &Path) -> io::Result<()> {
+    let linked = fs::hard_link(temp, target);
+    let _ = fs::remove_file(temp);
+    linked?;
+    fs::remove_file(replaced)
+}
+
+fn streams_equal(mut a: impl Read, mut b: impl Read) -> io::Result<bool> {
+    let mut left = vec![0u8; 64 * 1024];
+    let mut right = vec![0u8; 64 * 1024];
+    loop {
+        let read = read_full(&mut a, &mut left)?;
+        if read_full(&mut b, &mut right[..read])? != read {
+            return Ok(false);
+        }
+        if left[..read] != right[..read] {
+            return Ok(false);
+        }
+        if read < left.len() {
+            // `a` is exhausted; equal only if `b` is too.
+            return Ok(b.read(&mut right[..1])? == 0);
+        }
+    }
+}
+
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filetime::{FileTime, set_file_mtime};
+    use rand::Rng;
+
+    fn config() -> CompressInPlaceConfig {
+        CompressInPlaceConfig {
+            enabled: true,
+            min_file_bytes: 1,
+            min_idle_secs: 3_600,
+            ..CompressInPlaceConfig::default()
+        }
+    }
+
+    fn write_cold(path: &Path, contents: &[u8]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+        let old = SystemTime::now() - Duration::from_hours(48);
+        set_file_mtime(path, FileTime::from_system_time(old)).unwrap();
+    }
+
+    fn transcript() -> Vec<u8> {
+        (0..4_000)
+            .flat_map(|i| {
+                format!("{{\"turn\":{i},\"role\":\"assistant\",\"text\":\"ok\"}}\n").into_bytes()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cold_logs_are_swapped_for_verified_zst_copies_and_restored() {
+        let tmp = tempfile::tempdir().unwrap();
+        let log = tmp.path().join("session/run.jsonl");
+        let fresh = tmp.path().join("session/live.log");
+        let binary = tmp.path().join("session/blob.bin");
+        write_cold(&log, &transcript());
+        fs::write(&fresh, transcript()).unwrap();
+        write_cold(&binary, &transcript());
+        let modified = fs::metadata(&log).unwrap().modified().unwrap();
+
+        let report = compress_in_place(
+            tmp.path(),
+            &config(),
+            &HashSet::new(),
+            SystemTime::now(),
+            false,
+        );
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(report.files_compressed, 1);
+        assert!(report.bytes_saved() > 0);
+        assert!(!log.exists());
+        let zst = tmp.path().join("session/run.jsonl.zst");
+        assert_eq!(fs::metadata(&zst).unwrap().modified().unwrap(), modified);
+        assert!(fresh.exists() && binary.exists());
+        assert!(
+            fs::read_dir(tmp.path().join("session"))
+                .unwrap()
+                .flatten()
+                .all(|entry| !entry.file_name().to_string_lossy().ends_with(TEMP_SUFFIX))
+        );
+
+        let restored = decompress_in_place(tmp.path(), false);
+        assert!(restored.errors.is_empty(), "{:?}", restored.errors);
+        assert_eq!(restored.files_restored, 1);
+        assert_eq!(fs::read(&log).unwrap(), transcript());
+        assert_eq!(fs::metadata(&log).unwrap().modified().unwrap(), modified);
+        assert!(!zst.exists());
+    }
+
+    #[test]
+    fn open_incompressible_and_dry_run_files_are_left_alone() {
+        let tmp = tempfile::tempdir().unwrap();
+        let held = tmp.path().join("held.log");
+        let random = tmp.path().join("random.log");
+        write_cold(&held, &transcript());
+        let mut noise = vec![0u8; 64 * 1024];
+        rand::rng().fill_bytes(&mut noise);
+        write_cold(&random, &noise);
+
+        let dry = compress_in_place(
+            tmp.path(),
+            &config(),
+            &HashSet::new(),
+            SystemTime::now(),
+            true,
+        );
+        assert_eq!(dry.files_would_compress, 2);
+        assert!(held.exists() && random.exists());
+
+        let open: HashSet<PathBuf> = std::iter::once(held.clone()).collect();
+        let report = compress_in_place(tmp.path(), &config(), &open, SystemTime::now(), false);
+        assert_eq!(report.files_compressed, 0);
+        assert_eq!(report.files_skipped, 2);
+        assert!(held.exists() && random.exists());
+        assert!(!tmp.path().join("random.log.zst").exists());
+    }
+
+    #[test]
+    fn only_zst_files_sbh_wrote_are_restored() {
+        let tmp = tempfile::tempdir().unwrap();
+        let foreign = tmp.path().join("dataset.csv.zst");
+        let mut encoded = Vec::new();
+        zstd::stream::copy_encode(&transcript()[..], &mut encoded, 3).unwrap();
+        fs::write(&foreign, &encoded).unwrap();
+        let log = tmp.path().join("app.log");
+        write_cold(&log, &transcript());
+        compress_file(&log, 3, 10).unwrap();
+
+        let dry = decompress_in_place(tmp.path(), true);
+        assert_eq!(dry.files_would_restore, 1);
+        assert_eq!(dry.files_not_ours, 1);
+        assert_eq!(dry.bytes_would_restore, transcript().len() as u64);
+
+        let err = decompress_file(&foreign).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        let report = decompress_in_place(tmp.path(), false);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(report.files_restored, 1);
+        assert_eq!(report.files_not_ours, 1);
+        assert_eq!(fs::read(&log).unwrap(), transcript());
+        assert_eq!(fs::read(&foreign).unwrap(), encoded);
+        assert!(!tmp.path().join("dataset.csv").exists());
+    }
+
+    #[test]
+    fn existing_targets_are_never_replaced() {
+        let tmp = tempfile::tempdir().unwrap();
+        let log = tmp.path().join("app.log");
+        write_cold(&log, &transcript());
+        fs::write(tmp.path().join("app.log.zst"), b"unrelated").unwrap();
+
+        assert!(compress_file(&log, 3, 10).is_err());
+        assert_eq!(fs::read(&log).unwrap(), transcript());
+        assert_eq!(
+            fs::read(tmp.path().join("app.log.zst")).unwrap(),
+            b"unrelated"
+        );
+        assert_eq!(
+            compressed_original(Path::new("/x/a.log.zst")),
+            Some(PathBuf::from("/x/a.log"))
+        );
+        assert_eq!(compressed_original(Path::new("/x/.zst")), None);
+        assert_eq!(compressed_original(Path::new("/x/a.log")), None);
+    }
+}
@@ -0,0 +1,174 @@
+//! Shared-extent accounting for copy-on-write filesystems.
+//!
+//! On btrfs and reflink-enabled XFS a reflinked copy shares its data extents
+//! with the original, and deleting it frees nothing while another file still
+//! references them. For large candidates on those filesystems the scanner maps
+//! the candidate's extents with FIEMAP and moves the shared portion out of the
+//! reclaim estimate. Other filesystems are never probed.
+//!
+//! The probe covers the same files the walker sized: a file candidate itself,
+//! or the immediate child files of a directory candidate.
+
+#![allow(missing_docs)]
+#![allow(clippy::cast_precision_loss)]
+
+use std::path::Path;
+
+/// Candidates estimated below this are not probed.
+pub const PROBE_MIN_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Shared fraction at which the decision ledger notes that deleting the
+/// candidate would mostly free nothing.
+pub const MOSTLY_SHARED_FRACTION: f64 = 0.5;
+
+/// Upper bound on files mapped per directory candidate.
+#[cfg(target_os = "linux")]
+const MAX_PROBED_FILES: usize = 4_096;
+
+/// Extent totals over the probed files of one candidate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SharedExtents {
+    pub mapped_bytes: u64,
+    pub shared_bytes: u64,
+}
+
+impl SharedExtents {
+    #[must_use]
+    pub fn shared_fraction(self) -> f64 {
+        if self.mapped_bytes == 0 {
+            return 0.0;
+        }
+        self.shared_bytes as f64 / self.mapped_bytes as f64
+    }
+
+    /// Split `size_bytes` into `(reclaimable, shared)` by the mapped ratio.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn split(self, size_bytes: u64) -> (u64, u64) {
+        let shared = ((size_bytes as f64) * self.shared_fraction()).round() as u64;
+        let shared = shared.min(size_bytes);
+        (size_bytes - shared, shared)
+    }
+}
+
+/// `(reclaimable, shared)` bytes for a candidate estimated at `size_bytes`.
+///
+/// Small candidates, non-CoW filesystems and failed probes keep the whole
+/// estimate as reclaimable.
+#[must_use]
+pub fn discount_shared(path: &Path, is_dir: bool, size_bytes: u64) -> (u64, u64) {
+    if size_bytes < PROBE_MIN_BYTES || !is_cow_filesystem(path) {
+        return (size_bytes, 0);
+    }
+    probe(path, is_dir).map_or((size_bytes, 0), |extents| extents.split(size_bytes))
+}
+
+/// Whether `path` lives on btrfs or XFS, the filesystems whose reflinks
+/// FIEMAP reports as shared extents.
+#[cfg(target_os = "linux")]
+#[must_use]
+pub fn is_cow_filesystem(path: &Path) -> bool {
+    use nix::sys::statfs::{BTRFS_SUPER_MAGIC, XFS_SUPER_MAGIC, statfs};
+
+    statfs(path).is_ok_and(|stat| {
+        let fs_type = stat.filesystem_type();
+        fs_type == BTRFS_SUPER_MAGIC || fs_type == XFS_SUPER_MAGIC
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+#[must_use]
+pub const fn is_cow_filesystem(_path: &Path) -> bool {
+    false
+}
+
+/// Map the extents of a file candidate, or of a directory candidate's
+/// immediate child files. `None` when the filesystem does not support FIEMAP
+/// or nothing could be mapped.
+#[cfg(target_os = "linux")]
+#[must_use]
+pub fn probe(path: &Path, is_dir: bool) -> Option<SharedExtents> {
+    use std::fs::{self, OpenOptions};
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let files = if is_dir {
+        fs::read_dir(path)
+            .ok()?
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_file()))
+            .take(MAX_PROBED_FILES)
+            .map(|entry| entry.path())
+            .collect()
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut total = SharedExtents::default();
+    for file in files {
+        let Ok(handle) = OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_NOFOLLOW)
+            .open(&file)
+        else {
+            continue;
+        };
+        match sbh_fiemap::extent_usage(&handle) {
+            Ok(usage) => {
+                total.mapped_bytes = total.mapped_bytes.saturating_add(usage.mapped_bytes);
+                total.shared_bytes = total.shared_bytes.saturating_add(usage.shared_bytes);
+            }
+            Err(error) if error.raw_os_error() == Some(libc::EOPNOTSUPP) => return None,
+            Err(_) => {}
+        }
+    }
+    (total.mapped_bytes > 0).then_some(total)
+}
+
+#[cfg(not(target_os = "linux"))]
+#[must_use]
+pub const fn probe(_path: &Path, _is_dir: bool) -> Option<SharedExtents> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_moves_the_shared_ratio_out_of_the_estimate() {
+        let extents = SharedExtents {
+            mapped_bytes: 400,
+            shared_bytes: 300,
+        };
+        assert!((extents.shared_fraction() - 0.75).abs() < f64::EPSILON);
+        assert_eq!(extents.split(1_000), (250, 750));
+        assert_eq!(SharedExtents::default().split(1_000), (1_000, 0));
+    }
+
+    #[test]
+    fn small_or_non_cow_candidates_keep_their_estimate() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("blob"), vec![1u8; 64 * 1024]).unwrap();
+
+        assert_eq!(discount_shared(tmp.path(), true, 1_024), (1_024, 0));
+        if !is_cow_filesystem(tmp.path()) {
+            let big = PROBE_MIN_BYTES * 4;
+            assert_eq!(discount_shared(tmp.path(), true, big), (big, 0));
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn probe_of_unshared_files_reports_no_shared_bytes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let blob = tmp.path().join("blob");
+        std::fs::write(&blob, vec![7u8; 256 * 1024]).unwrap();
+        std::fs::File::open(&blob).unwrap().sync_all().unwrap();
+
+        // tmpfs has no FIEMAP; disk-backed filesystems map the written data.
+        if let Some(extents) = probe(tmp.path(), true) {
+            assert_eq!(extents.shared_bytes, 0);
+            assert!(extents.mapped_bytes > 0);
+        }
+    }
+}
@@ -201,6 +201,16 @@ pub enum ActionRecord {
     Delete,
     /// Flag for human review.
     Review,
+    /// Cap a cgroup's write bandwidth (`path` is the cgroup).
+    Throttle,
+    /// Lift a previously applied write cap.
+    Unthrottle,
+    /// Freeze a cgroup's processes as a last resort (`path` is the cgroup).
+    Freeze,
+    /// Thaw a previously frozen cgroup.
+    Thaw,
+    /// Compress the artifact's cold files in place instead of deleting it.
+    Compress,
 }
 
 impl From<DecisionAction> for ActionRecord {
@@ -209,6 +219,7 @@ impl From<DecisionAction> for ActionRecord {
             DecisionAction::Keep => Self::Keep,
             DecisionAction::Delete => Self::Delete,
             DecisionAction::Review => Self::Review,
+            DecisionAction::Compress => Self::Compress,
         }
     }
 }
@@ -219,6 +230,11 @@ impl fmt::Display for ActionRecord {
             Self::Keep => write!(f, "KEEP"),
             Self::Delete => write!(f, "DELETE"),
             Self::Review => write!(f, "REVIEW"),
+            Self::Throttle => write!(f, "THROTTLE"),
+            Self::Unthrottle => write!(f, "UNTHROTTLE"),
+            Self::Freeze => write!(f, "FREEZE"),
+            Self::Thaw => write!(f, "THAW"),
+            Self::Compress => write!(f, "COMPRESS"),
         }
     }
 }
@@ -322,6 +338,222 @@ impl DecisionRecordBuilder {
     }
 }
 
+/// Evidence behind a cgroup write-throttle or freeze decision.
+#[derive(Debug, Clone)]
+pub struct ThrottleEvidence {
+    /// Cgroup path relative to the cgroup v2 root (e.g. `/user.slice/build.scope`).
+    pub cgroup: String,
+    /// Bytes the cgroup wrote during the sampling window.
+    pub bytes_written: u64,
+    /// Length of the sampling window in seconds.
+    pub window_secs: u64,
+    /// Set when the protected-cgroup list vetoed the throttle.
+    pub veto_reason: Option<String>,
+    /// One-line explanation (limit, device, observed rate).
+    pub summary: String,
+}
+
+impl DecisionRecordBuilder {
+    /// Build a decision record for applying or lifting a write throttle, or
+    /// for freezing or thawing a writer.
+    ///
+    /// These decisions are not scored, so the factor fields stay zero and the
+    /// classification names the intervention rather than an artifact pattern.
+    pub fn build_throttle(
+        &mut self,
+        evidence: ThrottleEvidence,
+        action: ActionRecord,
+        policy_mode: PolicyMode,
+    ) -> DecisionRecord {
+        let id = self.next_id;
+        self.next_id += 1;
+        let vetoed = evidence.veto_reason.is_some();
+        let pattern_name = match action {
+            ActionRecord::Freeze | ActionRecord::Thaw => "cgroup-freeze",
+            _ => "cgroup-write-throttle",
+        };
+
+        DecisionRecord {
+            decision_id: id,
+            trace_id: format!("sbh-{id:08x}"),
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            policy_mode,
+            path: PathBuf::from(evidence.cgroup),
+            size_bytes: evidence.bytes_written,
+            age_secs: evidence.window_secs,
+            classification: ClassificationRecord {
+                pattern_name: pattern_name.to_string(),
+                category: "RunawayWriter".to_string(),
+                combined_confidence: 1.0,
+            },
+            factors: FactorsRecord {
+                location: 0.0,
+                name: 0.0,
+                age: 0.0,
+                size: 0.0,
+                structure: 0.0,
+                pressure_multiplier: 1.0,
+            },
+            factor_contributions: Vec::new(),
+            total_score: 0.0,
+            posterior_abandoned: 0.0,
+            expected_loss_keep: 0.0,
+            expected_loss_delete: 0.0,
+            calibration_score: 1.0,
+            fallback_active: false,
+            fallback_reason: None,
+            vetoed,
+            veto_reason: evidence.veto_reason,
+            action,
+            effective_action: vetoed.then_some(ActionRecord::Keep),
+            guard_status: None,
+            comparator_action: None,
+            summary: evidence.summary,
+        }
+    }
+}
+
+/// Evidence behind reclaiming (or keeping) an idle git worktree or clone.
+#[derive(Debug, Clone)]
+pub struct GitWorkspaceEvidence {
+    /// Working tree path.
+    pub path: PathBuf,
+    /// Apparent size of the working tree and, for a clone, its repository.
+    pub size_bytes: u64,
+    /// Time since git last touched the workspace.
+    pub idle_secs: u64,
+    /// `git-worktree` or `git-clone`.
+    pub pattern_name: &'static str,
+    /// Set when the git evidence, an open file or the removal kept it.
+    pub veto_reason: Option<String>,
+    /// Branch, HEAD, change and unpushed counts, and merge status.
+    pub summary: String,
+}
+
+impl DecisionRecordBuilder {
+    /// Build a decision record for an idle git workspace.
+    ///
+    /// Like throttle records these are not scored: the git evidence in the
+    /// summary and the veto reason are the whole decision.
+    pub fn build_git_workspace(
+        &mut self,
+        evidence: GitWorkspaceEvidence,
+        policy_mode: PolicyMode,
+    ) -> DecisionRecord {
+        let id = self.next_id;
+        self.next_id += 1;
+        let vetoed = evidence.veto_reason.is_some();
+
+        DecisionRecord {
+            decision_id: id,
+            trace_id: format!("sbh-{id:08x}"),
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            policy_mode,
+            path: evidence.path,
+            size_bytes: evidence.size_bytes,
+            age_secs: evidence.idle_secs,
+            classification: ClassificationRecord {
+                pattern_name: evidence.pattern_name.to_string(),
+                category: "AgentWorkspace".to_string(),
+                combined_confidence: 1.0,
+            },
+            factors: FactorsRecord {
+                location: 0.0,
+                name: 0.0,
+                age: 0.0,
+                size: 0.0,
+                structure: 0.0,
+                pressure_multiplier: 1.0,
+            },
+            factor_contributions: Vec::new(),
+            total_score: 0.0,
+            posterior_abandoned: if vetoed { 0.0 } else { 1.0 },
+            expected_loss_keep: 0.0,
+            expected_loss_delete: 0.0,
+            calibration_score: 1.0,
+            fallback_active: false,
+            fallback_reason: None,
+            vetoed,
+            veto_reason: evidence.veto_reason,
+            action: ActionRecord::Delete,
+            effective_action: vetoed.then_some(ActionRecord::Keep),
+            guard_status: None,
+            comparator_action: None,
+            summary: evidence.summary,
+        }
+    }
+}
+
+/// Evidence behind destroying (or, in a dry run, selecting) a filesystem
+/// snapshot during snapshot thinning.
+#[derive(Debug, Clone)]
+pub struct SnapshotEvidence {
+    /// btrfs subvolume path or ZFS `dataset@snapshot`.
+    pub name: String,
+    /// Tool that manages the snapshot (`snapper`, `zfs-auto-snapshot`).
+    pub manager: &'static str,
+    /// Bytes freed by destroying it; `None` when the filesystem cannot say.
+    pub exclusive_bytes: Option<u64>,
+    /// Set when destroying the snapshot failed.
+    pub veto_reason: Option<String>,
+    /// Retention group, mount and the volume's pressure level.
+    pub summary: String,
+}
+
+impl DecisionRecordBuilder {
+    /// Build a decision record for a thinned filesystem snapshot.
+    ///
+    /// Not scored: thinning only ever selects snapshots beyond a retention
+    /// group's newest `keep_latest`, so the summary carries the whole case.
+    pub fn build_snapshot(
+        &mut self,
+        evidence: SnapshotEvidence,
+        policy_mode: PolicyMode,
+    ) -> DecisionRecord {
+        let id = self.next_id;
+        self.next_id += 1;
+        let vetoed = evidence.veto_reason.is_some();
+
+        DecisionRecord {
+            decision_id: id,
+            trace_id: format!("sbh-{id:08x}"),
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            policy_mode,
+            path: PathBuf::from(evidence.name),
+            size_bytes: evidence.exclusive_bytes.unwrap_or(0),
+            age_secs: 0,
+            classification: ClassificationRecord {
+                pattern_name: evidence.manager.to_string(),
+                category: "FilesystemSnapshot".to_string(),
+                combined_confidence: 1.0,
+            },
+            factors: FactorsRecord {
+                location: 0.0,
+                name: 0.0,
+                age: 0.0,
+                size: 0.0,
+                structure: 0.0,
+                pressure_multiplier: 1.0,
+            },
+            factor_contributions: Vec::new(),
+            total_score: 0.0,
+            posterior_abandoned: 0.0,
+            expected_loss_keep: 0.0,
+            expected_loss_delete: 0.0,
+            calibration_score: 1.0,
+            fallback_active: false,
+            fallback_reason: None,
+            vetoed,
+            veto_reason: evidence.veto_reason,
+            action: ActionRecord::Delete,
+            effective_action: vetoed.then_some(ActionRecord::Keep),
+            guard_status: None,
+            comparator_action: None,
+            summary: evidence.summary,
+        }
+    }
+}
+
 impl Default for DecisionRecordBuilder {
     fn default() -> Self {
         Self::new()
@@ -616,7 +848,9 @@ pub fn decision_summary_line(record: &DecisionRecord) -> String {
 mod tests {
     use super::*;
     use crate::monitor::guardrails::{GuardDiagnostics, GuardStatus};
-    use crate::scanner::patterns::{ArtifactCategory, ArtifactClassification, StructuralSignals};
+    use crate::scanner::patterns::{
+        ArtifactCategory, ArtifactClassification, SiblingManifests, StructuralSignals,
+    };
     use crate::scanner::scoring::{
         ActiveReferenceSummary, CandidacyScore, CandidateInput, DecisionAction, DecisionOutcome,
         EvidenceLedger, EvidenceTerm, ScoreFactors, ScoringEngine,
@@ -1014,6 +1248,143 @@ mod tests {
         assert!(line.contains("RustTarget"));
     }
 
+    #[test]
+    fn throttle_records_carry_cgroup_evidence_and_vetoes() {
+        let mut builder = DecisionRecordBuilder::new();
+        let applied = builder.build_throttle(
+            ThrottleEvidence {
+                cgroup: "/user.slice/build.scope".to_string(),
+                bytes_written: 2_000_000_000,
+                window_secs: 5,
+                veto_reason: None,
+                summary: "cap to 32 MiB/s".to_string(),
+            },
+            ActionRecord::Throttle,
+            PolicyMode::Live,
+        );
+        assert_eq!(applied.path, PathBuf::from("/user.slice/build.scope"));
+        assert_eq!(applied.size_bytes, 2_000_000_000);
+        assert!(!applied.vetoed);
+        assert_eq!(applied.effective_action, None);
+        assert!(decision_summary_line(&applied).contains("THROTTLE /user.slice/build.scope"));
+
+        let vetoed = builder.build_throttle(
+            ThrottleEvidence {
+                cgroup: "/system.slice/postgresql.service".to_string(),
+                bytes_written: 1,
+                window_secs: 5,
+                veto_reason: Some("protected cgroup".to_string()),
+                summary: String::new(),
+            },
+            ActionRecord::Throttle,
+            PolicyMode::Live,
+        );
+        assert_eq!(vetoed.decision_id, applied.decision_id + 1);
+        assert!(vetoed.vetoed);
+        assert_eq!(vetoed.effective_action, Some(ActionRecord::Keep));
+
+        let json = vetoed.to_json_compact();
+        assert!(json.contains("\"action\":\"throttle\""));
+
+        let frozen = builder.build_throttle(
+            ThrottleEvidence {
+                cgroup: "/user.slice/build.scope".to_string(),
+                bytes_written: 0,
+                window_secs: 5,
+                veto_reason: None,
+                summary: "froze writer".to_string(),
+            },
+            ActionRecord::Freeze,
+            PolicyMode::Live,
+        );
+        assert_eq!(frozen.classification.pattern_name, "cgroup-freeze");
+        assert!(decision_summary_line(&frozen).contains("FREEZE /user.slice/build.scope"));
+    }
+
+    #[test]
+    fn git_workspace_records_carry_git_evidence() {
+        let mut builder = DecisionRecordBuilder::new();
+        let reclaimed = builder.build_git_workspace(
+            GitWorkspaceEvidence {
+                path: PathBuf::from("/data/projects/.worktrees/task-17"),
+                size_bytes: 5_000_000_000,
+                idle_secs: 20 * 86_400,
+                pattern_name: "git-worktree",
+                veto_reason: None,
+                summary: "worktree of /data/projects/app/.git on task-17 at 1a2b3c4: \
+                          merged into origin/main"
+                    .to_string(),
+            },
+            PolicyMode::Live,
+        );
+        assert_eq!(reclaimed.classification.pattern_name, "git-worktree");
+        assert!(!reclaimed.vetoed);
+        assert_eq!(reclaimed.effective_action, None);
+        assert!(reclaimed.summary.contains("merged into origin/main"));
+        assert!(
+            decision_summary_line(&reclaimed).contains("DELETE /data/projects/.worktrees/task-17")
+        );
+
+        let kept = builder.build_git_workspace(
+            GitWorkspaceEvidence {
+                path: PathBuf::from("/data/projects/.tmp-codex-9"),
+                size_bytes: 1,
+                idle_secs: 20 * 86_400,
+                pattern_name: "git-clone",
+                veto_reason: Some("2 commit(s) on no remote-tracking ref".to_string()),
+                summary: String::new(),
+            },
+            PolicyMode::Live,
+        );
+        assert_eq!(kept.decision_id, reclaimed.decision_id + 1);
+        assert!(kept.vetoed);
+        assert_eq!(kept.effective_action, Some(ActionRecord::Keep));
+        assert!(
+            format_explain(&kept, ExplainLevel::L0)
+                .contains("[vetoed: 2 commit(s) on no remote-tracking ref]")
+        );
+    }
+
+    #[test]
+    fn snapshot_records_carry_manager_and_exclusive_bytes() {
+        let mut builder = DecisionRecordBuilder::new();
+        let destroyed = builder.build_snapshot(
+            SnapshotEvidence {
+                name: "tank/data@zfs-auto-snap_hourly-2026-01-01-0000".to_string(),
+                manager: "zfs-auto-snapshot",
+                exclusive_bytes: Some(3_000_000_000),
+                veto_reason: None,
+                summary: "destroyed zfs-auto-snapshot:tank/data:hourly snapshot on /data \
+                          at Red pressure"
+                    .to_string(),
+            },
+            PolicyMode::Live,
+        );
+        assert_eq!(destroyed.classification.pattern_name, "zfs-auto-snapshot");
+        assert_eq!(destroyed.classification.category, "FilesystemSnapshot");
+        assert_eq!(destroyed.size_bytes, 3_000_000_000);
+        assert!(!destroyed.vetoed);
+        assert!(destroyed.summary.contains("at Red pressure"));
+        assert!(
+            decision_summary_line(&destroyed)
+                .contains("DELETE tank/data@zfs-auto-snap_hourly-2026-01-01-0000")
+        );
+
+        let failed = builder.build_snapshot(
+            SnapshotEvidence {
+                name: "@/.snapshots/42/snapshot".to_string(),
+                manager: "snapper",
+                exclusive_bytes: None,
+                veto_reason: Some("destroy failed: busy".to_string()),
+                summary: String::new(),
+            },
+            PolicyMode::Live,
+        );
+        assert_eq!(failed.decision_id, destroyed.decision_id + 1);
+        assert_eq!(failed.size_bytes, 0);
+        assert_eq!(failed.effective_action, Some(ActionRecord::Keep));
+    }
+
     #[test]
     fn guard_status_record_from_diagnostics() {
         let diag = sample_guard_diag();
@@ -1069,10 +1440,14 @@ mod tests {
                 has_git: false,
                 has_cargo_toml: false,
                 mostly_object_files: true,
+                has_jvm_outputs: false,
+                sibling_manifests: SiblingManifests::default(),
             },
             active_references: ActiveReferenceSummary::default(),
             is_open: false,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         };
 
         let scored = engine.score_candidate(&input, 0.5);
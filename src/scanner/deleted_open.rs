@@ -0,0 +1,383 @@
+//! Space held by deleted-but-open files.
+//!
+//! A file unlinked while a process still holds it open keeps its blocks until
+//! the last fd closes: `df` counts the space, but no directory walk can find
+//! it. This module groups the deleted fds reported by the platform layer by
+//! inode, attributes each file to the mount it lived on, and reclaims the
+//! space by truncating the inode through `/proc/<pid>/fd/<n>`.
+//!
+//! Truncation reuses the log truncator's safety model for append-only
+//! writers: the inode survives, so an `O_APPEND` writer keeps appending at the
+//! new end of file. A file is therefore only truncated when every holder is
+//! such a writer (nobody can read the discarded bytes back) and no process has
+//! it memory-mapped (truncating a mapped file raises `SIGBUS` in the mapper).
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use serde_json::{Value, json};
+
+use crate::core::config::DeletedOpenFilesConfig;
+use crate::platform::types::{DeletedOpenFile, OpenFileMode};
+use crate::scanner::log_truncator::{LogTruncationReport, SkipReason};
+
+/// One process fd keeping a deleted file alive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletedFileHolder {
+    /// Process holding the fd.
+    pub pid: i32,
+    /// Process name (`comm`), when readable.
+    pub process_name: Option<String>,
+    /// Descriptor number in that process.
+    pub fd: i32,
+    /// Access mode the fd was opened with.
+    pub mode: OpenFileMode,
+    /// Whether the fd appends (`O_APPEND`).
+    pub append: bool,
+}
+
+/// A deleted inode together with every fd that still holds it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeldDeletedFile {
+    /// Path the file had before it was unlinked.
+    pub path: PathBuf,
+    /// Mount the file lived on (longest mount-point prefix of `path`).
+    pub mount: Option<PathBuf>,
+    /// Device number of the filesystem holding the inode.
+    pub device: u64,
+    /// Inode number.
+    pub inode: u64,
+    /// Apparent file size.
+    pub size_bytes: u64,
+    /// Bytes actually allocated, i.e. what truncation frees.
+    pub allocated_bytes: u64,
+    /// Whether some process has the file memory-mapped.
+    pub mapped: bool,
+    /// Every fd on the inode, ordered by pid then fd.
+    pub holders: Vec<DeletedFileHolder>,
+}
+
+impl HeldDeletedFile {
+    /// The safety gate that keeps this file from being truncated, if any.
+    #[must_use]
+    pub fn truncation_veto(&self, config: &DeletedOpenFilesConfig) -> Option<SkipReason> {
+        if self.allocated_bytes < config.min_size_bytes {
+            return Some(SkipReason::BelowMinSize);
+        }
+        if self.mapped {
+            return Some(SkipReason::MemoryMapped);
+        }
+        if self
+            .holders
+            .iter()
+            .any(|holder| holder.mode != OpenFileMode::Write)
+        {
+            return Some(SkipReason::HeldForReading);
+        }
+        if self.holders.iter().any(|holder| !holder.append) {
+            return Some(SkipReason::NotAppendOnly);
+        }
+        None
+    }
+
+    /// JSON view used by `sbh status`, `sbh blame` and the daemon status dump.
+    #[must_use]
+    pub fn status_json(&self, config: &DeletedOpenFilesConfig) -> Value {
+        let holders: Vec<Value> = self
+            .holders
+            .iter()
+            .map(|holder| {
+                json!({
+                    "pid": holder.pid,
+                    "process": holder.process_name,
+                    "fd": holder.fd,
+                    "mode": holder.mode,
+                    "append": holder.append,
+                })
+            })
+            .collect();
+        json!({
+            "path": self.path,
+            "mount": self.mount,
+            "inode": self.inode,
+            "size_bytes": self.size_bytes,
+            "allocated_bytes": self.allocated_bytes,
+            "mapped": self.mapped,
+            "holders": holders,
+            "truncation_veto": self.truncation_veto(config).map(SkipReason::as_str),
+        })
+    }
+}
+
+/// Group deleted fds by inode and attribute each file to one of `mounts`.
+/// Files holding the most space come first.
+#[must_use]
+pub fn group_deleted_open_files(
+    files: Vec<DeletedOpenFile>,
+    mounts: &[PathBuf],
+) -> Vec<HeldDeletedFile> {
+    let mut by_inode: BTreeMap<(u64, u64), HeldDeletedFile> = BTreeMap::new();
+    for file in files {
+        let held = by_inode
+            .entry((file.device, file.inode))
+            .or_insert_with(|| HeldDeletedFile {
+                mount: mount_for(&file.path, mounts),
+                path: file.path.clone(),
+                device: file.device,
+                inode: file.inode,
+                size_bytes: file.size_bytes,
+                allocated_bytes: file.allocated_bytes,
+                mapped: false,
+                holders: Vec::new(),
+            });
+        held.mapped |= file.mapped;
+        held.size_bytes = held.size_bytes.max(file.size_bytes);
+        held.allocated_bytes = held.allocated_bytes.max(file.allocated_bytes);
+        held.holders.push(DeletedFileHolder {
+            pid: file.pid,
+            process_name: file.process_name,
+            fd: file.fd,
+            mode: file.mode,
+            append: file.append,
+        });
+    }
+
+    let mut held: Vec<HeldDeletedFile> = by_inode.into_values().collect();
+    for file in &mut held {
+        file.holders.sort_by(|left, right| {
+            left.pid
+                .cmp(&right.pid)
+                .then_with(|| left.fd.cmp(&right.fd))
+        });
+    }
+    held.sort_by(|left, right| {
+        right
+            .allocated_bytes
+            .cmp(&left.allocated_bytes)
+            .then_with(|| left.path.cmp(&right.path))
+    });
+    held
+}
+
+fn mount_for(path: &Path, mounts: &[PathBuf]) -> Option<PathBuf> {
+    mounts
+        .iter()
+        .filter(|mount| path.starts_with(mount))
+        .max_by_key(|mount| mount.as_os_str().len())
+        .cloned()
+}
+
+/// Truncate every file in `files` that passes the safety gates. Reporting
+/// mirrors a log truncation sweep.
+pub fn truncate_held_files<'a>(
+    files: impl IntoIterator<Item = &'a HeldDeletedFile>,
+    config: &DeletedOpenFilesConfig,
+    dry_run: bool,
+) -> LogTruncationReport {
+    let start = Instant::now();
+    let mut report = LogTruncationReport {
+        dry_run,
+        ..Default::default()
+    };
+
+    for file in files {
+        if let Some(reason) = file.truncation_veto(config) {
+            report.files_skipped += 1;
+            report.skipped_with_reason.push((file.path.clone(), reason));
+            continue;
+        }
+        if dry_run {
+            report.files_would_truncate += 1;
+            report.bytes_would_reclaim += file.allocated_bytes;
+            continue;
+        }
+        match truncate_through_holder(file) {
+            Ok(()) => {
+                report.files_truncated += 1;
+                report.bytes_reclaimed += file.allocated_bytes;
+            }
+            Err(e) => {
+                report.files_skipped += 1;
+                report.errors.push((file.path.clone(), e));
+            }
+        }
+    }
+
+    report.duration = start.elapsed();
+    report
+}
+
+/// Truncate the inode through the first holder's fd link. Unlike the log
+/// truncator this must follow the link: `/proc/<pid>/fd/<n>` is the only
+/// remaining way to the unlinked inode. The opened file is re-checked to be
+/// the same, still unlinked, regular file before anything is cut.
+#[cfg(unix)]
+fn truncate_through_holder(file: &HeldDeletedFile) -> Result<(), String> {
+    use std::os::unix::fs::{MetadataExt, OpenOptionsExt};
+
+    let holder = file
+        .holders
+        .first()
+        .ok_or_else(|| "no process holds the file".to_string())?;
+    let fd_path = PathBuf::from(format!("/proc/{}/fd/{}", holder.pid, holder.fd));
+    let f = std::fs::OpenOptions::new()
+        .write(true)
+        .custom_flags(libc::O_NONBLOCK | libc::O_CLOEXEC)
+        .open(&fd_path)
+        .map_err(|e| e.to_string())?;
+    let meta = f.metadata().map_err(|e| e.to_string())?;
+    if !meta.is_file() || meta.dev() != file.device || meta.ino() != file.inode || meta.nlink() != 0
+    {
+        return Err("fd no longer refers to the deleted file".to_string());
+    }
+    f.set_len(0).map_err(|e| e.to_string())
+}
+
+#[cfg(not(unix))]
+fn truncate_through_holder(_file: &HeldDeletedFile) -> Result<(), String> {
+    Err("deleted-file truncation is not supported on this platform".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fd(pid: i32, fd: i32, inode: u64, mode: OpenFileMode, append: bool) -> DeletedOpenFile {
+        DeletedOpenFile {
+            pid,
+            process_name: Some(format!("proc{pid}")),
+            fd,
+            path: PathBuf::from(format!("/data/logs/{inode}.log")),
+            device: 64,
+            inode,
+            size_bytes: inode * 1_000,
+            allocated_bytes: inode * 1_000,
+            mode,
+            append,
+            mapped: false,
+        }
+    }
+
+    fn config() -> DeletedOpenFilesConfig {
+        DeletedOpenFilesConfig {
+            truncate: true,
+            min_size_bytes: 2_000,
+            pressure_free_pct_ceiling: 10,
+        }
+    }
+
+    #[test]
+    fn groups_fds_by_inode_and_attributes_mounts() {
+        let mounts = [PathBuf::from("/"), PathBuf::from("/data")];
+        let held = group_deleted_open_files(
+            vec![
+                fd(20, 4, 7, OpenFileMode::Write, true),
+                fd(10, 3, 7, OpenFileMode::Write, true),
+                fd(10, 5, 3, OpenFileMode::Read, false),
+            ],
+            &mounts,
+        );
+        assert_eq!(held.len(), 2);
+        assert_eq!(held[0].inode, 7, "largest file first");
+        assert_eq!(held[0].mount, Some(PathBuf::from("/data")));
+        let holders: Vec<(i32, i32)> = held[0].holders.iter().map(|h| (h.pid, h.fd)).collect();
+        assert_eq!(holders, vec![(10, 3), (20, 4)]);
+
+        let json = held[0].status_json(&config());
+        assert_eq!(json["allocated_bytes"], 7_000);
+        assert_eq!(json["holders"][0]["process"], "proc10");
+        assert!(json["truncation_veto"].is_null());
+        assert_eq!(
+            held[1].status_json(&config())["truncation_veto"],
+            "held_for_reading"
+        );
+    }
+
+    #[test]
+    fn only_unmapped_append_only_files_pass_the_gate() {
+        let config = config();
+        let held = |files: Vec<DeletedOpenFile>| {
+            group_deleted_open_files(files, &[PathBuf::from("/")]).remove(0)
+        };
+
+        assert_eq!(
+            held(vec![fd(1, 3, 5, OpenFileMode::Write, true)]).truncation_veto(&config),
+            None
+        );
+        assert_eq!(
+            held(vec![fd(1, 3, 1, OpenFileMode::Write, true)]).truncation_veto(&config),
+            Some(SkipReason::BelowMinSize)
+        );
+        assert_eq!(
+            held(vec![
+                fd(1, 3, 5, OpenFileMode::Write, true),
+                fd(2, 3, 5, OpenFileMode::ReadWrite, true),
+            ])
+            .truncation_veto(&config),
+            Some(SkipReason::HeldForReading)
+        );
+        assert_eq!(
+            held(vec![fd(1, 3, 5, OpenFileMode::Write, false)]).truncation_veto(&config),
+            Some(SkipReason::NotAppendOnly)
+        );
+        let mut mapped = fd(1, 3, 5, OpenFileMode::Write, true);
+        mapped.mapped = true;
+        assert_eq!(
+            held(vec![mapped]).truncation_veto(&config),
+            Some(SkipReason::MemoryMapped)
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn truncates_unlinked_append_log_through_proc_fd() {
+        use std::io::Write as _;
+        use std::os::unix::fs::MetadataExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("held.log");
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(&[b'x'; 4096]).unwrap();
+        let meta = file.metadata().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let held = HeldDeletedFile {
+            path,
+            mount: None,
+            device: meta.dev(),
+            inode: meta.ino(),
+            size_bytes: 4096,
+            allocated_bytes: 4096,
+            mapped: false,
+            holders: vec![DeletedFileHolder {
+                pid: i32::try_from(std::process::id()).unwrap(),
+                process_name: None,
+                fd: std::os::fd::AsRawFd::as_raw_fd(&file),
+                mode: OpenFileMode::Write,
+                append: true,
+            }],
+        };
+        let config = DeletedOpenFilesConfig {
+            min_size_bytes: 1,
+            ..config()
+        };
+
+        let dry = truncate_held_files([&held], &config, true);
+        assert_eq!(dry.files_would_truncate, 1);
+        assert_eq!(file.metadata().unwrap().len(), 4096);
+
+        let report = truncate_held_files([&held], &config, false);
+        assert_eq!(report.files_truncated, 1, "{report:?}");
+        assert_eq!(report.bytes_reclaimed, 4096);
+        assert_eq!(file.metadata().unwrap().len(), 0);
+
+        // The writer carries on at the new end of file.
+        file.write_all(b"after").unwrap();
+        assert_eq!(file.metadata().unwrap().len(), 5);
+    }
+}
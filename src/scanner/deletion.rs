@@ -12,6 +12,10 @@
 //! 6. Candidate identity still matches the object observed by the scanner
 //!
 //! Circuit breaker: 3 consecutive failures -> halt batch (daemon retries next cycle).
+//!
+//! Candidates whose cleanup rule reclaims with the owning tool
+//! (`ReclaimCommand::Tool`) pass the same pre-flight checks, then run that tool
+//! instead of being removed; see [`crate::scanner::tool_reclaim`].
 
 #![allow(missing_docs)]
 #![allow(clippy::cast_precision_loss)]
@@ -24,10 +28,14 @@ use std::time::{Duration, Instant};
 use crate::core::errors::{Result, SbhError};
 use crate::logger::dual::{ActivityEvent, ActivityLoggerHandle};
 use crate::logger::jsonl::ScoreFactorsRecord;
+use crate::platform::cleanup_catalog::{CleanupRule, ToolReclaim};
 use crate::scanner::patterns::{
     ArtifactCategory, ArtifactClassification, StructuralSignals, is_obvious_build_artifact_basename,
 };
-use crate::scanner::scoring::{CandidacyScore, DecisionAction, ScoreFactors};
+use crate::scanner::scoring::{
+    CandidacyScore, DecisionAction, ScoreFactors, cleanup_rule_for_classification,
+};
+use crate::scanner::tool_reclaim::{self, ToolReclaimOutcome, ToolUser};
 use crate::scanner::walker;
 
 // ──────────────────── configuration ────────────────────
@@ -78,21 +86,26 @@ pub struct DeletionPlan {
 /// Summary after a deletion batch completes.
 #[derive(Debug, Clone)]
 pub struct DeletionReport {
-    /// Paths actually removed from the filesystem.
+    /// Paths actually removed from the filesystem, or trimmed by their
+    /// owning tool.
     pub items_deleted: usize,
     pub items_failed: usize,
     pub items_skipped: usize,
     /// Paths that passed all safety checks and would have been removed in dry-run mode.
     pub items_would_delete: usize,
-    /// Bytes actually reclaimed from the filesystem.
+    /// Bytes actually reclaimed from the filesystem. Tool runs contribute
+    /// their measured before/after delta.
     pub bytes_freed: u64,
-    /// Bytes that would have been reclaimed in dry-run mode.
+    /// Bytes that would have been reclaimed in dry-run mode. Tool-reclaimed
+    /// candidates contribute their rule's expected-bytes estimate.
     pub bytes_would_free: u64,
     pub duration: Duration,
     pub errors: Vec<DeletionError>,
     pub dry_run: bool,
     pub circuit_breaker_tripped: bool,
     pub deleted_paths: Vec<PathBuf>,
+    /// Owning-tool runs that completed, with their measured deltas.
+    pub tool_runs: Vec<ToolReclaimOutcome>,
     /// Paths skipped specifically because the executor cannot write to the
     /// parent directory. Almost always indicates a misconfigured systemd
     /// unit (`ProtectSystem=strict` + `ReadWritePaths=` whitelist that
@@ -188,6 +201,9 @@ pub enum SkipReason {
     /// Operator answered "no" (or quit) at an interactive prompt. Only ever
     /// produced by the interactive clean/emergency flows.
     UserDeclined,
+    /// The owning tool would have to run as another user, and the executor
+    /// cannot switch to that user.
+    ToolOwnerUnavailable,
 }
 
 impl SkipReason {
@@ -197,7 +213,7 @@ impl SkipReason {
     /// mapping a `skipped_by_reason` JSON key back to its prose. Previously this
     /// list was duplicated at each call site, so adding a variant silently left
     /// it unexplained. `all_covers_every_variant` guards completeness.
-    pub const ALL: [Self; 14] = [
+    pub const ALL: [Self; 15] = [
         Self::TargetFreeReached,
         Self::PathGone,
         Self::FileOpen,
@@ -212,6 +228,7 @@ impl SkipReason {
         Self::HardcodedSourceTree,
         Self::LooksLikeSourceCode,
         Self::UserDeclined,
+        Self::ToolOwnerUnavailable,
     ];
 
     /// Resolve a `skipped_by_reason` key back to its variant.
@@ -240,6 +257,7 @@ impl SkipReason {
             Self::HardcodedSourceTree => "hardcoded_source_tree",
             Self::LooksLikeSourceCode => "looks_like_source_code",
             Self::UserDeclined => "user_declined",
+            Self::ToolOwnerUnavailable => "tool_owner_unavailable",
         }
     }
 
@@ -266,6 +284,9 @@ impl SkipReason {
             }
             Self::LooksLikeSourceCode => "contains source-code marker files",
             Self::UserDeclined => "operator declined at the interactive prompt",
+            Self::ToolOwnerUnavailable => {
+                "cache belongs to another user and its tool cannot run as them"
+            }
         }
     }
 }
@@ -343,6 +364,7 @@ impl DeletionExecutor {
             dry_run: self.config.dry_run,
             circuit_breaker_tripped: false,
             deleted_paths: Vec::new(),
+            tool_runs: Vec::new(),
             not_writable_paths: Vec::new(),
             backoff_candidates: Vec::new(),
             skipped_by_reason: BTreeMap::new(),
@@ -359,7 +381,9 @@ impl DeletionExecutor {
                 .candidates
                 .iter()
                 .take(limit)
-                .map(|candidate| candidate.path.clone())
+                .flat_map(|candidate| {
+                    std::iter::once(candidate.path.clone()).chain(gradle_lock_dir(candidate))
+                })
                 .collect::<Vec<_>>();
             let (paths, complete) = walker::collect_open_path_ancestors(&roots);
 
@@ -462,15 +486,38 @@ impl DeletionExecutor {
                 }
             }
 
+            let tool_rule = tool_reclaim_rule(candidate);
+            let tool_user = tool_rule
+                .as_ref()
+                .and_then(|_| tool_reclaim::tool_user(&candidate.path));
+            if tool_rule.is_some() && tool_user.is_none() {
+                report.record_skip(SkipReason::ToolOwnerUnavailable);
+                consecutive_failures = 0;
+                continue;
+            }
             if self.config.dry_run {
                 report.items_would_delete += 1;
-                report.bytes_would_free += candidate.size_bytes;
+                report.bytes_would_free += tool_rule
+                    .as_ref()
+                    .map_or(candidate.size_bytes, |(_, tool)| {
+                        tool.expected_bytes(candidate.size_bytes)
+                    });
                 Self::log_dry_run(candidate);
                 continue;
             }
 
+            if let (Some((rule, tool)), Some(user)) = (&tool_rule, &tool_user) {
+                if self.reclaim_with_tool(candidate, rule, tool, user, &mut report) {
+                    consecutive_failures = 0;
+                } else {
+                    consecutive_failures += 1;
+                }
+                continue;
+            }
+
             // Actual deletion.
             let del_start = Instant::now();
+            let available_before = available_bytes_near(&candidate.path);
             match self.delete_path(candidate) {
                 Ok(()) => {
                     #[allow(clippy::cast_possible_truncation)]
@@ -480,7 +527,8 @@ impl DeletionExecutor {
                     report.deleted_paths.push(candidate.path.clone());
                     consecutive_failures = 0;
 
-                    self.log_deletion_success(candidate, duration_ms);
+                    let measured = measured_freed_bytes(&candidate.path, available_before);
+                    self.log_deletion_success(candidate, duration_ms, measured);
                 }
                 Err(e) => {
                     report.items_failed += 1;
@@ -591,6 +639,15 @@ impl DeletionExecutor {
             return Err(SkipReason::FileOpen);
         }
 
+        // 8. A Gradle daemon working on the project holds lock files in the
+        //    sibling `.gradle/` dir, not in `build/` itself.
+        if let Some(open) = open_paths
+            && let Some(lock_dir) = gradle_lock_dir(candidate)
+            && walker::is_path_open_by_ancestor(&lock_dir, open)
+        {
+            return Err(SkipReason::FileOpen);
+        }
+
         Ok(())
     }
 
@@ -669,6 +726,78 @@ impl DeletionExecutor {
         Ok(())
     }
 
+    /// Run the owning tool for `candidate` and record the outcome in
+    /// `report`. Returns whether the run succeeded.
+    fn reclaim_with_tool(
+        &self,
+        candidate: &CandidacyScore,
+        rule: &CleanupRule,
+        tool: &ToolReclaim,
+        user: &ToolUser,
+        report: &mut DeletionReport,
+    ) -> bool {
+        let available_before = available_bytes_near(&candidate.path);
+        match tool_reclaim::run_tool_reclaim(
+            &rule.name,
+            tool,
+            &candidate.path,
+            candidate.size_bytes,
+            user,
+        ) {
+            Ok(outcome) => {
+                #[allow(clippy::cast_possible_truncation)]
+                let duration_ms = outcome.duration.as_millis() as u64;
+                report.items_deleted += 1;
+                report.bytes_freed += outcome.bytes_freed();
+                report.deleted_paths.push(candidate.path.clone());
+                self.log_event(ActivityEvent::ArtifactDeleted {
+                    path: candidate.path.to_string_lossy().to_string(),
+                    size_bytes: outcome.bytes_freed(),
+                    score: candidate.total_score,
+                    factors: factors_to_record(&candidate.factors),
+                    pressure: String::new(),
+                    free_pct: 0.0,
+                    duration_ms,
+                    measured_freed_bytes: measured_freed_bytes(&candidate.path, available_before),
+                });
+                self.log_event(ActivityEvent::Info {
+                    message: format!(
+                        "{}: `{}` freed {} bytes of {} (expected {})",
+                        rule.name,
+                        outcome.argv.join(" "),
+                        outcome.bytes_freed(),
+                        outcome.bytes_before,
+                        outcome.expected_bytes,
+                    ),
+                });
+                report.tool_runs.push(outcome);
+                true
+            }
+            Err(e) => {
+                report.items_failed += 1;
+                eprintln!(
+                    "[SBH-EXECUTOR] tool fail: {} ({})",
+                    candidate.path.display(),
+                    e
+                );
+                let error = DeletionError {
+                    path: candidate.path.clone(),
+                    error: e.to_string(),
+                    error_code: e.code().to_string(),
+                    recoverable: e.is_retryable(),
+                };
+                self.log_event(ActivityEvent::ArtifactDeletionFailed {
+                    path: candidate.path.to_string_lossy().to_string(),
+                    error_code: error.error_code.clone(),
+                    error_message: error.error.clone(),
+                });
+                report.errors.push(error);
+                report.backoff_candidates.push(candidate.clone());
+                false
+            }
+        }
+    }
+
     // ──────────────────── logging helpers ────────────────────
 
     fn log_event(&self, event: ActivityEvent) {
@@ -677,7 +806,12 @@ impl DeletionExecutor {
         }
     }
 
-    fn log_deletion_success(&self, candidate: &CandidacyScore, duration_ms: u64) {
+    fn log_deletion_success(
+        &self,
+        candidate: &CandidacyScore,
+        duration_ms: u64,
+        measured_freed_bytes: Option<u64>,
+    ) {
         self.log_event(ActivityEvent::ArtifactDeleted {
             path: candidate.path.to_string_lossy().to_string(),
             size_bytes: candidate.size_bytes,
@@ -686,6 +820,7 @@ impl DeletionExecutor {
             pressure: String::new(), // Caller doesn't pass pressure level here
             free_pct: 0.0,
             duration_ms,
+            measured_freed_bytes,
         });
     }
 
@@ -695,6 +830,13 @@ impl DeletionExecutor {
     }
 }
 
+/// Cleanup rule and tool for candidates reclaimed by their owning tool.
+fn tool_reclaim_rule(candidate: &CandidacyScore) -> Option<(CleanupRule, ToolReclaim)> {
+    let rule = cleanup_rule_for_classification(&candidate.classification)?;
+    let tool = *rule.tool()?;
+    Some((rule, tool))
+}
+
 // ──────────────────── writable check ────────────────────
 
 fn identity_is_supported(identity: walker::FsIdentity) -> bool {
@@ -741,10 +883,15 @@ fn is_writable(path: &Path) -> bool {
 /// `remove_dir_all`, so a read-only directory remains a natural brake on
 /// deletion. The members here are all caches the owning tool rebuilds on demand:
 ///   * `GoCache` — GOCACHE/GOMODCACHE (dirs `0555`, files `0444`).
+///   * `BazelOutputBase` — Bazel writes its outputs and fetched external
+///     repositories read-only.
 ///   * cargo registry/git caches (`.cargo/registry/src` and git checkouts are
 ///     read-only) and rch's isolated `CARGO_HOME` staging dirs.
 fn classification_allows_force_remove(c: &ArtifactClassification) -> bool {
-    if c.category == ArtifactCategory::GoCache {
+    if matches!(
+        c.category,
+        ArtifactCategory::GoCache | ArtifactCategory::BazelOutputBase
+    ) {
         return true;
     }
     matches!(
@@ -757,6 +904,18 @@ fn classification_allows_force_remove(c: &ArtifactClassification) -> bool {
     )
 }
 
+/// Project `.gradle/` dir whose open lock files mean a Gradle daemon is
+/// still using a Gradle `build/` candidate.
+fn gradle_lock_dir(candidate: &CandidacyScore) -> Option<PathBuf> {
+    if candidate.classification.pattern_name != "opaque-gradle-build" {
+        return None;
+    }
+    candidate
+        .path
+        .parent()
+        .map(|project| project.join(".gradle"))
+}
+
 /// `fs::remove_dir_all` that defeats read-only directory/file permission bits.
 ///
 /// Standard `remove_dir_all` cannot unlink an entry inside a `0555` directory —
@@ -1029,6 +1188,34 @@ fn contains_cargo_manifest_without_artifact_markers(path: &Path) -> bool {
             || (signals.has_build && signals.has_deps))
 }
 
+// ──────────────────── reclaim measurement ────────────────────
+
+/// Bytes available to unprivileged writers on the filesystem holding `path`,
+/// read through its parent so the probe survives the path's removal.
+fn available_bytes_near(path: &Path) -> Option<u64> {
+    let parent = path.parent()?;
+    #[cfg(unix)]
+    {
+        let stat = nix::sys::statvfs::statvfs(parent).ok()?;
+        Some(
+            stat.blocks_available()
+                .saturating_mul(stat.fragment_size() as u64),
+        )
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = parent;
+        None
+    }
+}
+
+/// Growth in available bytes since `before`. Concurrent writers on the same
+/// filesystem skew this in either direction; it is recorded beside the
+/// estimate, never used in its place.
+fn measured_freed_bytes(path: &Path, before: Option<u64>) -> Option<u64> {
+    Some(available_bytes_near(path)?.saturating_sub(before?))
+}
+
 // ──────────────────── conversions ────────────────────
 
 fn factors_to_record(f: &ScoreFactors) -> ScoreFactorsRecord {
@@ -1172,6 +1359,7 @@ mod tests {
                 SkipReason::HardcodedSourceTree => 11,
                 SkipReason::LooksLikeSourceCode => 12,
                 SkipReason::UserDeclined => 13,
+                SkipReason::ToolOwnerUnavailable => 14,
             }
         }
         let mut seen = [false; SkipReason::ALL.len()];
@@ -1211,6 +1399,7 @@ mod tests {
             dry_run: false,
             circuit_breaker_tripped: false,
             deleted_paths: Vec::new(),
+            tool_runs: Vec::new(),
             not_writable_paths: Vec::new(),
             backoff_candidates: Vec::new(),
             skipped_by_reason: BTreeMap::new(),
@@ -1265,6 +1454,33 @@ mod tests {
         assert!(report.stalled(), "report should flag a no-progress run");
     }
 
+    #[test]
+    fn dry_run_reports_tool_estimate_for_tool_reclaimed_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = dir.path().join("store");
+        fs::create_dir_all(store.join("v3/files/00")).unwrap();
+        fs::write(store.join("v3/files/00/abc"), vec![0u8; 64]).unwrap();
+
+        let mut candidate = make_candidate(&store, 10_000, 0.95);
+        candidate.classification.pattern_name = Cow::Borrowed("pnpm-store");
+        candidate.classification.category = ArtifactCategory::CacheDir;
+
+        let executor = DeletionExecutor::new(
+            DeletionConfig {
+                dry_run: true,
+                check_open_files: false,
+                ..Default::default()
+            },
+            None,
+        );
+        let report = executor.execute(&executor.plan(vec![candidate]), None);
+
+        assert_eq!(report.items_would_delete, 1);
+        assert_eq!(report.bytes_would_free, 4_000);
+        assert!(report.tool_runs.is_empty());
+        assert!(store.join("v3/files/00/abc").exists());
+    }
+
     #[test]
     fn stalled_is_false_for_productive_and_dry_runs() {
         let base = DeletionReport {
@@ -1279,6 +1495,7 @@ mod tests {
             dry_run: false,
             circuit_breaker_tripped: false,
             deleted_paths: Vec::new(),
+            tool_runs: Vec::new(),
             not_writable_paths: Vec::new(),
             backoff_candidates: Vec::new(),
             skipped_by_reason: BTreeMap::new(),
@@ -1973,6 +2190,31 @@ mod tests {
         assert_eq!(result, Err(SkipReason::LooksLikeSourceCode));
     }
 
+    #[test]
+    fn preflight_skips_gradle_build_while_daemon_holds_project_locks() {
+        let dir = tempfile::tempdir().unwrap();
+        let build = dir.path().join("app/build");
+        fs::create_dir_all(build.join("classes")).unwrap();
+        let lock = dir
+            .path()
+            .join("app/.gradle/8.5/fileHashes/fileHashes.lock");
+        fs::create_dir_all(lock.parent().unwrap()).unwrap();
+        fs::write(&lock, "").unwrap();
+
+        let executor = DeletionExecutor::new(DeletionConfig::default(), None);
+        let mut candidate = make_candidate(&build, 1, 1.0);
+        candidate.classification.pattern_name = Cow::Borrowed("opaque-gradle-build");
+        candidate.classification.category = ArtifactCategory::JvmBuild;
+
+        let idle = HashSet::new();
+        assert_eq!(executor.preflight_check(&candidate, Some(&idle)), Ok(()));
+        let held: HashSet<PathBuf> = lock.ancestors().map(Path::to_path_buf).collect();
+        assert_eq!(
+            executor.preflight_check(&candidate, Some(&held)),
+            Err(SkipReason::FileOpen)
+        );
+    }
+
     #[test]
     fn preflight_cargo_manifest_still_takes_precedence_over_source_marker() {
         // Defensive: if a dir has BOTH Cargo.toml and a .rs file, the older
@@ -2389,4 +2631,20 @@ mod tests {
         assert_eq!(report.items_skipped, 1);
         assert!(src_dir.exists());
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn reclaim_is_measured_through_the_parent_after_removal() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let target = tmp.path().join("target");
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("blob"), vec![7u8; 256 * 1024]).unwrap();
+
+        let before = available_bytes_near(&target);
+        assert!(before.is_some());
+        fs::remove_dir_all(&target).unwrap();
+        assert!(measured_freed_bytes(&target, before).is_some());
+        assert_eq!(measured_freed_bytes(&target, None), None);
+        assert_eq!(available_bytes_near(Path::new("/")), None);
+    }
 }
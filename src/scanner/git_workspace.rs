@@ -0,0 +1,845 @@
+//! Reclaim of abandoned git worktrees and agent clones.
+//!
+//! The deletion executor refuses anything holding a `.git`, and that stays
+//! the rule for scored candidates. Agent swarms still leave a linked worktree
+//! or a full clone behind per task. With `[scanner.git_workspaces]` enabled,
+//! the daemon hands the ones its scan passes to this module, which reclaims a
+//! workspace only when git shows there is nothing to lose:
+//!   - no modified, staged or untracked files (ignored files do not count);
+//!   - no stash entries (clones only: a worktree shares its repo's stash);
+//!   - no commits missing from every remote-tracking ref;
+//!   - its HEAD is merged into a base branch, or its branch's upstream was
+//!     deleted, judged from local refs only (nothing is fetched);
+//!   - git has not touched it for `min_idle_days`.
+//!
+//! A linked worktree is removed through `git worktree remove`, which refuses
+//! dirty or locked worktrees on its own, followed by `git worktree prune`. A
+//! clone is removed directly. Any linked worktree qualifies; the caller only
+//! passes clones whose name matches an agent workspace pattern.
+//!
+//! Git runs with optional locks, hooks and fsmonitor off, so inspecting a
+//! workspace never rewrites its index (which would reset the idle clock) and
+//! never runs a program the repository configures. Workspaces owned by a
+//! user other than the daemon's are left alone, and so is a repository whose
+//! local or worktree config (including files it includes) defines filter
+//! drivers, since `git status` may run them.
+
+#![allow(missing_docs)]
+
+use std::collections::HashSet;
+use std::fs;
+use std::hash::BuildHasher;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::core::config::GitWorkspaceConfig;
+use crate::core::errors::{Result, SbhError};
+use crate::scanner::tool_reclaim::tree_size;
+
+/// Config overrides applied to every git invocation.
+const HARDENING: &[&str] = &[
+    "-c",
+    "core.fsmonitor=false",
+    "-c",
+    "core.hooksPath=/dev/null",
+];
+
+/// How a workspace relates to its repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitWorkspaceKind {
+    /// Linked worktree of the repository whose git dir is `common_dir`.
+    Worktree { common_dir: PathBuf },
+    /// Standalone clone with its own `.git` directory.
+    Clone,
+}
+
+/// A working tree found by the scan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitWorkspace {
+    pub path: PathBuf,
+    /// Per-worktree git dir (`<repo>/.git/worktrees/<name>` or `<path>/.git`).
+    pub git_dir: PathBuf,
+    pub kind: GitWorkspaceKind,
+}
+
+impl GitWorkspace {
+    #[must_use]
+    pub const fn is_worktree(&self) -> bool {
+        matches!(self.kind, GitWorkspaceKind::Worktree { .. })
+    }
+
+    /// Pattern name used in decision records.
+    #[must_use]
+    pub const fn label(&self) -> &'static str {
+        match self.kind {
+            GitWorkspaceKind::Worktree { .. } => "git-worktree",
+            GitWorkspaceKind::Clone => "git-clone",
+        }
+    }
+
+    /// Latest time git wrote to the workspace: its HEAD, index or HEAD
+    /// reflog, or a change to its top-level directory.
+    #[must_use]
+    pub fn last_activity(&self) -> SystemTime {
+        [
+            self.path.clone(),
+            self.git_dir.join("HEAD"),
+            self.git_dir.join("index"),
+            self.git_dir.join("logs").join("HEAD"),
+        ]
+        .iter()
+        .filter_map(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+        .max()
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+    }
+}
+
+/// Identify `path` as a linked worktree or a clone.
+///
+/// Submodule checkouts (a `.git` file pointing into `.git/modules`) and bare
+/// repositories are not workspaces.
+#[must_use]
+pub fn detect(path: &Path) -> Option<GitWorkspace> {
+    let dot_git = path.join(".git");
+    let meta = fs::symlink_metadata(&dot_git).ok()?;
+    if meta.is_dir() {
+        return dot_git.join("HEAD").is_file().then(|| GitWorkspace {
+            path: path.to_path_buf(),
+            git_dir: dot_git,
+            kind: GitWorkspaceKind::Clone,
+        });
+    }
+    if !meta.is_file() {
+        return None;
+    }
+    let contents = fs::read_to_string(&dot_git).ok()?;
+    let git_dir = path.join(contents.trim().strip_prefix("gitdir:")?.trim());
+    let admin = git_dir.parent()?;
+    if admin.file_name()? != "worktrees" || !git_dir.join("HEAD").is_file() {
+        return None;
+    }
+    Some(GitWorkspace {
+        path: path.to_path_buf(),
+        kind: GitWorkspaceKind::Worktree {
+            common_dir: admin.parent()?.to_path_buf(),
+        },
+        git_dir,
+    })
+}
+
+/// Why a workspace's branch no longer needs its working tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BranchFate {
+    /// HEAD is an ancestor of this base branch ref.
+    Merged(String),
+    /// The branch tracks this upstream, whose remote-tracking ref is gone.
+    UpstreamGone(String),
+}
+
+/// What git says about a workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitEvidence {
+    /// Abbreviated HEAD commit.
+    pub head: String,
+    /// Checked-out branch; `None` for a detached HEAD.
+    pub branch: Option<String>,
+    /// Modified, staged and untracked paths.
+    pub changed_paths: usize,
+    pub stashes: usize,
+    /// Commits reachable from the workspace but from no remote-tracking ref.
+    pub unpushed_commits: usize,
+    /// Set by `git worktree lock`.
+    pub locked: bool,
+    pub fate: Option<BranchFate>,
+    pub idle: Duration,
+}
+
+impl GitEvidence {
+    /// The first requirement the workspace fails, if any.
+    #[must_use]
+    pub fn blocker(&self) -> Option<String> {
+        if self.locked {
+            return Some("worktree is locked".to_string());
+        }
+        if self.changed_paths > 0 {
+            return Some(format!(
+                "{} modified or untracked path(s)",
+                self.changed_paths
+            ));
+        }
+        if self.stashes > 0 {
+            return Some(format!("{} stash entries", self.stashes));
+        }
+        if self.unpushed_commits > 0 {
+            return Some(format!(
+                "{} commit(s) on no remote-tracking ref",
+                self.unpushed_commits
+            ));
+        }
+        if self.fate.is_some() {
+            return None;
+        }
+        Some(self.branch.as_ref().map_or_else(
+            || "detached HEAD is not merged into a base branch".to_string(),
+            |branch| format!("branch {branch} is not merged and its upstream still exists"),
+        ))
+    }
+
+    /// One-line account of the evidence for the decision record.
+    #[must_use]
+    pub fn summary(&self, workspace: &GitWorkspace) -> String {
+        let kind = match &workspace.kind {
+            GitWorkspaceKind::Worktree { common_dir } => {
+                format!("worktree of {}", common_dir.display())
+            }
+            GitWorkspaceKind::Clone => "clone".to_string(),
+        };
+        let fate = match &self.fate {
+            Some(BranchFate::Merged(base)) => format!("merged into {base}"),
+            Some(BranchFate::UpstreamGone(upstream)) => format!("upstream {upstream} deleted"),
+            None => "not merged".to_string(),
+        };
+        format!(
+            "{kind} on {branch} at {head}: {changed} changed path(s), {stashes} stash(es), \
+             {unpushed} unpushed commit(s){locked}, {fate}, idle {days}d",
+            branch = self.branch.as_deref().unwrap_or("detached HEAD"),
+            head = self.head,
+            changed = self.changed_paths,
+            stashes = self.stashes,
+            unpushed = self.unpushed_commits,
+            locked = if self.locked { ", locked" } else { "" },
+            days = self.idle.as_secs() / 86_400,
+        )
+    }
+}
+
+/// Collect the git evidence for `workspace`.
+pub fn inspect(
+    workspace: &GitWorkspace,
+    base_branches: &[String],
+    idle: Duration,
+) -> Result<GitEvidence> {
+    let dir = workspace.path.as_path();
+    check_owner(workspace)?;
+    for scope in ["--local", "--worktree"] {
+        let filters = git(
+            dir,
+            &[
+                "config",
+                scope,
+                "--includes",
+                "--name-only",
+                "--get-regexp",
+                r"^filter\.",
+            ],
+        )?;
+        if !filters.stdout.is_empty() {
+            return Err(SbhError::Runtime {
+                details: "repository defines filter drivers; not inspected".to_string(),
+            });
+        }
+    }
+
+    let head = git_stdout(dir, &["rev-parse", "--verify", "--short", "HEAD"])?;
+    let branch = git(dir, &["symbolic-ref", "-q", "--short", "HEAD"])?;
+    let branch = branch
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&branch.stdout).trim().to_string());
+    let changed_paths = git_stdout(
+        dir,
+        &[
+            "status",
+            "--porcelain",
+            "--untracked-files=normal",
+            "--ignore-submodules=none",
+        ],
+    )?
+    .lines()
+    .count();
+    let (stashes, unpushed) = match workspace.kind {
+        // Stashes and other branches live in the shared repository and
+        // outlive the worktree; only commits on its HEAD could be lost.
+        GitWorkspaceKind::Worktree { .. } => (
+            0,
+            git_stdout(dir, &["rev-list", "--count", "HEAD", "--not", "--remotes"])?,
+        ),
+        GitWorkspaceKind::Clone => (
+            git_stdout(dir, &["stash", "list"])?.lines().count(),
+            git_stdout(
+                dir,
+                &[
+                    "rev-list",
+                    "--count",
+                    "HEAD",
+                    "--branches",
+                    "--tags",
+                    "--not",
+                    "--remotes",
+                ],
+            )?,
+        ),
+    };
+    let unpushed_commits = unpushed.parse().map_err(|_| SbhError::Runtime {
+        details: format!("unexpected rev-list output: {unpushed}"),
+    })?;
+
+    Ok(GitEvidence {
+        head,
+        fate: branch_fate(dir, branch.as_deref(), base_branches)?,
+        branch,
+        changed_paths,
+        stashes,
+        unpushed_commits,
+        locked: workspace.git_dir.join("locked").exists(),
+        idle,
+    })
+}
+
+/// Refuse a workspace whose files another user owns: its configuration
+/// would otherwise run with the daemon's privileges.
+#[cfg(unix)]
+fn check_owner(workspace: &GitWorkspace) -> Result<()> {
+    use std::os::unix::fs::MetadataExt;
+
+    let daemon_uid = nix::unistd::geteuid().as_raw();
+    let mut paths = vec![workspace.path.as_path(), workspace.git_dir.as_path()];
+    if let GitWorkspaceKind::Worktree { common_dir } = &workspace.kind {
+        paths.push(common_dir);
+    }
+    for path in paths {
+        let uid = fs::symlink_metadata(path)
+            .map_err(|e| SbhError::io(path, e))?
+            .uid();
+        if uid != daemon_uid {
+            return Err(SbhError::Runtime {
+                details: format!(
+                    "{} is owned by uid {uid}, not the daemon's uid {daemon_uid}; not inspected",
+                    path.display()
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+#[allow(clippy::unnecessary_wraps)]
+const fn check_owner(_workspace: &GitWorkspace) -> Result<()> {
+    Ok(())
+}
+
+fn branch_fate(
+    dir: &Path,
+    branch: Option<&str>,
+    base_branches: &[String],
+) -> Result<Option<BranchFate>> {
+    let own_ref = branch.map(|branch| format!("refs/heads/{branch}"));
+    for base in base_branches {
+        let refs = git_stdout(
+            dir,
+            &[
+                "for-each-ref",
+                "--format=%(refname)",
+                &format!("refs/heads/{base}"),
+                &format!("refs/remotes/*/{base}"),
+            ],
+        )?;
+        for base_ref in refs.lines() {
+            if own_ref.as_deref() == Some(base_ref) {
+                continue;
+            }
+            if git(dir, &["merge-base", "--is-ancestor", "HEAD", base_ref])?
+                .status
+                .success()
+            {
+                let short = base_ref
+                    .strip_prefix("refs/heads/")
+                    .or_else(|| base_ref.strip_prefix("refs/remotes/"))
+                    .unwrap_or(base_ref);
+                return Ok(Some(BranchFate::Merged(short.to_string())));
+            }
+        }
+    }
+
+    let Some(branch) = branch else {
+        return Ok(None);
+    };
+    let config = |key: &str| {
+        git(dir, &["config", "--get", &format!("branch.{branch}.{key}")]).map(|out| {
+            out.status
+                .success()
+                .then(|| String::from_utf8_lossy(&out.stdout).trim().to_string())
+        })
+    };
+    let (Some(remote), Some(merge)) = (config("remote")?, config("merge")?) else {
+        return Ok(None);
+    };
+    let Some(name) = merge.strip_prefix("refs/heads/") else {
+        return Ok(None);
+    };
+    if remote == "." {
+        return Ok(None);
+    }
+    let tracking = format!("refs/remotes/{remote}/{name}");
+    let exists = git(dir, &["show-ref", "--verify", "--quiet", &tracking])?
+        .status
+        .success();
+    Ok((!exists).then(|| BranchFate::UpstreamGone(format!("{remote}/{name}"))))
+}
+
+/// Remove a workspace the evidence cleared.
+pub fn remove(workspace: &GitWorkspace) -> Result<()> {
+    match &workspace.kind {
+        GitWorkspaceKind::Worktree { common_dir } => {
+            let path = workspace.path.to_string_lossy();
+            git_stdout(common_dir, &["worktree", "remove", &path])?;
+            git_stdout(common_dir, &["worktree", "prune"])?;
+            Ok(())
+        }
+        GitWorkspaceKind::Clone => {
+            fs::remove_dir_all(&workspace.path).map_err(|e| SbhError::io(&workspace.path, e))
+        }
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<Output> {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(HARDENING)
+        .args(args)
+        .env("GIT_OPTIONAL_LOCKS", "0")
+        .env("LC_ALL", "C")
+        .env_remove("GIT_DIR")
+        .env_remove("GIT_WORK_TREE")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| SbhError::Runtime {
+            details: format!("could not run git: {e}"),
+        })
+}
+
+fn git_stdout(dir: &Path, args: &[&str]) -> Result<String> {
+    let out = git(dir, args)?;
+    if !out.status.success() {
+        return Err(SbhError::Runtime {
+            details: format!(
+                "git {} exited with {}: {}",
+                args.join(" "),
+                out.status,
+                String::from_utf8_lossy(&out.stderr).trim()
+            ),
+        });
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+/// What happened to one idle workspace.
+#[derive(Debug, Clone)]
+pub struct GitWorkspaceOutcome {
+    pub workspace: GitWorkspace,
+    pub size_bytes: u64,
+    /// `None` when git could not be queried.
+    pub evidence: Option<GitEvidence>,
+    /// Why the workspace was kept; `None` when it was (or would be) removed.
+    pub veto_reason: Option<String>,
+    pub removed: bool,
+}
+
+/// Outcome of one pass over the workspaces a scan found.
+#[derive(Debug, Clone, Default)]
+pub struct GitWorkspaceReport {
+    /// One entry per workspace idle long enough to be inspected.
+    pub outcomes: Vec<GitWorkspaceOutcome>,
+    pub bytes_freed: u64,
+    pub bytes_would_free: u64,
+    pub errors: Vec<(PathBuf, String)>,
+    pub duration: Duration,
+    pub dry_run: bool,
+}
+
+/// Remove the idle workspaces that have nothing to lose.
+///
+/// Only workspaces idle for `min_idle_days` are inspected. `open_paths` is an
+/// open-path ancestor index covering `workspaces`; a workspace with an open
+/// file inside is kept.
+#[must_use]
+pub fn reclaim_workspaces<S: BuildHasher>(
+    workspaces: &[GitWorkspace],
+    config: &GitWorkspaceConfig,
+    open_paths: &HashSet<PathBuf, S>,
+    now: SystemTime,
+    dry_run: bool,
+) -> GitWorkspaceReport {
+    let start = Instant::now();
+    let mut report = GitWorkspaceReport {
+        dry_run,
+        ..GitWorkspaceReport::default()
+    };
+    let min_idle = Duration::from_hours(config.min_idle_days.saturating_mul(24));
+    for workspace in workspaces {
+        // An enclosing workspace removed earlier in the pass took it along.
+        if !workspace.path.exists() {
+            continue;
+        }
+        let idle = now
+            .duration_since(workspace.last_activity())
+            .unwrap_or(Duration::ZERO);
+        if idle < min_idle {
+            continue;
+        }
+        let (evidence, mut veto_reason) = match inspect(workspace, &config.base_branches, idle) {
+            Ok(evidence) => {
+                let blocker = evidence.blocker();
+                (Some(evidence), blocker)
+            }
+            Err(e) => (None, Some(format!("git inspection failed: {e}"))),
+        };
+        if veto_reason.is_none() && open_paths.contains(&workspace.path) {
+            veto_reason = Some("a process holds files open inside it".to_string());
+        }
+        let size_bytes = tree_size(&workspace.path);
+        let mut removed = false;
+        if veto_reason.is_none() {
+            if dry_run {
+                report.bytes_would_free += size_bytes;
+            } else {
+                match remove(workspace) {
+                    Ok(()) => {
+                        removed = true;
+                        report.bytes_freed += size_bytes;
+                    }
+                    Err(e) => {
+                        veto_reason = Some(format!("removal failed: {e}"));
+                        report.errors.push((workspace.path.clone(), e.to_string()));
+                    }
+                }
+            }
+        }
+        report.outcomes.push(GitWorkspaceOutcome {
+            workspace: workspace.clone(),
+            size_bytes,
+            evidence,
+            veto_reason,
+            removed,
+        });
+    }
+    report.duration = start.elapsed();
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filetime::{FileTime, set_file_mtime};
+
+    fn run(dir: &Path, args: &[&str]) {
+        let out = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args([
+                "-c",
+                "user.name=sbh",
+                "-c",
+                "user.email=sbh@example.invalid",
+            ])
+            .args([
+                "-c",
+                "init.defaultBranch=main",
+                "-c",
+                "commit.gpgsign=false",
+            ])
+            .args(args)
+            .env_remove("GIT_DIR")
+            .env_remove("GIT_WORK_TREE")
+            .output()
+            .unwrap();
+        assert!(
+            out.status.success(),
+            "git {args:?}: {}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+    }
+
+    /// A bare "remote" plus a clone of it with one pushed commit on `main`.
+    fn repo_with_origin(root: &Path) -> PathBuf {
+        let origin = root.join("origin.git");
+        let repo = root.join("repo");
+        fs::create_dir_all(&repo).unwrap();
+        run(root, &["init", "-q", "--bare", "origin.git"]);
+        run(&repo, &["init", "-q"]);
+        fs::write(repo.join("README"), "hello\n").unwrap();
+        run(&repo, &["add", "README"]);
+        run(&repo, &["commit", "-q", "-m", "init"]);
+        run(
+            &repo,
+            &["remote", "add", "origin", &origin.to_string_lossy()],
+        );
+        run(&repo, &["push", "-q", "-u", "origin", "main"]);
+        repo
+    }
+
+    fn age(workspace: &GitWorkspace, days: u64) {
+        let when = FileTime::from_system_time(SystemTime::now() - Duration::from_hours(days * 24));
+        for path in [
+            workspace.path.clone(),
+            workspace.git_dir.join("HEAD"),
+            workspace.git_dir.join("index"),
+            workspace.git_dir.join("logs/HEAD"),
+        ] {
+            if path.exists() {
+                set_file_mtime(&path, when).unwrap();
+            }
+        }
+    }
+
+    fn config() -> GitWorkspaceConfig {
+        GitWorkspaceConfig {
+            enabled: true,
+            ..GitWorkspaceConfig::default()
+        }
+    }
+
+    #[test]
+    fn detects_worktrees_and_clones_but_not_submodule_checkouts() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = repo_with_origin(dir.path());
+        let clone = detect(&repo).unwrap();
+        assert_eq!(clone.kind, GitWorkspaceKind::Clone);
+        assert_eq!(clone.label(), "git-clone");
+
+        let wt_path = dir.path().join("wt-task-1");
+        run(
+            &repo,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "task-1",
+                &wt_path.to_string_lossy(),
+            ],
+        );
+        let worktree = detect(&wt_path).unwrap();
+        assert!(worktree.is_worktree());
+        assert_eq!(
+            worktree.kind,
+            GitWorkspaceKind::Worktree {
+                common_dir: repo.join(".git")
+            }
+        );
+
+        let module = dir.path().join("vendored");
+        fs::create_dir_all(repo.join(".git/modules/vendored")).unwrap();
+        fs::write(
+            repo.join(".git/modules/vendored/HEAD"),
+            "ref: refs/heads/main\n",
+        )
+        .unwrap();
+        fs::create_dir_all(&module).unwrap();
+        fs::write(
+            module.join(".git"),
+            format!("gitdir: {}\n", repo.join(".git/modules/vendored").display()),
+        )
+        .unwrap();
+        assert!(detect(&module).is_none());
+        assert!(detect(dir.path()).is_none());
+    }
+
+    #[test]
+    fn merged_idle_worktree_is_removed_and_pruned() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = repo_with_origin(dir.path());
+        let wt_path = dir.path().join("wt-merged");
+        run(
+            &repo,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "done",
+                &wt_path.to_string_lossy(),
+            ],
+        );
+        fs::create_dir_all(wt_path.join("target")).unwrap();
+        fs::write(wt_path.join(".gitignore"), "target/\n").unwrap();
+        run(&wt_path, &["add", ".gitignore"]);
+        run(&wt_path, &["commit", "-q", "-m", "ignore target"]);
+        run(&repo, &["merge", "-q", "--ff-only", "done"]);
+        run(&repo, &["push", "-q", "origin", "main"]);
+        let worktree = detect(&wt_path).unwrap();
+        age(&worktree, 30);
+
+        let dry = reclaim_workspaces(
+            std::slice::from_ref(&worktree),
+            &config(),
+            &HashSet::<PathBuf>::new(),
+            SystemTime::now(),
+            true,
+        );
+        assert_eq!(dry.outcomes.len(), 1);
+        let outcome = &dry.outcomes[0];
+        assert_eq!(outcome.veto_reason, None, "{outcome:?}");
+        let evidence = outcome.evidence.as_ref().unwrap();
+        assert_eq!(evidence.fate, Some(BranchFate::Merged("main".to_string())));
+        assert!(evidence.summary(&worktree).contains("merged into main"));
+        assert!(wt_path.exists());
+
+        let report = reclaim_workspaces(
+            std::slice::from_ref(&worktree),
+            &config(),
+            &HashSet::<PathBuf>::new(),
+            SystemTime::now(),
+            false,
+        );
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert!(report.outcomes[0].removed);
+        assert!(!wt_path.exists());
+        assert!(!repo.join(".git/worktrees/wt-merged").exists());
+    }
+
+    #[test]
+    fn unsaved_work_or_recent_use_keeps_the_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = repo_with_origin(dir.path());
+        let wt_path = dir.path().join("wt-wip");
+        run(
+            &repo,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "wip",
+                &wt_path.to_string_lossy(),
+            ],
+        );
+        let worktree = detect(&wt_path).unwrap();
+        let no_open = HashSet::<PathBuf>::new();
+
+        // Fresh: not even inspected.
+        let fresh = reclaim_workspaces(
+            std::slice::from_ref(&worktree),
+            &config(),
+            &no_open,
+            SystemTime::now(),
+            false,
+        );
+        assert!(fresh.outcomes.is_empty());
+
+        // Untracked file.
+        fs::write(wt_path.join("notes.txt"), "todo\n").unwrap();
+        age(&worktree, 30);
+        let outcome = &reclaim_workspaces(
+            std::slice::from_ref(&worktree),
+            &config(),
+            &no_open,
+            SystemTime::now(),
+            false,
+        )
+        .outcomes[0];
+        assert!(!outcome.removed);
+        assert_eq!(
+            outcome.veto_reason.as_deref(),
+            Some("1 modified or untracked path(s)")
+        );
+
+        // Committed but never pushed.
+        run(&wt_path, &["add", "notes.txt"]);
+        run(&wt_path, &["commit", "-q", "-m", "wip"]);
+        age(&worktree, 30);
+        let outcome = &reclaim_workspaces(
+            std::slice::from_ref(&worktree),
+            &config(),
+            &no_open,
+            SystemTime::now(),
+            false,
+        )
+        .outcomes[0];
+        assert_eq!(
+            outcome.veto_reason.as_deref(),
+            Some("1 commit(s) on no remote-tracking ref")
+        );
+
+        // Pushed, but neither merged nor deleted upstream.
+        run(&wt_path, &["push", "-q", "-u", "origin", "wip"]);
+        age(&worktree, 30);
+        let outcome = &reclaim_workspaces(
+            std::slice::from_ref(&worktree),
+            &config(),
+            &no_open,
+            SystemTime::now(),
+            false,
+        )
+        .outcomes[0];
+        assert_eq!(
+            outcome.veto_reason.as_deref(),
+            Some("branch wip is not merged and its upstream still exists")
+        );
+
+        // Held open by a process.
+        let open: HashSet<PathBuf> = wt_path.ancestors().map(Path::to_path_buf).collect();
+        run(&repo, &["update-ref", "-d", "refs/remotes/origin/wip"]);
+        run(&repo, &["update-ref", "refs/remotes/origin/keep", "wip"]);
+        age(&worktree, 30);
+        let outcome = &reclaim_workspaces(
+            std::slice::from_ref(&worktree),
+            &config(),
+            &open,
+            SystemTime::now(),
+            false,
+        )
+        .outcomes[0];
+        assert_eq!(
+            outcome.evidence.as_ref().unwrap().fate,
+            Some(BranchFate::UpstreamGone("origin/wip".to_string()))
+        );
+        assert_eq!(
+            outcome.veto_reason.as_deref(),
+            Some("a process holds files open inside it")
+        );
+        assert!(wt_path.exists());
+    }
+
+    #[test]
+    fn filter_drivers_from_included_config_block_inspection() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = repo_with_origin(dir.path());
+        let clone = detect(&repo).unwrap();
+        assert!(inspect(&clone, &config().base_branches, Duration::ZERO).is_ok());
+
+        let included = dir.path().join("extra.gitconfig");
+        fs::write(&included, "[filter \"x\"]\n\tclean = touch pwned\n").unwrap();
+        run(
+            &repo,
+            &["config", "include.path", &included.to_string_lossy()],
+        );
+        let err = inspect(&clone, &config().base_branches, Duration::ZERO).unwrap_err();
+        assert!(err.to_string().contains("filter drivers"), "{err}");
+        assert!(!repo.join("pwned").exists());
+    }
+
+    #[test]
+    fn clone_with_a_stash_is_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = repo_with_origin(dir.path());
+        fs::write(repo.join("README"), "changed\n").unwrap();
+        run(&repo, &["stash", "-q"]);
+        let clone = detect(&repo).unwrap();
+        age(&clone, 30);
+        let evidence = inspect(&clone, &config().base_branches, Duration::ZERO).unwrap();
+        assert_eq!(evidence.changed_paths, 0);
+        assert_eq!(evidence.blocker().as_deref(), Some("1 stash entries"));
+
+        run(&repo, &["stash", "drop", "-q"]);
+        let evidence = inspect(&clone, &config().base_branches, Duration::ZERO).unwrap();
+        assert_eq!(
+            evidence.fate,
+            Some(BranchFate::Merged("origin/main".to_string()))
+        );
+        assert_eq!(evidence.blocker(), None);
+    }
+}
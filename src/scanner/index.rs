@@ -21,6 +21,7 @@ use crate::core::errors::{Result, SbhError};
 use crate::scanner::patterns::{
     ArtifactCategory, ArtifactClassification, OpaqueTreeClassification, OpaqueTreeDisposition,
 };
+use crate::scanner::provenance::{ArtifactOwner, ArtifactProvenance};
 use crate::scanner::scoring::{
     CandidacyScore, DecisionAction, DecisionOutcome, EvidenceLedger, ScoreFactors,
 };
@@ -163,6 +164,9 @@ pub struct CandidateIndexRecord {
     pub fail_count: u32,
     pub cooldown_until_nanos: Option<u128>,
     pub event_generation: u64,
+    /// Command that produced this candidate (`sbh run`), when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<ArtifactOwner>,
 }
 
 impl CandidateIndexRecord {
@@ -194,6 +198,7 @@ impl CandidateIndexRecord {
             fail_count: 0,
             cooldown_until_nanos: None,
             event_generation,
+            owner: None,
         }))
     }
 
@@ -277,7 +282,15 @@ impl CandidateIndexRecord {
             },
             ledger: EvidenceLedger {
                 terms: Vec::new(),
-                summary: "v2 persistent index candidate".to_string(),
+                summary: self.owner.as_ref().map_or_else(
+                    || "v2 persistent index candidate".to_string(),
+                    |owner| {
+                        format!(
+                            "v2 persistent index candidate (produced by `{}`)",
+                            owner.display_name()
+                        )
+                    },
+                ),
             },
         }
     }
@@ -349,6 +362,13 @@ impl ScannerCandidateIndex {
         self.records.get(&identity)
     }
 
+    /// Attach the `sbh run` owner of each record's path from `provenance`.
+    pub fn tag_owners(records: &mut [CandidateIndexRecord], provenance: &ArtifactProvenance) {
+        for record in records {
+            record.owner = provenance.owner_for(&record.path).cloned();
+        }
+    }
+
     pub fn upsert(&mut self, mut record: CandidateIndexRecord) {
         record.event_generation = self.event_generation;
         if let Some(existing) = self.records.get(&record.identity)
@@ -715,6 +735,7 @@ mod tests {
             fail_count: 0,
             cooldown_until_nanos: None,
             event_generation: 0,
+            owner: None,
         }
     }
 
@@ -774,6 +795,53 @@ mod tests {
         assert_eq!(loaded.get(record.identity), Some(&record));
     }
 
+    #[test]
+    fn owner_tags_survive_checkpoint_and_name_the_command() {
+        use crate::scanner::provenance::RunRecord;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("scanner-index.json");
+        let ctx = context("same");
+        let owner = ArtifactOwner {
+            run_id: "run-1".to_string(),
+            command: "cargo build".to_string(),
+            label: Some("nightly-build".to_string()),
+            cwd: PathBuf::from("/tmp"),
+            pid: None,
+            started_at: 1,
+            finished_at: Some(2),
+            exit_code: Some(0),
+            bytes_written: 1024,
+        };
+        let provenance = ArtifactProvenance::from_runs(vec![RunRecord {
+            owner: owner.clone(),
+            produced: vec![PathBuf::from("/tmp/target-1")],
+        }]);
+        let mut records = vec![
+            record(identity(1), identity(99)),
+            record(identity(2), identity(99)),
+        ];
+        ScannerCandidateIndex::tag_owners(&mut records, &provenance);
+        assert_eq!(records[0].owner.as_ref(), Some(&owner));
+        assert!(records[1].owner.is_none());
+        assert!(
+            records[0]
+                .to_candidate_score()
+                .ledger
+                .summary
+                .contains("produced by `nightly-build`")
+        );
+
+        let mut index = ScannerCandidateIndex::new(ctx.clone());
+        for record in records.clone() {
+            index.upsert(record);
+        }
+        index.save_checkpoint(&path).unwrap();
+        let (loaded, status) = ScannerCandidateIndex::load_checkpoint(&path, ctx);
+        assert_eq!(status, ScannerIndexLoadStatus::Loaded);
+        assert_eq!(loaded.get(identity(1)), Some(&records[0]));
+    }
+
     #[test]
     fn stale_config_invalidates_checkpoint() {
         let tmp = tempfile::tempdir().unwrap();
@@ -63,6 +63,28 @@ pub enum SkipReason {
     YoungerThanMinAge,
     /// The matched path was a symlink.
     SymlinkRejected,
+    /// A deleted file some process still has memory-mapped.
+    MemoryMapped,
+    /// A deleted file still open for reading (or with unknown access).
+    HeldForReading,
+    /// A deleted file with a writer not in `O_APPEND` mode.
+    NotAppendOnly,
+}
+
+impl SkipReason {
+    /// Stable snake_case key for JSON output.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::NotARegularFile => "not_a_regular_file",
+            Self::BelowMinSize => "below_min_size",
+            Self::YoungerThanMinAge => "younger_than_min_age",
+            Self::SymlinkRejected => "symlink_rejected",
+            Self::MemoryMapped => "memory_mapped",
+            Self::HeldForReading => "held_for_reading",
+            Self::NotAppendOnly => "not_append_only",
+        }
+    }
 }
 
 /// Execute one truncation pass.
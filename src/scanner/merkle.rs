@@ -789,6 +789,7 @@ mod tests {
             metadata: EntryMetadata {
                 size_bytes: size,
                 content_size_bytes: size,
+                apparent_size_bytes: size,
                 modified: UNIX_EPOCH + Duration::from_secs(modified_secs),
                 created: None,
                 is_dir: true,
@@ -796,6 +797,7 @@ mod tests {
                 device_id: 0,
                 kind: FsEntryKind::Directory,
                 permissions: 0o755,
+                content_entry_count: 1,
             },
             depth,
             structural_signals: StructuralSignals::default(),
@@ -1,13 +1,21 @@
 //! Artifact scanner: directory walker, pattern matching, multi-factor scoring, deletion.
 
+pub mod bazel;
+pub mod compress;
+pub mod cow_extents;
 pub mod decision_record;
+pub mod deleted_open;
 pub mod deletion;
 pub mod engine;
 pub mod events;
+pub mod git_workspace;
 pub mod index;
 pub mod log_truncator;
 pub mod merkle;
 pub mod patterns;
 pub mod protection;
+pub mod provenance;
 pub mod scoring;
+pub mod target_sweep;
+pub mod tool_reclaim;
 pub mod walker;
@@ -5,11 +5,18 @@
 
 use std::borrow::Cow;
 use std::path::Path;
+use std::sync::Arc;
 
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::ScannerConfig;
 use crate::platform::cleanup_catalog::{self, CleanupConfidence, CleanupRule, ReclaimCommand};
+use crate::scanner::bazel;
 
 /// High-level artifact category used by the scorer and CLI reports.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ArtifactCategory {
     RustTarget,
     NodeModules,
@@ -22,6 +29,14 @@ pub enum ArtifactCategory {
     /// directory `0555` and every cache file `0444`, which defeats a plain
     /// `remove_dir_all`.
     GoCache,
+    /// Bazel output base (`~/.cache/bazel/_bazel_$USER/<md5>`). Detected
+    /// structurally; output files are written read-only, so removal defeats
+    /// permissions the way it does for [`Self::GoCache`].
+    BazelOutputBase,
+    /// JVM build state: Maven `target/` and Gradle `build/` next to their
+    /// build file, a project's `.gradle/` dir, and Gradle's
+    /// `~/.gradle/caches/transforms-*` trees.
+    JvmBuild,
     TempDir,
     AgentWorkspace,
     Unknown,
@@ -38,6 +53,167 @@ pub struct StructuralSignals {
     pub has_git: bool,
     pub has_cargo_toml: bool,
     pub mostly_object_files: bool,
+    /// `classes/`, `libs/`, `maven-status/` or a similar JVM build-output child.
+    pub has_jvm_outputs: bool,
+    /// Build manifests in the parent directory, next to this one.
+    pub sibling_manifests: SiblingManifests,
+}
+
+/// Ecosystem manifests found in one directory.
+///
+/// The walker hands these to the directory's children, so an output dir
+/// with a generic name (`bin`, `obj`, `deps`, `_build`) is only a candidate
+/// when the manifest of the tool that writes it sits beside it.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SiblingManifests {
+    /// `*.csproj`, `*.fsproj` or `*.vbproj`.
+    pub dotnet_project: bool,
+    /// `Package.swift`.
+    pub swift_package: bool,
+    /// `build.zig`.
+    pub zig_build: bool,
+    /// `cabal.project` or `*.cabal`.
+    pub cabal_project: bool,
+    /// `mix.exs`.
+    pub mix_project: bool,
+}
+
+impl SiblingManifests {
+    /// Record `name` if it is one of the tracked manifest file names.
+    pub fn note(&mut self, name: &str) {
+        let extension = Path::new(name).extension().and_then(|ext| ext.to_str());
+        match (name, extension) {
+            (_, Some("csproj" | "fsproj" | "vbproj")) => self.dotnet_project = true,
+            ("Package.swift", _) => self.swift_package = true,
+            ("build.zig", _) => self.zig_build = true,
+            ("cabal.project", _) | (_, Some("cabal")) => self.cabal_project = true,
+            ("mix.exs", _) => self.mix_project = true,
+            _ => {}
+        }
+    }
+
+    /// Manifests in `dir`, read from disk.
+    #[must_use]
+    pub fn read(dir: &Path) -> Self {
+        let mut manifests = Self::default();
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                if let Some(name) = entry.file_name().to_str() {
+                    manifests.note(name);
+                }
+            }
+        }
+        manifests
+    }
+}
+
+/// Output dirs recognised only next to their tool's manifest:
+/// (name, manifest check, pattern label, category, confidence).
+type ManifestAdjacentOutput = (
+    &'static str,
+    fn(SiblingManifests) -> bool,
+    &'static str,
+    ArtifactCategory,
+    f64,
+);
+
+const MANIFEST_ADJACENT_OUTPUTS: &[ManifestAdjacentOutput] = &[
+    (
+        "bin",
+        |m| m.dotnet_project,
+        "dotnet-bin",
+        ArtifactCategory::BuildOutput,
+        0.88,
+    ),
+    (
+        "obj",
+        |m| m.dotnet_project,
+        "dotnet-obj",
+        ArtifactCategory::BuildOutput,
+        0.90,
+    ),
+    (
+        ".build",
+        |m| m.swift_package,
+        "swiftpm-build",
+        ArtifactCategory::BuildOutput,
+        0.92,
+    ),
+    (
+        ".zig-cache",
+        |m| m.zig_build,
+        "zig-cache",
+        ArtifactCategory::BuildOutput,
+        0.94,
+    ),
+    (
+        "zig-cache",
+        |m| m.zig_build,
+        "zig-cache",
+        ArtifactCategory::BuildOutput,
+        0.94,
+    ),
+    (
+        "zig-out",
+        |m| m.zig_build,
+        "zig-out",
+        ArtifactCategory::BuildOutput,
+        0.90,
+    ),
+    (
+        "dist-newstyle",
+        |m| m.cabal_project,
+        "cabal-dist-newstyle",
+        ArtifactCategory::BuildOutput,
+        0.94,
+    ),
+    (
+        "_build",
+        |m| m.mix_project,
+        "mix-build",
+        ArtifactCategory::BuildOutput,
+        0.92,
+    ),
+    (
+        "deps",
+        |m| m.mix_project,
+        "mix-deps",
+        ArtifactCategory::CacheDir,
+        0.86,
+    ),
+];
+
+/// Classification for an output dir named `name` next to `siblings`.
+fn manifest_adjacent_output(
+    name: &str,
+    siblings: SiblingManifests,
+) -> Option<ArtifactClassification> {
+    MANIFEST_ADJACENT_OUTPUTS
+        .iter()
+        .find(|(output, present, ..)| *output == name && present(siblings))
+        .map(
+            |&(_, _, label, category, confidence)| ArtifactClassification {
+                pattern_name: Cow::Borrowed(label),
+                category,
+                name_confidence: confidence,
+                structural_confidence: 0.0,
+                combined_confidence: confidence,
+            },
+        )
+}
+
+/// Whether `path` is an output dir that the manifest beside it accounts for.
+#[must_use]
+pub(crate) fn is_manifest_adjacent_output(path: &Path) -> bool {
+    let (Some(name), Some(parent)) = (path.file_name().and_then(|n| n.to_str()), path.parent())
+    else {
+        return false;
+    };
+    MANIFEST_ADJACENT_OUTPUTS
+        .iter()
+        .any(|(output, ..)| *output == name)
+        && manifest_adjacent_output(name, SiblingManifests::read(parent)).is_some()
 }
 
 impl StructuralSignals {
@@ -134,13 +310,18 @@ impl OpaqueTreeClassification {
 }
 
 /// Parent/path evidence available before deciding whether to descend.
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct OpaqueTreeContext {
     pub parent_has_cargo_toml: bool,
     pub parent_has_node_manifest: bool,
+    /// Parent holds `build.gradle(.kts)` or `settings.gradle(.kts)`.
+    pub parent_has_gradle_build: bool,
+    pub parent_has_maven_pom: bool,
 }
 
 /// Classify a directory before descent for the v2 opaque-tree scanner path.
+#[allow(clippy::too_many_lines)]
 #[must_use]
 pub fn classify_opaque_tree(
     path: &Path,
@@ -182,6 +363,33 @@ pub fn classify_opaque_tree(
         ));
     }
 
+    // Bazel output bases: an md5-hex name under `_bazel_$USER`. The
+    // DO_NOT_BUILD_HERE marker names the owning workspace; once that
+    // workspace is gone nothing can reuse the output base.
+    if let Some(output_base) = bazel::detect_output_base(path) {
+        return Some(if output_base.abandoned {
+            OpaqueTreeClassification::candidate(
+                "bazel output base whose workspace no longer exists",
+                "opaque-bazel-abandoned-output-base",
+                ArtifactCategory::BazelOutputBase,
+                0.94,
+            )
+        } else {
+            OpaqueTreeClassification::candidate(
+                "bazel output base of a live workspace: regenerable, slow to rebuild",
+                "opaque-bazel-output-base",
+                ArtifactCategory::BazelOutputBase,
+                0.62,
+            )
+        });
+    }
+
+    // JVM builds come before the cargo-target check: Maven also writes
+    // `target/`, and only its contents tell the two apart.
+    if let Some(jvm) = classify_jvm_build(path, &name, context) {
+        return Some(jvm);
+    }
+
     let tmp_like = is_tmp_like_path(path);
     if is_context_gated_dependency_tree_name(&name) {
         return Some(OpaqueTreeClassification::signal_only(
@@ -254,6 +462,92 @@ pub fn classify_opaque_tree(
     None
 }
 
+/// Children only a JVM build writes into its output dir.
+const MAVEN_OUTPUT_MARKERS: &[&str] = &[
+    "classes",
+    "test-classes",
+    "maven-status",
+    "maven-archiver",
+    "generated-sources",
+    "surefire-reports",
+];
+const GRADLE_OUTPUT_MARKERS: &[&str] = &["classes", "libs", "tmp"];
+
+/// Children cargo writes at the top of a target dir, none of which Maven
+/// produces.
+const CARGO_TARGET_MARKERS: &[&str] = &["CACHEDIR.TAG", ".rustc_info.json", "debug", "release"];
+
+/// Maven/Gradle output next to its build file, a project `.gradle/` dir, or
+/// a Gradle transforms cache.
+///
+/// The build file in the parent is the context; the output's own children
+/// must confirm it, so an unrelated `build/` beside a `build.gradle` stays
+/// untouched. A `target/` holding any cargo marker is left to the cargo
+/// checks even when a `pom.xml` sits next to it.
+fn classify_jvm_build(
+    path: &Path,
+    name: &str,
+    context: OpaqueTreeContext,
+) -> Option<OpaqueTreeClassification> {
+    if name == "target"
+        && context.parent_has_maven_pom
+        && has_any_child(path, MAVEN_OUTPUT_MARKERS)
+        && !has_any_child(path, CARGO_TARGET_MARKERS)
+    {
+        return Some(OpaqueTreeClassification::candidate(
+            "maven target/ next to pom.xml",
+            "opaque-maven-target",
+            ArtifactCategory::JvmBuild,
+            0.92,
+        ));
+    }
+    if name == "build"
+        && context.parent_has_gradle_build
+        && has_any_child(path, GRADLE_OUTPUT_MARKERS)
+    {
+        return Some(OpaqueTreeClassification::candidate(
+            "gradle build/ next to a gradle build script",
+            "opaque-gradle-build",
+            ArtifactCategory::JvmBuild,
+            0.90,
+        ));
+    }
+    if name == ".gradle" && context.parent_has_gradle_build {
+        return Some(OpaqueTreeClassification::candidate(
+            "gradle project cache next to a gradle build script",
+            "opaque-gradle-project-cache",
+            ArtifactCategory::JvmBuild,
+            0.86,
+        ));
+    }
+    if is_gradle_transforms_cache(path, name) {
+        return Some(OpaqueTreeClassification::candidate(
+            "gradle artifact transforms cache: regenerable",
+            "opaque-gradle-transforms",
+            ArtifactCategory::JvmBuild,
+            0.90,
+        ));
+    }
+    None
+}
+
+/// `.gradle/caches/transforms-<n>`.
+fn is_gradle_transforms_cache(path: &Path, name: &str) -> bool {
+    let Some(generation) = name.strip_prefix("transforms-") else {
+        return false;
+    };
+    if generation.is_empty() || !generation.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    let mut ancestors = path.ancestors().skip(1).map(Path::file_name);
+    ancestors.next() == Some(Some(std::ffi::OsStr::new("caches")))
+        && ancestors.next() == Some(Some(std::ffi::OsStr::new(".gradle")))
+}
+
+fn has_any_child(dir: &Path, names: &[&str]) -> bool {
+    names.iter().any(|name| dir.join(name).exists())
+}
+
 fn is_context_gated_dependency_tree_name(name: &str) -> bool {
     matches!(
         name,
@@ -338,6 +632,7 @@ pub(crate) fn is_obvious_build_artifact_basename(path: &Path) -> bool {
     // prevents `.rch-targetfoo` style false positives.
     let exact_artifacts: &[&str] = &[
         ".cargo-target",
+        ".gradle",
         ".next",
         ".nuxt",
         ".parcel-cache",
@@ -361,6 +656,12 @@ pub(crate) fn is_obvious_build_artifact_basename(path: &Path) -> bool {
         return true;
     }
 
+    // Generic output names (`bin`, `obj`, `deps`, `_build`, ...) qualify
+    // only while the manifest of the tool that writes them sits beside them.
+    if is_manifest_adjacent_output(path) {
+        return true;
+    }
+
     // Prefix-match basenames — match per-job/per-config suffixes produced
     // by cargo (`target-foo`, `target_bar`) and rch (`.rch-target-job-42`,
     // `rch-target-…`, `rch_target_…`, `.cargo-target-rusticmill`). Each
@@ -509,19 +810,100 @@ struct ArtifactPattern {
     category: ArtifactCategory,
 }
 
-/// User-provided pattern extension.
-#[derive(Debug, Clone, PartialEq)]
+/// How a custom pattern's `pattern` string is compared with a directory name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomMatchMode {
+    #[default]
+    Contains,
+    Prefix,
+    Suffix,
+    Exact,
+    /// Case-insensitive regular expression, searched within the name.
+    Regex,
+}
+
+/// Structural marker a custom pattern can require before it matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StructuralMarker {
+    Incremental,
+    Deps,
+    Build,
+    Fingerprint,
+    ObjectFiles,
+}
+
+impl StructuralMarker {
+    #[must_use]
+    pub const fn is_present(self, signals: StructuralSignals) -> bool {
+        match self {
+            Self::Incremental => signals.has_incremental,
+            Self::Deps => signals.has_deps,
+            Self::Build => signals.has_build,
+            Self::Fingerprint => signals.has_fingerprint,
+            Self::ObjectFiles => signals.mostly_object_files,
+        }
+    }
+}
+
+/// User-provided pattern extension (`[[scanner.custom_patterns]]`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CustomPattern {
     pub name: String,
+    #[serde(rename = "match", default)]
+    pub match_mode: CustomMatchMode,
+    #[serde(rename = "pattern")]
     pub needle: String,
+    #[serde(default = "default_custom_confidence")]
     pub confidence: f64,
     pub category: ArtifactCategory,
+    /// Every marker listed must be present in the directory's structural
+    /// signals; names alone never match when this is non-empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_markers: Vec<StructuralMarker>,
+}
+
+const fn default_custom_confidence() -> f64 {
+    0.80
+}
+
+impl CustomPattern {
+    /// Compile a `regex` pattern. Other match modes always succeed.
+    pub fn compile_regex(&self) -> Option<Result<Regex, regex::Error>> {
+        (self.match_mode == CustomMatchMode::Regex).then(|| {
+            RegexBuilder::new(&self.needle)
+                .case_insensitive(true)
+                .build()
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
 struct NormalizedCustomPattern {
     pattern: CustomPattern,
     lowercase_needle: String,
+    /// `None` for non-regex modes and for regexes that failed to compile
+    /// (config validation rejects those, so they never match here).
+    regex: Option<Regex>,
+}
+
+impl NormalizedCustomPattern {
+    fn matches(&self, name: &str, normalized: &str, signals: StructuralSignals) -> bool {
+        let name_matches = match self.pattern.match_mode {
+            CustomMatchMode::Contains => normalized.contains(&self.lowercase_needle),
+            CustomMatchMode::Prefix => normalized.starts_with(&self.lowercase_needle),
+            CustomMatchMode::Suffix => normalized.ends_with(&self.lowercase_needle),
+            CustomMatchMode::Exact => normalized == self.lowercase_needle,
+            CustomMatchMode::Regex => self.regex.as_ref().is_some_and(|re| re.is_match(name)),
+        };
+        name_matches
+            && self
+                .pattern
+                .required_markers
+                .iter()
+                .all(|marker| marker.is_present(signals))
+    }
 }
 
 /// Registry of built-in and custom patterns.
@@ -541,12 +923,19 @@ impl Default for ArtifactPatternRegistry {
 }
 
 impl ArtifactPatternRegistry {
+    /// Built-ins plus the operator's `[[scanner.custom_patterns]]`.
+    #[must_use]
+    pub fn from_config(config: &ScannerConfig) -> Self {
+        Self::default().with_custom(config.custom_patterns.clone())
+    }
+
     #[must_use]
     pub fn with_custom(mut self, custom: Vec<CustomPattern>) -> Self {
         self.custom = custom
             .into_iter()
             .map(|pattern| NormalizedCustomPattern {
                 lowercase_needle: pattern.needle.to_lowercase(),
+                regex: pattern.compile_regex().and_then(Result::ok),
                 pattern,
             })
             .collect();
@@ -556,7 +945,7 @@ impl ArtifactPatternRegistry {
     /// Classify one path name with optional structural evidence.
     #[must_use]
     pub fn classify(&self, path: &Path, signals: StructuralSignals) -> ArtifactClassification {
-        self.classify_with_cleanup_rules(path, signals, platform_cleanup_rules())
+        self.classify_with_cleanup_rules(path, signals, &platform_cleanup_rules())
     }
 
     /// Classify one path name against an explicit cleanup catalog.
@@ -565,7 +954,7 @@ impl ArtifactPatternRegistry {
         &self,
         path: &Path,
         signals: StructuralSignals,
-        cleanup_rules: &'static [CleanupRule],
+        cleanup_rules: &[CleanupRule],
     ) -> ArtifactClassification {
         self.classify_with_cleanup_rules_inner(path, signals, cleanup_rules, None)
     }
@@ -576,7 +965,7 @@ impl ArtifactPatternRegistry {
         &self,
         path: &Path,
         signals: StructuralSignals,
-        cleanup_rules: &'static [CleanupRule],
+        cleanup_rules: &[CleanupRule],
         home: &Path,
     ) -> ArtifactClassification {
         self.classify_with_cleanup_rules_inner(path, signals, cleanup_rules, Some(home))
@@ -586,13 +975,14 @@ impl ArtifactPatternRegistry {
         &self,
         path: &Path,
         signals: StructuralSignals,
-        cleanup_rules: &'static [CleanupRule],
+        cleanup_rules: &[CleanupRule],
         home: Option<&Path>,
     ) -> ArtifactClassification {
         let Some(name_os) = path.file_name() else {
             return ArtifactClassification::unknown();
         };
-        let normalized = name_os.to_string_lossy().to_lowercase();
+        let name = name_os.to_string_lossy();
+        let normalized = name.to_lowercase();
 
         let catalog_classification = cleanup_catalog_path_classification(path, cleanup_rules, home);
         let mut best = catalog_classification
@@ -613,7 +1003,7 @@ impl ArtifactPatternRegistry {
         }
 
         for custom in &self.custom {
-            if normalized.contains(&custom.lowercase_needle)
+            if custom.matches(&name, &normalized, signals)
                 && custom.pattern.confidence > best.name_confidence
             {
                 best = ArtifactClassification {
@@ -630,6 +1020,30 @@ impl ArtifactPatternRegistry {
             best = classification;
         }
 
+        // Generic output names count only next to their tool's manifest.
+        if let Some(adjacent) = manifest_adjacent_output(&name, signals.sibling_manifests)
+            && adjacent.name_confidence > best.name_confidence
+        {
+            best = adjacent;
+        }
+
+        // Maven also writes `target/`. JVM output markers with no cargo marker
+        // mean the bare-name match belongs to Maven.
+        if best.pattern_name == "cargo-target"
+            && signals.has_jvm_outputs
+            && !signals.has_fingerprint
+            && !signals.has_incremental
+            && !signals.has_deps
+        {
+            best = ArtifactClassification {
+                pattern_name: Cow::Borrowed("maven-target"),
+                category: ArtifactCategory::JvmBuild,
+                name_confidence: 0.80,
+                structural_confidence: 0.0,
+                combined_confidence: 0.80,
+            };
+        }
+
         // Structural rescue path: name is ambiguous but layout screams "Rust target".
         // Graduated confidence based on how many cargo-specific markers are present:
         // only cargo build output directories have .fingerprint + deps + incremental +
@@ -673,7 +1087,7 @@ impl ArtifactPatternRegistry {
 }
 
 #[must_use]
-pub fn platform_cleanup_rules() -> &'static [CleanupRule] {
+pub fn platform_cleanup_rules() -> Arc<[CleanupRule]> {
     #[cfg(target_os = "macos")]
     {
         crate::platform::macos::cleanup_catalog::cleanup_rules()
@@ -684,7 +1098,7 @@ pub fn platform_cleanup_rules() -> &'static [CleanupRule] {
     }
     #[cfg(not(any(target_os = "linux", target_os = "macos")))]
     {
-        &[]
+        Arc::from([])
     }
 }
 
@@ -699,7 +1113,7 @@ fn matches_builtin(kind: MatchKind, normalized: &str) -> bool {
 
 fn cleanup_catalog_path_classification(
     path: &Path,
-    cleanup_rules: &'static [CleanupRule],
+    cleanup_rules: &[CleanupRule],
     home: Option<&Path>,
 ) -> Option<ArtifactClassification> {
     let rule = home.map_or_else(
@@ -708,7 +1122,7 @@ fn cleanup_catalog_path_classification(
     )?;
     let confidence = cleanup_rule_name_confidence(rule);
     Some(ArtifactClassification {
-        pattern_name: Cow::Borrowed(rule.scanner_label()),
+        pattern_name: rule.scanner_label(),
         category: cleanup_rule_category(rule),
         name_confidence: confidence,
         structural_confidence: 0.0,
@@ -726,11 +1140,19 @@ fn cleanup_rule_name_confidence(rule: &CleanupRule) -> f64 {
 }
 
 fn cleanup_rule_category(rule: &CleanupRule) -> ArtifactCategory {
-    let name = rule.name;
+    if let Some(category) = crate::platform::linux::cleanup_catalog::configured_category(&rule.name)
+    {
+        return category;
+    }
+    let name = &*rule.name;
     if name.contains("target") {
         ArtifactCategory::RustTarget
+    } else if name.starts_with("go-") {
+        ArtifactCategory::GoCache
     } else if name.starts_with("electron")
         || name.contains("cache")
+        || name.ends_with("-store")
+        || name.ends_with("-repository")
         || name.contains("logs")
         || name.contains("ipsw")
     {
@@ -773,10 +1195,18 @@ fn structural_score(category: ArtifactCategory, signals: StructuralSignals) -> f
                 0.40
             }
         }
-        // GoCache is only ever produced by structural detection (GOCACHE /
-        // GOMODCACHE markers), so the directory IS the artifact — give it a
-        // strong, signal-independent structural score.
-        ArtifactCategory::GoCache => 0.90,
+        // GoCache and BazelOutputBase are only ever produced by structural
+        // detection (GOCACHE / GOMODCACHE / DO_NOT_BUILD_HERE markers), so the
+        // directory IS the artifact — give it a strong, signal-independent
+        // structural score.
+        ArtifactCategory::GoCache | ArtifactCategory::BazelOutputBase => 0.90,
+        ArtifactCategory::JvmBuild => {
+            if signals.has_jvm_outputs {
+                0.88
+            } else {
+                0.40
+            }
+        }
         ArtifactCategory::AgentWorkspace => 0.78,
         ArtifactCategory::Unknown => {
             if signals.has_fingerprint || (signals.has_incremental && signals.has_deps) {
@@ -1150,13 +1580,13 @@ fn builtin_patterns() -> Vec<ArtifactPattern> {
 /// Used by stats aggregation to group deleted items by pattern.
 /// Returns a simplified pattern string like "target/" or ".target*".
 pub fn extract_pattern_label(path: &str) -> String {
-    extract_pattern_label_with_cleanup_rules(path, platform_cleanup_rules())
+    extract_pattern_label_with_cleanup_rules(path, &platform_cleanup_rules())
 }
 
 #[must_use]
 pub fn extract_pattern_label_with_cleanup_rules(
     path: &str,
-    cleanup_rules: &'static [CleanupRule],
+    cleanup_rules: &[CleanupRule],
 ) -> String {
     extract_pattern_label_with_cleanup_context(path, cleanup_rules, None)
 }
@@ -1164,7 +1594,7 @@ pub fn extract_pattern_label_with_cleanup_rules(
 #[must_use]
 pub fn extract_pattern_label_with_cleanup_rules_and_home(
     path: &str,
-    cleanup_rules: &'static [CleanupRule],
+    cleanup_rules: &[CleanupRule],
     home: &Path,
 ) -> String {
     extract_pattern_label_with_cleanup_context(path, cleanup_rules, Some(home))
@@ -1172,7 +1602,7 @@ pub fn extract_pattern_label_with_cleanup_rules_and_home(
 
 fn extract_pattern_label_with_cleanup_context(
     path: &str,
-    cleanup_rules: &'static [CleanupRule],
+    cleanup_rules: &[CleanupRule],
     home: Option<&Path>,
 ) -> String {
     let p = Path::new(path);
@@ -1283,12 +1713,14 @@ fn extract_pattern_label_with_cleanup_context(
 #[cfg(test)]
 mod tests {
     use super::{
-        ArtifactCategory, ArtifactClassification, ArtifactPatternRegistry, CustomPattern,
-        OpaqueTreeContext, OpaqueTreeDisposition, StructuralSignals, classify_opaque_tree,
-        extract_pattern_label, extract_pattern_label_with_cleanup_rules,
-        has_descriptive_target_suffix, is_obvious_build_artifact_basename,
+        ArtifactCategory, ArtifactClassification, ArtifactPatternRegistry, CustomMatchMode,
+        CustomPattern, OpaqueTreeContext, OpaqueTreeDisposition, SiblingManifests,
+        StructuralMarker, StructuralSignals, classify_opaque_tree, extract_pattern_label,
+        extract_pattern_label_with_cleanup_rules, has_descriptive_target_suffix,
+        is_obvious_build_artifact_basename,
     };
     use crate::platform::{linux, macos};
+    use serde::Deserialize;
     use std::path::Path;
 
     fn classify_macos(
@@ -1296,11 +1728,15 @@ mod tests {
         path: &Path,
         signals: StructuralSignals,
     ) -> ArtifactClassification {
-        registry.classify_with_cleanup_rules(path, signals, macos::cleanup_catalog::cleanup_rules())
+        registry.classify_with_cleanup_rules(
+            path,
+            signals,
+            &macos::cleanup_catalog::cleanup_rules(),
+        )
     }
 
     fn extract_macos_pattern_label(path: &str) -> String {
-        extract_pattern_label_with_cleanup_rules(path, macos::cleanup_catalog::cleanup_rules())
+        extract_pattern_label_with_cleanup_rules(path, &macos::cleanup_catalog::cleanup_rules())
     }
 
     fn classify_linux(
@@ -1308,7 +1744,11 @@ mod tests {
         path: &Path,
         signals: StructuralSignals,
     ) -> ArtifactClassification {
-        registry.classify_with_cleanup_rules(path, signals, linux::cleanup_catalog::cleanup_rules())
+        registry.classify_with_cleanup_rules(
+            path,
+            signals,
+            &linux::cleanup_catalog::cleanup_rules(),
+        )
     }
 
     #[test]
@@ -1377,6 +1817,7 @@ mod tests {
             OpaqueTreeContext {
                 parent_has_cargo_toml: true,
                 parent_has_node_manifest: false,
+                ..OpaqueTreeContext::default()
             },
         )
         .expect("target should be classified");
@@ -1394,6 +1835,7 @@ mod tests {
                 OpaqueTreeContext {
                     parent_has_cargo_toml: true,
                     parent_has_node_manifest: false,
+                    ..OpaqueTreeContext::default()
                 },
             );
 
@@ -1476,6 +1918,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn manifest_adjacent_outputs_are_carved_out_only_beside_their_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let dotnet = dir.path().join("App");
+        let tools = dir.path().join("tools");
+        for output in [dotnet.join("bin"), dotnet.join("deps"), tools.join("bin")] {
+            std::fs::create_dir_all(output).unwrap();
+        }
+        std::fs::write(dotnet.join("App.csproj"), "<Project />").unwrap();
+
+        assert!(is_obvious_build_artifact_basename(&dotnet.join("bin")));
+        assert!(!is_obvious_build_artifact_basename(&dotnet.join("deps")));
+        assert!(!is_obvious_build_artifact_basename(&tools.join("bin")));
+
+        let mut siblings = SiblingManifests::default();
+        for name in ["README.md", "Cargo.toml", "csproj", "build.zig.zon"] {
+            siblings.note(name);
+        }
+        assert_eq!(siblings, SiblingManifests::default());
+        for name in [
+            "Lib.fsproj",
+            "Package.swift",
+            "build.zig",
+            "x.cabal",
+            "mix.exs",
+        ] {
+            siblings.note(name);
+        }
+        assert!(
+            siblings.dotnet_project
+                && siblings.swift_package
+                && siblings.zig_build
+                && siblings.cabal_project
+                && siblings.mix_project
+        );
+        assert!(SiblingManifests::read(&dotnet).dotnet_project);
+    }
+
     #[test]
     fn opaque_tree_does_not_promote_ambiguous_dependency_dirs() {
         for path in [
@@ -1505,6 +1985,7 @@ mod tests {
             OpaqueTreeContext {
                 parent_has_cargo_toml: false,
                 parent_has_node_manifest: true,
+                ..OpaqueTreeContext::default()
             },
         )
         .unwrap();
@@ -1522,9 +2003,11 @@ mod tests {
     fn custom_patterns_are_honored() {
         let registry = ArtifactPatternRegistry::default().with_custom(vec![CustomPattern {
             name: "my-cache".to_string(),
+            match_mode: CustomMatchMode::Contains,
             needle: "mytool-cache".to_string(),
             confidence: 0.88,
             category: ArtifactCategory::CacheDir,
+            required_markers: Vec::new(),
         }]);
         let classification =
             registry.classify(Path::new("mytool-cache-prod"), StructuralSignals::default());
@@ -1533,6 +2016,91 @@ mod tests {
         assert!(classification.combined_confidence > 0.60);
     }
 
+    #[test]
+    fn custom_pattern_match_modes_and_required_markers() {
+        let pattern =
+            |name: &str, match_mode, needle: &str, markers: Vec<StructuralMarker>| CustomPattern {
+                name: name.to_string(),
+                match_mode,
+                needle: needle.to_string(),
+                confidence: 0.9,
+                category: ArtifactCategory::BuildOutput,
+                required_markers: markers,
+            };
+        let registry = ArtifactPatternRegistry::default().with_custom(vec![
+            pattern(
+                "bazel-bin",
+                CustomMatchMode::Prefix,
+                "bazel-bin-",
+                Vec::new(),
+            ),
+            pattern(
+                "ci-out",
+                CustomMatchMode::Regex,
+                r"^out-ci-\d+$",
+                Vec::new(),
+            ),
+            pattern("gen-exact", CustomMatchMode::Exact, "GENERATED", Vec::new()),
+            pattern(
+                "obj-suffix",
+                CustomMatchMode::Suffix,
+                "-obj",
+                vec![StructuralMarker::ObjectFiles],
+            ),
+        ]);
+        let classify = |name: &str, signals| registry.classify(Path::new(name), signals);
+        let none = StructuralSignals::default();
+        let objects = StructuralSignals {
+            mostly_object_files: true,
+            ..StructuralSignals::default()
+        };
+
+        assert_eq!(classify("bazel-bin-linux", none).pattern_name, "bazel-bin");
+        assert_eq!(classify("Out-CI-42", none).pattern_name, "ci-out");
+        assert_ne!(classify("out-ci-42-keep", none).pattern_name, "ci-out");
+        assert_eq!(classify("generated", none).pattern_name, "gen-exact");
+        assert_ne!(classify("generated-src", none).pattern_name, "gen-exact");
+        assert_ne!(classify("linux-obj", none).pattern_name, "obj-suffix");
+        assert_eq!(classify("linux-obj", objects).pattern_name, "obj-suffix");
+    }
+
+    #[test]
+    fn custom_patterns_deserialize_from_toml() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            custom_patterns: Vec<CustomPattern>,
+        }
+        let parsed: Wrapper = toml::from_str(
+            r#"
+            [[custom_patterns]]
+            name = "bazel-bin"
+            match = "prefix"
+            pattern = "bazel-bin-"
+            category = "build_output"
+            required_markers = ["build"]
+
+            [[custom_patterns]]
+            name = "tool-cache"
+            pattern = "tool-cache"
+            category = "cache_dir"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.custom_patterns[0].match_mode,
+            CustomMatchMode::Prefix
+        );
+        assert_eq!(
+            parsed.custom_patterns[0].required_markers,
+            vec![StructuralMarker::Build]
+        );
+        assert_eq!(
+            parsed.custom_patterns[1].match_mode,
+            CustomMatchMode::Contains
+        );
+        assert!((parsed.custom_patterns[1].confidence - 0.80).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn xcode_derived_data_project_root_is_build_output() {
         let registry = ArtifactPatternRegistry::default();
@@ -1746,6 +2314,45 @@ mod tests {
         assert_ne!(classification.pattern_name, "user-named-trash");
     }
 
+    #[test]
+    fn linux_developer_caches_get_their_own_scanner_labels() {
+        let registry = ArtifactPatternRegistry::default();
+        for (path, label, category) in [
+            (
+                "/home/dev/.cache/pip",
+                "pip-cache",
+                ArtifactCategory::CacheDir,
+            ),
+            (
+                "/home/dev/.local/share/pnpm/store",
+                "pnpm-store",
+                ArtifactCategory::CacheDir,
+            ),
+            (
+                "/home/dev/.m2/repository",
+                "maven-repository",
+                ArtifactCategory::CacheDir,
+            ),
+            (
+                "/home/dev/.cache/go-build",
+                "go-build-cache",
+                ArtifactCategory::GoCache,
+            ),
+        ] {
+            let classification =
+                classify_linux(&registry, Path::new(path), StructuralSignals::default());
+            assert_eq!(classification.pattern_name, label, "{path}");
+            assert_eq!(classification.category, category, "{path}");
+            assert_eq!(
+                extract_pattern_label_with_cleanup_rules(
+                    path,
+                    &linux::cleanup_catalog::cleanup_rules()
+                ),
+                label
+            );
+        }
+    }
+
     #[test]
     fn linux_temp_target_catalog_uses_shared_classification() {
         let registry = ArtifactPatternRegistry::default();
@@ -1760,7 +2367,7 @@ mod tests {
         assert_eq!(
             extract_pattern_label_with_cleanup_rules(
                 path.to_str().unwrap(),
-                linux::cleanup_catalog::cleanup_rules(),
+                &linux::cleanup_catalog::cleanup_rules(),
             ),
             "linux-data-tmp-underscore-target"
         );
@@ -2213,6 +2820,138 @@ mod tests {
         assert_ne!(category, Some(ArtifactCategory::GoCache));
     }
 
+    #[test]
+    fn bazel_output_bases_score_by_workspace_liveness() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path().join("monorepo");
+        std::fs::create_dir(&workspace).unwrap();
+        std::fs::write(workspace.join("WORKSPACE.bazel"), "").unwrap();
+        let root = dir
+            .path()
+            .join(".cache/bazel/_bazel_dev/5d41402abc4b2a76b9719d911017c592");
+        std::fs::create_dir_all(root.join("execroot")).unwrap();
+        std::fs::write(
+            root.join("DO_NOT_BUILD_HERE"),
+            workspace.to_string_lossy().as_bytes(),
+        )
+        .unwrap();
+
+        let live = classify_opaque_tree(&root, OpaqueTreeContext::default())
+            .expect("output base should be an opaque candidate");
+        assert_eq!(live.disposition, OpaqueTreeDisposition::CandidateOpaque);
+        assert_eq!(
+            live.classification.category,
+            ArtifactCategory::BazelOutputBase
+        );
+        assert_eq!(live.classification.pattern_name, "opaque-bazel-output-base");
+
+        std::fs::remove_dir_all(&workspace).unwrap();
+        let abandoned = classify_opaque_tree(&root, OpaqueTreeContext::default()).unwrap();
+        assert_eq!(
+            abandoned.classification.pattern_name,
+            "opaque-bazel-abandoned-output-base"
+        );
+        assert!(
+            abandoned.classification.combined_confidence > live.classification.combined_confidence
+        );
+    }
+
+    #[test]
+    fn maven_target_is_told_apart_from_cargo_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("service/target");
+        std::fs::create_dir_all(target.join("classes")).unwrap();
+        std::fs::create_dir_all(target.join("maven-status")).unwrap();
+        let maven = OpaqueTreeContext {
+            parent_has_maven_pom: true,
+            ..OpaqueTreeContext::default()
+        };
+
+        let opaque = classify_opaque_tree(&target, maven).unwrap();
+        assert_eq!(opaque.classification.category, ArtifactCategory::JvmBuild);
+        assert_eq!(opaque.classification.pattern_name, "opaque-maven-target");
+
+        // A cargo marker wins even with a pom.xml next door.
+        std::fs::write(target.join("CACHEDIR.TAG"), "Signature: x").unwrap();
+        let polyglot = OpaqueTreeContext {
+            parent_has_cargo_toml: true,
+            ..maven
+        };
+        let opaque = classify_opaque_tree(&target, polyglot).unwrap();
+        assert_eq!(opaque.classification.category, ArtifactCategory::RustTarget);
+
+        // Without parent context, the bare-name match is retagged from the
+        // target's own markers.
+        let registry = ArtifactPatternRegistry::default();
+        let jvm_signals = StructuralSignals {
+            has_jvm_outputs: true,
+            ..StructuralSignals::default()
+        };
+        let classified = registry.classify(Path::new("/srv/service/target"), jvm_signals);
+        assert_eq!(classified.category, ArtifactCategory::JvmBuild);
+        assert_eq!(classified.pattern_name, "maven-target");
+        let cargo = registry.classify(
+            Path::new("/srv/service/target"),
+            StructuralSignals {
+                has_fingerprint: true,
+                ..jvm_signals
+            },
+        );
+        assert_eq!(cargo.category, ArtifactCategory::RustTarget);
+    }
+
+    #[test]
+    fn gradle_outputs_need_a_build_script_and_output_markers() {
+        let dir = tempfile::tempdir().unwrap();
+        let build = dir.path().join("app/build");
+        std::fs::create_dir_all(build.join("libs")).unwrap();
+        let gradle = OpaqueTreeContext {
+            parent_has_gradle_build: true,
+            ..OpaqueTreeContext::default()
+        };
+
+        let opaque = classify_opaque_tree(&build, gradle).unwrap();
+        assert_eq!(opaque.classification.pattern_name, "opaque-gradle-build");
+        // Temp-dir fixtures may still match the generic temp `build/` rule,
+        // but never as a Gradle output without both halves of the evidence.
+        let not_gradle = |path: &Path, context| {
+            classify_opaque_tree(path, context)
+                .is_none_or(|opaque| opaque.classification.category != ArtifactCategory::JvmBuild)
+        };
+        assert!(not_gradle(&build, OpaqueTreeContext::default()));
+
+        let bare = dir.path().join("other/build");
+        std::fs::create_dir_all(bare.join("docs")).unwrap();
+        assert!(not_gradle(&bare, gradle));
+
+        let project_cache = classify_opaque_tree(Path::new("/srv/app/.gradle"), gradle).unwrap();
+        assert_eq!(
+            project_cache.classification.pattern_name,
+            "opaque-gradle-project-cache"
+        );
+        assert!(
+            classify_opaque_tree(Path::new("/home/dev/.gradle"), OpaqueTreeContext::default())
+                .is_none()
+        );
+
+        let transforms = classify_opaque_tree(
+            Path::new("/home/dev/.gradle/caches/transforms-4"),
+            OpaqueTreeContext::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            transforms.classification.category,
+            ArtifactCategory::JvmBuild
+        );
+        assert!(
+            classify_opaque_tree(
+                Path::new("/srv/caches/transforms-4"),
+                OpaqueTreeContext::default()
+            )
+            .is_none()
+        );
+    }
+
     #[test]
     fn two_hex_char_name_predicate() {
         assert!(super::is_two_hex_char("00"));
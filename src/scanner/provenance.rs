@@ -0,0 +1,311 @@
+//! Artifact provenance: which command produced which directories.
+//!
+//! `sbh run -- <command>` snapshots the directory tree around its working
+//! directory before and after the command, and records the directories the
+//! command created together with the command line and how much the process
+//! tree wrote. The scanner tags candidate-index records that fall under a
+//! recorded directory with their owner, and `sbh blame` names the command
+//! instead of a bare PID.
+//!
+//! The ledger lives in `artifact-provenance.json` in the data directory. It is
+//! rewritten under an exclusive lock because concurrent `sbh run` invocations
+//! update it independently.
+
+#![allow(missing_docs)]
+
+use std::collections::BTreeSet;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::errors::{Result, SbhError};
+use crate::core::state_file::write_json_atomic;
+
+/// Runs kept in the ledger; the oldest are dropped first.
+pub const MAX_RECORDED_RUNS: usize = 512;
+
+/// How deep below the working directory `sbh run` looks for new directories.
+pub const PRODUCED_DIR_DEPTH: usize = 2;
+
+/// Unfinished runs older than this are assumed to have been killed.
+const STALE_ACTIVE_RUN_SECS: u64 = 7 * 24 * 3600;
+
+/// The command behind a set of produced directories.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactOwner {
+    pub run_id: String,
+    pub command: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    pub cwd: PathBuf,
+    /// PID of the wrapped command (meaningful while the run is active).
+    #[serde(default)]
+    pub pid: Option<i32>,
+    /// Unix seconds.
+    pub started_at: u64,
+    /// Unix seconds; `None` while the command is still running.
+    #[serde(default)]
+    pub finished_at: Option<u64>,
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+    /// Bytes written by the command's process tree (sampled).
+    #[serde(default)]
+    pub bytes_written: u64,
+}
+
+impl ArtifactOwner {
+    /// Label when given, otherwise the command line.
+    #[must_use]
+    pub fn display_name(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.command)
+    }
+
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.finished_at.is_none()
+    }
+}
+
+/// One `sbh run` invocation and the directories it created.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub owner: ArtifactOwner,
+    #[serde(default)]
+    pub produced: Vec<PathBuf>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProvenanceFile {
+    runs: Vec<RunRecord>,
+}
+
+/// Read-only view of the provenance ledger.
+#[derive(Debug, Clone, Default)]
+pub struct ArtifactProvenance {
+    runs: Vec<RunRecord>,
+}
+
+impl ArtifactProvenance {
+    /// Load the ledger. A missing or unreadable file yields an empty ledger.
+    #[must_use]
+    pub fn load(path: &Path) -> Self {
+        Self {
+            runs: read_runs(path),
+        }
+    }
+
+    #[must_use]
+    pub fn from_runs(runs: Vec<RunRecord>) -> Self {
+        Self { runs }
+    }
+
+    #[must_use]
+    pub fn runs(&self) -> &[RunRecord] {
+        &self.runs
+    }
+
+    /// Owner of the newest run whose produced directory contains `path`.
+    #[must_use]
+    pub fn owner_for(&self, path: &Path) -> Option<&ArtifactOwner> {
+        self.runs
+            .iter()
+            .rev()
+            .find(|run| run.produced.iter().any(|dir| path.starts_with(dir)))
+            .map(|run| &run.owner)
+    }
+
+    /// Active run wrapping process `pid`, if any.
+    #[must_use]
+    pub fn active_run_for_pid(&self, pid: i32) -> Option<&ArtifactOwner> {
+        self.runs
+            .iter()
+            .rev()
+            .map(|run| &run.owner)
+            .find(|owner| owner.is_active() && owner.pid == Some(pid))
+    }
+
+    /// Read-modify-write the ledger under an exclusive lock, then prune it:
+    /// runs whose directories are all gone, stale unfinished runs, and
+    /// anything past [`MAX_RECORDED_RUNS`].
+    pub fn update(path: &Path, now: u64, change: impl FnOnce(&mut Vec<RunRecord>)) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| SbhError::io(parent, e))?;
+        }
+        let lock_path = path.with_extension("lock");
+        let lock = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)
+            .map_err(|e| SbhError::io(&lock_path, e))?;
+        lock.lock().map_err(|e| SbhError::io(&lock_path, e))?;
+
+        let mut runs = read_runs(path);
+        change(&mut runs);
+        prune_runs(&mut runs, now);
+        let result = write_runs(path, runs);
+        let _ = lock.unlock();
+        result
+    }
+}
+
+fn prune_runs(runs: &mut Vec<RunRecord>, now: u64) {
+    runs.retain(|run| {
+        if run.owner.is_active() {
+            now.saturating_sub(run.owner.started_at) < STALE_ACTIVE_RUN_SECS
+        } else {
+            run.produced.iter().any(|dir| dir.exists())
+        }
+    });
+    if runs.len() > MAX_RECORDED_RUNS {
+        let excess = runs.len() - MAX_RECORDED_RUNS;
+        runs.drain(..excess);
+    }
+}
+
+fn read_runs(path: &Path) -> Vec<RunRecord> {
+    fs::read(path)
+        .ok()
+        .and_then(|raw| serde_json::from_slice::<ProvenanceFile>(&raw).ok())
+        .map(|file| file.runs)
+        .unwrap_or_default()
+}
+
+fn write_runs(path: &Path, runs: Vec<RunRecord>) -> Result<()> {
+    write_json_atomic(path, &ProvenanceFile { runs }).map_err(|e| SbhError::io(path, e))
+}
+
+/// Every real directory (no symlinks, no `.git`) up to `depth` levels below
+/// `root`, excluding `root` itself.
+#[must_use]
+pub fn directory_snapshot(root: &Path, depth: usize) -> BTreeSet<PathBuf> {
+    let mut dirs = BTreeSet::new();
+    let mut frontier = vec![root.to_path_buf()];
+    for _ in 0..depth {
+        let mut next = Vec::new();
+        for dir in frontier {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let is_dir = entry.file_type().is_ok_and(|kind| kind.is_dir());
+                if !is_dir || entry.file_name() == ".git" {
+                    continue;
+                }
+                let path = entry.path();
+                dirs.insert(path.clone());
+                next.push(path);
+            }
+        }
+        frontier = next;
+    }
+    dirs
+}
+
+/// Directories present in `after` but not `before`, keeping only the
+/// topmost new directory of each new subtree.
+#[must_use]
+pub fn produced_directories(before: &BTreeSet<PathBuf>, after: &BTreeSet<PathBuf>) -> Vec<PathBuf> {
+    let mut produced: Vec<PathBuf> = Vec::new();
+    // BTreeSet order puts a parent before its children.
+    for dir in after.difference(before) {
+        if !produced.iter().any(|known| dir.starts_with(known)) {
+            produced.push(dir.clone());
+        }
+    }
+    produced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owner(run_id: &str, pid: Option<i32>, finished_at: Option<u64>) -> ArtifactOwner {
+        ArtifactOwner {
+            run_id: run_id.to_string(),
+            command: "cargo build --release".to_string(),
+            label: None,
+            cwd: PathBuf::from("/data/proj"),
+            pid,
+            started_at: 1_000,
+            finished_at,
+            exit_code: finished_at.map(|_| 0),
+            bytes_written: 4096,
+        }
+    }
+
+    #[test]
+    fn produced_directories_keeps_topmost_new_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/bin")).unwrap();
+        fs::create_dir_all(root.join(".git/objects")).unwrap();
+        let before = directory_snapshot(root, PRODUCED_DIR_DEPTH);
+        assert!(before.contains(&root.join("src/bin")));
+        assert!(
+            !before
+                .iter()
+                .any(|path| path.starts_with(root.join(".git")))
+        );
+
+        fs::create_dir_all(root.join("target/release/deps")).unwrap();
+        fs::create_dir_all(root.join("src/generated")).unwrap();
+        let after = directory_snapshot(root, PRODUCED_DIR_DEPTH);
+        assert_eq!(
+            produced_directories(&before, &after),
+            vec![root.join("src/generated"), root.join("target")]
+        );
+    }
+
+    #[test]
+    fn ledger_update_records_owners_and_prunes_vanished_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger_path = dir.path().join("artifact-provenance.json");
+        let target = dir.path().join("target");
+        fs::create_dir_all(&target).unwrap();
+
+        ArtifactProvenance::update(&ledger_path, 1_000, |runs| {
+            runs.push(RunRecord {
+                owner: owner("run-1", Some(42), None),
+                produced: Vec::new(),
+            });
+        })
+        .unwrap();
+        let ledger = ArtifactProvenance::load(&ledger_path);
+        assert_eq!(
+            ledger.active_run_for_pid(42).map(|o| o.run_id.as_str()),
+            Some("run-1")
+        );
+
+        ArtifactProvenance::update(&ledger_path, 1_100, |runs| {
+            let run = runs
+                .iter_mut()
+                .find(|run| run.owner.run_id == "run-1")
+                .unwrap();
+            run.owner.finished_at = Some(1_100);
+            run.produced = vec![target.clone()];
+            runs.push(RunRecord {
+                owner: owner("run-gone", None, Some(1_050)),
+                produced: vec![dir.path().join("vanished")],
+            });
+        })
+        .unwrap();
+        let ledger = ArtifactProvenance::load(&ledger_path);
+        assert_eq!(ledger.runs().len(), 1);
+        assert!(ledger.active_run_for_pid(42).is_none());
+        let owner = ledger.owner_for(&target.join("release/app")).unwrap();
+        assert_eq!(owner.display_name(), "cargo build --release");
+        assert!(ledger.owner_for(dir.path()).is_none());
+    }
+
+    #[test]
+    fn stale_active_runs_are_dropped() {
+        let mut runs = vec![RunRecord {
+            owner: owner("run-killed", Some(7), None),
+            produced: Vec::new(),
+        }];
+        prune_runs(&mut runs, 1_000 + STALE_ACTIVE_RUN_SECS);
+        assert!(runs.is_empty());
+    }
+}
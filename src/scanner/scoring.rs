@@ -13,6 +13,7 @@ use std::time::Duration;
 use crate::core::config::ScoringConfig;
 use crate::platform::cleanup_catalog::{CleanupRule, ReclaimCommand};
 use crate::platform::{linux, macos};
+use crate::scanner::cow_extents::MOSTLY_SHARED_FRACTION;
 use crate::scanner::patterns::{
     ArtifactCategory, ArtifactClassification, StructuralSignals, is_obvious_build_artifact_basename,
 };
@@ -43,6 +44,9 @@ pub enum DecisionAction {
     Keep,
     Delete,
     Review,
+    /// Compress eligible files in place and keep the artifact. Chosen by the
+    /// policy engine below Red pressure, never proposed by scoring.
+    Compress,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -218,6 +222,13 @@ pub struct CandidacyScore {
 pub struct CandidateInput {
     pub path: PathBuf,
     pub size_bytes: u64,
+    /// Inodes freed by removing the candidate (a lower bound, like
+    /// `size_bytes`; 0 when unknown). Only scored under inode pressure.
+    pub entry_count: u64,
+    /// Bytes already excluded from `size_bytes` because their extents are
+    /// shared with data outside the candidate (CoW reflinks); 0 when unknown
+    /// or off copy-on-write filesystems.
+    pub shared_bytes: u64,
     pub age: Duration,
     pub classification: ArtifactClassification,
     pub signals: StructuralSignals,
@@ -249,6 +260,8 @@ pub struct ScoringEngine {
     false_positive_loss: f64,
     false_negative_loss: f64,
     calibration_floor: f64,
+    /// Inode pressure dominates: the size factor also credits entry count.
+    inode_pressure: bool,
 }
 
 impl ScoringEngine {
@@ -267,9 +280,18 @@ impl ScoringEngine {
             false_positive_loss: scoring.false_positive_loss,
             false_negative_loss: scoring.false_negative_loss,
             calibration_floor: scoring.calibration_floor,
+            inode_pressure: false,
         }
     }
 
+    /// Score for a volume running out of inodes rather than bytes: a tree of
+    /// many small files becomes as attractive as a large one.
+    #[must_use]
+    pub const fn with_inode_pressure(mut self, inode_pressure: bool) -> Self {
+        self.inode_pressure = inode_pressure;
+        self
+    }
+
     /// Score one candidate deterministically.
     #[must_use]
     pub fn score_candidate(&self, input: &CandidateInput, urgency: f64) -> CandidacyScore {
@@ -310,7 +332,11 @@ impl ScoringEngine {
             location: factor_location(&input.path),
             name: factor_name(&input.path, &input.classification),
             age: factor_age(input.age),
-            size: factor_size(input.size_bytes),
+            size: if self.inode_pressure {
+                factor_size(input.size_bytes).max(factor_entry_count(input.entry_count))
+            } else {
+                factor_size(input.size_bytes)
+            },
             structure: factor_structure(input.signals),
             pressure_multiplier: pressure_multiplier(urgency),
         };
@@ -358,7 +384,7 @@ impl ScoringEngine {
             ),
         );
 
-        let ledger = build_ledger(
+        let mut ledger = build_ledger(
             factors,
             self.weights,
             posterior_abandoned,
@@ -370,6 +396,7 @@ impl ScoringEngine {
             uncertainty,
             action,
         );
+        note_shared_yield(&mut ledger, input.size_bytes, input.shared_bytes);
 
         CandidacyScore {
             path: input.path.clone(),
@@ -507,7 +534,7 @@ fn cleanup_rule_veto_reason(input: &CandidateInput) -> Option<Cow<'static, str>>
     let rule = cleanup_rule_for_classification(&input.classification)?;
     match rule.reclaim_command {
         ReclaimCommand::ReportOnly => {
-            return Some(report_only_cleanup_rule_reason(rule));
+            return Some(report_only_cleanup_rule_reason(&rule));
         }
         ReclaimCommand::Refuse => {
             return Some(Cow::Owned(format!(
@@ -523,7 +550,8 @@ fn cleanup_rule_veto_reason(input: &CandidateInput) -> Option<Cow<'static, str>>
         }
         ReclaimCommand::RemoveTree
         | ReclaimCommand::RemoveMatchingFiles
-        | ReclaimCommand::PromptBeforeRemove => {}
+        | ReclaimCommand::PromptBeforeRemove
+        | ReclaimCommand::Tool(_) => {}
     }
 
     let minimum_age = rule.age_threshold.minimum_age;
@@ -541,7 +569,7 @@ fn cleanup_rule_veto_reason(input: &CandidateInput) -> Option<Cow<'static, str>>
 fn report_only_cleanup_rule_veto_reason(input: &CandidateInput) -> Option<Cow<'static, str>> {
     let rule = cleanup_rule_for_classification(&input.classification)?;
     if rule.reclaim_command == ReclaimCommand::ReportOnly {
-        return Some(report_only_cleanup_rule_reason(rule));
+        return Some(report_only_cleanup_rule_reason(&rule));
     }
     None
 }
@@ -562,9 +590,10 @@ fn cleanup_rule_allows_embedded_manifest(classification: &ArtifactClassification
         .is_some_and(|rule| rule.name == "release-work-buildroot")
 }
 
-fn cleanup_rule_for_classification(
+/// Catalog rule behind a rule-derived classification, macOS catalog first.
+pub(crate) fn cleanup_rule_for_classification(
     classification: &ArtifactClassification,
-) -> Option<&'static CleanupRule> {
+) -> Option<CleanupRule> {
     macos::cleanup_catalog::find_rule(classification.pattern_name.as_ref())
         .or_else(|| linux::cleanup_catalog::find_rule(classification.pattern_name.as_ref()))
 }
@@ -671,6 +700,8 @@ fn factor_name(path: &Path, classification: &ArtifactClassification) -> f64 {
     }
     if classification.category == ArtifactCategory::RustTarget {
         score += 0.15;
+    } else if classification.category == ArtifactCategory::JvmBuild {
+        score += 0.10;
     }
     if contains_ci(&name, "backup") || contains_ci(&name, "save") || contains_ci(&name, "important")
     {
@@ -715,6 +746,20 @@ fn factor_size(size_bytes: u64) -> f64 {
     }
 }
 
+fn factor_entry_count(entry_count: u64) -> f64 {
+    if entry_count < 10 {
+        0.05
+    } else if entry_count < 100 {
+        0.20
+    } else if entry_count < 1_000 {
+        0.40
+    } else if entry_count < 10_000 {
+        0.70
+    } else {
+        1.0
+    }
+}
+
 fn factor_structure(signals: StructuralSignals) -> f64 {
     if signals.has_git {
         return 0.0;
@@ -725,6 +770,9 @@ fn factor_structure(signals: StructuralSignals) -> f64 {
     if signals.has_deps && signals.has_build {
         return 0.85;
     }
+    if signals.has_jvm_outputs {
+        return 0.80;
+    }
     if signals.has_cargo_toml {
         return 0.05;
     }
@@ -883,6 +931,24 @@ fn epistemic_uncertainty(posterior_abandoned: f64, calibration: f64) -> f64 {
         .clamp(0.0, 1.0)
 }
 
+/// Record how much of the candidate's data is shared with other files, and
+/// flag candidates whose deletion would mostly free nothing. This goes in the
+/// summary, not `terms`: it is a yield note, not a weighted score factor.
+fn note_shared_yield(ledger: &mut EvidenceLedger, reclaimable_bytes: u64, shared_bytes: u64) {
+    if shared_bytes == 0 {
+        return;
+    }
+    let total = reclaimable_bytes.saturating_add(shared_bytes);
+    let fraction = shared_bytes as f64 / total as f64;
+    let _ = write!(ledger.summary, "; shared_extent_fraction={fraction:.3}");
+    if fraction >= MOSTLY_SHARED_FRACTION {
+        let _ = write!(
+            ledger.summary,
+            "; yield_mostly_shared: {shared_bytes} of {total} bytes are shared extents"
+        );
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn build_ledger(
     factors: ScoreFactors,
@@ -1052,12 +1118,14 @@ fn is_system_path(path: &Path) -> bool {
 #[cfg(test)]
 mod tests {
     use super::{
-        ActiveReferenceSummary, CandidateInput, DecisionAction, HARD_REFUSE_BUNDLE_EXTENSIONS,
-        ScoringEngine, is_system_path,
+        ActiveReferenceSummary, CandidacyScore, CandidateInput, DecisionAction,
+        HARD_REFUSE_BUNDLE_EXTENSIONS, ScoringEngine, is_system_path,
     };
     use crate::core::config::ScoringConfig;
     use crate::platform::types::SacredPathSource;
-    use crate::scanner::patterns::{ArtifactCategory, ArtifactClassification, StructuralSignals};
+    use crate::scanner::patterns::{
+        ArtifactCategory, ArtifactClassification, SiblingManifests, StructuralSignals,
+    };
     use crate::scanner::protection::{SacredOverlap, SacredOverlapKind};
     use std::borrow::Cow;
     use std::path::{Path, PathBuf};
@@ -1090,6 +1158,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.8,
         );
@@ -1120,6 +1190,8 @@ mod tests {
                     active_references: ActiveReferenceSummary::default(),
                     is_open: false,
                     excluded: false,
+                    entry_count: 0,
+                    shared_bytes: 0,
                 },
                 0.9,
             );
@@ -1191,6 +1263,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.5,
         );
@@ -1219,6 +1293,8 @@ mod tests {
                     active_references: ActiveReferenceSummary::default(),
                     is_open: false,
                     excluded: false,
+                    entry_count: 0,
+                    shared_bytes: 0,
                 },
                 0.9,
             );
@@ -1248,6 +1324,8 @@ mod tests {
                 active_references,
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.9,
         );
@@ -1278,6 +1356,8 @@ mod tests {
                 active_references,
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.9,
         );
@@ -1322,6 +1402,8 @@ mod tests {
                     active_references,
                     is_open: false,
                     excluded: false,
+                    entry_count: 0,
+                    shared_bytes: 0,
                 },
                 0.9,
             );
@@ -1356,10 +1438,14 @@ mod tests {
                     has_git: false,
                     has_cargo_toml: false,
                     mostly_object_files: true,
+                    has_jvm_outputs: false,
+                    sibling_manifests: SiblingManifests::default(),
                 },
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.7,
         );
@@ -1396,6 +1482,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.95,
         );
@@ -1431,6 +1519,8 @@ mod tests {
                     active_references: ActiveReferenceSummary::default(),
                     is_open: false,
                     excluded: false,
+                    entry_count: 0,
+                    shared_bytes: 0,
                 },
                 1.0,
             );
@@ -1463,6 +1553,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             1.0,
         );
@@ -1496,6 +1588,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             1.0,
         );
@@ -1527,6 +1621,8 @@ mod tests {
             active_references: ActiveReferenceSummary::default(),
             is_open: false,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         };
         let overlap = SacredOverlap {
             candidate_path: path.clone(),
@@ -1568,10 +1664,14 @@ mod tests {
                 has_git: false,
                 has_cargo_toml: false,
                 mostly_object_files: true,
+                has_jvm_outputs: false,
+                sibling_manifests: SiblingManifests::default(),
             },
             active_references: ActiveReferenceSummary::default(),
             is_open: false,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         };
         let a = engine.score_candidate(&input, 0.5);
         let b = engine.score_candidate(&input, 0.5);
@@ -1580,6 +1680,83 @@ mod tests {
         assert_eq!(a.ledger.summary, b.ledger.summary);
     }
 
+    #[test]
+    fn inode_pressure_credits_entry_count_over_size() {
+        let input = CandidateInput {
+            path: PathBuf::from("/data/projects/app/node_modules"),
+            size_bytes: 50 * 1_048_576,
+            age: Duration::from_hours(48),
+            classification: classification(0.9, ArtifactCategory::NodeModules),
+            signals: StructuralSignals::default(),
+            active_references: ActiveReferenceSummary::default(),
+            is_open: false,
+            excluded: false,
+            entry_count: 40_000,
+            shared_bytes: 0,
+        };
+        let byte_score = default_engine().score_candidate(&input, 0.8);
+        let inode_score = default_engine()
+            .with_inode_pressure(true)
+            .score_candidate(&input, 0.8);
+        assert!(inode_score.factors.size > byte_score.factors.size);
+        assert!((inode_score.factors.size - 1.0).abs() < f64::EPSILON);
+        assert!(inode_score.total_score > byte_score.total_score);
+
+        let sparse = CandidateInput {
+            entry_count: 3,
+            ..input
+        };
+        let sparse_bytes = default_engine().score_candidate(&sparse, 0.8);
+        let sparse_inodes = default_engine()
+            .with_inode_pressure(true)
+            .score_candidate(&sparse, 0.8);
+        assert!((sparse_inodes.factors.size - sparse_bytes.factors.size).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn mostly_shared_yield_is_noted_in_the_ledger() {
+        let input = CandidateInput {
+            path: PathBuf::from("/data/projects/app/target"),
+            size_bytes: 200 * 1_048_576,
+            age: Duration::from_hours(48),
+            classification: classification(0.9, ArtifactCategory::RustTarget),
+            signals: StructuralSignals::default(),
+            active_references: ActiveReferenceSummary::default(),
+            is_open: false,
+            excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
+        };
+        let unshared = default_engine().score_candidate(&input, 0.8);
+        assert!(!unshared.ledger.summary.contains("shared_extent_fraction"));
+
+        let reflinked = CandidateInput {
+            size_bytes: 50 * 1_048_576,
+            shared_bytes: 150 * 1_048_576,
+            ..input.clone()
+        };
+        let score = default_engine().score_candidate(&reflinked, 0.8);
+        let names = |score: &CandidacyScore| -> Vec<&str> {
+            score.ledger.terms.iter().map(|term| term.name).collect()
+        };
+        assert_eq!(names(&score), names(&unshared));
+        assert!(
+            score
+                .ledger
+                .summary
+                .contains("shared_extent_fraction=0.750")
+        );
+        assert!(score.ledger.summary.contains("yield_mostly_shared"));
+
+        let partly = CandidateInput {
+            size_bytes: 150 * 1_048_576,
+            shared_bytes: 50 * 1_048_576,
+            ..input
+        };
+        let score = default_engine().score_candidate(&partly, 0.8);
+        assert!(!score.ledger.summary.contains("yield_mostly_shared"));
+    }
+
     #[test]
     fn pressure_multiplier_increases_total_score() {
         let engine = default_engine();
@@ -1596,10 +1773,14 @@ mod tests {
                 has_git: false,
                 has_cargo_toml: false,
                 mostly_object_files: false,
+                has_jvm_outputs: false,
+                sibling_manifests: SiblingManifests::default(),
             },
             active_references: ActiveReferenceSummary::default(),
             is_open: false,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         };
         let low = engine.score_candidate(&input, 0.0);
         let high = engine.score_candidate(&input, 1.0);
@@ -1690,6 +1871,8 @@ mod tests {
             active_references: ActiveReferenceSummary::default(),
             is_open: false,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         };
 
         let score = engine.score_candidate(&input, 0.95);
@@ -1741,10 +1924,14 @@ mod tests {
                 has_git: false,
                 has_cargo_toml: false,
                 mostly_object_files: false,
+                has_jvm_outputs: false,
+                sibling_manifests: SiblingManifests::default(),
             },
             active_references: ActiveReferenceSummary::default(),
             is_open: false,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         };
 
         // At Red pressure (urgency ~0.7), must produce Delete.
@@ -1824,10 +2011,14 @@ mod tests {
                     has_git: false,
                     has_cargo_toml: false,
                     mostly_object_files: false,
+                    has_jvm_outputs: false,
+                    sibling_manifests: SiblingManifests::default(),
                 },
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             };
 
             let score = engine.score_candidate(&input, urgency);
@@ -0,0 +1,547 @@
+//! In-place sweep of live Cargo target directories (cargo-sweep style).
+//!
+//! A Rust target dir is either deleted whole or kept whole: one that was
+//! built recently or is held open by some process is vetoed, even when most
+//! of it is stale incremental sessions and dependency builds from old
+//! lockfiles. With `[scanner.target_sweep]` enabled, the daemon hands such
+//! targets to this module, which removes the stale parts and keeps the rest.
+//!
+//! Within each profile dir (`target/<profile>` or `target/<triple>/<profile>`):
+//!   - `incremental/<crate>-<hash>` dirs whose newest session is older than
+//!     the cutoff are removed.
+//!   - Every `.fingerprint/<unit>-<hash>` not used since the cutoff marks a
+//!     stale unit. Its fingerprint dir, the `deps/` files and the `build/`
+//!     dirs carrying the same hash are removed together.
+//!
+//! Cargo reads a fresh unit's fingerprint on every build but does not rewrite
+//! it, so a unit's last use is the later of its files' access and
+//! modification times, as in cargo-sweep. Directory access times are ignored
+//! because listing a directory, which the scanner does itself, updates them.
+//! On a `noatime` mount this degrades to write times.
+//!
+//! A profile whose `.cargo-lock` is open (a build is running) is skipped, and
+//! so is any unit a process holds open.
+
+#![allow(missing_docs)]
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::hash::BuildHasher;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::core::config::TargetSweepConfig;
+use crate::scanner::patterns::ArtifactCategory;
+use crate::scanner::protection::SacredOverlap;
+use crate::scanner::scoring::{
+    ActiveReferenceSummary, CandidacyScore, CandidateInput, DecisionAction, ScoringEngine,
+};
+use crate::scanner::tool_reclaim::tree_size;
+
+/// Age given to a target when checking whether anything besides recency or
+/// open files keeps it.
+const UNBLOCKED_PROBE_AGE: Duration = Duration::from_hours(365 * 24);
+
+/// Length of the metadata hash cargo appends to unit names.
+const CARGO_HASH_LEN: usize = 16;
+
+/// Outcome of one sweep over a set of targets.
+#[derive(Debug, Clone, Default)]
+pub struct TargetSweepReport {
+    pub profiles_swept: usize,
+    /// Profiles skipped because a build holds their `.cargo-lock`.
+    pub profiles_building: usize,
+    pub units_removed: usize,
+    pub units_would_remove: usize,
+    /// Stale units kept because a process holds them open.
+    pub units_open: usize,
+    pub bytes_freed: u64,
+    pub bytes_would_free: u64,
+    pub errors: Vec<(PathBuf, String)>,
+    pub duration: Duration,
+    pub dry_run: bool,
+}
+
+/// Whether a scored Rust target should be swept in place.
+///
+/// True only for a target the executor will not delete whole, and only if
+/// recency or open files are all that keep it. The target is re-scored as if
+/// old and unreferenced, so any other veto (a `.git` inside, a sacred
+/// overlap, a user exclusion, a bare `Cargo.toml`) still protects it.
+#[must_use]
+pub fn eligible_for_sweep(
+    engine: &ScoringEngine,
+    config: &TargetSweepConfig,
+    input: &CandidateInput,
+    score: &CandidacyScore,
+    urgency: f64,
+    sacred_overlaps: &[SacredOverlap],
+) -> bool {
+    if !config.enabled
+        || input.classification.category != ArtifactCategory::RustTarget
+        || input.signals.has_git
+        || input.size_bytes < config.min_target_size_bytes
+    {
+        return false;
+    }
+    if score.decision.action == DecisionAction::Delete && !score.vetoed {
+        return false;
+    }
+    let unblocked = CandidateInput {
+        age: UNBLOCKED_PROBE_AGE,
+        active_references: ActiveReferenceSummary::default(),
+        is_open: false,
+        ..input.clone()
+    };
+    !engine
+        .score_candidate_with_sacred_overlaps(&unblocked, urgency, sacred_overlaps)
+        .vetoed
+}
+
+/// Drop targets nested inside another target in the list.
+#[must_use]
+pub fn collapse_nested(mut targets: Vec<PathBuf>) -> Vec<PathBuf> {
+    targets.sort();
+    targets.dedup();
+    let mut kept: Vec<PathBuf> = Vec::new();
+    for target in targets {
+        if !kept.iter().any(|outer| target.starts_with(outer)) {
+            kept.push(target);
+        }
+    }
+    kept
+}
+
+/// Remove stale units from every profile under `targets`.
+///
+/// `open_paths` is an open-path ancestor index from
+/// [`collect_open_path_ancestors`](crate::scanner::walker::collect_open_path_ancestors)
+/// covering the targets.
+#[must_use]
+pub fn sweep_targets<S: BuildHasher>(
+    targets: &[PathBuf],
+    config: &TargetSweepConfig,
+    open_paths: &HashSet<PathBuf, S>,
+    now: SystemTime,
+    dry_run: bool,
+) -> TargetSweepReport {
+    let start = Instant::now();
+    let mut report = TargetSweepReport {
+        dry_run,
+        ..TargetSweepReport::default()
+    };
+    let cutoff = now
+        .checked_sub(Duration::from_hours(
+            config.stale_after_days.saturating_mul(24),
+        ))
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    for target in targets {
+        for profile in profile_dirs(target) {
+            if open_paths.contains(&profile.join(".cargo-lock")) {
+                report.profiles_building += 1;
+                continue;
+            }
+            report.profiles_swept += 1;
+            for unit in stale_units(&profile, cutoff) {
+                remove_unit(&unit, open_paths, dry_run, &mut report);
+            }
+        }
+    }
+    report.duration = start.elapsed();
+    report
+}
+
+fn remove_unit<S: BuildHasher>(
+    unit: &Path,
+    open_paths: &HashSet<PathBuf, S>,
+    dry_run: bool,
+    report: &mut TargetSweepReport,
+) {
+    if open_paths.contains(unit) {
+        report.units_open += 1;
+        return;
+    }
+    let Ok(meta) = fs::symlink_metadata(unit) else {
+        return;
+    };
+    if meta.file_type().is_symlink() {
+        return;
+    }
+    let bytes = tree_size(unit);
+    if dry_run {
+        report.units_would_remove += 1;
+        report.bytes_would_free += bytes;
+        return;
+    }
+    let result = if meta.is_dir() {
+        fs::remove_dir_all(unit)
+    } else {
+        fs::remove_file(unit)
+    };
+    match result {
+        Ok(()) => {
+            report.units_removed += 1;
+            report.bytes_freed += bytes;
+        }
+        Err(e) => report.errors.push((unit.to_path_buf(), e.to_string())),
+    }
+}
+
+/// `target` itself when it holds profile markers, else its profile children
+/// and `<triple>/<profile>` grandchildren.
+fn profile_dirs(target: &Path) -> Vec<PathBuf> {
+    if is_profile_dir(target) {
+        return vec![target.to_path_buf()];
+    }
+    let mut profiles = Vec::new();
+    for child in real_subdirs(target) {
+        if is_profile_dir(&child) {
+            profiles.push(child);
+        } else {
+            profiles.extend(
+                real_subdirs(&child)
+                    .into_iter()
+                    .filter(|grandchild| is_profile_dir(grandchild)),
+            );
+        }
+    }
+    profiles
+}
+
+fn is_profile_dir(dir: &Path) -> bool {
+    dir.join(".fingerprint").is_dir() || dir.join("incremental").is_dir()
+}
+
+fn real_subdirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .map(|entry| entry.path())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// Stale incremental crate dirs plus every path belonging to a stale
+/// fingerprinted unit, in a stable order.
+fn stale_units(profile: &Path, cutoff: SystemTime) -> Vec<PathBuf> {
+    let mut units: Vec<PathBuf> = real_subdirs(&profile.join("incremental"))
+        .into_iter()
+        .filter(|dir| last_used(dir) < cutoff)
+        .collect();
+
+    // Hash → fingerprint dir, for units whose fingerprint went stale.
+    let stale_hashes: BTreeMap<String, PathBuf> = real_subdirs(&profile.join(".fingerprint"))
+        .into_iter()
+        .filter(|dir| last_used(dir) < cutoff)
+        .filter_map(|dir| {
+            let hash = unit_hash(dir.file_name()?.to_str()?)?.to_string();
+            Some((hash, dir))
+        })
+        .collect();
+    if stale_hashes.is_empty() {
+        return units;
+    }
+
+    for subdir in ["deps", "build"] {
+        let Ok(entries) = fs::read_dir(profile.join(subdir)) else {
+            continue;
+        };
+        let mut matched: Vec<PathBuf> = entries
+            .flatten()
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .and_then(unit_hash)
+                    .is_some_and(|hash| stale_hashes.contains_key(hash))
+            })
+            .map(|entry| entry.path())
+            .collect();
+        matched.sort();
+        units.extend(matched);
+    }
+    units.extend(stale_hashes.into_values());
+    units
+}
+
+/// The cargo metadata hash in a unit name such as `serde-1a2b3c4d5e6f7a8b`,
+/// `libserde-1a2b3c4d5e6f7a8b.rlib` or `build-script-build-…`.
+fn unit_hash(name: &str) -> Option<&str> {
+    let stem = name.split('.').next()?;
+    let (_, hash) = stem.rsplit_once('-')?;
+    (hash.len() == CARGO_HASH_LEN && hash.bytes().all(|b| b.is_ascii_hexdigit())).then_some(hash)
+}
+
+/// Latest use of `dir` and its direct children: every mtime, plus the
+/// access time of the children that are files.
+fn last_used(dir: &Path) -> SystemTime {
+    let own = fs::symlink_metadata(dir)
+        .and_then(|meta| meta.modified())
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    let Ok(entries) = fs::read_dir(dir) else {
+        return own;
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| entry.metadata().ok())
+        .flat_map(|meta| {
+            let accessed = meta.is_file().then(|| meta.accessed().ok()).flatten();
+            meta.modified().ok().into_iter().chain(accessed)
+        })
+        .fold(own, SystemTime::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filetime::{FileTime, set_file_atime, set_file_times};
+
+    const OLD_HASH: &str = "0123456789abcdef";
+    const NEW_HASH: &str = "fedcba9876543210";
+
+    fn age(path: &Path, days: u64) {
+        let when = FileTime::from_system_time(SystemTime::now() - Duration::from_hours(days * 24));
+        set_file_times(path, when, when).unwrap();
+    }
+
+    fn write(path: &Path, len: usize) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![0u8; len]).unwrap();
+    }
+
+    /// A debug profile with one stale and one fresh unit of each kind.
+    fn fixture(target: &Path) -> PathBuf {
+        let profile = target.join("debug");
+        for (hash, days) in [(OLD_HASH, 30), (NEW_HASH, 1)] {
+            let fingerprint = profile.join(format!(".fingerprint/serde-{hash}"));
+            write(&fingerprint.join("lib-serde"), 16);
+            age(&fingerprint.join("lib-serde"), days);
+            age(&fingerprint, days);
+            write(&profile.join(format!("deps/libserde-{hash}.rlib")), 1_000);
+            write(&profile.join(format!("deps/serde-{hash}.d")), 10);
+            write(&profile.join(format!("build/serde-{hash}/output")), 100);
+
+            let incremental = profile.join(format!("incremental/app-{hash}"));
+            write(&incremental.join("s-session/dep-graph.bin"), 2_000);
+            age(&incremental.join("s-session"), days);
+            age(&incremental, days);
+        }
+        write(&profile.join("app"), 500);
+        profile
+    }
+
+    fn config() -> TargetSweepConfig {
+        TargetSweepConfig {
+            enabled: true,
+            ..TargetSweepConfig::default()
+        }
+    }
+
+    #[test]
+    fn sweep_removes_stale_units_and_keeps_the_live_build() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target");
+        let profile = fixture(&target);
+
+        let report = sweep_targets(
+            std::slice::from_ref(&target),
+            &config(),
+            &HashSet::new(),
+            SystemTime::now(),
+            false,
+        );
+
+        assert_eq!(report.profiles_swept, 1);
+        assert_eq!(report.units_removed, 5, "{report:?}");
+        assert_eq!(report.bytes_freed, 16 + 1_000 + 10 + 100 + 2_000);
+        assert!(report.errors.is_empty());
+        for stale in [
+            format!(".fingerprint/serde-{OLD_HASH}"),
+            format!("deps/libserde-{OLD_HASH}.rlib"),
+            format!("deps/serde-{OLD_HASH}.d"),
+            format!("build/serde-{OLD_HASH}"),
+            format!("incremental/app-{OLD_HASH}"),
+        ] {
+            assert!(!profile.join(&stale).exists(), "{stale} should be gone");
+        }
+        for fresh in [
+            format!(".fingerprint/serde-{NEW_HASH}"),
+            format!("deps/libserde-{NEW_HASH}.rlib"),
+            format!("build/serde-{NEW_HASH}"),
+            format!("incremental/app-{NEW_HASH}"),
+            "app".to_string(),
+        ] {
+            assert!(profile.join(&fresh).exists(), "{fresh} should stay");
+        }
+    }
+
+    #[test]
+    fn old_units_that_builds_still_read_are_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target");
+        let profile = fixture(&target);
+        // Built a month ago, and its fingerprint was read by today's build.
+        let fingerprint = profile.join(format!(".fingerprint/serde-{OLD_HASH}/lib-serde"));
+        set_file_atime(&fingerprint, FileTime::now()).unwrap();
+
+        let report = sweep_targets(
+            std::slice::from_ref(&target),
+            &config(),
+            &HashSet::new(),
+            SystemTime::now(),
+            false,
+        );
+
+        assert_eq!(report.units_removed, 1, "{report:?}");
+        assert!(!profile.join(format!("incremental/app-{OLD_HASH}")).exists());
+        for kept in [
+            format!(".fingerprint/serde-{OLD_HASH}"),
+            format!("deps/libserde-{OLD_HASH}.rlib"),
+            format!("build/serde-{OLD_HASH}"),
+        ] {
+            assert!(profile.join(&kept).exists(), "{kept} should stay");
+        }
+    }
+
+    #[test]
+    fn dry_run_and_open_files_leave_units_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target");
+        let profile = fixture(&target);
+        let rlib = profile.join(format!("deps/libserde-{OLD_HASH}.rlib"));
+
+        let dry = sweep_targets(
+            std::slice::from_ref(&target),
+            &config(),
+            &HashSet::new(),
+            SystemTime::now(),
+            true,
+        );
+        assert_eq!(dry.units_would_remove, 5);
+        assert_eq!(dry.units_removed, 0);
+        assert!(rlib.exists());
+
+        let open: HashSet<PathBuf> = rlib.ancestors().map(Path::to_path_buf).collect();
+        let held = sweep_targets(
+            std::slice::from_ref(&target),
+            &config(),
+            &open,
+            SystemTime::now(),
+            false,
+        );
+        assert_eq!(held.units_open, 1);
+        assert_eq!(held.units_removed, 4);
+        assert!(rlib.exists());
+
+        let building = HashSet::from([profile.join(".cargo-lock")]);
+        let report = sweep_targets(&[target], &config(), &building, SystemTime::now(), false);
+        assert_eq!(report.profiles_building, 1);
+        assert_eq!(report.profiles_swept, 0);
+    }
+
+    #[test]
+    fn only_targets_kept_for_recency_or_open_files_are_eligible() {
+        use crate::core::config::ScoringConfig;
+        use crate::scanner::patterns::{ArtifactClassification, StructuralSignals};
+        use std::borrow::Cow;
+
+        let engine = ScoringEngine::from_config(&ScoringConfig::default(), 30);
+        let input = CandidateInput {
+            path: PathBuf::from("/data/tmp/app/target"),
+            size_bytes: 50 * 1_073_741_824,
+            entry_count: 0,
+            shared_bytes: 0,
+            age: Duration::from_mins(1),
+            classification: ArtifactClassification {
+                pattern_name: Cow::Borrowed("target"),
+                category: ArtifactCategory::RustTarget,
+                name_confidence: 0.95,
+                structural_confidence: 0.9,
+                combined_confidence: 0.92,
+            },
+            signals: StructuralSignals {
+                has_incremental: true,
+                has_deps: true,
+                has_fingerprint: true,
+                ..StructuralSignals::default()
+            },
+            active_references: ActiveReferenceSummary::default(),
+            is_open: true,
+            excluded: false,
+        };
+        let recent = engine.score_candidate(&input, 0.5);
+        assert!(recent.vetoed);
+        assert!(eligible_for_sweep(
+            &engine,
+            &config(),
+            &input,
+            &recent,
+            0.5,
+            &[]
+        ));
+
+        let disabled = TargetSweepConfig::default();
+        assert!(!eligible_for_sweep(
+            &engine,
+            &disabled,
+            &input,
+            &recent,
+            0.5,
+            &[]
+        ));
+
+        let excluded = CandidateInput {
+            excluded: true,
+            ..input.clone()
+        };
+        let score = engine.score_candidate(&excluded, 0.5);
+        assert!(!eligible_for_sweep(
+            &engine,
+            &config(),
+            &excluded,
+            &score,
+            0.5,
+            &[]
+        ));
+
+        let small = CandidateInput {
+            size_bytes: 1_024,
+            ..input
+        };
+        let score = engine.score_candidate(&small, 0.5);
+        assert!(!eligible_for_sweep(
+            &engine,
+            &config(),
+            &small,
+            &score,
+            0.5,
+            &[]
+        ));
+    }
+
+    #[test]
+    fn unit_hashes_and_nested_targets() {
+        assert_eq!(unit_hash("libserde-0123456789abcdef.rlib"), Some(OLD_HASH));
+        assert_eq!(
+            unit_hash("build-script-build-0123456789abcdef"),
+            Some(OLD_HASH)
+        );
+        assert_eq!(unit_hash("app"), None);
+        assert_eq!(unit_hash("my-crate.d"), None);
+
+        let collapsed = collapse_nested(vec![
+            PathBuf::from("/p/target/debug"),
+            PathBuf::from("/p/target"),
+            PathBuf::from("/q/target"),
+            PathBuf::from("/p/target"),
+        ]);
+        assert_eq!(
+            collapsed,
+            vec![PathBuf::from("/p/target"), PathBuf::from("/q/target")]
+        );
+    }
+}
@@ -0,0 +1,359 @@
+//! Owning-tool reclaim: trim a cache by running the tool that maintains it.
+//!
+//! Candidates matched by a cleanup rule whose `reclaim_command` is
+//! [`ReclaimCommand::Tool`](crate::platform::cleanup_catalog::ReclaimCommand::Tool)
+//! are never removed by the deletion executor. It runs the tool against the
+//! matched path instead (after the same pre-flight checks), kills it once the
+//! rule's timeout passes, and credits the batch with the size change measured
+//! before and after the run. Dry runs report the rule's expected-bytes
+//! estimate and never start the tool.
+//!
+//! The tool runs as the user who owns the cache, with that user's `HOME`, so
+//! a root daemon never leaves root-owned files in someone's `~/.npm` or pnpm
+//! store. When the daemon can neither switch to that user nor is that user,
+//! the candidate is skipped. Each run gets its own process group, and a
+//! timeout kills the whole group rather than just the direct child.
+
+#![allow(missing_docs)]
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::core::errors::{Result, SbhError};
+use crate::platform::cleanup_catalog::ToolReclaim;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Bytes of tool stderr kept for the failure message.
+const STDERR_TAIL_BYTES: usize = 512;
+
+/// One completed tool run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolReclaimOutcome {
+    pub path: PathBuf,
+    pub rule: String,
+    pub argv: Vec<String>,
+    /// Estimate from the rule, for comparison with the measured delta.
+    pub expected_bytes: u64,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub duration: Duration,
+}
+
+impl ToolReclaimOutcome {
+    /// Measured reclaim; zero when the cache grew during the run.
+    #[must_use]
+    pub const fn bytes_freed(&self) -> u64 {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+}
+
+/// Whose identity an owning tool runs under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolUser {
+    /// The cache belongs to the daemon's own user.
+    Daemon,
+    /// Another user's cache; the tool runs with their ids and home.
+    Owner {
+        uid: u32,
+        gid: u32,
+        name: String,
+        home: PathBuf,
+    },
+}
+
+/// The user a tool reclaiming `path` must run as, or `None` when the daemon
+/// cannot run it as the owner of `path` (it is not root, or the owner has no
+/// passwd entry).
+#[cfg(unix)]
+#[must_use]
+pub fn tool_user(path: &Path) -> Option<ToolUser> {
+    use nix::unistd::{Uid, User, geteuid};
+    use std::os::unix::fs::MetadataExt;
+
+    let meta = fs::symlink_metadata(path).ok()?;
+    let euid = geteuid();
+    if meta.uid() == euid.as_raw() {
+        return Some(ToolUser::Daemon);
+    }
+    if !euid.is_root() {
+        return None;
+    }
+    let user = User::from_uid(Uid::from_raw(meta.uid())).ok()??;
+    Some(ToolUser::Owner {
+        uid: meta.uid(),
+        gid: meta.gid(),
+        name: user.name,
+        home: user.dir,
+    })
+}
+
+#[cfg(not(unix))]
+#[must_use]
+pub fn tool_user(_path: &Path) -> Option<ToolUser> {
+    Some(ToolUser::Daemon)
+}
+
+/// Run `tool` as `user` against `path` and measure what it freed.
+///
+/// `size_bytes` is the scanner's size for the candidate and only feeds the
+/// expected-bytes estimate. A missing program, a non-zero exit and a timeout
+/// are all errors; a timed-out tool and everything it started are killed
+/// before this returns.
+pub fn run_tool_reclaim(
+    rule: &str,
+    tool: &ToolReclaim,
+    path: &Path,
+    size_bytes: u64,
+    user: &ToolUser,
+) -> Result<ToolReclaimOutcome> {
+    let argv = tool.resolved_argv(path);
+    let Some((program, rest)) = argv.split_first() else {
+        return Err(SbhError::Runtime {
+            details: format!("{rule}: reclaim tool has an empty argv"),
+        });
+    };
+
+    let bytes_before = tree_size(path);
+    let start = Instant::now();
+    let mut child = tool_command(program, rest, user)
+        .envs(tool.resolved_env(path))
+        .current_dir(path.parent().unwrap_or(path))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| SbhError::Runtime {
+            details: format!("{rule}: could not start {program}: {e}"),
+        })?;
+
+    // Drain stderr on its own thread so a chatty tool cannot block on a full
+    // pipe while we wait for it.
+    let stderr_reader = child.stderr.take().map(|mut stderr| {
+        thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = stderr.read_to_end(&mut buf);
+            let tail = buf.len().saturating_sub(STDERR_TAIL_BYTES);
+            String::from_utf8_lossy(&buf[tail..]).trim().to_string()
+        })
+    });
+
+    let timeout = Duration::from_secs(tool.timeout_secs);
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if start.elapsed() >= timeout => {
+                kill_group(&mut child);
+                let _ = child.wait();
+                return Err(SbhError::Runtime {
+                    details: format!(
+                        "{rule}: {} killed after {}s timeout",
+                        argv.join(" "),
+                        tool.timeout_secs
+                    ),
+                });
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(e) => {
+                kill_group(&mut child);
+                return Err(SbhError::io(path, e));
+            }
+        }
+    };
+    let stderr = stderr_reader
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default();
+
+    if !status.success() {
+        let mut details = format!("{rule}: {} exited with {status}", argv.join(" "));
+        if !stderr.is_empty() {
+            details.push_str(": ");
+            details.push_str(&stderr);
+        }
+        return Err(SbhError::Runtime { details });
+    }
+
+    Ok(ToolReclaimOutcome {
+        path: path.to_path_buf(),
+        rule: rule.to_string(),
+        expected_bytes: tool.expected_bytes(size_bytes),
+        argv,
+        bytes_before,
+        bytes_after: tree_size(path),
+        duration: start.elapsed(),
+    })
+}
+
+/// `program` with `args`, set up to run as `user` in a process group of its own.
+fn tool_command(program: &str, args: &[String], user: &ToolUser) -> Command {
+    let mut command = Command::new(program);
+    command.args(args);
+    if let ToolUser::Owner {
+        uid,
+        gid,
+        name,
+        home,
+    } = user
+    {
+        // The daemon's XDG dirs would point the tool back at root's caches.
+        for var in [
+            "XDG_CACHE_HOME",
+            "XDG_CONFIG_HOME",
+            "XDG_DATA_HOME",
+            "XDG_STATE_HOME",
+        ] {
+            command.env_remove(var);
+        }
+        command
+            .env("HOME", home)
+            .env("USER", name)
+            .env("LOGNAME", name);
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            command.uid(*uid).gid(*gid);
+        }
+        #[cfg(not(unix))]
+        let _ = (uid, gid);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    command
+}
+
+/// Kill `child` and every process it started in its process group.
+fn kill_group(child: &mut Child) {
+    #[cfg(unix)]
+    if let Ok(pid) = i32::try_from(child.id()) {
+        let _ = nix::sys::signal::killpg(
+            nix::unistd::Pid::from_raw(pid),
+            nix::sys::signal::Signal::SIGKILL,
+        );
+    }
+    let _ = child.kill();
+}
+
+/// Apparent size of everything under `path`, without following symlinks.
+/// Unreadable entries count as zero.
+pub(crate) fn tree_size(path: &Path) -> u64 {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| tree_size(&entry.path()))
+        .sum()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[allow(clippy::literal_string_with_formatting_args)]
+    fn tool(argv: &'static [&'static str], timeout_secs: u64) -> ToolReclaim {
+        ToolReclaim {
+            argv,
+            env: &[("SBH_TOOL_TARGET", "{path}")],
+            timeout_secs,
+            expected_reclaim_percent: 50,
+        }
+    }
+
+    #[test]
+    fn tool_run_measures_the_before_after_delta() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("cache");
+        fs::create_dir(&cache).unwrap();
+        fs::write(cache.join("keep"), vec![0u8; 1_000]).unwrap();
+        fs::write(cache.join("stale"), vec![0u8; 3_000]).unwrap();
+
+        let outcome = run_tool_reclaim(
+            "test-cache",
+            &tool(&["sh", "-c", "rm \"$SBH_TOOL_TARGET/stale\""], 10),
+            &cache,
+            8_000,
+            &ToolUser::Daemon,
+        )
+        .unwrap();
+
+        assert_eq!(outcome.bytes_before, 4_000);
+        assert_eq!(outcome.bytes_after, 1_000);
+        assert_eq!(outcome.bytes_freed(), 3_000);
+        assert_eq!(outcome.expected_bytes, 4_000);
+        assert!(cache.join("keep").exists());
+    }
+
+    #[test]
+    fn tools_run_as_the_cache_owner_or_not_at_all() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(tool_user(dir.path()), Some(ToolUser::Daemon));
+        assert_eq!(tool_user(&dir.path().join("missing")), None);
+    }
+
+    #[test]
+    fn tool_failures_and_timeouts_are_errors() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let failed = run_tool_reclaim(
+            "test-cache",
+            &tool(&["sh", "-c", "echo cache locked >&2; exit 3"], 10),
+            dir.path(),
+            0,
+            &ToolUser::Daemon,
+        )
+        .unwrap_err();
+        assert!(failed.to_string().contains("cache locked"), "{failed}");
+
+        let start = Instant::now();
+        let timed_out = run_tool_reclaim(
+            "test-cache",
+            &tool(
+                &[
+                    "sh",
+                    "-c",
+                    "sleep 30 & echo $! > \"$SBH_TOOL_TARGET/grandchild.pid\"; wait",
+                ],
+                1,
+            ),
+            dir.path(),
+            0,
+            &ToolUser::Daemon,
+        )
+        .unwrap_err();
+        assert!(timed_out.to_string().contains("timeout"), "{timed_out}");
+        assert!(start.elapsed() < Duration::from_secs(10));
+        // Whatever the tool started dies with it.
+        let grandchild = fs::read_to_string(dir.path().join("grandchild.pid")).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let proc_stat = fs::read_to_string(format!("/proc/{}/stat", grandchild.trim()));
+        assert!(
+            proc_stat
+                .as_deref()
+                .map_or(true, |line| line.contains(") Z ")),
+            "grandchild still running: {proc_stat:?}"
+        );
+
+        let missing = run_tool_reclaim(
+            "test-cache",
+            &tool(&["sbh-no-such-reclaim-tool"], 10),
+            dir.path(),
+            0,
+            &ToolUser::Daemon,
+        )
+        .unwrap_err();
+        assert!(missing.to_string().contains("could not start"), "{missing}");
+    }
+}
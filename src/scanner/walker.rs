@@ -21,8 +21,8 @@ use crossbeam_channel as channel;
 use crate::core::errors::{Result, SbhError};
 use crate::platform::pal::Platform;
 use crate::scanner::patterns::{
-    OpaqueTreeClassification, OpaqueTreeContext, OpaqueTreeDisposition, StructuralSignals,
-    classify_opaque_tree,
+    OpaqueTreeClassification, OpaqueTreeContext, OpaqueTreeDisposition, SiblingManifests,
+    StructuralSignals, classify_opaque_tree,
 };
 use crate::scanner::protection::ProtectionRegistry;
 use crate::scanner::scoring::ActiveReferenceSummary;
@@ -80,12 +80,21 @@ pub struct EntryMetadata {
     /// Allocated on-disk size (`st_blocks * 512` on Unix), not the apparent
     /// length: sparse files count only the blocks they actually consume.
     pub size_bytes: u64,
-    /// Estimated content size for directories: sum of immediate children's file
-    /// sizes observed during iteration. For files, equals `size_bytes`.
+    /// Estimated reclaimable content size for directories: allocated blocks of
+    /// the immediate child files observed during iteration, each inode counted
+    /// once and only when all of its hard links are among those children. For
+    /// files, equals `size_bytes` unless the file has other links, then 0.
     /// This is a lower bound (capped at `MAX_ENTRIES_PER_DIR` children, does not
     /// recurse into subdirectories), but far more useful for scoring than the
     /// inode entry size (~4096) that `size_bytes` returns for directories.
     pub content_size_bytes: u64,
+    /// Apparent length (`st_size`) of the same files, summed per name. Shown
+    /// beside `content_size_bytes` so hard links and sparse files are visible.
+    pub apparent_size_bytes: u64,
+    /// Inodes freed by removing the entry: itself plus, for directories, the
+    /// immediate children seen during iteration. A lower bound capped like
+    /// `content_size_bytes`.
+    pub content_entry_count: u64,
     pub modified: SystemTime,
     pub created: Option<SystemTime>,
     pub is_dir: bool,
@@ -158,9 +167,14 @@ struct WorkItem {
     depth: usize,
     root_device_id: u64,
     opaque_tree: Option<OpaqueTreeClassification>,
+    /// Manifests in the parent directory.
+    sibling_manifests: SiblingManifests,
 }
 
 const OPAQUE_CANDIDATE_SIZE_FLOOR: u64 = 100 * 1_048_576;
+/// Opaque build trees are not descended, so their file count is unknown;
+/// they routinely hold thousands of files.
+const OPAQUE_CANDIDATE_ENTRY_FLOOR: u64 = 1_000;
 
 /// Parallel directory walker with safety guards.
 ///
@@ -262,6 +276,7 @@ impl DirectoryWalker {
                 depth: 0,
                 root_device_id: dev,
                 opaque_tree: None,
+                sibling_manifests: SiblingManifests::default(),
             });
         }
 
@@ -330,6 +345,7 @@ fn walker_thread(
                     item.depth,
                     item.root_device_id,
                     item.opaque_tree,
+                    item.sibling_manifests,
                     work_tx,
                     result_tx,
                     in_flight,
@@ -387,6 +403,7 @@ fn process_directory(
     depth: usize,
     root_dev: u64,
     opaque_tree: Option<OpaqueTreeClassification>,
+    sibling_manifests: SiblingManifests,
     work_tx: &channel::Sender<WorkItem>,
     result_tx: &channel::Sender<WalkEntry>,
     in_flight: &AtomicUsize,
@@ -438,11 +455,17 @@ fn process_directory(
     };
 
     // State for structural signals (incremental accumulation).
-    let mut signals = StructuralSignals::default();
+    let mut signals = StructuralSignals {
+        sibling_manifests,
+        ..StructuralSignals::default()
+    };
+    let mut manifests = SiblingManifests::default();
     let mut object_count = 0u32;
     let mut total_count = 0u32;
-    let mut content_size: u64 = 0;
+    let mut content_size = ReclaimTally::default();
     let mut parent_has_node_manifest = false;
+    let mut parent_has_gradle_build = false;
+    let mut parent_has_maven_pom = false;
 
     // Collect child directories during iteration; queue them AFTER the loop.
     // This prevents a race where a child dir is queued and processed by another
@@ -506,9 +529,18 @@ fn process_directory(
                 | "bun.lockb" => {
                     parent_has_node_manifest = true;
                 }
+                "build.gradle" | "build.gradle.kts" | "settings.gradle" | "settings.gradle.kts" => {
+                    parent_has_gradle_build = true;
+                }
+                "pom.xml" => parent_has_maven_pom = true,
+                "classes" | "test-classes" | "libs" | "maven-status" | "maven-archiver" => {
+                    signals.has_jvm_outputs = true;
+                }
                 _ => {}
             }
 
+            manifests.note(&name);
+
             // Check extension for object file heuristics.
             if let Some(ext) = Path::new(name_os).extension() {
                 let ext_str = ext.to_string_lossy();
@@ -549,9 +581,7 @@ fn process_directory(
         // For child dirs: skip (their recursive size will be computed when they
         // are processed as their own WalkEntry).
         if !is_dir && let Ok(child_meta) = entry.metadata() {
-            // Allocated blocks, not apparent length (see `allocated_size`):
-            // sparse files must not inflate the directory's reclaim estimate.
-            content_size = content_size.saturating_add(allocated_size(&child_meta));
+            content_size.add(&child_meta);
         }
 
         // ─── Collect Child Dirs ───
@@ -580,6 +610,8 @@ fn process_directory(
                 OpaqueTreeContext {
                     parent_has_cargo_toml: signals.has_cargo_toml,
                     parent_has_node_manifest,
+                    parent_has_gradle_build,
+                    parent_has_maven_pom,
                 },
             )
         } else {
@@ -598,6 +630,8 @@ fn process_directory(
                     if emeta.is_dir {
                         emeta.content_size_bytes =
                             emeta.content_size_bytes.max(OPAQUE_CANDIDATE_SIZE_FLOOR);
+                        emeta.content_entry_count =
+                            emeta.content_entry_count.max(OPAQUE_CANDIDATE_ENTRY_FLOOR);
                     }
                     let walk_entry = WalkEntry {
                         path: child_path,
@@ -624,6 +658,7 @@ fn process_directory(
                     depth: depth + 1,
                     root_device_id: root_dev,
                     opaque_tree: opaque_tree.clone(),
+                    sibling_manifests: manifests,
                 },
                 Duration::from_millis(100),
             ) {
@@ -657,8 +692,12 @@ fn process_directory(
         // sizes. This is a lower bound (doesn't recurse into subdirs, capped at
         // MAX_ENTRIES_PER_DIR children) but vastly better than the inode entry
         // size (~4096) for scoring purposes.
-        if emeta.is_dir && content_size > 0 {
-            emeta.content_size_bytes = content_size;
+        if emeta.is_dir && content_size.apparent > 0 {
+            emeta.content_size_bytes = content_size.reclaimable_bytes();
+            emeta.apparent_size_bytes = content_size.apparent;
+        }
+        if emeta.is_dir {
+            emeta.content_entry_count = u64::from(total_count) + 1;
         }
         let walk_entry = WalkEntry {
             path: dir_path.to_path_buf(),
@@ -689,6 +728,9 @@ fn signals_from_children(child_names: &[String]) -> StructuralSignals {
             ".fingerprint" => signals.has_fingerprint = true,
             ".git" => signals.has_git = true,
             "cargo.toml" => signals.has_cargo_toml = true,
+            "classes" | "test-classes" | "libs" | "maven-status" | "maven-archiver" => {
+                signals.has_jvm_outputs = true;
+            }
             _ => {}
         }
         // Names are already lowercased, so case-insensitive matching is not needed.
@@ -727,6 +769,64 @@ fn allocated_size(meta: &fs::Metadata) -> u64 {
     }
 }
 
+/// Reclaim estimate over one directory's child files.
+///
+/// Sizes are allocated blocks (see `allocated_size`). A file with several
+/// hard links is counted once, and only if every link is a child of this
+/// directory: cargo links binaries between `deps/` and the profile dir, and
+/// pnpm and uv stores link into many projects, so removing one name frees
+/// nothing while others remain.
+///
+/// Links are not matched across directories, so a file whose links sit in
+/// different directories of one candidate (`target/debug/tool` and
+/// `target/debug/deps/tool-<hash>`) is counted in neither. Deleting `target/`
+/// would free it; the estimate under-counts it, in line with the estimate as
+/// a whole, which only covers immediate children and is a lower bound.
+#[derive(Debug, Default)]
+struct ReclaimTally {
+    apparent: u64,
+    reclaimable: u64,
+    /// (dev, ino) -> (link count, links seen, allocated bytes).
+    linked: HashMap<(u64, u64), (u64, u64, u64)>,
+}
+
+impl ReclaimTally {
+    fn add(&mut self, meta: &fs::Metadata) {
+        self.apparent = self.apparent.saturating_add(meta.len());
+        let allocated = allocated_size(meta);
+        let (key, links) = hard_link_identity(meta);
+        if links > 1 {
+            self.linked.entry(key).or_insert((links, 0, allocated)).1 += 1;
+        } else {
+            self.reclaimable = self.reclaimable.saturating_add(allocated);
+        }
+    }
+
+    fn reclaimable_bytes(&self) -> u64 {
+        self.linked
+            .values()
+            .filter(|(links, seen, _)| seen >= links)
+            .fold(self.reclaimable, |total, (_, _, allocated)| {
+                total.saturating_add(*allocated)
+            })
+    }
+}
+
+/// `((dev, ino), st_nlink)` of a file. Where links are not reported every
+/// file counts as singly linked.
+fn hard_link_identity(meta: &fs::Metadata) -> ((u64, u64), u64) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        ((meta.dev(), meta.ino()), meta.nlink())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = meta;
+        ((0, 0), 1)
+    }
+}
+
 /// Extract `EntryMetadata` from `fs::Metadata` (Unix-specific fields via MetadataExt).
 fn entry_metadata(meta: &fs::Metadata) -> EntryMetadata {
     let file_type = meta.file_type();
@@ -747,9 +847,17 @@ fn entry_metadata(meta: &fs::Metadata) -> EntryMetadata {
         // hard-linked/cloned tree smaller). Scoring, ranking, and the reclaim
         // estimate all consume this field as "bytes freed if removed".
         let size = allocated_size(meta);
+        // A file linked elsewhere keeps its blocks when this name goes.
+        let reclaimable = if !meta.is_dir() && meta.nlink() > 1 {
+            0
+        } else {
+            size
+        };
         EntryMetadata {
             size_bytes: size,
-            content_size_bytes: size, // Overridden for directories in process_directory.
+            content_size_bytes: reclaimable, // Overridden for directories in process_directory.
+            apparent_size_bytes: meta.len(),
+            content_entry_count: 1,
             modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
             created: meta.created().ok(),
             is_dir: meta.is_dir(),
@@ -765,6 +873,8 @@ fn entry_metadata(meta: &fs::Metadata) -> EntryMetadata {
         EntryMetadata {
             size_bytes: size,
             content_size_bytes: size,
+            apparent_size_bytes: size,
+            content_entry_count: 1,
             modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
             created: meta.created().ok(),
             is_dir: meta.is_dir(),
@@ -1583,6 +1693,8 @@ mod tests {
                 fs_type: "test".to_string(),
                 mount_point: path.to_path_buf(),
                 is_readonly: false,
+                total_inodes: 0,
+                free_inodes: 0,
             })
         }
 
@@ -1652,6 +1764,8 @@ mod tests {
             resident_memory_bytes: None,
             cpu_user_micros: None,
             cpu_system_micros: None,
+            uid: None,
+            cgroup: None,
         }
     }
 
@@ -1724,6 +1838,60 @@ mod tests {
             dir_entry.metadata.content_size_bytes,
             APPARENT
         );
+        assert_eq!(dir_entry.metadata.apparent_size_bytes, APPARENT);
+    }
+
+    /// Hard links: an inode is counted once, and only toward a directory that
+    /// holds every one of its links.
+    #[cfg(unix)]
+    #[test]
+    fn hard_linked_files_count_once_and_only_where_all_links_live() {
+        use std::os::unix::fs::MetadataExt;
+        const LEN: usize = 256 * 1024;
+
+        let tmp = TempDir::new().unwrap();
+        let profile = tmp.path().join("debug");
+        let deps = profile.join("deps");
+        fs::create_dir_all(&deps).unwrap();
+        // cargo-style: the profile-dir binary is a link to the deps/ build.
+        fs::write(deps.join("tool-0123456789abcdef"), vec![7u8; LEN]).unwrap();
+        fs::hard_link(deps.join("tool-0123456789abcdef"), profile.join("tool")).unwrap();
+        // Two links to one inode inside the same directory.
+        fs::write(deps.join("libdup.rlib"), vec![1u8; LEN]).unwrap();
+        fs::hard_link(deps.join("libdup.rlib"), deps.join("libdup-copy.rlib")).unwrap();
+        // A plain file.
+        fs::write(profile.join("plain.d"), vec![2u8; LEN]).unwrap();
+        let blocks = |path: &Path| fs::metadata(path).unwrap().blocks() * 512;
+
+        let walker =
+            DirectoryWalker::new(test_config(tmp.path()), ProtectionRegistry::marker_only());
+        let entries = walker.walk().unwrap();
+        let meta_of = |path: &Path| {
+            entries
+                .iter()
+                .find(|e| e.path == path)
+                .map(|e| e.metadata.clone())
+                .unwrap()
+        };
+
+        let deps_meta = meta_of(&deps);
+        assert_eq!(
+            deps_meta.content_size_bytes,
+            blocks(&deps.join("libdup.rlib")),
+            "the deps/ binary is still linked from the profile dir"
+        );
+        assert_eq!(deps_meta.apparent_size_bytes, 3 * LEN as u64);
+
+        let profile_meta = meta_of(&profile);
+        assert_eq!(
+            profile_meta.content_size_bytes,
+            blocks(&profile.join("plain.d"))
+        );
+        assert_eq!(profile_meta.apparent_size_bytes, 2 * LEN as u64);
+
+        let linked = entry_metadata(&fs::symlink_metadata(profile.join("tool")).unwrap());
+        assert_eq!(linked.content_size_bytes, 0);
+        assert!(linked.size_bytes > 0);
     }
 
     #[test]
@@ -2203,6 +2371,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: is_path_open_by_ancestor(&entry.path, open_ancestors),
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             };
             let score = scoring.score_candidate(&input, 0.9);
             if score.vetoed {
@@ -2397,6 +2567,8 @@ mod tests {
             active_references: ActiveReferenceSummary::default(),
             is_open: true,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         };
         let score =
             ScoringEngine::from_config(&ScoringConfig::default(), 0).score_candidate(&input, 0.9);
@@ -2653,6 +2825,7 @@ mod tests {
                 metadata: EntryMetadata {
                     size_bytes: 0,
                     content_size_bytes: 0,
+                    apparent_size_bytes: 0,
                     modified: SystemTime::UNIX_EPOCH,
                     created: None,
                     is_dir: true,
@@ -2660,6 +2833,7 @@ mod tests {
                     device_id: 0,
                     kind: FsEntryKind::Directory,
                     permissions: 0,
+                    content_entry_count: 1,
                 },
                 depth: 1,
                 structural_signals: StructuralSignals::default(),
@@ -2690,6 +2864,7 @@ mod tests {
                 1,
                 0,
                 None,
+                SiblingManifests::default(),
                 &work_tx,
                 &result_tx,
                 &in_flight,
@@ -712,6 +712,8 @@ mod tests {
             fs_type: "tmpfs".to_string(),
             mount_point: PathBuf::from("/tmp"),
             is_readonly: false,
+            total_inodes: 0,
+            free_inodes: 0,
         };
         Arc::new(MockPlatform::new(
             vec![mount.clone()],
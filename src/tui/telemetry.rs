@@ -1083,6 +1083,7 @@ fn parse_event_type(input: &str) -> Option<crate::logger::jsonl::EventType> {
     let compact = normalized.replace('_', "");
     match normalized.as_str() {
         "artifact_delete" => Some(crate::logger::jsonl::EventType::ArtifactDelete),
+        "artifact_compress" => Some(crate::logger::jsonl::EventType::ArtifactCompress),
         "ballast_release" => Some(crate::logger::jsonl::EventType::BallastRelease),
         "ballast_replenish" => Some(crate::logger::jsonl::EventType::BallastReplenish),
         "ballast_provision" => Some(crate::logger::jsonl::EventType::BallastProvision),
@@ -1094,8 +1095,10 @@ fn parse_event_type(input: &str) -> Option<crate::logger::jsonl::EventType> {
         "info" => Some(crate::logger::jsonl::EventType::Info),
         "error" => Some(crate::logger::jsonl::EventType::Error),
         "emergency" => Some(crate::logger::jsonl::EventType::Emergency),
+        "decision" => Some(crate::logger::jsonl::EventType::Decision),
         _ => match compact.as_str() {
             "artifactdelete" => Some(crate::logger::jsonl::EventType::ArtifactDelete),
+            "artifactcompress" => Some(crate::logger::jsonl::EventType::ArtifactCompress),
             "ballastrelease" => Some(crate::logger::jsonl::EventType::BallastRelease),
             "ballastreplenish" => Some(crate::logger::jsonl::EventType::BallastReplenish),
             "ballastprovision" => Some(crate::logger::jsonl::EventType::BallastProvision),
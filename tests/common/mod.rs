@@ -212,6 +212,8 @@ impl MockPlatform {
                 fs_type: "ext4".to_string(),
                 mount_point: data_path.clone(),
                 is_readonly: false,
+                total_inodes: 0,
+                free_inodes: 0,
             },
         );
 
@@ -255,6 +257,8 @@ impl MockPlatform {
                 fs_type: "ext4".to_string(),
                 mount_point: path.clone(),
                 is_readonly: false,
+                total_inodes: 0,
+                free_inodes: 0,
             },
         );
         self.mounts.push(MountPoint {
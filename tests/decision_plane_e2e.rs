@@ -23,7 +23,7 @@ use storage_ballast_helper::scanner::decision_record::{
     ActionRecord, ExplainLevel, PolicyMode, format_explain,
 };
 use storage_ballast_helper::scanner::patterns::{
-    ArtifactCategory, ArtifactClassification, StructuralSignals,
+    ArtifactCategory, ArtifactClassification, SiblingManifests, StructuralSignals,
 };
 use storage_ballast_helper::scanner::scoring::{
     ActiveReferenceSummary, CandidateInput, ScoringEngine,
@@ -88,10 +88,14 @@ fn make_candidate(
             has_git: false,
             has_cargo_toml: false,
             mostly_object_files: rng.next_bool(0.6),
+            has_jvm_outputs: false,
+            sibling_manifests: SiblingManifests::default(),
         },
         active_references: ActiveReferenceSummary::default(),
         is_open: false,
         excluded: false,
+        entry_count: 0,
+        shared_bytes: 0,
     }
 }
 
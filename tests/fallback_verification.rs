@@ -99,6 +99,8 @@ fn mock_platform() -> Arc<dyn storage_ballast_helper::platform::pal::Platform> {
         fs_type: "tmpfs".to_string(),
         mount_point: PathBuf::from("/tmp"),
         is_readonly: false,
+        total_inodes: 0,
+        free_inodes: 0,
     };
     Arc::new(MockPlatform::new(
         vec![mount.clone()],
@@ -41,7 +41,8 @@ use storage_ballast_helper::scanner::decision_record::{
 };
 use storage_ballast_helper::scanner::deletion::{DeletionConfig, DeletionExecutor};
 use storage_ballast_helper::scanner::patterns::{
-    ArtifactCategory, ArtifactClassification, ArtifactPatternRegistry, StructuralSignals,
+    ArtifactCategory, ArtifactClassification, ArtifactPatternRegistry, SiblingManifests,
+    StructuralSignals,
 };
 use storage_ballast_helper::scanner::protection::{
     ProtectionRegistry, SacredOverlapKind, find_sacred_overlaps,
@@ -2310,6 +2311,8 @@ fn synthetic_cleanup_candidate(path: &Path) -> CandidateInput {
         active_references: ActiveReferenceSummary::default(),
         is_open: false,
         excluded: false,
+        entry_count: 0,
+        shared_bytes: 0,
     }
 }
 
@@ -2528,6 +2531,8 @@ proptest! {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: true,
+                entry_count: 0,
+                shared_bytes: 0,
             };
 
             let score = engine.score_candidate(&input, 1.0);
@@ -2561,6 +2566,8 @@ proptest! {
             has_git: false,
             has_cargo_toml: false,
             mostly_object_files,
+            has_jvm_outputs: false,
+            sibling_manifests: SiblingManifests::default(),
         };
         let registry = ArtifactPatternRegistry::default();
         let engine = ScoringEngine::from_config(&ScoringConfig::default(), 30);
@@ -2575,6 +2582,8 @@ proptest! {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.75,
         );
@@ -3245,6 +3254,8 @@ fn green_pressure_no_deletions() {
         active_references: ActiveReferenceSummary::default(),
         is_open: false,
         excluded: false,
+        entry_count: 0,
+        shared_bytes: 0,
     };
 
     let score = scoring.score_candidate(&input, 0.0); // Green: urgency=0
@@ -3408,6 +3419,8 @@ fn scoring_pipeline_ranks_artifacts_above_source() {
         active_references: ActiveReferenceSummary::default(),
         is_open: false,
         excluded: false,
+        entry_count: 0,
+        shared_bytes: 0,
     };
 
     // Unknown source file — should not be recommended for deletion.
@@ -3420,6 +3433,8 @@ fn scoring_pipeline_ranks_artifacts_above_source() {
         active_references: ActiveReferenceSummary::default(),
         is_open: false,
         excluded: false,
+        entry_count: 0,
+        shared_bytes: 0,
     };
 
     let urgency = 0.8;
@@ -3479,6 +3494,8 @@ fn dry_run_deletes_nothing() {
         active_references: ActiveReferenceSummary::default(),
         is_open: false,
         excluded: false,
+        entry_count: 0,
+        shared_bytes: 0,
     };
 
     let scored = scoring.score_candidate(&candidate, 0.9);
@@ -3707,6 +3724,8 @@ fn batch_scoring_ranks_correctly() {
             active_references: ActiveReferenceSummary::default(),
             is_open: false,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         },
         CandidateInput {
             path: PathBuf::from("/tmp/project/notes.txt"),
@@ -3717,6 +3736,8 @@ fn batch_scoring_ranks_correctly() {
             active_references: ActiveReferenceSummary::default(),
             is_open: false,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         },
     ];
 
@@ -3774,10 +3795,14 @@ fn e2e_candidate(path: &str, size_gb: u64, age_hours: u64, confidence: f64) -> C
             has_git: false,
             has_cargo_toml: false,
             mostly_object_files: true,
+            has_jvm_outputs: false,
+            sibling_manifests: SiblingManifests::default(),
         },
         active_references: ActiveReferenceSummary::default(),
         is_open: false,
         excluded: false,
+        entry_count: 0,
+        shared_bytes: 0,
     }
 }
 
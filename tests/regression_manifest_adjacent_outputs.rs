@@ -0,0 +1,177 @@
+#![allow(missing_docs)]
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+    use storage_ballast_helper::core::config::ScoringConfig;
+    use storage_ballast_helper::scanner::patterns::{
+        ArtifactCategory, ArtifactPatternRegistry, SiblingManifests, StructuralSignals,
+    };
+    use storage_ballast_helper::scanner::protection::ProtectionRegistry;
+    use storage_ballast_helper::scanner::scoring::{
+        ActiveReferenceSummary, CandidateInput, DecisionAction, ScoringEngine,
+    };
+    use storage_ballast_helper::scanner::walker::{DirectoryWalker, WalkerConfig};
+
+    /// (output dir, manifest file that must sit beside it, expected label)
+    const CASES: &[(&str, &str, &str)] = &[
+        ("bin", "App.csproj", "dotnet-bin"),
+        ("obj", "Lib.fsproj", "dotnet-obj"),
+        (".build", "Package.swift", "swiftpm-build"),
+        (".zig-cache", "build.zig", "zig-cache"),
+        ("zig-out", "build.zig", "zig-out"),
+        ("dist-newstyle", "cabal.project", "cabal-dist-newstyle"),
+        ("dist-newstyle", "pkg.cabal", "cabal-dist-newstyle"),
+        ("_build", "mix.exs", "mix-build"),
+        ("deps", "mix.exs", "mix-deps"),
+    ];
+
+    fn default_engine() -> ScoringEngine {
+        ScoringEngine::from_config(&ScoringConfig::default(), 4) // 4 hours min age
+    }
+
+    fn beside(manifest: &str) -> StructuralSignals {
+        let mut sibling_manifests = SiblingManifests::default();
+        sibling_manifests.note(manifest);
+        StructuralSignals {
+            sibling_manifests,
+            ..StructuralSignals::default()
+        }
+    }
+
+    fn stale_input(path: PathBuf, signals: StructuralSignals) -> CandidateInput {
+        let registry = ArtifactPatternRegistry::default();
+        CandidateInput {
+            classification: registry.classify(&path, signals),
+            path,
+            size_bytes: 3 * 1_073_741_824,
+            age: Duration::from_hours(72),
+            signals,
+            active_references: ActiveReferenceSummary::default(),
+            is_open: false,
+            excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn outputs_next_to_their_manifest_are_actionable() {
+        let engine = default_engine();
+        for (output, manifest, label) in CASES {
+            let path = PathBuf::from(format!("/srv/monorepo/pkg/{output}"));
+            let input = stale_input(path, beside(manifest));
+            assert_eq!(
+                input.classification.pattern_name, *label,
+                "{output} beside {manifest}"
+            );
+            assert_ne!(input.classification.category, ArtifactCategory::Unknown);
+
+            let score = engine.score_candidate(&input, 0.8);
+            assert!(!score.vetoed, "{output}: {:?}", score.veto_reason);
+            assert_eq!(
+                score.decision.action,
+                DecisionAction::Delete,
+                "{output} beside {manifest} should be reclaimable"
+            );
+        }
+    }
+
+    #[test]
+    fn generic_output_names_without_manifest_are_never_candidates() {
+        let engine = default_engine();
+        for name in ["bin", "obj", "deps", "_build", ".build"] {
+            let path = PathBuf::from(format!("/srv/monorepo/tools/{name}"));
+            let input = stale_input(path, StructuralSignals::default());
+            assert_eq!(
+                input.classification.category,
+                ArtifactCategory::Unknown,
+                "{name} without a sibling manifest must stay unclassified"
+            );
+            assert_eq!(
+                engine.score_candidate(&input, 0.8).decision.action,
+                DecisionAction::Keep,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn a_manifest_only_vouches_for_its_own_tool_outputs() {
+        let registry = ArtifactPatternRegistry::default();
+        // `deps` beside a .csproj, `bin` beside mix.exs, `obj` beside Package.swift.
+        for (name, manifest) in [
+            ("deps", "App.csproj"),
+            ("bin", "mix.exs"),
+            ("obj", "Package.swift"),
+            ("_build", "build.zig"),
+        ] {
+            let classification = registry.classify(
+                Path::new(&format!("/srv/monorepo/pkg/{name}")),
+                beside(manifest),
+            );
+            assert_eq!(
+                classification.category,
+                ArtifactCategory::Unknown,
+                "{name} beside {manifest}"
+            );
+        }
+    }
+
+    #[test]
+    fn walker_hands_parent_manifests_to_child_dirs() {
+        let root = tempfile::tempdir().unwrap();
+        let dotnet = root.path().join("src/App");
+        let scripts = root.path().join("scripts");
+        for dir in [
+            dotnet.join("bin/Debug"),
+            dotnet.join("obj"),
+            scripts.join("bin"),
+        ] {
+            fs::create_dir_all(dir).unwrap();
+        }
+        fs::write(dotnet.join("App.csproj"), "<Project />").unwrap();
+        fs::write(scripts.join("bin/deploy.sh"), "#!/bin/sh\n").unwrap();
+
+        let walker = DirectoryWalker::new(
+            WalkerConfig {
+                root_paths: vec![root.path().to_path_buf()],
+                max_depth: 5,
+                follow_symlinks: false,
+                cross_devices: false,
+                parallelism: 1,
+                excluded_paths: HashSet::new(),
+                opaque_pruning: false,
+            },
+            ProtectionRegistry::new(None).unwrap(),
+        );
+        let registry = ArtifactPatternRegistry::default();
+        let labels: Vec<(PathBuf, String)> = walker
+            .walk()
+            .unwrap()
+            .into_iter()
+            .map(|entry| {
+                let label = registry
+                    .classify(&entry.path, entry.structural_signals)
+                    .pattern_name
+                    .into_owned();
+                (entry.path, label)
+            })
+            .collect();
+        let label_of = |path: &Path| {
+            labels.iter().find(|(entry, _)| entry == path).map_or_else(
+                || panic!("{} not walked", path.display()),
+                |(_, label)| label.as_str(),
+            )
+        };
+
+        assert_eq!(label_of(&dotnet.join("bin")), "dotnet-bin");
+        assert_eq!(label_of(&dotnet.join("obj")), "dotnet-obj");
+        assert_eq!(label_of(&scripts.join("bin")), "unknown");
+        // Manifest adjacency does not leak to grandchildren.
+        assert_eq!(label_of(&dotnet.join("bin/Debug")), "unknown");
+    }
+}
@@ -31,7 +31,11 @@ mod tests {
         path: &Path,
         signals: StructuralSignals,
     ) -> ArtifactClassification {
-        registry.classify_with_cleanup_rules(path, signals, macos::cleanup_catalog::cleanup_rules())
+        registry.classify_with_cleanup_rules(
+            path,
+            signals,
+            &macos::cleanup_catalog::cleanup_rules(),
+        )
     }
 
     fn classify_macos_with_home(
@@ -43,7 +47,7 @@ mod tests {
         registry.classify_with_cleanup_rules_and_home(
             path,
             signals,
-            macos::cleanup_catalog::cleanup_rules(),
+            &macos::cleanup_catalog::cleanup_rules(),
             home,
         )
     }
@@ -57,7 +61,7 @@ mod tests {
             .expect("create synthetic mac cleanup root");
         let fake_home = fake_root.path().join("Users").join("operator");
         let registry = ArtifactPatternRegistry::default();
-        let rules = macos::cleanup_catalog::cleanup_rules();
+        let rules = &*macos::cleanup_catalog::cleanup_rules();
         let mut temp_dirs = Vec::new();
         let mut matched_rule_names = BTreeSet::new();
         let mut scanned_labels = BTreeSet::new();
@@ -87,7 +91,7 @@ mod tests {
                 path.display()
             );
             assert_eq!(matched.confidence, rule.confidence);
-            matched_rule_names.insert(matched.name);
+            matched_rule_names.insert(matched.name.clone());
 
             if rule.is_scan_visible_candidate() {
                 let scanner_match =
@@ -146,7 +150,7 @@ mod tests {
         rule: &CleanupRule,
         fake_home: &Path,
     ) -> (PathBuf, Option<TempDir>, bool) {
-        match rule.name {
+        match rule.name.as_ref() {
             "xcode-derived-data" => (
                 fake_home.join("Library/Developer/Xcode/DerivedData/sbh-demo-abc123"),
                 None,
@@ -352,6 +356,8 @@ mod tests {
             active_references: ActiveReferenceSummary::default(),
             is_open: false,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         };
 
         let score = engine.score_candidate(&input, 0.5);
@@ -388,6 +394,8 @@ mod tests {
             active_references: ActiveReferenceSummary::default(),
             is_open: false,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         };
 
         // Score:
@@ -434,6 +442,8 @@ mod tests {
             active_references: ActiveReferenceSummary::default(),
             is_open: false,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         };
 
         let score = engine.score_candidate(&input, 0.95);
@@ -466,6 +476,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.95,
         );
@@ -499,6 +511,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.95,
         );
@@ -533,6 +547,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.8,
         );
@@ -563,6 +579,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.95,
         );
@@ -588,6 +606,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.95,
         );
@@ -618,6 +638,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.8,
         );
@@ -648,6 +670,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.95,
         );
@@ -665,6 +689,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.95,
         );
@@ -699,6 +725,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.95,
         );
@@ -731,6 +759,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.95,
         );
@@ -765,6 +795,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             1.0,
         );
@@ -804,6 +836,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.95,
         );
@@ -832,6 +866,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.95,
         );
@@ -884,6 +920,8 @@ mod tests {
                 active_references: ActiveReferenceSummary::default(),
                 is_open: false,
                 excluded: false,
+                entry_count: 0,
+                shared_bytes: 0,
             },
             0.95,
             &overlaps,
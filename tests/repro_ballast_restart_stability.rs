@@ -9,7 +9,9 @@ mod tests {
     use storage_ballast_helper::ballast::manager::BallastManager;
     use storage_ballast_helper::ballast::release::BallastReleaseController;
     use storage_ballast_helper::core::config::BallastConfig;
-    use storage_ballast_helper::monitor::pid::{PressureLevel, PressureResponse};
+    use storage_ballast_helper::monitor::pid::{
+        PressureDimension, PressureLevel, PressureResponse,
+    };
 
     fn test_config() -> BallastConfig {
         BallastConfig {
@@ -32,6 +34,7 @@ mod tests {
             causing_mount: PathBuf::from("/test"),
             free_pct: 5.0,
             predicted_seconds: None,
+            dimension: PressureDimension::Bytes,
         }
     }
 
@@ -15,6 +15,7 @@ mod tests {
             metadata: EntryMetadata {
                 size_bytes: 100,
                 content_size_bytes: 100,
+                apparent_size_bytes: 100,
                 modified: SystemTime::UNIX_EPOCH + Duration::from_secs(mtime_secs),
                 created: None,
                 is_dir: false,
@@ -22,6 +23,7 @@ mod tests {
                 device_id: 0,
                 kind: FsEntryKind::File,
                 permissions: 0,
+                content_entry_count: 1,
             },
             depth: 1,
             structural_signals: StructuralSignals::default(),
@@ -42,6 +42,8 @@ mod tests {
             active_references: ActiveReferenceSummary::default(),
             is_open: false,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         };
 
         let score = engine.score_candidate(&input, 0.5);
@@ -75,6 +77,8 @@ mod tests {
             active_references: ActiveReferenceSummary::default(),
             is_open: false,
             excluded: false,
+            entry_count: 0,
+            shared_bytes: 0,
         };
 
         let score = engine.score_candidate(&input, 0.5);
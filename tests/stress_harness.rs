@@ -274,6 +274,8 @@ fn make_candidate(path: &str, size: u64, age_secs: u64) -> CandidateInput {
         active_references: ActiveReferenceSummary::default(),
         is_open: false,
         excluded: false,
+        entry_count: 0,
+        shared_bytes: 0,
     }
 }
 
@@ -288,6 +290,7 @@ fn make_walk_entry(path: &str, size: u64, depth: usize) -> WalkEntry {
         metadata: EntryMetadata {
             size_bytes: size,
             content_size_bytes: size,
+            apparent_size_bytes: size,
             modified: SystemTime::now() - Duration::from_hours(1),
             created: None,
             is_dir: true,
@@ -295,6 +298,7 @@ fn make_walk_entry(path: &str, size: u64, depth: usize) -> WalkEntry {
             device_id: 1,
             kind: FsEntryKind::Directory,
             permissions: 0o755,
+            content_entry_count: 1,
         },
         depth,
         structural_signals: StructuralSignals::default(),
@@ -1180,6 +1184,7 @@ fn run_scenario_g(seed: u64, iterations: usize) -> ScenarioResult {
                     metadata: EntryMetadata {
                         size_bytes: 1024 * (u64_from_usize(i) + 1),
                         content_size_bytes: 1024 * (u64_from_usize(i) + 1),
+                        apparent_size_bytes: 1024 * (u64_from_usize(i) + 1),
                         modified: SystemTime::now()
                             - Duration::from_secs(3600 * (u64_from_usize(i) + 1)),
                         created: None,
@@ -1188,6 +1193,7 @@ fn run_scenario_g(seed: u64, iterations: usize) -> ScenarioResult {
                         device_id: 1,
                         kind: FsEntryKind::Directory,
                         permissions: 0o755,
+                        content_entry_count: 1,
                     },
                     depth: 2,
                     structural_signals: StructuralSignals {
@@ -24,7 +24,7 @@ use storage_ballast_helper::monitor::pid::{PidPressureController, PressureLevel,
 use storage_ballast_helper::platform::pal::MockPlatform;
 use storage_ballast_helper::scanner::decision_record::ActionRecord;
 use storage_ballast_helper::scanner::patterns::{
-    ArtifactCategory, ArtifactClassification, StructuralSignals,
+    ArtifactCategory, ArtifactClassification, SiblingManifests, StructuralSignals,
 };
 use storage_ballast_helper::scanner::scoring::{
     ActiveReferenceSummary, CandidateInput, ScoringEngine,
@@ -106,10 +106,14 @@ fn make_candidate(idx: usize, age_hours: u64, size_gib: u64) -> CandidateInput {
             has_git: false,
             has_cargo_toml: false,
             mostly_object_files: true,
+            has_jvm_outputs: false,
+            sibling_manifests: SiblingManifests::default(),
         },
         active_references: ActiveReferenceSummary::default(),
         is_open: false,
         excluded: false,
+        entry_count: 0,
+        shared_bytes: 0,
     }
 }
 